@@ -0,0 +1,14 @@
+fn main() {
+    // The gRPC service is generated from its published definition; the vendored protoc
+    // keeps the build from depending on one being installed.
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("no vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::configure()
+            .build_client(false)
+            .type_attribute(".penguin.v1", "#[derive(serde::Deserialize)]")
+            .compile_protos(&["proto/penguin.proto"], &["proto"])
+            .expect("cannot compile proto/penguin.proto");
+    }
+}
@@ -0,0 +1,199 @@
+//! Request handling shared by the machine-facing modes (HTTP server, JSON-RPC, and the
+//! browser native messaging host).
+//!
+//! Each operation takes its parameters as a JSON value and returns a JSON result, so the
+//! transports only have to deal with framing, authentication, and error mapping.
+
+use penguin::personal::PersonalInfo;
+use penguin::{strength, GenerateOptions, Penguin};
+use rand::seq::SliceRandom;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Upper bound on values returned by a single call.
+const MAX_COUNT: usize = 1000;
+
+/// Upper bound on the length of a generated password.
+const MAX_LENGTH: usize = 1024;
+
+/// Upper bound on the words in a generated passphrase.
+const MAX_WORDS_PER_PHRASE: usize = 64;
+
+/// The result of an operation; errors describe invalid parameters.
+pub type ApiResult = Result<Value, String>;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenerateParams {
+    #[serde(default)]
+    words: Vec<String>,
+    #[serde(default = "default_count")]
+    count: usize,
+    complexity: Option<String>,
+    whole_words: Option<bool>,
+    length: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnalyzeParams {
+    password: String,
+    #[serde(default)]
+    personal: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PassphraseParams {
+    words: Vec<String>,
+    #[serde(default = "default_count")]
+    count: usize,
+    #[serde(default = "default_words_per_phrase")]
+    words_per_phrase: usize,
+    #[serde(default = "default_separator")]
+    separator: String,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+fn default_words_per_phrase() -> usize {
+    4
+}
+
+fn default_separator() -> String {
+    String::from("-")
+}
+
+/// Dispatches an operation by name, returning `None` for unknown operations.
+pub fn call(operation: &str, params: Value) -> Option<ApiResult> {
+    let outcome = match operation {
+        "generate" => Some(parse(params).and_then(generate)),
+        "analyze" => Some(parse(params).and_then(analyze)),
+        "passphrase" => Some(parse(params).and_then(passphrase)),
+        _ => None,
+    };
+    crate::metrics::record_call(operation, matches!(outcome, Some(Ok(_))));
+    outcome
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, String> {
+    serde_json::from_value(params).map_err(|err| err.to_string())
+}
+
+fn check_count(count: usize) -> Result<(), String> {
+    if count > MAX_COUNT {
+        return Err(format!("count must not exceed {}", MAX_COUNT));
+    }
+    Ok(())
+}
+
+fn check_length(length: Option<usize>) -> Result<(), String> {
+    match length {
+        Some(length) if length > MAX_LENGTH => {
+            Err(format!("length must not exceed {}", MAX_LENGTH))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_words_per_phrase(words_per_phrase: usize) -> Result<(), String> {
+    if words_per_phrase > MAX_WORDS_PER_PHRASE {
+        return Err(format!(
+            "words_per_phrase must not exceed {}",
+            MAX_WORDS_PER_PHRASE
+        ));
+    }
+    Ok(())
+}
+
+fn generate(params: GenerateParams) -> ApiResult {
+    check_count(params.count)?;
+    check_length(params.length)?;
+    let complexity = match params.complexity.as_deref() {
+        Some(name) => Some(
+            crate::complexity_from_name(name)
+                .ok_or_else(|| format!("unknown complexity '{}'", name))?,
+        ),
+        None => None,
+    };
+
+    let mut options = GenerateOptions::new();
+    if let Some(complexity) = complexity {
+        options = options.complexity(complexity);
+    }
+    if let Some(whole_words) = params.whole_words {
+        options = options.whole_words(whole_words);
+    }
+    if let Some(length) = params.length {
+        options = options.length(length);
+    }
+    // Through the mixer rather than `generate`, to get the stats back.
+    let mixer = options.to_mixer().map_err(|err| err.to_string())?;
+    let penguin = Penguin::new(params.words.iter().map(|s| s.as_str()).collect());
+    let (passwords, stats) = penguin
+        .generate_with_stats(params.count, &mixer)
+        .map_err(|err| err.to_string())?;
+    crate::metrics::record_generation(&stats);
+    Ok(json!({ "passwords": passwords }))
+}
+
+fn analyze(params: AnalyzeParams) -> ApiResult {
+    let mut personal = PersonalInfo::new();
+    for detail in &params.personal {
+        personal.add(detail);
+    }
+    let report = strength::estimate_entropy_with(&params.password, &personal);
+    let mut analysis = json!({
+        "length": report.length,
+        "entropy_bits": report.entropy_bits,
+        "charset_size": report.charset_size,
+        "has_lowercase": report.has_lowercase,
+        "has_uppercase": report.has_uppercase,
+        "has_digits": report.has_digits,
+        "has_special": report.has_special,
+        "keyboard_walks": report
+            .keyboard_walks
+            .iter()
+            .map(|walk| walk.keys.as_str())
+            .collect::<Vec<_>>(),
+        "personal_info": report.personal_info,
+        "dates": report
+            .dates
+            .iter()
+            .map(|date| json!({ "text": date.text, "kind": date.kind.to_string() }))
+            .collect::<Vec<_>>(),
+    });
+    #[cfg(feature = "markov")]
+    {
+        analysis["guess_bits"] = json!(penguin::markov::guess_bits(&params.password));
+    }
+    let bits = crate::crack::bits(&params.password);
+    analysis["crack_seconds"] = strength::Attacker::ALL
+        .iter()
+        .map(|attacker| (attacker.to_string(), json!(attacker.seconds_to_crack(bits))))
+        .collect();
+    Ok(analysis)
+}
+
+/// Joins words picked independently at random from the given list, diceware-style.
+fn passphrase(params: PassphraseParams) -> ApiResult {
+    check_count(params.count)?;
+    check_words_per_phrase(params.words_per_phrase)?;
+    if params.words.is_empty() {
+        return Err(String::from("words must not be empty"));
+    }
+
+    let mut rng = penguin::entropy::rng();
+    let passphrases: Vec<String> = (0..params.count)
+        .map(|_| {
+            (0..params.words_per_phrase)
+                .map(|_| params.words.choose(&mut rng).unwrap().as_str())
+                .collect::<Vec<_>>()
+                .join(&params.separator)
+        })
+        .collect();
+    Ok(json!({ "passphrases": passphrases }))
+}
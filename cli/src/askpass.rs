@@ -0,0 +1,75 @@
+//! An askpass program backed by the OS keyring, for `penguin askpass`.
+//!
+//! ssh (through `SSH_ASKPASS`) and `sudo -A` (through `SUDO_ASKPASS`) run an askpass
+//! program with the prompt as its argument and read the answer from its stdout. The
+//! answer to every prompt is kept in the keyring under the service `penguin-askpass`,
+//! with the prompt itself as the account, so `alice@build01's password:` and
+//! `[sudo] password for alice:` are answered without typing once they are known.
+//!
+//! The first time a prompt is seen, it is shown on the terminal with hidden input. The
+//! answer typed there is saved; an empty answer generates a password instead, shows it
+//! once so it can be set on the other side, and saves that. Confirmations ssh asks for,
+//! like accepting a new host key, are always passed to the terminal and never saved.
+
+use crate::tty::Terminal;
+use penguin::{random_password, Charset};
+
+/// The keyring service every answer is saved under.
+const SERVICE: &str = "penguin-askpass";
+
+/// How long the passwords generated for empty answers are.
+const GENERATED_LENGTH: usize = 24;
+
+/// Prints the answer to the prompt, or exits with status 1 when there is none.
+pub fn run(prompt: &[String]) {
+    let prompt = prompt.join(" ");
+    let account = prompt.trim();
+    if account.is_empty() {
+        crate::fail(crate::i18n::tr("askpass needs the prompt as its argument"));
+    }
+
+    // ssh sets this for yes/no questions, whose answer depends on the moment.
+    if std::env::var("SSH_ASKPASS_PROMPT").is_ok_and(|kind| kind == "confirm") {
+        let answer = Terminal::open()
+            .and_then(|mut tty| tty.ask(&format!("{} ", account)))
+            .unwrap_or_else(|| std::process::exit(1));
+        println!("{}", answer);
+        return;
+    }
+
+    let entry = keyring::Entry::new(SERVICE, account)
+        .unwrap_or_else(|err| crate::fail(format!("cannot open the keyring: {}", err)));
+    let answer = match entry.get_password() {
+        Ok(answer) => answer,
+        Err(keyring::Error::NoEntry) => {
+            let answer = ask(account).unwrap_or_else(|| std::process::exit(1));
+            entry
+                .set_password(&answer)
+                .unwrap_or_else(|err| crate::fail(format!("cannot save to the keyring: {}", err)));
+            answer
+        }
+        Err(err) => crate::fail(format!("cannot read the keyring: {}", err)),
+    };
+    println!("{}", answer);
+}
+
+/// Asks for the answer at a hidden prompt on the terminal, generating a password when it
+/// is left empty. Returns `None` without a terminal.
+fn ask(prompt: &str) -> Option<String> {
+    let mut tty = Terminal::open()?;
+    let hint = crate::i18n::tr("(leave empty to generate a password)");
+    let typed = rpassword::prompt_password(format!("{} {} ", prompt, hint)).ok()?;
+    if !typed.is_empty() {
+        return Some(typed);
+    }
+
+    let password = random_password(GENERATED_LENGTH, &Charset::default());
+    tty.say(&format!(
+        "\n   {}\n\n{}\n",
+        password,
+        crate::i18n::tr(
+            "This password is saved to the keyring. Set it on the other side before it is used."
+        )
+    ));
+    Some(password)
+}
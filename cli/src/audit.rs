@@ -0,0 +1,79 @@
+//! Append-only audit log of generation events, written with `--audit-log`.
+//!
+//! Rotating shared credentials usually has to leave a trail for change management.
+//! Every generated password adds one JSON line with when it was made, its label, the
+//! policy and strategy it was generated with, its length and estimated entropy, and a
+//! SHA-256 hash of the output. The plaintext never reaches the log; the hash lets an auditor
+//! confirm that a deployed password is the one that was logged.
+
+use penguin::hex;
+use penguin::mixer::{ComplexityLevel, GenerationStyle, PenguinMixer};
+use penguin::strength;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::Path;
+
+/// Appends one record per password to the log, creating it on first use.
+pub fn append(path: &Path, passwords: &[String], labels: &[String], mixer: &PenguinMixer) {
+    let timestamp = crate::date::timestamp();
+    let policy = policy(mixer);
+    let strategy = strategy(mixer);
+
+    let mut lines = String::new();
+    for (i, password) in passwords.iter().enumerate() {
+        let record = json!({
+            "timestamp": timestamp,
+            "label": labels.get(i),
+            "policy": policy,
+            "strategy": strategy,
+            "length": password.chars().count(),
+            "entropy_bits": (strength::estimate_entropy(password).entropy_bits * 10.0).round() / 10.0,
+            "sha256": hex::encode(&Sha256::digest(password.as_bytes())),
+        });
+        lines.push_str(&format!("{}\n", record));
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+}
+
+/// Describes the settings the mixer generates with, as recorded in the log and receipts.
+pub fn policy(mixer: &PenguinMixer) -> Value {
+    let excluded: Vec<String> = mixer
+        .excluded_classes
+        .iter()
+        .map(|class| format!("{:?}", class).to_lowercase())
+        .collect();
+    json!({
+        "complexity": format!("{:?}", mixer.complexity).to_lowercase(),
+        "whole_words": mixer.use_whole_words,
+        "excluded_classes": excluded,
+    })
+}
+
+/// Names how the mixer builds passwords: fully random, from whole words, from mixed
+/// characters of the words, or from pronounceable syllables.
+pub fn strategy(mixer: &PenguinMixer) -> &'static str {
+    match mixer.generation {
+        GenerationStyle::Pronounceable if mixer.complexity != ComplexityLevel::Penguin => {
+            "pronounceable"
+        }
+        _ => strategy_for(mixer.complexity, mixer.use_whole_words),
+    }
+}
+
+/// Names the strategy a mixer with these settings would use; see [`strategy`].
+pub fn strategy_for(complexity: ComplexityLevel, whole_words: bool) -> &'static str {
+    match complexity {
+        ComplexityLevel::Penguin => "random",
+        _ if whole_words => "whole-words",
+        _ => "mixed",
+    }
+}
@@ -0,0 +1,128 @@
+//! Storing generated passwords in AWS Secrets Manager and SSM Parameter Store.
+//!
+//! With the `aws` feature, `--aws-secret <name>` creates the secret or, if it already
+//! exists, rotates it by storing a new version, and `--aws-ssm <path>` writes a
+//! `SecureString` parameter, overwriting any previous value. Credentials and region are
+//! resolved like the AWS CLI does (environment, profiles, SSO, instance roles). Only the
+//! ARN of the stored secret is printed, so the password never crosses the terminal.
+//!
+//! The feature also lets `--format sops --kms <arn>` encrypt the SOPS data key with KMS.
+
+use aws_sdk_secretsmanager::error::DisplayErrorContext;
+use aws_sdk_secretsmanager::operation::put_secret_value::PutSecretValueError;
+use aws_sdk_ssm::types::ParameterType;
+use std::future::Future;
+
+/// Where in AWS the password should be stored.
+pub enum Destination<'a> {
+    SecretsManager(&'a str),
+    Parameter(&'a str),
+}
+
+/// Stores the password and returns the ARN of the secret or parameter.
+pub fn store(destination: Destination, password: &str) -> Result<String, String> {
+    match destination {
+        Destination::SecretsManager(name) => block_on(put_secret(name, password)),
+        Destination::Parameter(path) => block_on(put_parameter(path, password)),
+    }
+}
+
+/// Encrypts a SOPS data key with a KMS key and returns the ciphertext blob.
+pub fn kms_encrypt(arn: &str, data_key: &[u8]) -> Result<Vec<u8>, String> {
+    block_on(async {
+        let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        let output = aws_sdk_kms::Client::new(&config)
+            .encrypt()
+            .key_id(arn)
+            .plaintext(aws_sdk_kms::primitives::Blob::new(data_key))
+            .send()
+            .await
+            .map_err(|err| format!("cannot encrypt with {}: {}", arn, DisplayErrorContext(err)))?;
+        output
+            .ciphertext_blob
+            .map(|blob| blob.into_inner())
+            .ok_or_else(|| format!("{} returned no ciphertext", arn))
+    })
+}
+
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| crate::fail(format!("cannot start the AWS client: {}", err)))
+        .block_on(future)
+}
+
+async fn put_secret(name: &str, password: &str) -> Result<String, String> {
+    let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let client = aws_sdk_secretsmanager::Client::new(&config);
+
+    let rotated = client
+        .put_secret_value()
+        .secret_id(name)
+        .secret_string(password)
+        .send()
+        .await;
+    let arn = match rotated {
+        Ok(output) => output.arn,
+        Err(err)
+            if matches!(
+                err.as_service_error(),
+                Some(PutSecretValueError::ResourceNotFoundException(_))
+            ) =>
+        {
+            client
+                .create_secret()
+                .name(name)
+                .secret_string(password)
+                .send()
+                .await
+                .map_err(|err| format!("cannot create the secret: {}", DisplayErrorContext(err)))?
+                .arn
+        }
+        Err(err) => {
+            return Err(format!(
+                "cannot store the secret: {}",
+                DisplayErrorContext(err)
+            ))
+        }
+    };
+    Ok(arn.unwrap_or_else(|| name.to_string()))
+}
+
+async fn put_parameter(path: &str, password: &str) -> Result<String, String> {
+    let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let client = aws_sdk_ssm::Client::new(&config);
+
+    client
+        .put_parameter()
+        .name(path)
+        .value(password)
+        .r#type(ParameterType::SecureString)
+        .overwrite(true)
+        .send()
+        .await
+        .map_err(|err| {
+            format!(
+                "cannot store the parameter: {}",
+                aws_sdk_ssm::error::DisplayErrorContext(err)
+            )
+        })?;
+
+    // PutParameter returns no ARN, so look it up without decrypting the value.
+    let parameter = client
+        .get_parameter()
+        .name(path)
+        .send()
+        .await
+        .map_err(|err| {
+            format!(
+                "cannot look up the parameter: {}",
+                aws_sdk_ssm::error::DisplayErrorContext(err)
+            )
+        })?
+        .parameter;
+    Ok(parameter
+        .and_then(|parameter| parameter.arn)
+        .unwrap_or_else(|| path.to_string()))
+}
@@ -0,0 +1,117 @@
+//! Manifest-driven batch generation.
+//!
+//! A manifest is a TOML file listing the accounts that need a password. Top-level keys
+//! provide defaults for every entry, and each `[[entry]]` table may override them:
+//!
+//! ```toml
+//! words = ["penguin", "glacier"]
+//! complexity = "medium"
+//! length = 16
+//!
+//! [[entry]]
+//! label = "github"
+//! username = "deploy-bot"
+//!
+//! [[entry]]
+//! label = "database"
+//! username = "app"
+//! complexity = "penguin"
+//! ```
+
+use crate::output::{self, Entry, OutputArgs};
+use penguin::{GenerateOptions, Penguin};
+use serde::Deserialize;
+use std::path::Path;
+
+// The generation settings are listed on both tables rather than flattened in from a
+// shared struct, as serde can't combine `flatten` with `deny_unknown_fields`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    words: Option<Vec<String>>,
+    complexity: Option<String>,
+    whole_words: Option<bool>,
+    length: Option<usize>,
+    #[serde(default)]
+    entry: Vec<ManifestEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestEntry {
+    label: String,
+    #[serde(default)]
+    username: String,
+    words: Option<Vec<String>>,
+    complexity: Option<String>,
+    whole_words: Option<bool>,
+    length: Option<usize>,
+}
+
+/// Generates a password for every entry of the manifest and prints the combined report.
+pub fn run(path: &Path, output: &OutputArgs) {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err)));
+    let manifest: Manifest = toml::from_str(&contents)
+        .unwrap_or_else(|err| crate::fail(format!("invalid manifest {}: {}", path.display(), err)));
+
+    if manifest.entry.is_empty() {
+        crate::fail(format!(
+            "manifest {} has no [[entry]] tables",
+            path.display()
+        ));
+    }
+
+    let defaults = &manifest;
+    let entries: Vec<Entry> = manifest
+        .entry
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let words = entry
+                .words
+                .as_ref()
+                .or(defaults.words.as_ref())
+                .cloned()
+                .unwrap_or_default();
+            let complexity = crate::parse_complexity(
+                entry
+                    .complexity
+                    .as_deref()
+                    .or(defaults.complexity.as_deref())
+                    .unwrap_or("basic"),
+            );
+
+            let whole_words = entry.whole_words.or(defaults.whole_words).unwrap_or(false);
+
+            let penguin = Penguin::new(words.iter().map(|s| s.as_str()).collect());
+            let options = GenerateOptions::new()
+                .complexity(complexity)
+                .whole_words(whole_words)
+                .length(entry.length.or(defaults.length).unwrap_or(12));
+            let password = penguin
+                .generate(&options)
+                .unwrap_or_else(|err| crate::fail(format!("entry '{}': {}", entry.label, err)))
+                .remove(0);
+
+            Entry {
+                index: i + 1,
+                label: Some(entry.label.clone()),
+                username: entry.username.clone(),
+                shares: Vec::new(),
+                password,
+                hint: None,
+                word_hint: None,
+                notes: output::generated_notes(complexity),
+                expires: None,
+                strategy: crate::audit::strategy_for(complexity, whole_words),
+                policy: None,
+                complexity: Some(complexity),
+                entropy_bits: None,
+            }
+        })
+        .collect();
+
+    output::emit(output, &output::render(output, &entries));
+    output::offer_reveal(output, &entries);
+}
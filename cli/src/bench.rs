@@ -0,0 +1,63 @@
+//! Generation throughput on the local machine, measured with `penguin bench`.
+//!
+//! Each complexity level is timed with each strategy it supports: whole words and mixed
+//! characters for the word-based levels, and fully random for the penguin level. Every
+//! row generates the same number of passwords through the same batch path as
+//! `penguin generate`, so `--jobs` measures the thread pool as well.
+
+use penguin::mixer::{ComplexityLevel, PenguinMixer};
+use std::time::Instant;
+
+/// Base words used when none are given.
+const SAMPLE_WORDS: [&str; 4] = ["penguin", "glacier", "iceberg", "snowfall"];
+
+/// The rows of the table: level name, complexity, and whether whole words are used.
+const CASES: [(&str, ComplexityLevel, bool); 7] = [
+    ("basic", ComplexityLevel::Basic, true),
+    ("basic", ComplexityLevel::Basic, false),
+    ("medium", ComplexityLevel::Medium, true),
+    ("medium", ComplexityLevel::Medium, false),
+    ("hard", ComplexityLevel::Hard, true),
+    ("hard", ComplexityLevel::Hard, false),
+    ("penguin", ComplexityLevel::Penguin, false),
+];
+
+/// Times `count` passwords of `length` characters per row on `jobs` threads and prints
+/// the table.
+pub fn run(words: &[String], count: usize, length: usize, jobs: usize) {
+    let words: Vec<&str> = if words.is_empty() {
+        SAMPLE_WORDS.to_vec()
+    } else {
+        words.iter().map(String::as_str).collect()
+    };
+
+    println!(
+        "{} passwords per row, {} characters, {} thread{}\n",
+        count,
+        length,
+        jobs,
+        if jobs == 1 { "" } else { "s" }
+    );
+    println!(
+        "{:<10} {:<12} {:>14} {:>14}",
+        "complexity", "strategy", "passwords/s", "µs/password"
+    );
+    for (name, complexity, whole_words) in CASES {
+        let mixer = PenguinMixer::try_new(complexity, whole_words, length)
+            .unwrap_or_else(|err| crate::fail(err.to_string()));
+        let strategy = crate::audit::strategy(&mixer);
+
+        let started = Instant::now();
+        let (passwords, _) = crate::progress::generate(&words, &mixer, count, jobs, false);
+        let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+        std::hint::black_box(passwords);
+
+        println!(
+            "{:<10} {:<12} {:>14.0} {:>14.2}",
+            name,
+            strategy,
+            count as f64 / elapsed,
+            elapsed * 1e6 / count as f64
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! The `penguin check` command, which reports on the strength of a password.
+//!
+//! Provisioning scripts have to know whether a password meets their entropy floor
+//! before handing it out. `penguin check` prints the password's length and classes, its
+//! estimated entropy and rating, and the base words, keyboard walks, and dates that
+//! lower it. With `--min-entropy` it exits with status 1 when the estimate falls short,
+//! so a script can test passwords from anywhere, not only ones penguin generated. The
+//! password is read at a hidden prompt, or as a line of stdin, unless given as an
+//! argument.
+
+use penguin::mixer::CharClass;
+use penguin::render::Meter;
+use penguin::strength;
+use std::io::IsTerminal;
+
+/// Prints the report on the password and exits with status 1 when its estimate is
+/// below `min_entropy`.
+pub fn run(password: Option<String>, words: &[String], min_entropy: Option<f64>) {
+    let password = password.unwrap_or_else(read_password);
+    if password.is_empty() {
+        crate::fail(crate::i18n::tr("no password was entered"));
+    }
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let report = strength::estimate_entropy_with_words(&password, &words);
+    let count = |class| {
+        password
+            .chars()
+            .filter(|&c| CharClass::of(c) == Some(class))
+            .count()
+    };
+    let listed = |items: Vec<String>| {
+        if items.is_empty() {
+            String::from("none")
+        } else {
+            items.join(", ")
+        }
+    };
+
+    println!(
+        "   length: {} ({} lowercase, {} uppercase, {} digits, {} specials)",
+        report.length,
+        count(CharClass::Lowercase),
+        count(CharClass::Uppercase),
+        count(CharClass::Digits),
+        count(CharClass::Specials),
+    );
+    println!(
+        "   entropy: {:.1} bits {}",
+        report.entropy_bits,
+        Meter::from_report(&report)
+    );
+    println!("   charset: {} characters", report.charset_size);
+    if !words.is_empty() {
+        println!("   base words: {}", listed(report.base_words.clone()));
+    }
+    println!(
+        "   keyboard walks: {}",
+        listed(
+            report
+                .keyboard_walks
+                .iter()
+                .map(|walk| walk.keys.clone())
+                .collect()
+        )
+    );
+    println!(
+        "   dates: {}",
+        listed(report.dates.iter().map(|date| date.text.clone()).collect())
+    );
+
+    if let Some(min) = min_entropy {
+        if report.entropy_bits < min {
+            eprintln!(
+                "{}",
+                crate::i18n::trf(
+                    "{0} bits is below the minimum of {1} bits",
+                    &[&format!("{:.1}", report.entropy_bits), &min],
+                )
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read_password() -> String {
+    let password = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password(crate::i18n::tr("Password to check: "))
+    } else {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).map(|_| line)
+    }
+    .unwrap_or_else(|err| crate::fail(format!("cannot read the password: {}", err)));
+    password.trim_end_matches(['\r', '\n']).to_string()
+}
@@ -0,0 +1,335 @@
+//! Clipboard access through the platform's command-line tools.
+//!
+//! The text is always handed over on stdin, so it never shows up in the process list.
+//! The first tool that fits the session is used: `wl-copy` under Wayland, `xclip` or
+//! `xsel` under X11, `pbcopy` on macOS, `clip.exe` on Windows, and the Termux:API
+//! `termux-clipboard-set` on Android. Wayland and X11 also
+//! have a primary selection, the one middle-click pastes, which `--selection primary`
+//! writes to instead.
+//!
+//! `--clear-after <seconds>` empties the selection again after a while. The clearing is
+//! left to a detached copy of penguin (`penguin clipboard-clear`), so it happens even
+//! though penguin itself exits right after copying. It is skipped when the selection no
+//! longer holds the password, so whatever was copied since is left alone. With the
+//! `notify` feature, a desktop notification says when the selection was cleared, and
+//! `--notify-countdown` shows one counting down the seconds left to paste.
+//!
+//! On Termux, [`share`] also hands a password to the Android share sheet with
+//! `termux-share`, to send it straight to a password manager or another app.
+
+use clap::{Args, ValueEnum};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+
+/// Which selection to write to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Selection {
+    /// The regular clipboard, pasted with Ctrl+V
+    #[default]
+    Clipboard,
+    /// The Wayland and X11 primary selection, pasted with the middle button
+    Primary,
+}
+
+/// Options for commands that copy to the clipboard.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct ClipboardArgs {
+    /// Selection to copy to
+    #[arg(long, value_enum, default_value_t = Selection::Clipboard)]
+    pub selection: Selection,
+
+    /// Clear the copied password from the selection after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub clear_after: Option<u64>,
+
+    /// Show a desktop notification counting down until the clipboard is cleared
+    #[cfg(feature = "notify")]
+    #[arg(long, requires = "clear_after")]
+    pub notify_countdown: bool,
+}
+
+/// Candidate tools in order of preference, as program and arguments, for writing or
+/// reading the selection.
+fn tools(selection: Selection, read: bool) -> Vec<(&'static str, &'static [&'static str])> {
+    use Selection::{Clipboard, Primary};
+    let mut tools: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+    if is_termux() && selection == Clipboard {
+        tools.push(if read {
+            ("termux-clipboard-get", &[])
+        } else {
+            ("termux-clipboard-set", &[])
+        });
+    }
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        tools.push(match (selection, read) {
+            (Clipboard, false) => ("wl-copy", &[]),
+            (Primary, false) => ("wl-copy", &["--primary"]),
+            (Clipboard, true) => ("wl-paste", &["--no-newline"]),
+            (Primary, true) => ("wl-paste", &["--no-newline", "--primary"]),
+        });
+    }
+    // Under Wayland the X tools are only a fallback, for when XWayland provides a display.
+    if std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        tools.extend(match (selection, read) {
+            (Clipboard, false) => [
+                ("xclip", &["-selection", "clipboard"][..]),
+                ("xsel", &["--clipboard", "--input"][..]),
+            ],
+            (Primary, false) => [
+                ("xclip", &["-selection", "primary"][..]),
+                ("xsel", &["--primary", "--input"][..]),
+            ],
+            (Clipboard, true) => [
+                ("xclip", &["-selection", "clipboard", "-out"][..]),
+                ("xsel", &["--clipboard", "--output"][..]),
+            ],
+            (Primary, true) => [
+                ("xclip", &["-selection", "primary", "-out"][..]),
+                ("xsel", &["--primary", "--output"][..]),
+            ],
+        });
+    }
+    if selection == Clipboard {
+        tools.push(if read {
+            ("pbpaste", &[])
+        } else {
+            ("pbcopy", &[])
+        });
+        if !read {
+            tools.push(("clip.exe", &[]));
+        }
+    }
+    tools
+}
+
+/// Copies the text to the selection, and schedules clearing it when asked to.
+pub fn copy(text: &str, args: &ClipboardArgs) -> Result<(), String> {
+    write(text, args.selection)?;
+    if let Some(seconds) = args.clear_after {
+        schedule_clear(text, args, seconds)?;
+    }
+    Ok(())
+}
+
+fn write(text: &str, selection: Selection) -> Result<(), String> {
+    for (program, args) in tools(selection, false) {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        let written = child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(text.as_bytes());
+        let status = child.wait();
+        if written.is_ok() && status.is_ok_and(|status| status.success()) {
+            return Ok(());
+        }
+    }
+    Err(match selection {
+        Selection::Clipboard if is_termux() => String::from(
+            "no clipboard tool found (install the Termux:API app and `pkg install termux-api`)",
+        ),
+        Selection::Clipboard => {
+            String::from("no clipboard tool found (install wl-clipboard, xclip, or xsel)")
+        }
+        Selection::Primary => String::from(
+            "no tool for the primary selection found (install wl-clipboard, xclip, or xsel)",
+        ),
+    })
+}
+
+/// Returns whether penguin runs inside Termux on Android.
+fn is_termux() -> bool {
+    std::env::var_os("TERMUX_VERSION").is_some()
+        || std::env::var("PREFIX").is_ok_and(|prefix| prefix.contains("/com.termux/"))
+}
+
+/// Opens the Android share sheet with the text, on Termux.
+pub fn share(text: &str) -> Result<(), String> {
+    if !is_termux() {
+        return Err(String::from(
+            "--share is only available in Termux on Android",
+        ));
+    }
+    let mut child = Command::new("termux-share")
+        .args(["--content-type", "text/plain"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run termux-share (pkg install termux-api): {}", err))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(text.as_bytes())
+        .map_err(|err| format!("cannot talk to termux-share: {}", err))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("cannot run termux-share: {}", err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "cannot share the password: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Returns what the selection holds, or `None` when no tool can read it.
+fn read(selection: Selection) -> Option<String> {
+    tools(selection, true)
+        .into_iter()
+        .find_map(|(program, args)| {
+            let output = Command::new(program)
+                .args(args)
+                .stdin(Stdio::null())
+                .stderr(Stdio::null())
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+}
+
+/// Starts a detached `penguin clipboard-clear`, handing it the SHA-256 of the text on
+/// stdin so the password itself never leaves this process.
+fn schedule_clear(text: &str, args: &ClipboardArgs, seconds: u64) -> Result<(), String> {
+    let exe = std::env::current_exe()
+        .map_err(|err| format!("cannot schedule clearing the clipboard: {}", err))?;
+    let selection = args
+        .selection
+        .to_possible_value()
+        .expect("selections are not skipped");
+    let mut command = Command::new(exe);
+    command
+        .args([
+            "clipboard-clear",
+            "--after",
+            &seconds.to_string(),
+            "--selection",
+        ])
+        .arg(selection.get_name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(feature = "notify")]
+    if args.notify_countdown {
+        command.arg("--countdown");
+    }
+    // A session of its own keeps the clearer alive when the terminal closes.
+    #[cfg(unix)]
+    // SAFETY: the closure runs between fork and exec, and only calls setsid, which is
+    // async-signal-safe, and reads errno when it fails.
+    unsafe {
+        std::os::unix::process::CommandExt::pre_exec(&mut command, || {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("cannot schedule clearing the clipboard: {}", err))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(digest(text).as_bytes())
+        .map_err(|err| format!("cannot schedule clearing the clipboard: {}", err))
+}
+
+/// Waits, then empties the selection if it still holds the text whose SHA-256 arrives
+/// on stdin. Runs as `penguin clipboard-clear`.
+pub fn clear_later(seconds: u64, selection: Selection, countdown: bool) {
+    let mut expected = String::new();
+    if std::io::stdin().read_to_string(&mut expected).is_err() {
+        return;
+    }
+    if countdown {
+        notify::countdown(seconds);
+    } else {
+        std::thread::sleep(std::time::Duration::from_secs(seconds));
+    }
+    // Without a way to read the selection, clearing it is the safer mistake.
+    let unchanged = read(selection).is_none_or(|current| digest(&current) == expected.trim());
+    if unchanged && write("", selection).is_ok() {
+        notify::cleared();
+    }
+}
+
+/// Desktop notifications about clearing, through notify-rust. Failing to show one never
+/// keeps the selection from being cleared.
+#[cfg(feature = "notify")]
+mod notify {
+    use notify_rust::{Notification, Timeout};
+    use std::time::Duration;
+
+    fn notification(body: &str) -> Notification {
+        let mut notification = Notification::new();
+        notification
+            .appname("penguin")
+            .summary("penguin")
+            .body(body);
+        notification
+    }
+
+    fn remaining(seconds: u64) -> String {
+        crate::i18n::trf("clipboard clears in {0} seconds", &[&seconds])
+    }
+
+    /// Sleeps for the seconds, showing how many are left. Where notifications can be
+    /// updated in place (the freedesktop servers on Linux and the BSDs), the count goes
+    /// down every second; elsewhere one notification lasts the whole wait.
+    pub fn countdown(seconds: u64) {
+        let mut first = notification(&remaining(seconds));
+        first.timeout(Timeout::Milliseconds((seconds * 1000) as u32));
+        #[cfg(all(unix, not(target_os = "macos")))]
+        if let Ok(mut handle) = first.show() {
+            for left in (1..seconds).rev() {
+                std::thread::sleep(Duration::from_secs(1));
+                handle.body(&remaining(left));
+                let _ = handle.update();
+            }
+            std::thread::sleep(Duration::from_secs(1));
+            handle.close();
+            return;
+        }
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        let _ = first.show();
+        std::thread::sleep(Duration::from_secs(seconds));
+    }
+
+    pub fn cleared() {
+        let _ = notification(crate::i18n::tr("clipboard cleared"))
+            .timeout(Timeout::Milliseconds(5000))
+            .show();
+    }
+}
+
+#[cfg(not(feature = "notify"))]
+mod notify {
+    pub fn countdown(seconds: u64) {
+        std::thread::sleep(std::time::Duration::from_secs(seconds));
+    }
+
+    pub fn cleared() {}
+}
+
+fn digest(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
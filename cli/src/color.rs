@@ -0,0 +1,54 @@
+//! Terminal colors for the plain output format.
+//!
+//! Digits and specials are highlighted in different colors so a password can be read
+//! back without confusing `0` and `O` or `1` and `l`, and a badge colored by the
+//! strength rating follows each password, in the colors of [`penguin::render::Meter`]. Colors are only used when stdout is a
+//! terminal, and `--no-color` or a non-empty `NO_COLOR` turns them off.
+
+use penguin::render::{Meter, RESET};
+use std::io::IsTerminal;
+
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+
+/// Returns whether output going straight to stdout should be colored.
+pub fn enabled(no_color: bool) -> bool {
+    !no_color
+        && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        && std::io::stdout().is_terminal()
+}
+
+/// Wraps each run of digits in cyan and each run of specials in magenta.
+pub fn highlight(password: &str) -> String {
+    let mut out = String::with_capacity(password.len() * 2);
+    let mut current = None;
+    for c in password.chars() {
+        let color = if c.is_ascii_digit() {
+            Some(CYAN)
+        } else if c.is_alphanumeric() {
+            None
+        } else {
+            Some(MAGENTA)
+        };
+        if color != current {
+            if current.is_some() {
+                out.push_str(RESET);
+            }
+            if let Some(color) = color {
+                out.push_str(color);
+            }
+            current = color;
+        }
+        out.push(c);
+    }
+    if current.is_some() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// A `[weak]`, `[fair]`, `[strong]`, or `[excellent]` badge in red, yellow, green, or blue.
+pub fn badge(password: &str) -> String {
+    let meter = Meter::of(password);
+    format!("{}[{}]{}", meter.color().ansi(), meter.label(), RESET)
+}
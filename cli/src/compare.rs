@@ -0,0 +1,75 @@
+//! The `penguin compare` command, for coaching someone through a password change.
+//!
+//! Both passwords are read at hidden prompts. The report shows how strong each one is,
+//! how similar they are by [`strength::similarity`], and whether the new one is a
+//! meaningful improvement: it has to be clearly harder to guess and more than a small
+//! edit of the old one, since guessers try `Winter2024!` right after `Winter2023!`. The
+//! command exits with status 1 when it isn't.
+
+use penguin::render::Meter;
+use penguin::strength;
+
+/// Similarity from which the new password counts as a variant of the current one.
+const SIMILAR: f64 = 0.7;
+
+/// How many more guess bits the new password needs to count as an improvement.
+const IMPROVEMENT_BITS: f64 = 10.0;
+
+/// Reads both passwords, prints the comparison, and exits with status 1 unless the new
+/// password is a meaningful improvement.
+pub fn run() {
+    let current = prompt("Current password: ");
+    let new = prompt("New password: ");
+
+    let (current_bits, new_bits) = (crate::crack::bits(&current), crate::crack::bits(&new));
+    let similarity = strength::similarity(&current, &new);
+    println!(
+        "{}",
+        describe(crate::i18n::tr("current"), &current, current_bits)
+    );
+    println!("{}", describe(crate::i18n::tr("new"), &new, new_bits));
+    println!("   similarity: {:.2}", similarity);
+
+    let verdict = if similarity >= SIMILAR {
+        Err(crate::i18n::tr(
+            "not an improvement: the new password is a small change to the current one",
+        )
+        .to_string())
+    } else if new_bits < current_bits + IMPROVEMENT_BITS {
+        Err(crate::i18n::trf(
+            "not a meaningful improvement: the new password is only {0} bits harder to guess",
+            &[&format!("{:.1}", new_bits - current_bits)],
+        ))
+    } else {
+        Ok(crate::i18n::trf(
+            "a meaningful improvement: the new password is {0} bits harder to guess",
+            &[&format!("{:.1}", new_bits - current_bits)],
+        ))
+    };
+    match verdict {
+        Ok(verdict) => println!("\n{}", verdict),
+        Err(verdict) => {
+            println!("\n{}", verdict);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn prompt(message: &'static str) -> String {
+    let password = rpassword::prompt_password(crate::i18n::tr(message))
+        .unwrap_or_else(|err| crate::fail(format!("cannot read the password: {}", err)));
+    if password.is_empty() {
+        crate::fail(crate::i18n::tr("no password was entered"));
+    }
+    password
+}
+
+/// One line on a password's length, charset entropy and rating, and guess bits.
+fn describe(name: &str, password: &str, bits: f64) -> String {
+    let report = strength::estimate_entropy(password);
+    let meter = Meter::from_report(&report);
+    format!(
+        "   {}: {} characters, {:.1} bits {}, about {:.1} bits to guess",
+        name, report.length, report.entropy_bits, meter, bits
+    )
+}
@@ -0,0 +1,245 @@
+//! The user configuration file and the `penguin config` subcommands.
+//!
+//! Defaults for the generation flags can live in `$XDG_CONFIG_HOME/penguin/config.toml`
+//! (`~/.config/penguin/config.toml` when the variable is unset). Each key in the file
+//! stands in for a `PENGUIN_*` environment variable that isn't set, so the precedence
+//! is flags over environment over file, and the file is honored everywhere the
+//! environment variables are.
+//!
+//! A `[serve]` table configures `penguin serve`: the bearer token of each client and how
+//! many requests a client may make per minute.
+//!
+//! `penguin config init` writes a commented template, and `penguin config show` prints
+//! every setting with the value in effect and where it came from.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The settings the file can hold, with the environment variable each one fills in and
+/// the value used when neither provides it.
+const SETTINGS: [(&str, &str, &str); 8] = [
+    ("complexity", "PENGUIN_COMPLEXITY", "basic"),
+    ("length", "PENGUIN_LENGTH", "12"),
+    ("whole_words", "PENGUIN_WHOLE_WORDS", "false"),
+    ("style", "PENGUIN_STYLE", "none"),
+    ("format", "PENGUIN_FORMAT", "plain"),
+    ("wordlist", "PENGUIN_WORDLIST", "none"),
+    ("never_reuse", "PENGUIN_NEVER_REUSE", "false"),
+    ("masked", "PENGUIN_MASKED", "false"),
+];
+
+const TEMPLATE: &str = r#"# Penguin configuration.
+#
+# Every setting is optional. PENGUIN_* environment variables override the values
+# here, and command-line flags override both.
+
+# Complexity level: basic, medium, hard, or penguin.
+# complexity = "basic"
+
+# Password length.
+# length = 12
+
+# Keep base words whole instead of mixing their characters.
+# whole_words = false
+
+# Named recipe: xkcd, corporate, legacy, or maximum.
+# style = "xkcd"
+
+# Output format, as accepted by --format.
+# format = "plain"
+
+# File of base words, one per line.
+# wordlist = "~/.config/penguin/words.txt"
+
+# Re-roll passwords handed out before, keeping salted hashes of every password
+# in ~/.local/share/penguin/issued.
+# never_reuse = false
+
+# Show passwords masked, with their entropy, until --reveal or a keypress.
+# masked = false
+
+# Settings for `penguin serve`. Keep the file readable only by you when it
+# holds tokens.
+# [serve]
+# Requests each client may make per minute; unset for no limit.
+# rate_limit = 60
+# Bearer tokens by client name, each rate-limited on its own.
+# [serve.tokens]
+# ci = "a-long-random-token"
+"#;
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct File {
+    complexity: Option<String>,
+    length: Option<usize>,
+    whole_words: Option<bool>,
+    style: Option<String>,
+    format: Option<String>,
+    wordlist: Option<String>,
+    never_reuse: Option<bool>,
+    masked: Option<bool>,
+    #[serde(default)]
+    serve: Serve,
+}
+
+/// The `[serve]` table.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Serve {
+    /// Requests each client may make per minute.
+    pub rate_limit: Option<u32>,
+    /// Bearer tokens by client name.
+    #[serde(default)]
+    pub tokens: BTreeMap<String, String>,
+}
+
+impl File {
+    fn get(&self, key: &str) -> Option<String> {
+        match key {
+            "complexity" => self.complexity.clone(),
+            "length" => self.length.map(|length| length.to_string()),
+            "whole_words" => self.whole_words.map(|whole| whole.to_string()),
+            "style" => self.style.clone(),
+            "format" => self.format.clone(),
+            "wordlist" => self.wordlist.as_deref().map(expand_home),
+            "never_reuse" => self.never_reuse.map(|never| never.to_string()),
+            "masked" => self.masked.map(|masked| masked.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Where a setting's effective value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Environment,
+    File,
+    Default,
+}
+
+/// A setting with the value in effect before command-line flags are applied.
+pub struct Resolved {
+    key: &'static str,
+    var: &'static str,
+    value: String,
+    source: Source,
+}
+
+/// Returns the path of the configuration file, whether or not it exists.
+pub fn path() -> Option<PathBuf> {
+    match std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir).join("penguin/config.toml")),
+        None => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config/penguin/config.toml")),
+    }
+}
+
+/// Loads the configuration file and fills in the environment variables it provides
+/// values for. Must run before the command line is parsed and before any thread starts.
+pub fn apply() -> Vec<Resolved> {
+    let file = load();
+    SETTINGS
+        .iter()
+        .map(|&(key, var, default)| {
+            if let Some(value) = std::env::var(var).ok().filter(|value| !value.is_empty()) {
+                return Resolved {
+                    key,
+                    var,
+                    value,
+                    source: Source::Environment,
+                };
+            }
+            match file.get(key) {
+                Some(value) => {
+                    std::env::set_var(var, &value);
+                    Resolved {
+                        key,
+                        var,
+                        value,
+                        source: Source::File,
+                    }
+                }
+                None => Resolved {
+                    key,
+                    var,
+                    value: default.to_string(),
+                    source: Source::Default,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Returns the `[serve]` table of the configuration file.
+pub fn serve() -> Serve {
+    load().serve
+}
+
+fn load() -> File {
+    let Some(path) = path() else {
+        return File::default();
+    };
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return File::default(),
+        Err(err) => crate::fail(format!("cannot read {}: {}", path.display(), err)),
+    };
+    toml::from_str(&contents)
+        .unwrap_or_else(|err| crate::fail(format!("invalid config {}: {}", path.display(), err)))
+}
+
+/// Writes the commented template to the configuration path, refusing to replace an
+/// existing file unless `force` is set.
+pub fn init(force: bool) {
+    let path = path().unwrap_or_else(|| {
+        crate::fail("cannot locate the config directory; set XDG_CONFIG_HOME or HOME")
+    });
+    if path.exists() && !force {
+        crate::fail(format!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        ));
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|err| crate::fail(format!("cannot create {}: {}", dir.display(), err)));
+    }
+    std::fs::write(&path, TEMPLATE)
+        .unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+    println!("Wrote {}", path.display());
+}
+
+/// Prints the effective value and origin of every setting.
+pub fn show(resolved: &[Resolved]) {
+    match path() {
+        Some(path) if path.exists() => println!("# config file: {}", path.display()),
+        Some(path) => println!("# config file: {} (not found)", path.display()),
+        None => println!("# config file: none"),
+    }
+    let width = SETTINGS
+        .iter()
+        .map(|(key, ..)| key.len())
+        .max()
+        .unwrap_or_default();
+    for setting in resolved {
+        let origin = match setting.source {
+            Source::Environment => format!("from {}", setting.var),
+            Source::File => String::from("from config file"),
+            Source::Default => String::from("default"),
+        };
+        println!(
+            "{:width$} = {:<20} # {}",
+            setting.key, setting.value, origin
+        );
+    }
+}
+
+/// Expands a leading `~/` to the home directory.
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
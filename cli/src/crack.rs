@@ -0,0 +1,45 @@
+//! Crack-time estimates against a choice of attackers, printed with `--crack-time`.
+//!
+//! One figure for "time to crack" is usually the most optimistic one. The table lists
+//! the average time to guess each password for the attacks that matter to the user, from
+//! a throttled login form to a GPU rig working through a leaked MD5 database. Times are
+//! based on the realistic estimate: the Markov guess bits with the `markov` feature, and
+//! the charset entropy otherwise.
+
+use penguin::strength::{self, Attacker};
+
+/// Bits a guesser has to work through for a password.
+pub fn bits(password: &str) -> f64 {
+    #[cfg(feature = "markov")]
+    {
+        penguin::markov::guess_bits(password)
+    }
+    #[cfg(not(feature = "markov"))]
+    {
+        strength::estimate_entropy(password).entropy_bits
+    }
+}
+
+/// Renders the table of every password, numbered like the plain output.
+pub fn report(passwords: &[String], attackers: &[Attacker]) -> String {
+    let width = attackers
+        .iter()
+        .map(|attacker| attacker.description().len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from("\n> Time to crack, on average:\n");
+    for (i, password) in passwords.iter().enumerate() {
+        let bits = bits(password);
+        out.push_str(&format!("   {}. {:.1} bits\n", i + 1, bits));
+        for attacker in attackers {
+            out.push_str(&format!(
+                "      {:<width$}  {}\n",
+                attacker.description(),
+                strength::format_duration(attacker.seconds_to_crack(bits)),
+                width = width
+            ));
+        }
+    }
+    out.push('\n');
+    out
+}
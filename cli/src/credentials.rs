@@ -0,0 +1,99 @@
+//! Matched username and password pairs for `penguin credentials`.
+//!
+//! Provisioning demo tenants needs both halves of every login. Generating them in one
+//! pass keeps the counts matched and guarantees the usernames in a batch are distinct.
+
+use clap::ValueEnum;
+use penguin::{mixer::PenguinMixer, pronounce, username};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// How many times a colliding username is regenerated before a digit is added.
+const MAX_COLLISIONS: usize = 100;
+
+/// How the pairs are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned columns for reading in a terminal
+    Table,
+    /// CSV with a "username","password" header
+    Csv,
+    /// A JSON array of {"username", "password"} objects
+    Json,
+}
+
+#[derive(Serialize)]
+struct Credential {
+    username: String,
+    password: String,
+}
+
+/// Generates `count` pairs, building passwords from the mixer when words are given and
+/// pronounceable ones otherwise, and renders them in the chosen format.
+pub fn run(words: &[String], mixer: &PenguinMixer, count: usize, digits: usize, format: Format) {
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let mut seen = HashSet::new();
+    let mut credentials = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut digits = digits;
+        let mut collisions = 0;
+        let username = loop {
+            let candidate = username::generate(digits);
+            if seen.insert(candidate.clone()) {
+                break candidate;
+            }
+            collisions += 1;
+            if collisions == MAX_COLLISIONS {
+                digits += 1;
+                collisions = 0;
+            }
+        };
+        let password = if words.is_empty() {
+            pronounce::generate(mixer.length).to_string()
+        } else {
+            mixer
+                .mix_password(&words)
+                .unwrap_or_else(|err| crate::fail(err.to_string()))
+        };
+        credentials.push(Credential { username, password });
+    }
+
+    print!("{}", render(&credentials, format));
+}
+
+fn render(credentials: &[Credential], format: Format) -> String {
+    match format {
+        Format::Table => {
+            let width = credentials
+                .iter()
+                .map(|c| c.username.len())
+                .chain(["username".len()])
+                .max()
+                .unwrap_or_default();
+            let mut out = format!("{:width$}  password\n", "username");
+            for credential in credentials {
+                out.push_str(&format!(
+                    "{:width$}  {}\n",
+                    credential.username, credential.password
+                ));
+            }
+            out
+        }
+        Format::Csv => {
+            let mut out = String::from("\"username\",\"password\"\n");
+            for credential in credentials {
+                out.push_str(&format!(
+                    "\"{}\",\"{}\"\n",
+                    credential.username,
+                    credential.password.replace('"', "\"\"")
+                ));
+            }
+            out
+        }
+        Format::Json => {
+            let json =
+                serde_json::to_string_pretty(credentials).expect("credentials serialize to JSON");
+            format!("{}\n", json)
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! `penguin daemon`: the JSON-RPC protocol of `penguin rpc` on a unix domain socket.
+//!
+//! Desktop integrations like launchers and browser hosts ask for a password far more
+//! often than it is worth spawning a process for, and a process's arguments are visible
+//! to every user. The daemon listens on a socket instead, `$XDG_RUNTIME_DIR/penguin.sock`
+//! unless `--socket` says otherwise, and serves each connection on its own thread, one
+//! request per line as on stdin.
+//!
+//! The socket is created readable and writable by its owner only, and every connection
+//! is checked against the peer's credentials as well: one from a process of another user
+//! is closed before a request is read. A stale socket left behind by an earlier daemon
+//! is replaced, but one that still accepts connections is left to its daemon.
+
+use std::io::BufReader;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+/// The socket used when `--socket` isn't given.
+pub fn default_socket() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("penguin.sock"),
+        None => crate::fail("XDG_RUNTIME_DIR is not set; pass --socket"),
+    }
+}
+
+/// Listens on the socket and serves connections until the process is stopped.
+pub fn run(socket: &Path) {
+    let listener = bind(socket).unwrap_or_else(|err| {
+        crate::fail(format!("cannot listen on {}: {}", socket.display(), err))
+    });
+    eprintln!(
+        "{}",
+        crate::i18n::trf("Listening on {0}", &[&socket.display()])
+    );
+
+    // SAFETY: getuid has no preconditions and cannot fail.
+    let owner = unsafe { libc::getuid() };
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                tracing::warn!(%err, "cannot accept a connection");
+                continue;
+            }
+        };
+        match peer_uid(&stream) {
+            Ok(uid) if uid == owner => {}
+            Ok(uid) => {
+                tracing::warn!(uid, "refused a connection from another user");
+                continue;
+            }
+            Err(err) => {
+                tracing::warn!(%err, "cannot read the peer's credentials");
+                continue;
+            }
+        }
+        std::thread::spawn(move || {
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(err) => {
+                    tracing::warn!(%err, "cannot read from a connection");
+                    return;
+                }
+            };
+            if let Err(err) = crate::rpc::serve(reader, &stream) {
+                tracing::debug!(%err, "a connection ended with an error");
+            }
+        });
+    }
+}
+
+/// Binds the socket, replacing a stale one, and restricts it to its owner.
+fn bind(socket: &Path) -> std::io::Result<UnixListener> {
+    if let Ok(metadata) = std::fs::symlink_metadata(socket) {
+        if !metadata.file_type().is_socket() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "the path exists and is not a socket",
+            ));
+        }
+        if UnixStream::connect(socket).is_ok() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                "another daemon is listening on it",
+            ));
+        }
+        std::fs::remove_file(socket)?;
+    }
+    let listener = UnixListener::bind(socket)?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Returns the user id of the process on the other end of the connection.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn peer_uid(stream: &UnixStream) -> std::io::Result<libc::uid_t> {
+    let mut credentials = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    // SAFETY: SO_PEERCRED fills a ucred, and len holds its size.
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut credentials as *mut libc::ucred).cast(),
+            &mut len,
+        )
+    };
+    if result != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(credentials.uid)
+}
+
+/// Returns the user id of the process on the other end of the connection.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn peer_uid(stream: &UnixStream) -> std::io::Result<libc::uid_t> {
+    let (mut uid, mut gid) = (0, 0);
+    // SAFETY: getpeereid writes the ids through the two pointers.
+    if unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(uid)
+}
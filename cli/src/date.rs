@@ -0,0 +1,98 @@
+//! Calendar dates for timestamps and expiry dates, counted in days since 1970-01-01 UTC.
+
+/// The current time in seconds since the Unix epoch.
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Today's date in UTC.
+pub fn today() -> i64 {
+    (now() / 86_400) as i64
+}
+
+/// The current UTC time as an RFC 3339 timestamp, such as `2024-05-01T12:30:00Z`.
+pub fn timestamp() -> String {
+    let secs = now();
+    let time = secs % 86_400;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format(today()),
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Formats a date as `YYYY-MM-DD`.
+pub fn format(days: i64) -> String {
+    // Howard Hinnant's days-to-civil conversion, shifted so years start in March.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Parses a `YYYY-MM-DD` date.
+pub fn parse(value: &str) -> Option<i64> {
+    let mut parts = value.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+    );
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // The inverse of the conversion in `format`.
+    let shifted = year - i64::from(month <= 2);
+    let era = shifted.div_euclid(400);
+    let yoe = shifted.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    // Days past the end of the month roll over into the next one; reject them.
+    (format(days) == format!("{:04}-{:02}-{:02}", year, month, day)).then_some(days)
+}
+
+/// Parses a span of days written as `30d`, `12w`, or `1y`.
+pub fn parse_span(value: &str) -> Result<i64, String> {
+    let invalid = || {
+        format!(
+            "invalid duration '{}'; use days, weeks, or years as in 90d, 12w, or 1y",
+            value
+        )
+    };
+    let end = value.char_indices().last().map_or(0, |(i, _)| i);
+    let (count, unit) = value.split_at(end);
+    let count = i64::from(count.parse::<u32>().map_err(|_| invalid())?);
+    match unit {
+        "d" => Ok(count),
+        "w" => Ok(count * 7),
+        "y" => Ok(count * 365),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses an expiry given as a span from today or as a `YYYY-MM-DD` date.
+pub fn parse_expiry(value: &str) -> Result<i64, String> {
+    match parse(value) {
+        Some(date) => Ok(date),
+        None => parse_span(value).map(|span| today() + span).map_err(|_| {
+            format!(
+                "invalid expiry '{}'; use a span such as 90d, 12w, or 1y, or a date such as 2027-01-31",
+                value
+            )
+        }),
+    }
+}
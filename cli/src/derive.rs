@@ -0,0 +1,189 @@
+//! The `penguin derive` command, which derives a site's password from a master password.
+//!
+//! Nothing is stored: the master password, the site, and the counter give the same
+//! password on every run, so `penguin derive github.com` works as a password manager
+//! without a vault, and `--counter 2` rotates the site's password. The master password is
+//! read at a hidden prompt, or as a line of stdin, and `--confirm` asks for it twice,
+//! since a typo gives a different password rather than an error. `--verify-file` guards
+//! against typos without the second prompt: on first use it asks twice and saves a tag of
+//! the master password there, and later runs refuse a master password that doesn't match
+//! the tag. A policy file and the composition flags shape the password; they have to be
+//! given the same way every time, as they change what is derived.
+//!
+//! `--from N -n COUNT` prints passwords `N` to `N + COUNT - 1` of the site's stream
+//! instead, for provisioning jobs that split a batch into shards or resume one.
+//!
+//! `penguin verify-vectors` derives the known-answer passwords of the derivation's
+//! version, or those of a file, and exits with status 1 unless every one matches, so
+//! packagers can check a build before anyone relies on it.
+
+use crate::rules::RuleArgs;
+use penguin::derive::{self, DeriveOptions};
+use penguin::policy::PasswordPolicy;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use zeroize::Zeroize;
+
+/// Which passwords of the site to derive.
+pub enum Which {
+    /// The password for a counter.
+    Counter(u32),
+    /// `count` passwords of the site's stream, starting at index `from`.
+    Stream { from: u64, count: u64 },
+}
+
+/// Prints the passwords derived for the site.
+pub fn run(
+    site: &str,
+    which: Which,
+    options: DeriveOptions,
+    policy_file: Option<&Path>,
+    rules: &RuleArgs,
+    confirm: bool,
+    verify_file: Option<&Path>,
+) {
+    let policy = policy_file.map(|path| {
+        std::fs::read_to_string(path)
+            .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err)))
+            .parse::<PasswordPolicy>()
+            .unwrap_or_else(|err| crate::fail(format!("{}: {}", path.display(), err)))
+    });
+    let options = DeriveOptions {
+        policy: rules.apply(policy),
+        ..options
+    };
+
+    let mut master = read_master("Master password: ");
+    let first_use = verify_file.is_some_and(|path| !path.exists());
+    if confirm || first_use {
+        let mut again = read_master("Master password again: ");
+        let same = penguin::compare(&master, &again);
+        again.zeroize();
+        if !same {
+            master.zeroize();
+            crate::fail(crate::i18n::tr("the master passwords don't match"));
+        }
+    }
+    if let Some(path) = verify_file {
+        check_master(&mut master, path, first_use);
+    }
+    let mut stdout = std::io::stdout().lock();
+    match which {
+        Which::Counter(counter) => {
+            let derived = derive::derive(&master, site, counter, &options);
+            master.zeroize();
+            print_derived(&mut stdout, derived);
+        }
+        Which::Stream { from, count } => {
+            let seed = derive::seed(&master, site);
+            master.zeroize();
+            let seed = seed.unwrap_or_else(|err| crate::fail(err));
+            // Each password is printed as soon as it is derived, so a long stream isn't
+            // held in memory and a failure keeps the ones before it.
+            for n in from..from.saturating_add(count) {
+                print_derived(&mut stdout, derive::derive_nth(&seed, n, &options));
+            }
+        }
+    }
+}
+
+/// Prints a derived password and wipes it, or fails with the error that stopped it.
+/// Stops quietly once the reader of a pipe has gone away, as `head` does.
+fn print_derived(out: &mut impl Write, derived: Result<String, penguin::PenguinError>) {
+    let mut password = derived.unwrap_or_else(|err| crate::fail(err));
+    let written = writeln!(out, "{}", password).and_then(|()| out.flush());
+    password.zeroize();
+    match written {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+        Err(err) => crate::fail(format!("cannot write output: {}", err)),
+    }
+}
+
+/// Saves a tag of the master password to the file on first use, and otherwise fails
+/// unless the master password matches the tag in it.
+fn check_master(master: &mut String, path: &Path, first_use: bool) {
+    if first_use {
+        let tag = derive::verification(master);
+        crate::output::write_private(path, format!("{}\n", tag).as_bytes());
+        eprintln!(
+            "{}",
+            crate::i18n::trf(
+                "saved a check of the master password to {0}",
+                &[&path.display()]
+            )
+        );
+        return;
+    }
+    let tag = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err)));
+    match derive::verify(master, &tag) {
+        Ok(true) => {}
+        Ok(false) => {
+            master.zeroize();
+            crate::fail(crate::i18n::trf(
+                "the master password doesn't match the one checked by {0}",
+                &[&path.display()],
+            ));
+        }
+        Err(err) => crate::fail(format!("{}: {}", path.display(), err)),
+    }
+}
+
+fn read_master(prompt: &'static str) -> String {
+    let mut typed = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password(crate::i18n::tr(prompt))
+    } else {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).map(|_| line)
+    }
+    .unwrap_or_else(|err| crate::fail(format!("cannot read the master password: {}", err)));
+    let master = typed.trim_end_matches(['\r', '\n']).to_string();
+    typed.zeroize();
+    if master.is_empty() {
+        crate::fail(crate::i18n::tr("no password was entered"));
+    }
+    master
+}
+
+/// Prints a line per known answer and exits non-zero if any didn't match.
+pub fn verify_vectors(file: Option<&Path>) {
+    let text = match file {
+        Some(path) => std::fs::read_to_string(path)
+            .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err))),
+        None => String::from(derive::KNOWN_ANSWERS),
+    };
+    let answers = derive::known_answers(&text).unwrap_or_else(|err| crate::fail(err));
+    let mut failed = 0;
+    for answer in &answers {
+        let passed = answer.passes();
+        let mode = match answer.mode {
+            derive::Mode::Counter => "counter",
+            derive::Mode::Stream => "stream",
+        };
+        println!(
+            "{} {} {} {} {} {}",
+            if passed { "PASS" } else { "FAIL" },
+            mode,
+            answer.site,
+            answer.index,
+            format!("{:?}", answer.options.complexity).to_lowercase(),
+            answer.options.length
+        );
+        failed += usize::from(!passed);
+    }
+
+    if failed > 0 {
+        eprintln!(
+            "{}",
+            crate::i18n::trf(
+                "error: {0}",
+                &[&crate::i18n::trf(
+                    "{0} of {1} known answers of derivation version {2} didn't match",
+                    &[&failed, &answers.len(), &derive::VERSION],
+                )],
+            )
+        );
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,220 @@
+//! Encryption of rendered output for safe handoff.
+//!
+//! With the `age` feature, `--encrypt-to <recipient>` (repeatable) encrypts the output to
+//! one or more age recipients. Files are written in age's binary format and output on
+//! stdout is ASCII-armored, so either can be decrypted with `age -d`.
+//!
+//! `--gpg-recipient <key>` (repeatable) pipes the output through the system `gpg` instead,
+//! for organizations standardized on OpenPGP. The same binary/armored split applies, and
+//! the recipients' public keys must already be in the user's keyring.
+//!
+//! `--systemd-encrypt` hands a `systemd-cred` credential to `systemd-creds encrypt`, which
+//! binds it to the host key or TPM. Files can be used with `LoadCredentialEncrypted=`,
+//! and on stdout the result is printed as a `SetCredentialEncrypted=` line for unit files.
+//!
+//! The `ansible-vault` format is encrypted in-process as Ansible Vault 1.1: PBKDF2-SHA256
+//! stretches the vault password into an AES-256-CTR key, an HMAC-SHA256 key, and a
+//! counter, exactly as `ansible-vault encrypt` does, so Ansible decrypts it unaided.
+
+use penguin::hex;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Encrypts the data to every recipient, armoring it when `armor` is set.
+#[cfg(feature = "age")]
+pub fn age(recipients: &[String], data: &[u8], armor: bool) -> Result<Vec<u8>, String> {
+    let recipients = recipients
+        .iter()
+        .map(|recipient| {
+            recipient
+                .parse::<age::x25519::Recipient>()
+                .map_err(|err| format!("invalid age recipient '{}': {}", recipient, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let encryptor = age::Encryptor::with_recipients(
+        recipients
+            .iter()
+            .map(|recipient| recipient as &dyn age::Recipient),
+    )
+    .map_err(|err| format!("cannot encrypt: {}", err))?;
+
+    let format = if armor {
+        age::armor::Format::AsciiArmor
+    } else {
+        age::armor::Format::Binary
+    };
+    let mut out = Vec::new();
+    let encrypt = || -> std::io::Result<()> {
+        let armored = age::armor::ArmoredWriter::wrap_output(&mut out, format)?;
+        let mut writer = encryptor.wrap_output(armored)?;
+        writer.write_all(data)?;
+        writer.finish()?.finish()?;
+        Ok(())
+    };
+    encrypt().map_err(|err| format!("cannot encrypt: {}", err))?;
+    Ok(out)
+}
+
+/// Encrypts the data to every recipient with `gpg`, armoring it when `armor` is set.
+pub fn gpg(recipients: &[String], data: &[u8], armor: bool) -> Result<Vec<u8>, String> {
+    let mut command = Command::new("gpg");
+    command.args(["--batch", "--yes", "--encrypt", "--output", "-"]);
+    if armor {
+        command.arg("--armor");
+    }
+    for recipient in recipients {
+        command.args(["--recipient", recipient]);
+    }
+
+    pipe(command, data, "gpg")
+}
+
+/// Runs the command with the data on stdin and returns what it wrote to stdout.
+fn pipe(mut command: Command, data: &[u8], program: &str) -> Result<Vec<u8>, String> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run {}: {}", program, err))?;
+    // Feed stdin from a separate thread so a large output can't deadlock on full pipes.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = data.to_vec();
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("cannot run {}: {}", program, err))?;
+    let written = writer.join().expect("the writer thread does not panic");
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed to encrypt: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    written.map_err(|err| format!("cannot talk to {}: {}", program, err))?;
+    Ok(output.stdout)
+}
+
+/// Encrypts a credential with `systemd-creds`, printing it as a unit file setting when
+/// `pretty` is set.
+pub fn systemd_creds(name: &str, data: &[u8], pretty: bool) -> Result<Vec<u8>, String> {
+    let mut command = Command::new("systemd-creds");
+    command.arg("encrypt").arg(format!("--name={}", name));
+    if pretty {
+        command.arg("--pretty");
+    }
+    command.args(["-", "-"]);
+    pipe(command, data, "systemd-creds")
+}
+
+/// PBKDF2 rounds Ansible uses to stretch vault passwords.
+const ANSIBLE_VAULT_ROUNDS: u32 = 10_000;
+
+/// Encrypts the data with a vault password into the text of an Ansible Vault 1.1 file.
+pub fn ansible_vault(password: &[u8], data: &[u8]) -> String {
+    use rand::RngCore;
+
+    let mut salt = [0u8; 32];
+    penguin::entropy::rng().fill_bytes(&mut salt);
+    ansible_vault_with_salt(password, &salt, data)
+}
+
+fn ansible_vault_with_salt(password: &[u8], salt: &[u8; 32], data: &[u8]) -> String {
+    use aes::cipher::{KeyIvInit, StreamCipher};
+    use hmac::Mac;
+
+    let mut keys = [0u8; 80];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password, salt, ANSIBLE_VAULT_ROUNDS, &mut keys);
+    let (cipher_key, rest) = keys.split_at(32);
+    let (mac_key, counter) = rest.split_at(32);
+
+    // Ansible pads to the AES block size even though counter mode doesn't need it.
+    let padding = 16 - data.len() % 16;
+    let mut ciphertext = data.to_vec();
+    ciphertext.extend(std::iter::repeat_n(padding as u8, padding));
+    ctr::Ctr128BE::<aes::Aes256>::new(cipher_key.into(), counter.into())
+        .apply_keystream(&mut ciphertext);
+    let mut mac =
+        hmac::Hmac::<sha2::Sha256>::new_from_slice(mac_key).expect("HMAC takes keys of any length");
+    mac.update(&ciphertext);
+
+    let body = format!(
+        "{}\n{}\n{}",
+        hex::encode(salt),
+        hex::encode(&mac.finalize().into_bytes()),
+        hex::encode(&ciphertext)
+    );
+    let mut out = String::from("$ANSIBLE_VAULT;1.1;AES256\n");
+    for line in hex::encode(body.as_bytes()).as_bytes().chunks(80) {
+        out.push_str(std::str::from_utf8(line).expect("hex is ASCII"));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::{KeyIvInit, StreamCipher};
+    use hmac::Mac;
+
+    const PASSWORD: &[u8] = b"correct horse battery staple";
+    const PLAINTEXT: &[u8] = b"db: 'hunter2-Xy9!'\napi: 'p@ss word'\n";
+
+    /// Made by Python's `cryptography` following `VaultAES256.encrypt` in
+    /// ansible/parsing/vault, with the salt fixed to the bytes 0 through 31.
+    const FIXTURE: &str = concat!(
+        "$ANSIBLE_VAULT;1.1;AES256\n",
+        "30303031303230333034303530363037303830393061306230633064306530663130313131323133\n",
+        "3134313531363137313831393161316231633164316531660a653536633761323561633432323165\n",
+        "31623838636338363163343631366438616637616231346562356636333162656538633163653838\n",
+        "6234333266626637320a316333376461363337646161323831353561313234653237643336386265\n",
+        "33376331643837333664613163336239663262343038353332643462633236363332353639383063\n",
+        "3635343434353566306131376135653832343433633234393937\n",
+    );
+
+    fn unhex(text: &str) -> Vec<u8> {
+        hex::decode(text).expect("valid hex")
+    }
+
+    /// Decrypts a vault file the way `VaultAES256.decrypt` does, checking the HMAC and
+    /// the PKCS#7 padding.
+    fn decrypt(password: &[u8], vault: &str) -> Vec<u8> {
+        let mut lines = vault.lines();
+        assert_eq!(lines.next(), Some("$ANSIBLE_VAULT;1.1;AES256"));
+        let body = String::from_utf8(unhex(&lines.collect::<String>())).expect("hex body");
+        let fields: Vec<&str> = body.split('\n').collect();
+        assert_eq!(fields.len(), 3);
+        let (salt, tag, mut ciphertext) = (unhex(fields[0]), unhex(fields[1]), unhex(fields[2]));
+
+        let mut keys = [0u8; 80];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password, &salt, 10_000, &mut keys);
+        let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(&keys[32..64]).unwrap();
+        mac.update(&ciphertext);
+        mac.verify_slice(&tag).expect("the HMAC matches");
+        ctr::Ctr128BE::<aes::Aes256>::new(keys[..32].into(), keys[64..].into())
+            .apply_keystream(&mut ciphertext);
+
+        let padding = *ciphertext.last().expect("not empty") as usize;
+        assert!((1..=16).contains(&padding));
+        let (plaintext, pad) = ciphertext.split_at(ciphertext.len() - padding);
+        assert!(pad.iter().all(|&byte| byte as usize == padding));
+        plaintext.to_vec()
+    }
+
+    #[test]
+    fn ansible_vault_matches_ansible_output() {
+        let salt: [u8; 32] = std::array::from_fn(|i| i as u8);
+        assert_eq!(ansible_vault_with_salt(PASSWORD, &salt, PLAINTEXT), FIXTURE);
+    }
+
+    #[test]
+    fn ansible_vault_decrypts() {
+        assert_eq!(decrypt(PASSWORD, FIXTURE), PLAINTEXT);
+        for data in [&b""[..], b"0123456789abcdef", PLAINTEXT] {
+            assert_eq!(decrypt(PASSWORD, &ansible_vault(PASSWORD, data)), data);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! A git credential helper backed by the OS keyring, for `penguin git-credential`.
+//!
+//! With `git config credential.helper "penguin git-credential"`, git runs the helper
+//! with `get`, `store`, or `erase` and writes the protocol, host, and any known user name
+//! as `key=value` lines on stdin. Credentials are kept in the keyring under the service
+//! `penguin-git`, one entry per `protocol://host` (with the path when git sends one), so
+//! `get` answers by host even when git doesn't know the user name yet.
+//!
+//! When `get` finds nothing and a terminal is attached, the helper offers to generate a
+//! new password, shows it once on the terminal so it can be set on the server, and saves
+//! it. Without a terminal it stays silent and git falls back to asking.
+
+use crate::tty::Terminal;
+use penguin::{random_password, Charset};
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+/// The keyring service every git credential is saved under.
+const SERVICE: &str = "penguin-git";
+
+/// How long the passwords offered for new hosts are.
+const GENERATED_LENGTH: usize = 24;
+
+/// The operation git asks the helper to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Action {
+    /// Print the saved credential for a host
+    Get,
+    /// Save a credential git has used successfully
+    Store,
+    /// Delete a credential the server rejected
+    Erase,
+}
+
+/// Reads git's request from stdin and performs the action.
+pub fn run(action: Action) {
+    let request = read_request(io::stdin().lock())
+        .unwrap_or_else(|err| crate::fail(format!("cannot read the request: {}", err)));
+    let Some(account) = account(&request) else {
+        // Git only omits the host for helpers it can't use anyway, like file:// remotes.
+        return;
+    };
+    let entry = keyring::Entry::new(SERVICE, &account)
+        .unwrap_or_else(|err| crate::fail(format!("cannot open the keyring: {}", err)));
+    let username = request.get("username").map(String::as_str);
+
+    match action {
+        Action::Get => {
+            let saved = load(&entry).filter(|(user, _)| username.is_none_or(|name| name == user));
+            let credential = saved.or_else(|| offer(&entry, &account, username));
+            if let Some((user, password)) = credential {
+                println!("username={}\npassword={}", user, password);
+            }
+        }
+        Action::Store => {
+            let (Some(user), Some(password)) = (username, request.get("password")) else {
+                return;
+            };
+            save(&entry, user, password);
+        }
+        Action::Erase => {
+            if load(&entry).is_some_and(|(user, _)| username.is_none_or(|name| name == user)) {
+                entry.delete_credential().unwrap_or_else(|err| {
+                    crate::fail(format!("cannot erase {}: {}", account, err))
+                });
+            }
+        }
+    }
+}
+
+/// Parses `key=value` lines up to the first empty line or the end of input.
+fn read_request(input: impl BufRead) -> io::Result<HashMap<String, String>> {
+    let mut request = HashMap::new();
+    for line in input.lines() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            request.insert(key.to_string(), value.to_string());
+        }
+    }
+    Ok(request)
+}
+
+/// Returns the keyring account for the request: `protocol://host`, followed by the path
+/// when git sends one (with `credential.useHttpPath`).
+fn account(request: &HashMap<String, String>) -> Option<String> {
+    let host = request.get("host").filter(|host| !host.is_empty())?;
+    let protocol = request.get("protocol").map_or("https", String::as_str);
+    Some(match request.get("path").filter(|path| !path.is_empty()) {
+        Some(path) => format!("{}://{}/{}", protocol, host, path),
+        None => format!("{}://{}", protocol, host),
+    })
+}
+
+/// Returns the saved user name and password, stored one per line.
+fn load(entry: &keyring::Entry) -> Option<(String, String)> {
+    match entry.get_password() {
+        Ok(secret) => {
+            let (user, password) = secret.split_once('\n')?;
+            Some((user.to_string(), password.to_string()))
+        }
+        Err(keyring::Error::NoEntry) => None,
+        Err(err) => crate::fail(format!("cannot read the keyring: {}", err)),
+    }
+}
+
+fn save(entry: &keyring::Entry, user: &str, password: &str) {
+    entry
+        .set_password(&format!("{}\n{}", user, password))
+        .unwrap_or_else(|err| crate::fail(format!("cannot save to the keyring: {}", err)));
+}
+
+/// Asks on the terminal whether to generate a password for the host, and saves and
+/// returns it with the user name. Returns `None` without a terminal or when declined.
+fn offer(
+    entry: &keyring::Entry,
+    account: &str,
+    username: Option<&str>,
+) -> Option<(String, String)> {
+    let mut tty = Terminal::open()?;
+    let answer = tty.ask(&crate::i18n::trf(
+        "No saved password for {0}. Generate one? [y/N] ",
+        &[&account],
+    ))?;
+    if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+        return None;
+    }
+    let user = match username {
+        Some(user) => user.to_string(),
+        None => tty
+            .ask(&crate::i18n::trf("Username for {0}: ", &[&account]))
+            .filter(|user| !user.is_empty())?,
+    };
+
+    let password = random_password(GENERATED_LENGTH, &Charset::default());
+    save(entry, &user, &password);
+    tty.say(&format!(
+        "\n   {}\n\n{}\n",
+        password,
+        crate::i18n::trf(
+            "Saved to the keyring. Set it as the password of {0} on {1} before git uses it.",
+            &[&user, &account],
+        )
+    ));
+    Some((user, password))
+}
@@ -0,0 +1,129 @@
+//! gRPC server mode, built with the `grpc` feature.
+//!
+//! `proto/penguin.proto` defines the service, which offers the operations of the HTTP
+//! API with the same defaults and error messages, for platforms that standardize on gRPC.
+//! Every call must carry `authorization: Bearer <token>` metadata. Like the HTTP server,
+//! nothing about a call but its method and status is logged.
+
+// tonic's interceptors and handlers return `Status` by value.
+#![allow(clippy::result_large_err)]
+
+use crate::api;
+use penguin::Charset;
+use proto::penguin_server::{Penguin, PenguinServer};
+use proto::{
+    AnalyzeReply, AnalyzeRequest, GenerateReply, GenerateRequest, PassphraseReply,
+    PassphraseRequest,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("penguin.v1");
+}
+
+/// Starts the server and handles calls until the process is terminated.
+///
+/// When no token is configured a random one is generated and printed to stderr, so
+/// the service is never reachable without authentication.
+pub fn run(listen: SocketAddr, token: Option<String>) {
+    let token = token.unwrap_or_else(|| {
+        let token = penguin::random_password(32, &Charset::ALPHANUMERIC);
+        eprintln!("penguin: generated API token: {}", token);
+        token
+    });
+    let authorize = move |request: Request<()>| {
+        let given = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match given {
+            Some(given) if penguin::compare(given, &token) => Ok(request),
+            _ => Err(Status::unauthenticated("missing or invalid bearer token")),
+        }
+    };
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| crate::fail(format!("cannot start the runtime: {}", err)));
+    eprintln!("penguin: listening on grpc://{}", listen);
+    runtime
+        .block_on(
+            tonic::transport::Server::builder()
+                .add_service(PenguinServer::with_interceptor(Service, authorize))
+                .serve(listen),
+        )
+        .unwrap_or_else(|err| crate::fail(format!("cannot listen on {}: {}", listen, err)));
+}
+
+struct Service;
+
+#[tonic::async_trait]
+impl Penguin for Service {
+    async fn generate(
+        &self,
+        request: Request<GenerateRequest>,
+    ) -> Result<Response<GenerateReply>, Status> {
+        let request = request.into_inner();
+        let mut params = json!({ "words": request.words });
+        set(&mut params, "count", request.count);
+        set(&mut params, "complexity", request.complexity);
+        set(&mut params, "whole_words", request.whole_words);
+        set(&mut params, "length", request.length);
+        call("Generate", "generate", params)
+    }
+
+    async fn analyze(
+        &self,
+        request: Request<AnalyzeRequest>,
+    ) -> Result<Response<AnalyzeReply>, Status> {
+        let request = request.into_inner();
+        let params = json!({ "password": request.password, "personal": request.personal });
+        call("Analyze", "analyze", params)
+    }
+
+    async fn passphrase(
+        &self,
+        request: Request<PassphraseRequest>,
+    ) -> Result<Response<PassphraseReply>, Status> {
+        let request = request.into_inner();
+        let mut params = json!({ "words": request.words });
+        set(&mut params, "count", request.count);
+        set(&mut params, "words_per_phrase", request.words_per_phrase);
+        set(&mut params, "separator", request.separator);
+        call("Passphrase", "passphrase", params)
+    }
+}
+
+/// Adds an optional field to the JSON parameters only when it was set, so the API's
+/// defaults apply otherwise.
+fn set(params: &mut Value, key: &str, value: Option<impl Into<Value>>) {
+    if let Some(value) = value {
+        params[key] = value.into();
+    }
+}
+
+/// Runs an API operation and converts its JSON result into the reply message.
+fn call<T: DeserializeOwned>(
+    method: &str,
+    operation: &str,
+    params: Value,
+) -> Result<Response<T>, Status> {
+    let outcome = match api::call(operation, params) {
+        Some(Ok(result)) => serde_json::from_value(result)
+            .map(Response::new)
+            .map_err(|err| Status::internal(err.to_string())),
+        Some(Err(message)) => Err(Status::invalid_argument(message)),
+        None => Err(Status::unimplemented(operation)),
+    };
+    let status = match &outcome {
+        Ok(_) => tonic::Code::Ok,
+        Err(status) => status.code(),
+    };
+    eprintln!("penguin: {} {:?}", method, status);
+    outcome
+}
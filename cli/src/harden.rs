@@ -0,0 +1,44 @@
+//! Process hardening applied with `--hardened`, or on every run in builds with the
+//! `hardened` feature.
+//!
+//! A crash while a password is in memory can write it into a core file, and any
+//! process running as the same user can read it through a debugger. Before any secret
+//! is generated, hardening sets the core file size limit to zero and, on Linux, marks
+//! the process as not dumpable, which also blocks `ptrace` attachment by unprivileged
+//! processes. On macOS the process denies debugger attachment instead. Other platforms
+//! only get the core file limit.
+
+/// Disables core dumps and debugger attachment for the rest of the process's life.
+pub fn apply() {
+    #[cfg(unix)]
+    {
+        let limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        // SAFETY: setrlimit only reads the limit passed by reference.
+        if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &limit) } != 0 {
+            crate::fail(format!(
+                "cannot disable core dumps: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        // SAFETY: PR_SET_DUMPABLE takes a single integer argument.
+        if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0) } != 0 {
+            crate::fail(format!(
+                "cannot mark the process as not dumpable: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // SAFETY: PT_DENY_ATTACH ignores the pid, address, and data arguments.
+        unsafe { libc::ptrace(libc::PT_DENY_ATTACH, 0, std::ptr::null_mut(), 0) };
+    }
+}
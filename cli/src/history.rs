@@ -0,0 +1,137 @@
+//! Expiry history of generated passwords, and the `penguin history` subcommands.
+//!
+//! Rotation only happens if someone remembers when it is due. Every password generated
+//! with `--expires` adds one JSON line to `$XDG_DATA_HOME/penguin/history.jsonl`
+//! (`~/.local/share/penguin/history.jsonl` when the variable is unset) with its label,
+//! where it was stored, when it was made, and when it expires. Like the audit log, the
+//! history never holds the password itself.
+//!
+//! `penguin history due` lists the entries that have expired or will soon. A label that
+//! was generated again since counts as rotated, so only its latest entry is listed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// One generated password as recorded in the history.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    label: Option<String>,
+    /// Where the password went, such as `vault:secret/data/ci/db#password`, when it
+    /// was stored rather than printed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    store: Option<String>,
+    created: String,
+    expires: String,
+}
+
+/// Returns the path of the history file, whether or not it exists.
+pub fn path() -> Option<PathBuf> {
+    match std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir).join("penguin/history.jsonl")),
+        None => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".local/share/penguin/history.jsonl")),
+    }
+}
+
+/// Appends one record per password, labeled in order, that expires on `expires`. Does
+/// nothing for passwords generated without `--expires`.
+pub fn record(expires: Option<i64>, count: usize, labels: &[String], store: Option<&str>) {
+    let Some(expires) = expires else {
+        return;
+    };
+    let path = path().unwrap_or_else(|| {
+        crate::fail("cannot locate the data directory; set XDG_DATA_HOME or HOME")
+    });
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|err| crate::fail(format!("cannot create {}: {}", dir.display(), err)));
+    }
+
+    let created = crate::date::timestamp();
+    let expires = crate::date::format(expires);
+    let mut lines = String::new();
+    for i in 0..count {
+        let record = Record {
+            label: labels.get(i).cloned(),
+            store: store.map(String::from),
+            created: created.clone(),
+            expires: expires.clone(),
+        };
+        lines.push_str(&format!(
+            "{}\n",
+            serde_json::to_string(&record).unwrap_or_default()
+        ));
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+}
+
+/// Prints the latest entry of every label that has expired or expires within
+/// `within` days, soonest first.
+pub fn due(within: i64) {
+    let Some(path) = path() else {
+        return;
+    };
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => crate::fail(format!("cannot read {}: {}", path.display(), err)),
+    };
+
+    let mut latest: HashMap<String, Record> = HashMap::new();
+    let mut unlabeled = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = serde_json::from_str(line).unwrap_or_else(|err| {
+            crate::fail(format!("{}:{}: {}", path.display(), number + 1, err))
+        });
+        // Lines are appended in order, so a later record for a label replaces it.
+        match record.label.clone().or_else(|| record.store.clone()) {
+            Some(name) => {
+                latest.insert(name, record);
+            }
+            None => unlabeled.push(record),
+        }
+    }
+
+    let today = crate::date::today();
+    let mut due: Vec<(i64, Record)> = latest
+        .into_values()
+        .chain(unlabeled)
+        .filter_map(|record| {
+            let expires = crate::date::parse(&record.expires)?;
+            (expires - today <= within).then_some((expires, record))
+        })
+        .collect();
+    due.sort_by_key(|(expires, _)| *expires);
+
+    if due.is_empty() {
+        println!("{}", crate::i18n::tr("Nothing is due for rotation."));
+        return;
+    }
+    for (expires, record) in due {
+        let days = expires - today;
+        let status = match days {
+            0 => crate::i18n::tr("expires today").to_string(),
+            days if days < 0 => crate::i18n::trf("expired {0} days ago", &[&-days]),
+            days => crate::i18n::trf("due in {0} days", &[&days]),
+        };
+        let name = match (&record.label, &record.store) {
+            (Some(label), Some(store)) => format!("{} ({})", label, store),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => format!("created {}", record.created),
+        };
+        println!("{}  {:<20}  {}", record.expires, status, name);
+    }
+}
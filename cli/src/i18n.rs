@@ -0,0 +1,111 @@
+//! Translations of the command-line tool's messages, enabled by the `i18n` feature.
+//!
+//! Messages are looked up gettext-style: the English text is the message id, and each
+//! language ships a `.po` catalog under `cli/src/locales` mapping ids to translations.
+//! Placeholders are written `{0}`, `{1}`, and so on, so a translation can reorder them.
+//! Without the feature, or when a catalog has no entry, the English text is used.
+//!
+//! The language comes from `--lang` or, failing that, from `LC_ALL`, `LC_MESSAGES`, or
+//! `LANG`, in that order.
+
+#[cfg(feature = "i18n")]
+use std::collections::HashMap;
+#[cfg(feature = "i18n")]
+use std::sync::OnceLock;
+
+/// Catalogs by language code.
+#[cfg(feature = "i18n")]
+const CATALOGS: [(&str, &str); 3] = [
+    ("de", include_str!("locales/de.po")),
+    ("es", include_str!("locales/es.po")),
+    ("fr", include_str!("locales/fr.po")),
+];
+
+#[cfg(feature = "i18n")]
+static CATALOG: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Selects the language for the rest of the run; `None` falls back to the locale
+/// environment variables. Only the first call has an effect.
+#[cfg(feature = "i18n")]
+pub fn init(lang: Option<&str>) {
+    let lang = lang.map(String::from).or_else(|| {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+    });
+    let code = lang
+        .as_deref()
+        .unwrap_or_default()
+        .split(['_', '.', '-', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let catalog = CATALOGS
+        .iter()
+        .find(|(language, _)| *language == code)
+        .map(|(_, po)| parse(po))
+        .unwrap_or_default();
+    let _ = CATALOG.set(catalog);
+}
+
+/// Returns the translation of a message, or the message itself.
+pub fn tr(msgid: &'static str) -> &'static str {
+    #[cfg(feature = "i18n")]
+    if let Some(msgstr) = CATALOG.get().and_then(|catalog| catalog.get(msgid)) {
+        return msgstr;
+    }
+    msgid
+}
+
+/// Translates a message and fills in its `{0}`, `{1}`, ... placeholders.
+pub fn trf(msgid: &'static str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut message = tr(msgid).to_string();
+    for (i, arg) in args.iter().enumerate() {
+        message = message.replace(&format!("{{{}}}", i), &arg.to_string());
+    }
+    message
+}
+
+/// Parses the `msgid`/`msgstr` pairs of a `.po` catalog, skipping untranslated entries.
+#[cfg(feature = "i18n")]
+fn parse(po: &str) -> HashMap<String, String> {
+    let mut catalog = HashMap::new();
+    let mut msgid = None;
+    for line in po.lines().map(str::trim) {
+        if let Some(id) = line.strip_prefix("msgid ") {
+            msgid = Some(unquote(id));
+        } else if let Some(msgstr) = line.strip_prefix("msgstr ") {
+            if let Some(id) = msgid.take() {
+                let msgstr = unquote(msgstr);
+                if !id.is_empty() && !msgstr.is_empty() {
+                    catalog.insert(id, msgstr);
+                }
+            }
+        }
+    }
+    catalog
+}
+
+#[cfg(feature = "i18n")]
+fn unquote(text: &str) -> String {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text);
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(escaped) => out.push(escaped),
+            None => {}
+        }
+    }
+    out
+}
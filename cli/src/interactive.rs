@@ -0,0 +1,127 @@
+//! The accept/re-roll loop of `penguin generate --interactive`.
+//!
+//! When only one password is needed, generating a batch and picking from it leaves the
+//! rejected candidates on screen. The loop shows one candidate at a time on stderr
+//! instead: `y` accepts it, `c` accepts it and copies it to the clipboard, `n` draws
+//! another, `e` changes the length, complexity, or whole-word mode, and `q` gives up.
+//! Answers are read a line at a time, so each key is followed by Enter.
+//!
+//! Candidates are shown masked, or only in part with `--mask`, so a screen share doesn't
+//! expose every one of them. `r` shows the current candidate in full for a few seconds
+//! and erases it again, since a terminal can't tell how long a key is held. `--reveal`
+//! shows every candidate in full.
+
+use crate::output::OutputArgs;
+use penguin::mask;
+use penguin::mixer::{ComplexityLevel, PenguinMixer};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::time::Duration;
+
+/// How long `r` shows a candidate in full before erasing it.
+const REVEAL_FOR: Duration = Duration::from_secs(5);
+
+/// What to do with the accepted password.
+pub enum Accepted {
+    /// Hand it on to the usual output.
+    Print(String),
+    /// Put it on the clipboard instead of printing it.
+    Copy(String),
+}
+
+/// Shows candidates until one is accepted, changing the mixer when asked to, and returns
+/// it. Exits when the user quits or stdin closes.
+pub fn choose(words: &[&str], mixer: &mut PenguinMixer, output: &OutputArgs) -> Accepted {
+    if !io::stdin().is_terminal() {
+        crate::fail(crate::i18n::tr("--interactive needs a terminal on stdin"));
+    }
+    loop {
+        let candidate = mixer
+            .mix_password(words)
+            .unwrap_or_else(|err| crate::fail(err.to_string()));
+        let bits = format!(
+            "{:.1}",
+            penguin::strength::estimate_entropy(&candidate).entropy_bits
+        );
+        let shown = if output.reveal {
+            candidate.clone()
+        } else {
+            let (first, last) = output.mask.unwrap_or((0, 0));
+            mask::mask(&candidate, first, last)
+        };
+        eprintln!("\n   {}", shown);
+        eprintln!("   {}", crate::i18n::trf("about {0} bits", &[&bits]));
+        loop {
+            match ask(crate::i18n::tr(
+                "Accept? [y]es, [c]opy, [n]ext, [r]eveal, [e]dit, [q]uit: ",
+            ))
+            .as_str()
+            {
+                "y" | "yes" => return Accepted::Print(candidate),
+                "c" | "copy" => return Accepted::Copy(candidate),
+                "r" | "reveal" => reveal(&candidate),
+                "e" | "edit" => {
+                    edit(words, mixer);
+                    break;
+                }
+                "q" | "quit" => std::process::exit(1),
+                _ => break,
+            }
+        }
+    }
+}
+
+/// Shows the candidate in full below the prompt for [`REVEAL_FOR`], then erases it.
+fn reveal(candidate: &str) {
+    let term = console::Term::stderr();
+    let _ = term.write_line(&format!("   {}", candidate));
+    std::thread::sleep(REVEAL_FOR);
+    let _ = term.clear_last_lines(1);
+}
+
+/// Asks for new settings, keeping the current ones for empty answers and anything the
+/// mixer rejects.
+fn edit(words: &[&str], mixer: &mut PenguinMixer) {
+    let previous = (mixer.length, mixer.complexity, mixer.use_whole_words);
+
+    let answer = ask(crate::i18n::trf("Length [{0}]: ", &[&mixer.length]));
+    if let Ok(length) = answer.parse() {
+        mixer.length = length;
+    } else if !answer.is_empty() {
+        eprintln!("{}", crate::i18n::trf("'{0}' is not a length", &[&answer]));
+    }
+    let current = format!("{:?}", mixer.complexity).to_lowercase();
+    let answer = ask(crate::i18n::trf("Complexity [{0}]: ", &[&current]));
+    if let Some(level) = crate::complexity_from_name(&answer) {
+        mixer.complexity = level;
+    } else if !answer.is_empty() {
+        eprintln!(
+            "{}",
+            crate::i18n::trf("unknown complexity '{0}'", &[&answer])
+        );
+    }
+    if !matches!(mixer.complexity, ComplexityLevel::Penguin) {
+        let current = if mixer.use_whole_words { "y" } else { "n" };
+        match ask(crate::i18n::trf("Whole words [{0}]: ", &[&current])).as_str() {
+            "y" | "yes" => mixer.use_whole_words = true,
+            "n" | "no" => mixer.use_whole_words = false,
+            _ => {}
+        }
+    }
+
+    if let Err(err) = mixer.validate(words) {
+        eprintln!("{}", crate::i18n::trf("error: {0}", &[&err]));
+        (mixer.length, mixer.complexity, mixer.use_whole_words) = previous;
+    }
+}
+
+/// Prints a prompt on stderr and returns the trimmed, lowercased answer. Exits when stdin
+/// closes.
+fn ask(prompt: impl AsRef<str>) -> String {
+    eprint!("{}", prompt.as_ref());
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    match io::stdin().lock().read_line(&mut answer) {
+        Ok(0) | Err(_) => std::process::exit(1),
+        Ok(_) => answer.trim().to_lowercase(),
+    }
+}
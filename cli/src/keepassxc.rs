@@ -0,0 +1,83 @@
+//! Saving generated passwords straight into a KeePassXC database.
+//!
+//! Importing a CSV export means the passwords sit in a plaintext file until it is
+//! deleted. `--save-keepassxc <db.kdbx> --entry <group/title>` instead drives
+//! `keepassxc-cli add`, which adds the entry to an existing database. The database
+//! password is asked for at a hidden prompt, and both it and the new password are fed to
+//! `keepassxc-cli` on stdin, so neither appears in the process list or on disk.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// An entry to add to a KeePassXC database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeepassEntry {
+    pub database: PathBuf,
+    /// Path of the entry within the database, such as `Servers/db01`; the groups must
+    /// already exist.
+    pub entry: String,
+    pub username: Option<String>,
+    /// Key file that unlocks the database along with its password.
+    pub key_file: Option<PathBuf>,
+    pub notes: String,
+}
+
+/// Prompts for the database password and adds the entry with the password.
+pub fn save(entry: &KeepassEntry, password: &str) -> Result<(), String> {
+    let prompt = crate::i18n::trf("Password to unlock {0}: ", &[&entry.database.display()]);
+    let unlock = rpassword::prompt_password(prompt)
+        .map_err(|err| format!("cannot read the database password: {}", err))?;
+    add(entry, &unlock, password)
+}
+
+fn add(entry: &KeepassEntry, unlock: &str, password: &str) -> Result<(), String> {
+    let mut command = Command::new("keepassxc-cli");
+    command.args(["add", "--quiet", "--password-prompt"]);
+    if let Some(username) = &entry.username {
+        command.args(["--username", username]);
+    }
+    if let Some(key_file) = &entry.key_file {
+        command.arg("--key-file").arg(key_file);
+    }
+    if !entry.notes.is_empty() {
+        command.args(["--notes", &entry.notes]);
+    }
+    command.arg(path_arg(&entry.database)).arg(&entry.entry);
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run keepassxc-cli: {}", err))?;
+    // keepassxc-cli reads the database password first, then the entry's.
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(format!("{}\n{}\n", unlock, password).as_bytes())
+        .map_err(|err| format!("cannot talk to keepassxc-cli: {}", err))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("cannot run keepassxc-cli: {}", err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "cannot add the entry to {}: {}",
+            entry.database.display(),
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Keeps a database path starting with `-` from being read as an option.
+fn path_arg(path: &Path) -> PathBuf {
+    if path.to_string_lossy().starts_with('-') {
+        Path::new(".").join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
@@ -0,0 +1,82 @@
+//! Saving generated passwords to the macOS Keychain as Safari-compatible entries.
+//!
+//! The generic keyring integration stores plain generic passwords, which Safari's
+//! autofill ignores. `--save-keychain <server>/<account>` instead writes an internet
+//! password for `https://<server>` with a label and kind, the fields Keychain Access
+//! shows as Name, Kind, and Where, using the system `security` tool. The command is fed
+//! on stdin, so the password never appears in the process list.
+//!
+//! Entries go to the login keychain, whose items stay on the Mac: the login keychain is
+//! never synced through iCloud Keychain.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Kind shown for entries Safari creates itself.
+pub const DEFAULT_KIND: &str = "Web form password";
+
+/// A Keychain entry to create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeychainItem {
+    /// Host name the entry is for, shown as "Where" (`https://<server>`).
+    pub server: String,
+    pub account: String,
+    /// Name shown in Keychain Access; defaults to the server.
+    pub label: Option<String>,
+    pub kind: String,
+}
+
+/// Parses `server/account`; the server is everything before the first `/`.
+pub fn parse_target(value: &str) -> Result<(String, String), String> {
+    match value.split_once('/') {
+        Some((server, account)) if !server.is_empty() && !account.is_empty() => {
+            Ok((server.to_string(), account.to_string()))
+        }
+        _ => Err(String::from("expected <server>/<account>")),
+    }
+}
+
+/// Adds the entry to the login keychain, updating it if it already exists.
+pub fn save(item: &KeychainItem, password: &str) -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Err(String::from("--save-keychain is only available on macOS"));
+    }
+
+    let label = item.label.as_deref().unwrap_or(&item.server);
+    let command = format!(
+        "add-internet-password -U -r htps -s {} -a {} -l {} -D {} -w {}\n",
+        quote(&item.server),
+        quote(&item.account),
+        quote(label),
+        quote(&item.kind),
+        quote(password)
+    );
+
+    let mut child = Command::new("security")
+        .arg("-i")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run security: {}", err))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(command.as_bytes())
+        .map_err(|err| format!("cannot talk to security: {}", err))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("cannot run security: {}", err))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || !stderr.trim().is_empty() {
+        return Err(format!("cannot save to the keychain: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Quotes an argument for the `security -i` command parser.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,48 @@
+//! Saving generated passwords straight into OS-protected credential storage.
+//!
+//! With the `keyring` feature, `--save-keyring <service>/<account>` stores the password
+//! through the platform's native store: the Secret Service on Linux, the Keychain on
+//! macOS, and the Credential Manager on Windows. The password is then never written to
+//! the terminal.
+//!
+//! On Windows, `--save-credman <target>` writes a generic credential with an explicit
+//! target name and user name instead, which is what `cmdkey` and other native tooling
+//! look up when provisioning service accounts.
+
+/// A keyring location given as `service/account`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyringTarget {
+    pub service: String,
+    pub account: String,
+}
+
+/// Parses `service/account`; the service is everything before the first `/`.
+pub fn parse_target(value: &str) -> Result<KeyringTarget, String> {
+    match value.split_once('/') {
+        Some((service, account)) if !service.is_empty() && !account.is_empty() => {
+            Ok(KeyringTarget {
+                service: service.to_string(),
+                account: account.to_string(),
+            })
+        }
+        _ => Err(String::from("expected <service>/<account>")),
+    }
+}
+
+/// Stores the password under the target, replacing any password already saved there.
+pub fn save(target: &KeyringTarget, password: &str) -> Result<(), String> {
+    keyring::Entry::new(&target.service, &target.account)
+        .and_then(|entry| entry.set_password(password))
+        .map_err(|err| format!("cannot save to the keyring: {}", err))
+}
+
+/// Writes a Windows generic credential under the target name for the given user,
+/// replacing any credential already stored there.
+pub fn save_credman(target: &str, user: &str, password: &str) -> Result<(), String> {
+    if !cfg!(windows) {
+        return Err(String::from("--save-credman is only available on Windows"));
+    }
+    keyring::Entry::new_with_target(target, "penguin", user)
+        .and_then(|entry| entry.set_password(password))
+        .map_err(|err| format!("cannot save to the Credential Manager: {}", err))
+}
@@ -0,0 +1,48 @@
+//! `userPassword` hashes for the `ldif` format.
+//!
+//! Directory servers compare binds against a hash tagged with its scheme. `{SSHA}` is
+//! salted SHA-1, which every LDAP server understands: the base64 of the digest of the
+//! password and salt, followed by the salt. `{ARGON2}` wraps an Argon2id PHC string as
+//! OpenLDAP's `argon2` module and 389 Directory Server read it, and is far slower to
+//! brute-force when the directory leaks.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use clap::ValueEnum;
+use rand::RngCore;
+use sha1::{Digest, Sha1};
+
+/// How many random bytes salt each hash.
+const SALT_LEN: usize = 16;
+
+/// The password storage scheme of `userPassword` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Scheme {
+    /// Salted SHA-1, supported by every directory server
+    Ssha,
+    /// Argon2id, for servers with an argon2 password module
+    Argon2,
+}
+
+/// Returns the `userPassword` value for a password, tagged with its scheme.
+pub fn hash(scheme: Scheme, password: &str) -> String {
+    let mut salt = [0u8; SALT_LEN];
+    penguin::entropy::rng().fill_bytes(&mut salt);
+    match scheme {
+        Scheme::Ssha => {
+            let mut hasher = Sha1::new();
+            hasher.update(password.as_bytes());
+            hasher.update(salt);
+            let mut digest = hasher.finalize().to_vec();
+            digest.extend_from_slice(&salt);
+            format!("{{SSHA}}{}", BASE64.encode(digest))
+        }
+        Scheme::Argon2 => {
+            use argon2::password_hash::{PasswordHasher, SaltString};
+            let salt = SaltString::encode_b64(&salt).expect("16 bytes make a valid salt");
+            let hash = argon2::Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .expect("the default Argon2 parameters are valid");
+            format!("{{ARGON2}}{}", hash)
+        }
+    }
+}
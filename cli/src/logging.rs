@@ -0,0 +1,31 @@
+//! Diagnostic logging for `--log-level`, written to stderr.
+//!
+//! The generator reports each accepted password's attempt count and length, every
+//! rejected candidate with the reason (a forbidden substring, a filter, or a policy
+//! violation), and how long each batch took. That is enough to see which constraint is
+//! making generation slow or impossible. No event carries a password or a base word.
+
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use tracing::Level;
+
+/// How log events are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// One readable line per event
+    Human,
+    /// One JSON object per line
+    Json,
+}
+
+/// Starts writing events at `level` and above to stderr.
+pub fn init(level: Level, format: LogFormat) {
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Human => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
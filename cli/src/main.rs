@@ -0,0 +1,2646 @@
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use output::{Entry, Format, OutputArgs, PlainArgs};
+use penguin::{
+    backupcode, dictation, emoji, entropy, hint, layout,
+    mixer::{
+        CharClass, ComplexityLevel, GenerationStyle, PenguinMixer, SeparatorPolicy, ShortLength,
+    },
+    otp, passphrase,
+    policy::PasswordPolicy,
+    productkey, pronounce, regexgen, safe, schneier, sentence, shamir, strength,
+    tokenize::Tokenizer,
+    variants, Charset, PenguinError,
+};
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+mod api;
+#[cfg(feature = "keyring")]
+mod askpass;
+mod audit;
+#[cfg(feature = "aws")]
+mod aws;
+mod batch;
+mod bench;
+mod bitwarden;
+mod check;
+mod clipboard;
+mod color;
+mod compare;
+mod config;
+mod crack;
+mod credentials;
+#[cfg(unix)]
+mod daemon;
+mod date;
+mod derive;
+mod encrypt;
+#[cfg(feature = "keyring")]
+mod gitcred;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod harden;
+mod history;
+mod i18n;
+mod interactive;
+mod keepassxc;
+mod keychain;
+#[cfg(feature = "keyring")]
+mod keystore;
+mod ldap;
+mod logging;
+mod menu;
+mod metrics;
+mod native;
+mod onepassword;
+mod output;
+mod picker;
+mod progress;
+mod pwgen;
+mod receipt;
+mod reuse;
+mod rotate;
+mod rpc;
+mod rules;
+#[cfg(feature = "secure-mem")]
+mod securemem;
+mod selftest;
+mod serve;
+mod sites;
+mod sops;
+mod stats;
+mod style;
+mod template;
+#[cfg(feature = "keyring")]
+mod tty;
+#[cfg(feature = "vault")]
+mod vault;
+mod verbose;
+mod watch;
+mod wifi;
+mod wordlists;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Disable core dumps and debugger attachment and check the entropy source before generating anything
+    #[arg(long, global = true)]
+    hardened: bool,
+
+    /// Where random numbers come from (system, hw, chacha20), falling back to system without a hardware generator
+    #[arg(long, global = true, value_name = "SOURCE", env = "PENGUIN_ENTROPY")]
+    entropy: Option<entropy::EntropySource>,
+
+    /// Bytes the chacha20 source outputs before rekeying from the operating system [default: 65536]
+    #[arg(long, global = true, value_name = "BYTES", value_parser = clap::value_parser!(u64).range(1..))]
+    reseed_interval: Option<u64>,
+
+    /// Language for messages, such as de or fr [default: from LC_ALL, LC_MESSAGES, or LANG]
+    #[cfg(feature = "i18n")]
+    #[arg(long, global = true)]
+    lang: Option<String>,
+
+    /// Log generation attempts, rejections, and timing to stderr at this level and above
+    #[arg(long, global = true, env = "PENGUIN_LOG_LEVEL", value_name = "LEVEL")]
+    log_level: Option<tracing::Level>,
+
+    /// How log lines are written
+    #[arg(long, global = true, value_enum, default_value_t = logging::LogFormat::Human)]
+    log_format: logging::LogFormat,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate passwords
+    #[command(alias = "g")]
+    Generate(Box<GenerateArgs>),
+
+    /// Generate one password per entry of a TOML manifest
+    Batch {
+        /// Manifest listing the entries to generate
+        manifest: PathBuf,
+
+        #[command(flatten)]
+        output: Box<OutputArgs>,
+    },
+
+    /// Generate a fresh password for every entry of a JSON Lines export, under its policy
+    Rotate {
+        /// Earlier export written with --format jsonl
+        old: PathBuf,
+
+        /// Where to write the new export
+        #[arg(short = 'o', long, value_name = "PATH")]
+        output: PathBuf,
+
+        /// Where to write the old and new password of every entry
+        #[arg(long, value_name = "PATH")]
+        mapping: PathBuf,
+
+        /// Base words the word-based entries were generated from (comma-separated)
+        #[arg(short = 'w', long, value_delimiter = ',')]
+        words: Vec<String>,
+
+        /// File of base words, one per line, used alongside any given with -w
+        #[arg(long, value_name = "PATH", env = "PENGUIN_WORDLIST")]
+        wordlist: Option<PathBuf>,
+
+        /// Directory of the policy files the entries name, as <name>.toml
+        #[arg(long, value_name = "DIR")]
+        policy_dir: Option<PathBuf>,
+    },
+
+    /// Serve password generation and analysis over a local HTTP JSON API
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        listen: SocketAddr,
+
+        /// Bearer token clients must send (a random one is printed when unset)
+        #[arg(long, env = "PENGUIN_SERVE_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+
+    /// Serve password generation and analysis over gRPC (see proto/penguin.proto)
+    #[cfg(feature = "grpc")]
+    Grpc {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:50051")]
+        listen: SocketAddr,
+
+        /// Bearer token clients must send (a random one is printed when unset)
+        #[arg(long, env = "PENGUIN_GRPC_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+
+    /// Speak JSON-RPC 2.0 on stdin/stdout, one request per line
+    Rpc,
+
+    /// Serve the JSON-RPC protocol on a unix socket, to the socket owner only
+    #[cfg(unix)]
+    Daemon {
+        /// Socket to listen on [default: $XDG_RUNTIME_DIR/penguin.sock]
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+
+    /// Act as a git credential helper backed by the OS keyring
+    #[cfg(feature = "keyring")]
+    GitCredential {
+        /// Operation git asks for
+        #[arg(value_enum)]
+        action: gitcred::Action,
+    },
+
+    /// Answer ssh and sudo askpass prompts from the OS keyring
+    #[cfg(feature = "keyring")]
+    Askpass {
+        /// Prompt given by ssh or sudo
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        prompt: Vec<String>,
+    },
+
+    /// Clear the clipboard after a delay if it still holds the password hashed on stdin
+    #[command(hide = true)]
+    ClipboardClear {
+        #[arg(long)]
+        after: u64,
+
+        #[arg(long, value_enum)]
+        selection: clipboard::Selection,
+
+        #[arg(long)]
+        countdown: bool,
+    },
+
+    /// Act as a Chrome/Firefox native messaging host for browser extensions
+    NativeHost {
+        /// Arguments browsers pass when launching the host (origin, manifest path); ignored
+        #[arg(hide = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        caller: Vec<String>,
+    },
+
+    /// Generate a TOTP secret and its otpauth:// enrollment URI
+    OtpSecret {
+        /// Service name shown in the authenticator app
+        #[arg(long)]
+        issuer: String,
+
+        /// Account name shown in the authenticator app
+        #[arg(long)]
+        account: String,
+
+        /// Number of digits per code (6 to 8, which authenticator apps accept)
+        #[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u32).range(6..=8))]
+        digits: u32,
+
+        /// Seconds each code stays valid
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+        period: u64,
+
+        /// Also print the URI as a QR code for scanning
+        #[cfg(feature = "qr")]
+        #[arg(long)]
+        qr: bool,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate a WiFi passphrase and its WIFI: QR payload
+    Wifi {
+        /// Network name
+        #[arg(long)]
+        ssid: String,
+
+        /// Network security type
+        #[arg(long, value_enum, default_value_t = wifi::Security::Wpa2)]
+        security: wifi::Security,
+
+        /// Passphrase length (8 to 63 characters)
+        #[arg(short = 'l', long, default_value_t = 20)]
+        length: usize,
+
+        /// Mark the network as hidden in the payload
+        #[arg(long)]
+        hidden: bool,
+
+        /// Also print the payload as a QR code for scanning
+        #[cfg(feature = "qr")]
+        #[arg(long)]
+        qr: bool,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Reconstruct a password from Shamir shares created with --split
+    Combine {
+        /// Shares to combine (read from stdin, one per line, when omitted)
+        shares: Vec<String>,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate passwords with pwgen-compatible flags and arguments
+    Pwgen(pwgen::PwgenArgs),
+
+    /// Generate pronounceable passwords, printed with their syllable breakdown
+    Pronounce {
+        /// Password length [default: 10, or one pass through --grammar]
+        #[arg(short = 'l', long)]
+        length: Option<usize>,
+
+        /// Syllable grammar, e.g. CVC-CVVC-dd (C consonant, V vowel, d digit, | alternatives)
+        #[arg(long)]
+        grammar: Option<String>,
+
+        /// Letters to use for consonants
+        #[arg(long)]
+        consonants: Option<String>,
+
+        /// Letters to use for vowels
+        #[arg(long)]
+        vowels: Option<String>,
+
+        /// Number of passwords to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate memorable mini-sentence passphrases such as purple-otter-eats-cactus7
+    Sentence {
+        /// Slots to fill, in order (adjective, noun, verb, digit)
+        #[arg(
+            long,
+            value_delimiter = ',',
+            default_value = "adjective,noun,verb,noun,digit"
+        )]
+        template: Vec<sentence::Slot>,
+
+        /// Text inserted between words
+        #[arg(long, default_value = "-")]
+        separator: String,
+
+        /// Number of passphrases to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate diceware passphrases from the EFF large wordlist, such as cactus-oblong-ashtray-pulse-widget
+    Passphrase {
+        /// Words per passphrase
+        #[arg(short = 'w', long, default_value_t = 6, value_parser = clap::value_parser!(u64).range(1..))]
+        words: u64,
+
+        /// Text inserted between words
+        #[arg(long, default_value = "-")]
+        separator: String,
+
+        /// Casing of the words (lower, upper, title, or random, which adds a bit per word)
+        #[arg(long, default_value = "lower")]
+        capitalize: passphrase::Capitalization,
+
+        /// Pick the words from this file (one per line) instead of the EFF list
+        #[arg(long, value_name = "PATH")]
+        wordlist: Option<PathBuf>,
+
+        /// Number of passphrases to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Report a password's length, classes, estimated entropy, and the words, walks, and dates that weaken it
+    Check {
+        /// The password; read at a hidden prompt or from stdin when left out
+        password: Option<String>,
+
+        /// Base words to look for, which count for hardly any entropy
+        #[arg(short = 'w', long, value_delimiter = ',')]
+        words: Vec<String>,
+
+        /// Exit with status 1 when the estimate is below this many bits
+        #[arg(long, value_name = "BITS", value_parser = parse_bits)]
+        min_entropy: Option<f64>,
+    },
+
+    /// Derive a site's password from a master password typed at a hidden prompt, the same on every run
+    Derive {
+        /// The site the password is for, such as github.com
+        site: String,
+
+        /// Raise to rotate the site's password
+        #[arg(short = 'i', long, default_value_t = 1)]
+        counter: u32,
+
+        /// Print passwords of the site's stream from index N instead of the one for --counter
+        #[arg(long, value_name = "N", conflicts_with = "counter")]
+        from: Option<u64>,
+
+        /// With --from, how many passwords of the stream to print
+        #[arg(short = 'n', long, default_value_t = 1, requires = "from")]
+        number: u64,
+
+        /// Complexity level (basic, medium, hard, penguin)
+        #[arg(short = 'c', long, default_value = "hard")]
+        complexity: String,
+
+        /// Password length (penguin complexity is always 64)
+        #[arg(short = 'l', long, default_value_t = 16)]
+        length: usize,
+
+        /// TOML policy with length bounds, class minimums, forbidden substrings, and a required pattern
+        #[arg(long, value_name = "PATH")]
+        policy_file: Option<PathBuf>,
+
+        #[command(flatten)]
+        rules: rules::RuleArgs,
+
+        /// Ask for the master password twice
+        #[arg(long)]
+        confirm: bool,
+
+        /// Check the master password against a tag saved in this file, saving one on first use
+        #[arg(long, value_name = "PATH")]
+        verify_file: Option<PathBuf>,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Check that this build derives the known-answer passwords of its derivation version
+    VerifyVectors {
+        /// Vectors to check instead of the built-in ones, in the same tab-separated format
+        file: Option<PathBuf>,
+    },
+
+    /// Compare a current and a new password typed at hidden prompts, and judge whether the new one is a real improvement
+    Compare,
+
+    /// Condense a sentence typed at a hidden prompt into a password (Schneier's scheme)
+    #[command(alias = "schneier")]
+    FromSentence {
+        /// Replace a word with text instead of its first letter, e.g. went=WENT (repeatable)
+        #[arg(long, value_name = "WORD=TEXT", value_parser = parse_substitution)]
+        substitute: Vec<(String, String)>,
+
+        /// Don't apply the built-in substitutions (to=2, for=4, and=&, at=@, ...)
+        #[arg(long)]
+        no_default_substitutions: bool,
+
+        /// Keep the case of first letters as typed
+        #[arg(long)]
+        keep_case: bool,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate random strings that match a regular expression, with their entropy
+    Matching {
+        /// Pattern the whole string must match, e.g. '[A-Z][a-z0-9]{10,}[!?]'
+        pattern: String,
+
+        /// Length to aim for when the pattern leaves it open
+        #[arg(short = 'l', long, default_value_t = 16)]
+        length: usize,
+
+        /// Number of strings to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate product keys or voucher codes such as 7G3KD-PQ2XN-88RTY-ZC4MH
+    #[command(alias = "key")]
+    ProductKey {
+        /// Number of groups per key
+        #[arg(short = 'g', long, default_value_t = 4)]
+        groups: usize,
+
+        /// Characters per group
+        #[arg(short = 's', long, default_value_t = 5)]
+        group_size: usize,
+
+        /// Allow characters that are easy to confuse (0/O, 1/I/L, 2/Z, 5/S, 8/B)
+        #[arg(long)]
+        allow_ambiguous: bool,
+
+        /// Number of keys to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Generate recovery codes ending in a check character that catches typos
+    #[command(alias = "codes")]
+    BackupCodes {
+        /// Random characters per code, not counting the check character
+        #[arg(short = 'l', long, default_value_t = 8)]
+        length: usize,
+
+        /// Number of codes to generate
+        #[arg(short = 'n', long, default_value_t = 10)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Check a backup code's check character before using it
+    VerifyCode {
+        /// The code, with or without hyphens
+        code: String,
+    },
+
+    /// Generate passphrases made of emoji
+    Emoji {
+        /// Number of symbols per passphrase
+        #[arg(short = 'l', long, default_value_t = 6)]
+        length: usize,
+
+        /// Mix the digits 0-9 into the symbol pool
+        #[arg(long)]
+        digits: bool,
+
+        /// Number of passphrases to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Print candidates for dmenu/rofi, or type or copy the chosen one with --select
+    Menu {
+        /// Read the chosen candidate from stdin and type it into the focused window
+        #[arg(long)]
+        select: bool,
+
+        /// With --select, copy the choice to the clipboard instead of typing it
+        #[arg(long, requires = "select")]
+        copy: bool,
+
+        #[command(flatten)]
+        clipboard: clipboard::ClipboardArgs,
+
+        /// Words to build candidates from (pronounceable candidates when omitted)
+        #[arg(short = 'w', long, value_delimiter = ',', conflicts_with = "select")]
+        words: Vec<String>,
+
+        /// Complexity level (basic, medium, hard, penguin)
+        #[arg(short = 'c', long, default_value = "hard", conflicts_with = "select")]
+        complexity: String,
+
+        /// Candidate length
+        #[arg(short = 'l', long, default_value_t = 16, conflicts_with = "select")]
+        length: usize,
+
+        /// Number of candidates
+        #[arg(short = 'n', long, default_value_t = 10, conflicts_with = "select")]
+        number: usize,
+    },
+
+    /// Generate matched username and password pairs
+    Credentials {
+        /// Words to build passwords from (pronounceable passwords when omitted)
+        #[arg(short = 'w', long, value_delimiter = ',')]
+        words: Vec<String>,
+
+        /// Complexity level (basic, medium, hard, penguin)
+        #[arg(short = 'c', long, default_value = "hard")]
+        complexity: String,
+
+        /// Password length
+        #[arg(short = 'l', long, default_value_t = 16)]
+        length: usize,
+
+        /// Digits appended to each username
+        #[arg(long, default_value_t = 2)]
+        username_digits: usize,
+
+        /// Output format
+        #[arg(short = 'f', long, value_enum, default_value_t = credentials::Format::Table)]
+        format: credentials::Format,
+
+        /// Number of pairs to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        number: usize,
+
+        #[command(flatten)]
+        plain: PlainArgs,
+    },
+
+    /// Create or inspect the configuration file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Check word lists for repeats and clean them up
+    Wordlist {
+        #[command(subcommand)]
+        action: WordlistAction,
+    },
+
+    /// Work with the expiry history of passwords generated with --expires
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+
+    /// Check passwords against the receipts written with --receipt
+    Receipt {
+        #[command(subcommand)]
+        action: ReceiptAction,
+    },
+
+    /// Print the password rules known for a site, or for every known site
+    SiteRules {
+        /// Domain or URL of the site
+        site: Option<String>,
+    },
+
+    /// Check the random number generators with quick statistical tests
+    Selftest,
+
+    /// Measure passwords per second for each complexity level and strategy
+    Bench {
+        /// Base words to generate from [default: a built-in sample]
+        #[arg(short = 'w', long, value_delimiter = ',')]
+        words: Vec<String>,
+
+        /// Passwords generated per row
+        #[arg(short = 'n', long, default_value_t = 20_000, value_parser = clap::value_parser!(u64).range(1..))]
+        number: u64,
+
+        /// Password length for the word-based levels
+        #[arg(short = 'l', long, default_value_t = 16)]
+        length: usize,
+
+        /// Worker threads
+        #[arg(short = 'j', long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        jobs: u64,
+    },
+
+    /// Print the current and next TOTP codes for a base32 secret
+    Totp {
+        /// Base32 secret (prompted for without echo when omitted)
+        secret: Option<String>,
+
+        /// Number of digits per code
+        #[arg(long, default_value_t = 6)]
+        digits: u32,
+
+        /// Seconds each code stays valid
+        #[arg(long, default_value_t = 30)]
+        period: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Write a commented config file to the XDG config location
+    Init {
+        /// Replace an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print every setting with its effective value and where it came from
+    Show,
+}
+
+#[derive(Subcommand)]
+enum WordlistAction {
+    /// Report repeated words, words of the wrong length, and the entropy they cost
+    Lint {
+        /// Word list with one word per line
+        path: PathBuf,
+
+        /// Shortest word length accepted
+        #[arg(long, default_value_t = 1)]
+        min_length: usize,
+
+        /// Longest word length accepted [default: no limit]
+        #[arg(long)]
+        max_length: Option<usize>,
+    },
+
+    /// Print the list lowercased, without accents or repeats, and within the lengths
+    Normalize {
+        /// Word list with one word per line
+        path: PathBuf,
+
+        /// Drop words shorter than this
+        #[arg(long, default_value_t = 1)]
+        min_length: usize,
+
+        /// Drop words longer than this [default: no limit]
+        #[arg(long)]
+        max_length: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// List passwords that have expired or expire soon, soonest first
+    Due {
+        /// How far ahead to look, as a span such as 14d, 4w, or 1y
+        #[arg(long, default_value = "14d", value_parser = date::parse_span)]
+        within: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReceiptAction {
+    /// Read a password and print the receipts it matches, failing when there are none
+    Verify {
+        /// Receipt file written with --receipt
+        path: PathBuf,
+
+        /// Only match receipts of this run
+        #[arg(long, value_name = "ID")]
+        run: Option<String>,
+
+        /// Only match receipts with this label
+        #[arg(long)]
+        label: Option<String>,
+    },
+}
+
+/// How `--mnemonic` picks the word for each character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MnemonicStyle {
+    /// A random noun starting with each lowercase letter
+    Random,
+    /// A fixed word for every character, the same each time
+    Phonetic,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Words to use for password generation (comma-separated)
+    #[arg(short = 'w', long, value_delimiter = ',')]
+    words: Vec<String>,
+
+    /// Split the base words further: at whitespace, also at punctuation, or also at
+    /// camelCase capitals
+    #[arg(long, value_name = "MODE", default_value = "whitespace")]
+    tokenize: Tokenizer,
+
+    /// Type the base words at a hidden prompt instead, keeping them out of shell history and ps
+    #[arg(long, conflicts_with = "words")]
+    prompt_words: bool,
+
+    /// File of base words, one per line, used alongside any given with -w (- reads stdin)
+    #[arg(
+        long,
+        visible_alias = "words-file",
+        value_name = "PATH",
+        env = "PENGUIN_WORDLIST"
+    )]
+    wordlist: Option<PathBuf>,
+
+    /// Add variants of each base word: plural, reversed, no-vowels, capitalized [default: all]
+    #[arg(long, value_name = "VARIANTS", value_delimiter = ',', num_args = 0..)]
+    expand_words: Option<Vec<variants::Variant>>,
+
+    /// Number of passwords to generate [default: number of labels, or 1]
+    #[arg(short = 'n', long)]
+    number: Option<usize>,
+
+    /// Show one candidate at a time to accept (y), copy (c), re-roll (n), or tweak (e)
+    #[arg(long, conflicts_with = "number")]
+    interactive: bool,
+
+    /// Hand the password to the Android share sheet instead of printing it (Termux)
+    #[arg(long, conflicts_with_all = ["number", "pick", "split", "mnemonic"])]
+    share: bool,
+
+    /// Pick one of the candidates in a fuzzy selector; copies it to the clipboard and prints only its number
+    #[arg(long, conflicts_with = "interactive")]
+    pick: bool,
+
+    /// Clear the screen and show a new batch every interval (30s, 5m, 1h) or keypress, without saving any
+    #[arg(
+        long,
+        value_name = "INTERVAL",
+        value_parser = watch::parse_interval,
+        conflicts_with_all = [
+            "interactive",
+            "pick",
+            "output_file",
+            "gpg_recipient",
+            "systemd_encrypt",
+        ]
+    )]
+    #[cfg_attr(feature = "age", arg(conflicts_with = "encrypt_to"))]
+    watch: Option<std::time::Duration>,
+
+    /// Complexity level (basic, medium, hard, penguin) [default: basic]
+    #[arg(short = 'c', long, env = "PENGUIN_COMPLEXITY")]
+    complexity: Option<String>,
+
+    /// Preset bundling complexity, length, separators, and character classes (flags override it)
+    #[arg(long, value_enum, env = "PENGUIN_STYLE")]
+    style: Option<style::Style>,
+
+    /// Template from ~/.config/penguin/templates/NAME.toml with a pattern, policy, and settings
+    #[arg(long, value_name = "NAME", conflicts_with = "style")]
+    template: Option<String>,
+
+    /// Regular expression every password is generated to match, instead of mixing base words
+    #[arg(long, value_name = "REGEX", conflicts_with = "charset")]
+    pattern: Option<String>,
+
+    /// The policy of the --template, if it has one
+    #[arg(skip)]
+    template_policy: Option<PasswordPolicy>,
+
+    /// Use whole words
+    #[arg(short = 'u', long = "whole-words", env = "PENGUIN_WHOLE_WORDS")]
+    whole_words: bool,
+
+    /// Build passwords from syllables that can be read aloud; base words are optional
+    #[arg(long, conflicts_with_all = ["whole_words", "pattern"])]
+    pronounceable: bool,
+
+    /// Re-roll any password this machine handed out before, and remember the new ones
+    #[arg(long, env = "PENGUIN_NEVER_REUSE")]
+    never_reuse: bool,
+
+    /// With --whole-words, pick words that fill the length exactly instead of padding and cutting
+    #[arg(long)]
+    exact_length: bool,
+
+    /// With --whole-words, let passwords run up to N characters past the length to keep the last word whole
+    #[arg(long, value_name = "N", conflicts_with = "exact_length")]
+    max_overshoot: Option<usize>,
+
+    /// With --whole-words, shorten a word that would overrun the length to its first syllables
+    #[arg(long, conflicts_with = "exact_length")]
+    abbreviate: bool,
+
+    /// With --whole-words, build every password from at least N base words, even past the length
+    #[arg(long, value_name = "N", conflicts_with = "exact_length")]
+    min_words: Option<usize>,
+
+    /// With --whole-words, what to do when every word is longer than the length (cut, abbreviate, mix, error) [default: cut, with a warning]
+    #[arg(long, value_name = "HANDLING")]
+    on_short_length: Option<ShortLength>,
+
+    /// Password length
+    #[arg(short = 'l', long, env = "PENGUIN_LENGTH")]
+    length: Option<usize>,
+
+    /// Text to start every password with (counts towards the length)
+    #[arg(long, default_value = "")]
+    prefix: String,
+
+    /// Text to end every password with (counts towards the length)
+    #[arg(long, default_value = "")]
+    suffix: String,
+
+    /// Fixed text to insert after each word in whole-word mode
+    #[arg(long, conflicts_with_all = ["separator_classes", "separator_count"])]
+    separator: Option<String>,
+
+    /// Character classes to draw random separators from (lowercase, uppercase, digits, specials)
+    #[arg(long, value_delimiter = ',')]
+    separator_classes: Vec<CharClass>,
+
+    /// Number of random separator characters to insert after each word
+    #[arg(long)]
+    separator_count: Option<usize>,
+
+    /// Leave digits out of the generated passwords
+    #[arg(long)]
+    no_numbers: bool,
+
+    /// Leave special characters out of the generated passwords
+    #[arg(long)]
+    no_special: bool,
+
+    /// Draw penguin-level passwords from a named charset (alphanumeric, base58, url-safe, printable)
+    #[arg(long, value_name = "NAME")]
+    charset: Option<Charset>,
+
+    /// Only produce passwords that need no quoting in a format (shell, yaml, json, ini)
+    #[arg(long, value_name = "FORMAT")]
+    safe_for: Option<safe::SafeFor>,
+
+    /// Stick to symbols on phones' first symbol layer (.-_!) and group letters, digits, and symbols
+    #[arg(long)]
+    mobile_friendly: bool,
+
+    /// Keyboard layout whose dead-key and AltGr symbols are left out (qwerty, azerty, qwertz, dvorak)
+    #[arg(long, conflicts_with = "mobile_friendly")]
+    layout: Option<layout::Layout>,
+
+    /// Favor symbols on unmodified keys of the chosen --layout
+    #[arg(long, requires = "layout")]
+    weight_reach: bool,
+
+    /// Only keep passwords that alternate hands and avoid same-finger bigrams on --layout (default qwerty)
+    #[arg(long)]
+    alternate_hands: bool,
+
+    /// Reject passwords with keyboard walks (qwerty, asdf, 1qaz) longer than N keys on any layout
+    #[arg(long, value_name = "N")]
+    max_walk: Option<usize>,
+
+    /// Characters that must each appear in every password (e.g. '@7Z')
+    #[arg(long)]
+    must_include: Vec<String>,
+
+    /// Group of characters of which at least one must appear in every password (repeatable)
+    #[arg(long)]
+    must_include_any: Vec<String>,
+
+    /// Substrings no password may contain, compared case-insensitively (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    avoid_words: Vec<String>,
+
+    /// File listing substrings to avoid, one per line (blank lines and # comments are skipped)
+    #[arg(long)]
+    avoid_words_file: Option<PathBuf>,
+
+    /// A name, birthday, employer, or email address no password may contain, even in leet spelling (repeatable)
+    #[arg(long, value_name = "DETAIL")]
+    personal: Vec<String>,
+
+    /// Re-roll passwords with digits that read as a date (a year 1900-2099, DDMM, or MMYY)
+    #[arg(long)]
+    avoid_dates: bool,
+
+    /// Re-roll passwords with sound-alike letters side by side or both O and 0, for reading aloud
+    #[arg(long)]
+    dictation: bool,
+
+    /// Use lowercase only, so a password read aloud never needs "capital"
+    #[arg(long, requires = "dictation")]
+    single_case: bool,
+
+    /// Re-roll passwords that contain profanity in any of the embedded languages
+    #[cfg(feature = "profanity")]
+    #[arg(long)]
+    clean: bool,
+
+    /// Print each password as N Shamir shares, any K of which reconstruct it (e.g. 2/3)
+    #[arg(long, value_name = "K/N", value_parser = parse_split)]
+    split: Option<(u8, u8)>,
+
+    /// Write the first half of each password to FIRST and the second half to SECOND,
+    /// for two custodians, instead of printing it
+    #[arg(
+        long,
+        value_name = "FIRST,SECOND",
+        value_parser = parse_halves,
+        conflicts_with_all = ["split", "mnemonic"]
+    )]
+    split_halves: Option<(PathBuf, PathBuf)>,
+
+    /// Print distribution, duplicate, entropy, and compliance statistics for the batch to stderr
+    #[arg(long)]
+    stats: bool,
+
+    /// Print each password's length, class counts, base words used, strategy, and entropy to stderr
+    #[arg(short = 'v', long)]
+    verbose: bool,
+
+    /// Print how long each password holds out against attackers (online, bcrypt, md5; all by default) to stderr
+    #[arg(long, value_name = "ATTACKERS", num_args = 0.., value_delimiter = ',')]
+    crack_time: Option<Vec<strength::Attacker>>,
+
+    /// Print a memory aid mapping each character to a word (k9Tw -> kite 9 Tango whale);
+    /// `phonetic` uses fixed words, so the aid can be worked out again from the password
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STYLE",
+        num_args = 0..=1,
+        default_missing_value = "random",
+        conflicts_with = "split"
+    )]
+    mnemonic: Option<MnemonicStyle>,
+
+    /// Also write the memory aid into the notes of exported entries
+    #[arg(long, requires = "mnemonic")]
+    store_mnemonic: bool,
+
+    /// Add a hint that describes the words of whole-word passwords by first letters or categories without naming them, stored in exported notes
+    #[arg(long, value_name = "STYLE", conflicts_with = "mnemonic")]
+    hint: Option<hint::HintStyle>,
+
+    /// Fewest bits a hint must leave a guesser who knows the base words; passwords whose hint would leave fewer get none
+    #[arg(long, value_name = "BITS", default_value_t = 30.0, requires = "hint")]
+    hint_min_bits: f64,
+
+    /// Store the password in the OS keyring as <service>/<account> instead of printing it
+    #[cfg(feature = "keyring")]
+    #[arg(
+        long,
+        value_name = "SERVICE/ACCOUNT",
+        value_parser = keystore::parse_target,
+        conflicts_with_all = ["split", "mnemonic"]
+    )]
+    save_keyring: Option<keystore::KeyringTarget>,
+
+    /// Store the password as a Windows generic credential with this target name
+    #[cfg(feature = "keyring")]
+    #[arg(
+        long,
+        value_name = "TARGET",
+        requires = "credman_user",
+        conflicts_with_all = ["split", "mnemonic"]
+    )]
+    save_credman: Option<String>,
+
+    /// User name stored with the Windows credential
+    #[cfg(feature = "keyring")]
+    #[arg(long, requires = "save_credman")]
+    credman_user: Option<String>,
+
+    /// Store the password in the macOS Keychain for https://<server> instead of printing it
+    #[arg(
+        long,
+        value_name = "SERVER/ACCOUNT",
+        value_parser = keychain::parse_target,
+        conflicts_with_all = ["split", "mnemonic"]
+    )]
+    save_keychain: Option<(String, String)>,
+
+    /// Name of the Keychain entry [default: the server]
+    #[arg(long, requires = "save_keychain")]
+    keychain_label: Option<String>,
+
+    /// Kind of the Keychain entry
+    #[arg(long, requires = "save_keychain", default_value = keychain::DEFAULT_KIND)]
+    keychain_kind: String,
+
+    /// Add the password to this KeePassXC database with keepassxc-cli instead of printing it
+    #[arg(
+        long,
+        value_name = "DB.kdbx",
+        requires = "entry",
+        conflicts_with_all = ["split", "mnemonic"]
+    )]
+    save_keepassxc: Option<PathBuf>,
+
+    /// Path of the new KeePassXC entry, as Group/Title
+    #[arg(long, value_name = "PATH", requires = "save_keepassxc")]
+    entry: Option<String>,
+
+    /// User name of the new KeePassXC entry
+    #[arg(long, value_name = "USER", requires = "save_keepassxc")]
+    entry_username: Option<String>,
+
+    /// Key file unlocking the KeePassXC database along with its password
+    #[arg(long, value_name = "FILE", requires = "save_keepassxc")]
+    keepassxc_key_file: Option<PathBuf>,
+
+    /// Create a 1Password login item with the password through the op CLI instead of printing it
+    #[arg(
+        long,
+        requires = "title",
+        conflicts_with_all = ["split", "mnemonic", "save_keepassxc"]
+    )]
+    save_op: bool,
+
+    /// Vault the 1Password item is created in [default: op's default vault]
+    #[arg(long, value_name = "VAULT", requires = "save_op")]
+    op_vault: Option<String>,
+
+    /// Title of the 1Password item
+    #[arg(long, requires = "save_op")]
+    title: Option<String>,
+
+    /// User name of the 1Password item
+    #[arg(long, value_name = "USER", requires = "save_op")]
+    op_username: Option<String>,
+
+    /// Website of the 1Password item
+    #[arg(long, value_name = "URL", requires = "save_op")]
+    op_url: Option<String>,
+
+    /// Create a Bitwarden login item named by --name through the bw CLI instead of printing it
+    #[arg(
+        long,
+        requires = "name",
+        conflicts_with_all = ["split", "mnemonic", "save_keepassxc", "save_op"]
+    )]
+    save_bw: bool,
+
+    /// Existing Bitwarden folder the item is filed in
+    #[arg(long, requires = "save_bw")]
+    folder: Option<String>,
+
+    /// User name of the Bitwarden item
+    #[arg(long, value_name = "USER", requires = "save_bw")]
+    bw_username: Option<String>,
+
+    /// Website of the Bitwarden item
+    #[arg(long, value_name = "URL", requires = "save_bw")]
+    bw_url: Option<String>,
+
+    /// Write the password into a Vault KV v2 secret (VAULT_ADDR and VAULT_TOKEN from env)
+    #[cfg(feature = "vault")]
+    #[arg(
+        long,
+        value_name = "PATH#KEY",
+        value_parser = vault::parse_target,
+        conflicts_with_all = ["split", "mnemonic"]
+    )]
+    vault_write: Option<vault::VaultTarget>,
+
+    /// Create or rotate an AWS Secrets Manager secret with the password, printing its ARN
+    #[cfg(feature = "aws")]
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["split", "mnemonic"])]
+    aws_secret: Option<String>,
+
+    /// Write the password to an SSM SecureString parameter, printing its ARN
+    #[cfg(feature = "aws")]
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["split", "mnemonic", "aws_secret"]
+    )]
+    aws_ssm: Option<String>,
+
+    /// Don't print the generated passwords on stdout (for when they are stored elsewhere or written with --output)
+    #[arg(short = 'q', long)]
+    quiet: bool,
+
+    /// TOML policy with length bounds, class minimums, forbidden substrings, and a required pattern
+    #[arg(long, value_name = "PATH")]
+    policy_file: Option<PathBuf>,
+
+    /// Follow a site's known password rules (length, required and allowed characters)
+    #[arg(long, value_name = "DOMAIN", conflicts_with = "policy_file")]
+    site: Option<String>,
+
+    #[command(flatten)]
+    rules: rules::RuleArgs,
+
+    /// Candidates to try before giving up on the policy, filters, and avoided words [default: 1000]
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    max_attempts: Option<u64>,
+
+    /// Regenerate passwords whose entropy, counting base words they contain as known, is below this many bits
+    #[arg(long, value_name = "BITS", value_parser = parse_bits)]
+    min_entropy: Option<f64>,
+
+    /// Record an expiry date (a span such as 90d, 12w, or 1y, or YYYY-MM-DD) in exports,
+    /// Vault metadata, and the history read by `penguin history due`
+    #[arg(long, value_name = "WHEN", value_parser = date::parse_expiry)]
+    expires: Option<i64>,
+
+    /// Append a JSON record per password (label, policy, entropy, SHA-256, never the plaintext)
+    #[arg(long, value_name = "PATH")]
+    audit_log: Option<PathBuf>,
+
+    /// Append a receipt per password (run, label, policy, BLAKE3 hash, never the plaintext)
+    /// that `penguin receipt verify` checks passwords against
+    #[arg(long, value_name = "PATH")]
+    receipt: Option<PathBuf>,
+
+    /// Worker threads for generating the batch [default: all cores for 1000 or more passwords]
+    #[arg(short = 'j', long, value_parser = clap::value_parser!(u64).range(1..))]
+    jobs: Option<u64>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    #[command(flatten)]
+    clipboard: clipboard::ClipboardArgs,
+
+    /// Names to attach to the generated passwords, in order (comma-separated or repeated)
+    #[arg(long = "label", value_delimiter = ',')]
+    labels: Vec<String>,
+
+    /// Read labels from stdin, one per line, and print `label<TAB>password` in the plain format
+    #[arg(long, conflicts_with_all = ["labels", "split"])]
+    per_line: bool,
+}
+
+fn main() {
+    let resolved = config::apply();
+    let cli = Cli::parse();
+    #[cfg(feature = "i18n")]
+    i18n::init(cli.lang.as_deref());
+    if cli.hardened || cfg!(feature = "hardened") {
+        harden::apply();
+    }
+    if let Some(level) = cli.log_level {
+        logging::init(level, cli.log_format);
+    }
+    if let Some(bytes) = cli.reseed_interval {
+        entropy::set_reseed_interval(bytes);
+    }
+    if let Some(source) = cli.entropy {
+        let used = entropy::set_source(source);
+        if used != source {
+            eprintln!(
+                "{}",
+                i18n::tr(
+                    "warning: no hardware random number generator found; \
+                     using the system generator"
+                )
+            );
+        }
+        tracing::info!(source = %used, backend = used.backend(), "selected the entropy source");
+    }
+    if cli.hardened || cfg!(feature = "hardened") {
+        let report = entropy::health_check();
+        if !report.is_healthy() {
+            fail(i18n::trf(
+                "the entropy source failed its health check: {0}",
+                &[&report.problems.join("; ")],
+            ));
+        }
+        tracing::info!(latency = ?report.latency, source = %report.source, "the entropy source is healthy");
+    }
+
+    match cli.command {
+        Commands::Generate(args) => generate(*args),
+        Commands::Batch { manifest, output } => batch::run(&manifest, &output),
+        Commands::Rotate {
+            old,
+            output,
+            mapping,
+            words,
+            wordlist,
+            policy_dir,
+        } => rotate::run(
+            &old,
+            &output,
+            &mapping,
+            &rotate::Sources {
+                words: &words,
+                wordlist: wordlist.as_deref(),
+                policy_dir: policy_dir.as_deref(),
+            },
+        ),
+        Commands::Serve { listen, token } => serve::run(listen, token),
+        #[cfg(feature = "grpc")]
+        Commands::Grpc { listen, token } => grpc::run(listen, token),
+        Commands::Rpc => rpc::run(),
+        #[cfg(unix)]
+        Commands::Daemon { socket } => daemon::run(&socket.unwrap_or_else(daemon::default_socket)),
+        #[cfg(feature = "keyring")]
+        Commands::GitCredential { action } => gitcred::run(action),
+        #[cfg(feature = "keyring")]
+        Commands::Askpass { prompt } => askpass::run(&prompt),
+        Commands::ClipboardClear {
+            after,
+            selection,
+            countdown,
+        } => clipboard::clear_later(after, selection, countdown),
+        Commands::NativeHost { .. } => native::run(),
+        Commands::OtpSecret {
+            issuer,
+            account,
+            digits,
+            period,
+            #[cfg(feature = "qr")]
+            qr,
+            plain,
+        } => {
+            plain.guard();
+            let secret = otp::generate_secret();
+            let mut uri = otp::TotpUri::new(&secret, &issuer, &account);
+            uri.digits = digits;
+            uri.period = period;
+            let uri = uri.to_string();
+
+            println!("secret: {}", secret);
+            println!("uri:    {}", uri);
+            #[cfg(feature = "qr")]
+            if qr {
+                println!("\n{}", output::qr_code(&uri));
+            }
+        }
+        Commands::Wifi {
+            ssid,
+            security,
+            length,
+            hidden,
+            #[cfg(feature = "qr")]
+            qr,
+            plain,
+        } => {
+            #[cfg(not(feature = "qr"))]
+            let qr = false;
+            plain.guard();
+            wifi::run(&ssid, security, length, hidden, qr);
+        }
+        Commands::Totp {
+            secret,
+            digits,
+            period,
+        } => totp(secret, digits, period),
+        Commands::Combine { shares, plain } => {
+            plain.guard();
+            combine(shares)
+        }
+        Commands::Pwgen(args) => {
+            args.plain.guard();
+            pwgen::run(args)
+        }
+        Commands::Selftest => selftest::run(),
+        Commands::Bench {
+            words,
+            number,
+            length,
+            jobs,
+        } => bench::run(&words, number as usize, length, jobs as usize),
+        Commands::Menu {
+            select,
+            copy,
+            clipboard,
+            words,
+            complexity,
+            length,
+            number,
+        } => {
+            if select {
+                menu::select(copy.then_some(clipboard));
+            } else {
+                let mixer = PenguinMixer::new(parse_complexity(&complexity), false, length);
+                menu::candidates(&words, &mixer, number);
+            }
+        }
+        Commands::Config { action } => match action {
+            ConfigAction::Init { force } => config::init(force),
+            ConfigAction::Show => config::show(&resolved),
+        },
+        Commands::SiteRules { site } => sites::show(site.as_deref()),
+        Commands::Wordlist { action } => match action {
+            WordlistAction::Lint {
+                path,
+                min_length,
+                max_length,
+            } => wordlists::lint(&path, min_length, max_length),
+            WordlistAction::Normalize {
+                path,
+                min_length,
+                max_length,
+            } => wordlists::normalize(&path, min_length, max_length),
+        },
+        Commands::History { action } => match action {
+            HistoryAction::Due { within } => history::due(within),
+        },
+        Commands::Receipt { action } => match action {
+            ReceiptAction::Verify { path, run, label } => {
+                receipt::verify(&path, run.as_deref(), label.as_deref())
+            }
+        },
+        Commands::Credentials {
+            words,
+            complexity,
+            length,
+            username_digits,
+            format,
+            number,
+            plain,
+        } => {
+            if format == credentials::Format::Table {
+                plain.guard();
+            }
+            let mixer = PenguinMixer::new(parse_complexity(&complexity), false, length);
+            credentials::run(&words, &mixer, number, username_digits, format);
+        }
+        Commands::Emoji {
+            length,
+            digits,
+            number,
+            plain,
+        } => {
+            if length == 0 {
+                fail("the passphrase length must be at least 1");
+            }
+            plain.guard();
+            for _ in 0..number {
+                let passphrase = emoji::generate(length, digits);
+                println!("{} ({:.1} bits)", passphrase, passphrase.entropy_bits);
+            }
+        }
+        Commands::Matching {
+            pattern,
+            length,
+            number,
+            plain,
+        } => {
+            plain.guard();
+            for _ in 0..number {
+                let matched = regexgen::generate_matching(&pattern, length)
+                    .unwrap_or_else(|err| fail(format!("{}: {}", pattern, err)));
+                println!("{} ({:.1} bits)", matched, matched.entropy_bits);
+            }
+        }
+        Commands::ProductKey {
+            groups,
+            group_size,
+            allow_ambiguous,
+            number,
+            plain,
+        } => {
+            if groups == 0 || group_size == 0 {
+                fail("product keys need at least one group of at least one character");
+            }
+            plain.guard();
+            for _ in 0..number {
+                let key = productkey::generate(groups, group_size, !allow_ambiguous);
+                println!("{} ({:.1} bits)", key, key.entropy_bits);
+            }
+        }
+        Commands::BackupCodes {
+            length,
+            number,
+            plain,
+        } => {
+            if length == 0 {
+                fail("backup codes need at least one character besides the check character");
+            }
+            plain.guard();
+            eprintln!("# {:.1} bits per code", backupcode::entropy_bits(length));
+            for _ in 0..number {
+                println!("{}", backupcode::generate(length));
+            }
+        }
+        Commands::VerifyCode { code } => match backupcode::verify(&code) {
+            Ok(()) => println!("{}: valid", code),
+            Err(err) => fail(format!("{}: {}", code, err)),
+        },
+        Commands::Check {
+            password,
+            words,
+            min_entropy,
+        } => check::run(password, &words, min_entropy),
+        Commands::Derive {
+            site,
+            counter,
+            from,
+            number,
+            complexity,
+            length,
+            policy_file,
+            rules,
+            confirm,
+            verify_file,
+            plain,
+        } => {
+            plain.guard();
+            let complexity = complexity_from_name(&complexity)
+                .unwrap_or_else(|| fail(format!("unknown complexity '{}'", complexity)));
+            let options = penguin::derive::DeriveOptions {
+                complexity,
+                length,
+                policy: None,
+            };
+            let which = match from {
+                Some(from) => derive::Which::Stream {
+                    from,
+                    count: number,
+                },
+                None => derive::Which::Counter(counter),
+            };
+            derive::run(
+                &site,
+                which,
+                options,
+                policy_file.as_deref(),
+                &rules,
+                confirm,
+                verify_file.as_deref(),
+            )
+        }
+        Commands::VerifyVectors { file } => derive::verify_vectors(file.as_deref()),
+        Commands::Compare => compare::run(),
+        Commands::FromSentence {
+            substitute,
+            no_default_substitutions,
+            keep_case,
+            plain,
+        } => {
+            plain.guard();
+            from_sentence(substitute, no_default_substitutions, keep_case)
+        }
+        Commands::Sentence {
+            template,
+            separator,
+            number,
+            plain,
+        } => {
+            plain.guard();
+            for _ in 0..number {
+                let sentence = sentence::generate(&template);
+                println!(
+                    "{} ({:.1} bits)",
+                    sentence.join(&separator),
+                    sentence.entropy_bits
+                );
+            }
+        }
+        Commands::Passphrase {
+            words,
+            separator,
+            capitalize,
+            wordlist,
+            number,
+            output,
+        } => passphrases(
+            words as usize,
+            &separator,
+            capitalize,
+            wordlist,
+            number,
+            output,
+        ),
+        Commands::Pronounce {
+            length,
+            grammar,
+            consonants,
+            vowels,
+            number,
+            plain,
+        } => {
+            plain.guard();
+            pronounce(length, grammar, consonants, vowels, number)
+        }
+    }
+}
+
+fn passphrases(
+    words: usize,
+    separator: &str,
+    capitalization: passphrase::Capitalization,
+    wordlist: Option<PathBuf>,
+    number: usize,
+    output: OutputArgs,
+) {
+    let own = wordlist.map(|path| {
+        let contents = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| fail(format!("cannot read {}: {}", path.display(), err)));
+        let list = penguin::wordlist::Wordlist::parse(&contents).normalize(..);
+        if list.len() < 2 {
+            fail(format!(
+                "{} needs at least two different words",
+                path.display()
+            ));
+        }
+        list
+    });
+    let own: Option<Vec<&str>> = own
+        .as_ref()
+        .map(|list| list.words().iter().map(String::as_str).collect());
+    let list = own.as_deref().unwrap_or(penguin::wordlist::eff_large());
+    let passphrases: Vec<_> = (0..number)
+        .map(|_| passphrase::generate(list, words, capitalization))
+        .collect();
+    // The plain format keeps the entropy beside each passphrase; like every other
+    // format, it still goes through the plaintext guard in `output::emit`.
+    let rendered = if output.format == Format::Plain
+        && !output.bare
+        && !output.null
+        && output.mask.is_none()
+        && !output.hides_plaintext()
+    {
+        passphrases
+            .iter()
+            .map(|passphrase| {
+                format!(
+                    "{} ({:.1} bits)\n",
+                    passphrase.join(separator),
+                    passphrase.entropy_bits
+                )
+            })
+            .collect()
+    } else {
+        let entries: Vec<Entry> = passphrases
+            .iter()
+            .enumerate()
+            .map(|(i, passphrase)| Entry {
+                index: i + 1,
+                label: None,
+                username: String::new(),
+                shares: Vec::new(),
+                password: passphrase.join(separator),
+                hint: None,
+                word_hint: None,
+                notes: format!(
+                    "Generated by penguin ({} words, {:.1} bits)",
+                    words, passphrase.entropy_bits
+                ),
+                expires: None,
+                strategy: "passphrase",
+                policy: None,
+                complexity: None,
+                entropy_bits: Some(passphrase.entropy_bits),
+            })
+            .collect();
+        output::render(&output, &entries)
+    };
+    output::emit(&output, &rendered);
+}
+
+/// Parses a positive number of bits.
+fn parse_bits(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(bits) if bits.is_finite() && bits > 0.0 => Ok(bits),
+        _ => Err(format!("'{}' is not a positive number of bits", value)),
+    }
+}
+
+fn combine(shares: Vec<String>) {
+    let shares = if shares.is_empty() {
+        std::io::stdin()
+            .lines()
+            .map(|line| line.unwrap_or_else(|err| fail(format!("cannot read stdin: {}", err))))
+            .filter(|line| !line.trim().is_empty())
+            .collect()
+    } else {
+        shares
+    };
+
+    let shares: Vec<shamir::Share> = shares
+        .iter()
+        .map(|share| share.parse().unwrap_or_else(|err| fail(err)))
+        .collect();
+    let secret = shamir::combine(&shares).unwrap_or_else(|err| fail(err));
+    match String::from_utf8(secret) {
+        Ok(password) => println!("{}", password),
+        Err(_) => fail("the combined shares do not form a valid password"),
+    }
+}
+
+/// Reads a sentence at a hidden prompt and prints the password it condenses into.
+fn from_sentence(substitutions: Vec<(String, String)>, plain: bool, keep_case: bool) {
+    let mut scheme = if plain {
+        schneier::Scheme::initials()
+    } else {
+        schneier::Scheme::default()
+    };
+    scheme.keep_case = keep_case;
+    for (word, text) in &substitutions {
+        scheme.substitute(word, text);
+    }
+
+    let sentence = rpassword::prompt_password(i18n::tr("Sentence: "))
+        .unwrap_or_else(|err| fail(format!("cannot read the sentence: {}", err)));
+    let password = scheme.apply(&sentence);
+    if password.is_empty() {
+        fail(i18n::tr("no sentence was entered"));
+    }
+    if password.chars().count() < SHORT_SENTENCE_PASSWORD {
+        eprintln!(
+            "{}",
+            i18n::trf(
+                "warning: the password is only {0} characters long; use a longer sentence",
+                &[&password.chars().count()],
+            )
+        );
+    }
+    println!("{}", password);
+}
+
+/// Prints passwords built from the syllable grammar, each with its breakdown.
+fn pronounce(
+    length: Option<usize>,
+    grammar: Option<String>,
+    consonants: Option<String>,
+    vowels: Option<String>,
+    number: usize,
+) {
+    let length = match (length, &grammar) {
+        (Some(0), _) => fail("the password length must be at least 1"),
+        (None, None) => Some(10),
+        (length, _) => length,
+    };
+    let mut grammar: pronounce::Grammar = match grammar {
+        Some(pattern) => pattern
+            .parse()
+            .unwrap_or_else(|err| fail(format!("invalid grammar '{}': {}", pattern, err))),
+        None => pronounce::Grammar::default(),
+    };
+    if let Some(consonants) = consonants {
+        grammar.consonants = consonants;
+    }
+    if let Some(vowels) = vowels {
+        grammar.vowels = vowels;
+    }
+    if grammar.consonants.is_empty() || grammar.vowels.is_empty() {
+        fail("the consonant and vowel sets must not be empty");
+    }
+
+    for _ in 0..number {
+        let password = grammar.generate(length);
+        println!("{} ({})", password, password.breakdown());
+    }
+}
+
+fn parse_substitution(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((word, text)) if !word.trim().is_empty() => {
+            Ok((word.trim().to_string(), text.to_string()))
+        }
+        _ => Err(String::from("expected WORD=TEXT, for example went=WENT")),
+    }
+}
+
+/// Parses a `K/N` share specification for `--split`.
+fn parse_split(value: &str) -> Result<(u8, u8), String> {
+    let (threshold, count) = value
+        .split_once('/')
+        .ok_or_else(|| String::from("expected K/N, for example 2/3"))?;
+    let threshold: u8 = threshold
+        .parse()
+        .map_err(|_| format!("invalid threshold '{}'", threshold))?;
+    let count: u8 = count
+        .parse()
+        .map_err(|_| format!("invalid share count '{}'", count))?;
+    if threshold < 2 || threshold > count {
+        return Err(String::from(
+            "the threshold must be between 2 and the share count",
+        ));
+    }
+    Ok((threshold, count))
+}
+
+fn parse_halves(value: &str) -> Result<(PathBuf, PathBuf), String> {
+    match value.split_once(',') {
+        Some((first, second)) if !first.is_empty() && !second.is_empty() && first != second => {
+            Ok((PathBuf::from(first), PathBuf::from(second)))
+        }
+        _ => Err(String::from(
+            "expected two different files, for example a.txt,b.txt",
+        )),
+    }
+}
+
+fn totp(secret: Option<String>, digits: u32, period: u64) {
+    if period == 0 || !(1..=10).contains(&digits) {
+        fail("--period must be positive and --digits between 1 and 10");
+    }
+
+    let secret = match secret {
+        Some(secret) => secret,
+        None => rpassword::prompt_password("TOTP secret: ")
+            .unwrap_or_else(|err| fail(format!("cannot read secret: {}", err))),
+    };
+    let key = otp::base32_decode(secret.trim())
+        .filter(|key| !key.is_empty())
+        .unwrap_or_else(|| fail("the secret is not valid base32"));
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_else(|_| fail("the system clock is set before 1970"))
+        .as_secs();
+    let code = |time| otp::totp(&key, time, period, digits).unwrap_or_else(|err| fail(err));
+    println!(
+        "current: {} (valid for {}s)",
+        code(now),
+        period - now % period
+    );
+    println!("next:    {}", code(now + period));
+}
+
+/// Names a base word in error messages, by position instead of by value when it was
+/// typed at the hidden prompt.
+fn base_word_name(words: &[String], index: usize, prompted: bool) -> String {
+    if prompted {
+        i18n::trf("base word #{0}", &[&(index + 1)])
+    } else {
+        i18n::trf("the base word '{0}'", &[&words[index]])
+    }
+}
+
+/// Below this many characters a password condensed from a sentence draws a warning.
+const SHORT_SENTENCE_PASSWORD: usize = 10;
+
+/// Symbols reachable from the first symbol layer of common phone keyboards.
+const MOBILE_SPECIAL_CHARS: &str = ".-_!";
+
+/// Looks up a complexity level by its name, ignoring case.
+fn complexity_from_name(name: &str) -> Option<ComplexityLevel> {
+    match name.to_lowercase().as_str() {
+        "basic" => Some(ComplexityLevel::Basic),
+        "medium" => Some(ComplexityLevel::Medium),
+        "hard" => Some(ComplexityLevel::Hard),
+        "penguin" => Some(ComplexityLevel::Penguin),
+        _ => None,
+    }
+}
+
+/// Parses a complexity name as accepted on the command line, falling back to basic.
+fn parse_complexity(name: &str) -> ComplexityLevel {
+    complexity_from_name(name).unwrap_or(ComplexityLevel::Basic)
+}
+
+/// Builds the separator policy from the separator flags. Giving only a count keeps
+/// the character classes the complexity level would use.
+fn separator_policy(args: &GenerateArgs, complexity: ComplexityLevel) -> SeparatorPolicy {
+    if let Some(separator) = &args.separator {
+        return SeparatorPolicy::Fixed(separator.clone());
+    }
+    if args.separator_classes.is_empty() && args.separator_count.is_none() {
+        return SeparatorPolicy::ByComplexity;
+    }
+
+    let classes = if !args.separator_classes.is_empty() {
+        args.separator_classes.clone()
+    } else if matches!(complexity, ComplexityLevel::Basic) {
+        vec![CharClass::Digits]
+    } else {
+        vec![CharClass::Specials, CharClass::Digits]
+    };
+    SeparatorPolicy::Random {
+        classes,
+        count: args.separator_count.unwrap_or(1),
+    }
+}
+
+/// Prints an error message and exits with a failure status.
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", i18n::trf("error: {0}", &[&message]));
+    std::process::exit(1);
+}
+
+fn generate(mut args: GenerateArgs) {
+    if let Some(style) = args.style {
+        style.apply(&mut args);
+    }
+    if let Some(name) = args.template.clone() {
+        template::apply(&name, &mut args);
+    }
+
+    if args.prompt_words {
+        let words = rpassword::prompt_password(i18n::tr("Base words (comma-separated): "))
+            .unwrap_or_else(|err| fail(format!("cannot read base words: {}", err)));
+        args.words = words
+            .split(',')
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect();
+        if args.words.is_empty() {
+            fail(i18n::tr("no base words were entered"));
+        }
+    }
+
+    if let Some(path) = &args.wordlist {
+        let contents = if path.as_os_str() == "-" {
+            if args.per_line {
+                fail("--per-line reads labels from stdin, so the base words can't come from there too");
+            }
+            std::io::read_to_string(std::io::stdin())
+                .unwrap_or_else(|err| fail(format!("cannot read stdin: {}", err)))
+        } else {
+            std::fs::read_to_string(path)
+                .unwrap_or_else(|err| fail(format!("cannot read {}: {}", path.display(), err)))
+        };
+        args.words.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from),
+        );
+    }
+
+    let tokens: Vec<String> = args
+        .words
+        .iter()
+        .flat_map(|phrase| args.tokenize.split(phrase))
+        .map(String::from)
+        .collect();
+    args.words = tokens;
+
+    if let Some(chosen) = &args.expand_words {
+        let chosen = if chosen.is_empty() {
+            &variants::Variant::ALL[..]
+        } else {
+            chosen.as_slice()
+        };
+        let words: Vec<&str> = args.words.iter().map(String::as_str).collect();
+        let expanded = variants::expand(&words, chosen);
+        tracing::debug!(
+            base_words = words.len(),
+            expanded = expanded.len(),
+            "expanded the base words"
+        );
+        args.words = expanded;
+    }
+
+    if args.per_line {
+        args.labels = std::io::stdin()
+            .lines()
+            .map(|line| line.unwrap_or_else(|err| fail(format!("cannot read stdin: {}", err))))
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        if args.labels.is_empty() {
+            fail(i18n::tr("--per-line needs at least one label on stdin"));
+        }
+    }
+
+    let number = match args.number {
+        Some(number) if !args.labels.is_empty() && number != args.labels.len() => {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "{} labels were given but --number asks for {} passwords",
+                        args.labels.len(),
+                        number
+                    ),
+                )
+                .exit();
+        }
+        Some(number) => number,
+        None if !args.labels.is_empty() => args.labels.len(),
+        None if args.pick => picker::CANDIDATES,
+        None => 1,
+    };
+
+    if args.charset.is_some() {
+        let complexity = args
+            .complexity
+            .get_or_insert_with(|| String::from("penguin"));
+        if !matches!(
+            complexity_from_name(complexity),
+            Some(ComplexityLevel::Penguin)
+        ) {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "--charset only applies to the penguin complexity level",
+                )
+                .exit();
+        }
+    }
+
+    let complexity_level = parse_complexity(args.complexity.as_deref().unwrap_or("basic"));
+    let degenerate = match complexity_level {
+        ComplexityLevel::Basic => args.no_numbers,
+        ComplexityLevel::Medium | ComplexityLevel::Hard => args.no_numbers && args.no_special,
+        ComplexityLevel::Penguin => false,
+    };
+    if degenerate {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "{} complexity without its extra character classes is just the base words; \
+                     choose a higher complexity or keep numbers/special characters",
+                    format!("{:?}", complexity_level).to_lowercase()
+                ),
+            )
+            .exit();
+    }
+
+    let mut mixer = PenguinMixer::try_new(
+        complexity_level,
+        args.whole_words,
+        args.length.unwrap_or(12),
+    )
+    .unwrap_or_else(|err| fail(err.to_string()));
+    if args.pronounceable {
+        mixer.generation = GenerationStyle::Pronounceable;
+    }
+    mixer.separator = separator_policy(&args, complexity_level);
+    if args.no_numbers {
+        mixer.excluded_classes.push(CharClass::Digits);
+    }
+    if args.no_special {
+        mixer.excluded_classes.push(CharClass::Specials);
+    }
+    mixer.charset = args.charset.take();
+    if args.mobile_friendly {
+        mixer.special_chars = String::from(MOBILE_SPECIAL_CHARS);
+        mixer.group_classes = true;
+    }
+    if let Some(layout) = args.layout {
+        mixer.special_chars = layout.special_chars(args.weight_reach);
+    }
+    mixer.required_chars = args
+        .must_include
+        .iter()
+        .flat_map(|chars| chars.chars().map(String::from))
+        .chain(args.must_include_any.iter().cloned())
+        .collect();
+    mixer.prefix = args.prefix;
+    mixer.suffix = args.suffix;
+
+    mixer.forbidden_substrings = args.avoid_words;
+    if let Some(path) = &args.avoid_words_file {
+        let contents = std::fs::read_to_string(path)
+            .unwrap_or_else(|err| fail(format!("cannot read {}: {}", path.display(), err)));
+        mixer.forbidden_substrings.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from),
+        );
+    }
+    #[cfg(feature = "profanity")]
+    if args.clean {
+        mixer.reject_if.push(penguin::profanity::contains_profanity);
+    }
+    let policy = match (&args.policy_file, &args.site) {
+        (Some(path), _) => {
+            let policy: PasswordPolicy = std::fs::read_to_string(path)
+                .unwrap_or_else(|err| fail(format!("cannot read {}: {}", path.display(), err)))
+                .parse()
+                .unwrap_or_else(|err| fail(format!("{}: {}", path.display(), err)));
+            Some((policy, path.display().to_string()))
+        }
+        (None, Some(site)) => Some((sites::policy(site), site.clone())),
+        (None, None) => args.template_policy.take().map(|policy| {
+            let name = args.template.clone().unwrap_or_default();
+            (policy, format!("template {}", name))
+        }),
+    };
+    let policy = match policy {
+        Some((policy, source)) => args
+            .rules
+            .apply(Some(policy))
+            .map(|policy| (policy, source)),
+        None => args
+            .rules
+            .apply(None)
+            .map(|policy| (policy, String::from("the composition rules"))),
+    };
+    if let Some((policy, source)) = &policy {
+        if let Some(length) = args.length {
+            if policy.clamp_length(length) != length {
+                fail(format!(
+                    "--length {} is outside the bounds of {}",
+                    length, source
+                ));
+            }
+        }
+        policy.configure(&mut mixer);
+    }
+    mixer.min_entropy = args.min_entropy;
+    if let Some(attempts) = args.max_attempts {
+        mixer.max_attempts = attempts as usize;
+    }
+    if let Some(format) = args.safe_for {
+        format.configure(&mut mixer);
+    }
+    if args.alternate_hands {
+        mixer
+            .reject_if
+            .push(match args.layout.unwrap_or(layout::Layout::Qwerty) {
+                layout::Layout::Qwerty => {
+                    |p| !layout::Layout::Qwerty.typing_score(p).is_comfortable()
+                }
+                layout::Layout::Azerty => {
+                    |p| !layout::Layout::Azerty.typing_score(p).is_comfortable()
+                }
+                layout::Layout::Qwertz => {
+                    |p| !layout::Layout::Qwertz.typing_score(p).is_comfortable()
+                }
+                layout::Layout::Dvorak => {
+                    |p| !layout::Layout::Dvorak.typing_score(p).is_comfortable()
+                }
+            });
+    }
+    mixer.max_walk = args.max_walk;
+    mixer.avoid_dates |= args.avoid_dates;
+    for detail in &args.personal {
+        mixer.personal.add(detail);
+    }
+    if args.dictation {
+        mixer
+            .reject_if
+            .push(|password| !dictation::is_dictation_safe(password));
+    }
+    if args.single_case {
+        mixer.excluded_classes.push(CharClass::Uppercase);
+        for word in &mut args.words {
+            *word = word.to_lowercase();
+        }
+    }
+    if let Some(min_words) = args.min_words {
+        if !mixer.use_whole_words || matches!(mixer.complexity, ComplexityLevel::Penguin) {
+            fail("--min-words only applies to whole-word passwords; add --whole-words");
+        }
+        mixer.min_words = min_words;
+    }
+    if args.hint.is_some()
+        && (!mixer.use_whole_words || matches!(mixer.complexity, ComplexityLevel::Penguin))
+    {
+        fail("--hint only applies to whole-word passwords; add --whole-words");
+    }
+    if let Some(handling) = args.on_short_length {
+        if !mixer.use_whole_words || matches!(mixer.complexity, ComplexityLevel::Penguin) {
+            fail("--on-short-length only applies to whole-word passwords; add --whole-words");
+        }
+        mixer.on_short_length = handling;
+    }
+    mixer.pattern = args.pattern.take();
+    if args.never_reuse {
+        reuse::enable();
+        mixer.reject_if.push(reuse::seen);
+    }
+    let words: Vec<&str> = args.words.iter().map(String::as_str).collect();
+    match mixer.validate(&words) {
+        Ok(()) => {}
+        Err(PenguinError::NoWords) => fail(i18n::tr(
+            "no base words to build passwords from; pass some with -w or use --complexity penguin",
+        )),
+        Err(err) => fail(err.to_string()),
+    }
+    if args.on_short_length.is_none()
+        && !args.exact_length
+        && args.min_words.is_none()
+        && mixer.overflows(&words)
+    {
+        let budget = mixer.length - mixer.prefix.len() - mixer.suffix.len();
+        eprintln!(
+            "{}",
+            i18n::trf(
+                "warning: every base word is longer than {0} characters and will be cut; \
+                 pick a handling with --on-short-length",
+                &[&budget]
+            )
+        );
+    }
+    if args.exact_length {
+        if !mixer.use_whole_words || matches!(mixer.complexity, ComplexityLevel::Penguin) {
+            fail("--exact-length only applies to whole-word passwords; add --whole-words");
+        }
+        mixer.exact_fit = true;
+        if !mixer.fits_exactly(&words) {
+            fail(format!(
+                "no combination of the base words and their separators is exactly {} characters \
+                 long; change --length or the words",
+                mixer.length
+            ));
+        }
+    }
+    if let Some(extra) = args.max_overshoot {
+        if !mixer.use_whole_words || matches!(mixer.complexity, ComplexityLevel::Penguin) {
+            fail("--max-overshoot only applies to whole-word passwords; add --whole-words");
+        }
+        mixer.max_overshoot = Some(extra);
+    }
+    if args.abbreviate {
+        if !mixer.use_whole_words || matches!(mixer.complexity, ComplexityLevel::Penguin) {
+            fail("--abbreviate only applies to whole-word passwords; add --whole-words");
+        }
+        mixer.abbreviate = true;
+    }
+    if mixer.use_whole_words {
+        let forbidden_word = args.words.iter().position(|word| {
+            let word = word.to_lowercase();
+            mixer
+                .forbidden_substrings
+                .iter()
+                .any(|avoided| !avoided.is_empty() && word.contains(&avoided.to_lowercase()))
+        });
+        if let Some(index) = forbidden_word {
+            fail(i18n::trf(
+                "{0} contains an avoided word; remove it or drop --whole-words",
+                &[&base_word_name(&args.words, index, args.prompt_words)],
+            ));
+        }
+        if let Some(index) = args
+            .words
+            .iter()
+            .position(|word| mixer.personal.contains(word))
+        {
+            fail(i18n::trf(
+                "{0} contains personal information; remove it or drop --whole-words",
+                &[&base_word_name(&args.words, index, args.prompt_words)],
+            ));
+        }
+        if args.dictation {
+            if let Some(index) = args
+                .words
+                .iter()
+                .position(|word| !dictation::is_dictation_safe(word))
+            {
+                fail(i18n::trf(
+                    "{0} is easy to mishear when spelled out; pick another or drop --whole-words",
+                    &[&base_word_name(&args.words, index, args.prompt_words)],
+                ));
+            }
+        }
+        if let Some(format) = args.safe_for {
+            if let Some(index) = args
+                .words
+                .iter()
+                .position(|word| !word.chars().all(|c| format.allows(c)))
+            {
+                fail(i18n::trf(
+                    "{0} needs quoting in {1}; pick another or drop --whole-words",
+                    &[
+                        &base_word_name(&args.words, index, args.prompt_words),
+                        &format,
+                    ],
+                ));
+            }
+        }
+    }
+
+    tracing::debug!(
+        complexity = ?mixer.complexity,
+        length = mixer.length,
+        whole_words = mixer.use_whole_words,
+        base_words = args.words.len(),
+        required_groups = mixer.required_chars.len(),
+        forbidden_substrings = mixer.forbidden_substrings.len(),
+        filters = mixer.reject_if.len(),
+        policy = mixer.policy.is_some(),
+        "configured the generator"
+    );
+    let jobs = progress::jobs(args.jobs.map(|jobs| jobs as usize), number);
+    if let Some(interval) = args.watch {
+        let strategy = audit::strategy(&mixer);
+        output::guard(&args.output);
+        watch::run(interval, || {
+            let (passwords, _) = progress::generate(&words, &mixer, number, jobs, false);
+            reuse::record(&passwords);
+            let entries: Vec<Entry> = passwords
+                .into_iter()
+                .enumerate()
+                .map(|(i, password)| Entry {
+                    index: i + 1,
+                    label: args.labels.get(i).cloned(),
+                    username: String::new(),
+                    shares: Vec::new(),
+                    hint: None,
+                    word_hint: None,
+                    notes: String::new(),
+                    expires: None,
+                    strategy,
+                    policy: None,
+                    complexity: Some(mixer.complexity()),
+                    entropy_bits: None,
+                    password,
+                })
+                .collect();
+            let mut stdout = std::io::stdout().lock();
+            write!(stdout, "{}", output::render(&args.output, &entries))
+                .and_then(|()| stdout.flush())
+        });
+    }
+    let mut copy = false;
+    let (passwords, generation) = if args.interactive {
+        let accepted = match interactive::choose(&words, &mut mixer, &args.output) {
+            interactive::Accepted::Print(password) => password,
+            interactive::Accepted::Copy(password) => {
+                copy = true;
+                password
+            }
+        };
+        (vec![accepted], penguin::GenerationStats::default())
+    } else {
+        progress::generate(&words, &mixer, number, jobs, !args.quiet)
+    };
+    if !args.words.is_empty() && passwords.iter().any(String::is_empty) {
+        fail(i18n::tr(
+            "could not generate passwords that avoid all of the avoided words and filters",
+        ));
+    }
+    reuse::record(&passwords);
+    if args.pick {
+        let Some(index) = picker::pick(&passwords) else {
+            std::process::exit(1);
+        };
+        let label: Vec<String> = args.labels.get(index).cloned().into_iter().collect();
+        if let Some(path) = &args.audit_log {
+            audit::append(path, &passwords[index..=index], &label, &mixer);
+        }
+        if let Some(path) = &args.receipt {
+            receipt::append(path, &passwords[index..=index], &label, &mixer);
+        }
+        clipboard::copy(&passwords[index], &args.clipboard).unwrap_or_else(|err| fail(err));
+        history::record(args.expires, 1, &label, None);
+        println!("{}", index + 1);
+        return;
+    }
+
+    if args.stats {
+        eprint!("{}", stats::report(&passwords, &mixer));
+    }
+    if args.verbose {
+        eprint!("{}", verbose::report(&passwords, &words, &mixer));
+    }
+    if let Some(attackers) = &args.crack_time {
+        let attackers = if attackers.is_empty() {
+            &strength::Attacker::ALL[..]
+        } else {
+            attackers
+        };
+        eprint!("{}", crack::report(&passwords, attackers));
+    }
+    if args.stats || args.verbose {
+        eprint!("{}", stats::generation(&generation));
+    }
+    if let Some(path) = &args.audit_log {
+        audit::append(path, &passwords, &args.labels, &mixer);
+    }
+    if let Some(path) = &args.receipt {
+        receipt::append(path, &passwords, &args.labels, &mixer);
+    }
+    if copy {
+        clipboard::copy(&passwords[0], &args.clipboard).unwrap_or_else(|err| fail(err));
+        history::record(args.expires, 1, &args.labels, None);
+        eprintln!("{}", i18n::tr("Copied the password to the clipboard"));
+        return;
+    }
+    if args.share {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--share"],
+            ));
+        };
+        clipboard::share(password).unwrap_or_else(|err| fail(err));
+        history::record(args.expires, 1, &args.labels, None);
+        return;
+    }
+
+    #[cfg(feature = "keyring")]
+    if let Some(target) = &args.save_keyring {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--save-keyring"],
+            ));
+        };
+        keystore::save(target, password).unwrap_or_else(|err| fail(err));
+        let store = format!("keyring:{}/{}", target.service, target.account);
+        history::record(args.expires, 1, &args.labels, Some(&store));
+        println!(
+            "{}",
+            i18n::trf(
+                "Saved the password to the keyring as {0}/{1}",
+                &[&target.service, &target.account],
+            )
+        );
+        return;
+    }
+
+    #[cfg(feature = "keyring")]
+    if let Some(target) = &args.save_credman {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--save-credman"],
+            ));
+        };
+        let user = args.credman_user.as_deref().unwrap_or_default();
+        keystore::save_credman(target, user, password).unwrap_or_else(|err| fail(err));
+        let store = format!("credman:{}", target);
+        history::record(args.expires, 1, &args.labels, Some(&store));
+        println!(
+            "{}",
+            i18n::trf(
+                "Saved the password to the Credential Manager as {0} ({1})",
+                &[&target, &user],
+            )
+        );
+        return;
+    }
+
+    #[cfg(feature = "vault")]
+    if let Some(target) = &args.vault_write {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--vault-write"],
+            ));
+        };
+        let expires = args.expires.map(date::format);
+        vault::write(target, password, expires.as_deref()).unwrap_or_else(|err| fail(err));
+        eprintln!(
+            "{}",
+            i18n::trf(
+                "Wrote the password to Vault at {0}#{1}",
+                &[&target.path, &target.key],
+            )
+        );
+    }
+
+    #[cfg(feature = "aws")]
+    {
+        let destination = match (&args.aws_secret, &args.aws_ssm) {
+            (Some(name), _) => Some(aws::Destination::SecretsManager(name)),
+            (None, Some(path)) => Some(aws::Destination::Parameter(path)),
+            (None, None) => None,
+        };
+        if let Some(destination) = destination {
+            let [password] = passwords.as_slice() else {
+                fail(i18n::tr(
+                    "--aws-secret and --aws-ssm store a single password; drop --number and --label",
+                ));
+            };
+            println!(
+                "{}",
+                aws::store(destination, password).unwrap_or_else(|err| fail(err))
+            );
+            let store = match (&args.aws_secret, &args.aws_ssm) {
+                (Some(name), _) => format!("aws-secret:{}", name),
+                (None, path) => format!("aws-ssm:{}", path.as_deref().unwrap_or_default()),
+            };
+            history::record(args.expires, 1, &args.labels, Some(&store));
+            return;
+        }
+    }
+
+    if let Some((server, account)) = args.save_keychain {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--save-keychain"],
+            ));
+        };
+        let item = keychain::KeychainItem {
+            server,
+            account,
+            label: args.keychain_label,
+            kind: args.keychain_kind,
+        };
+        keychain::save(&item, password).unwrap_or_else(|err| fail(err));
+        let store = format!("keychain:{}/{}", item.server, item.account);
+        history::record(args.expires, 1, &args.labels, Some(&store));
+        println!(
+            "{}",
+            i18n::trf(
+                "Saved the password to the keychain for https://{0} ({1})",
+                &[&item.server, &item.account],
+            )
+        );
+        return;
+    }
+
+    if let Some(database) = args.save_keepassxc {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--save-keepassxc"],
+            ));
+        };
+        let entry = keepassxc::KeepassEntry {
+            database,
+            entry: args.entry.unwrap_or_default(),
+            username: args.entry_username,
+            key_file: args.keepassxc_key_file,
+            notes: output::generated_notes(mixer.complexity),
+        };
+        keepassxc::save(&entry, password).unwrap_or_else(|err| fail(err));
+        let store = format!("keepassxc:{}#{}", entry.database.display(), entry.entry);
+        history::record(args.expires, 1, &args.labels, Some(&store));
+        println!(
+            "{}",
+            i18n::trf(
+                "Added the password to {0} as {1}",
+                &[&entry.database.display(), &entry.entry],
+            )
+        );
+        return;
+    }
+
+    if args.save_op {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--save-op"],
+            ));
+        };
+        let item = onepassword::OpItem {
+            title: args.title.unwrap_or_default(),
+            vault: args.op_vault,
+            username: args.op_username,
+            url: args.op_url,
+            notes: output::generated_notes(mixer.complexity),
+        };
+        let id = onepassword::save(&item, password).unwrap_or_else(|err| fail(err));
+        let store = format!("1password:{}", id);
+        history::record(args.expires, 1, &args.labels, Some(&store));
+        println!(
+            "{}",
+            i18n::trf("Created the 1Password item {0} ({1})", &[&item.title, &id])
+        );
+        return;
+    }
+
+    if args.save_bw {
+        let [password] = passwords.as_slice() else {
+            fail(i18n::trf(
+                "{0} stores a single password; drop --number and --label",
+                &[&"--save-bw"],
+            ));
+        };
+        let item = bitwarden::BwItem {
+            name: args.output.name.clone().unwrap_or_default(),
+            folder: args.folder,
+            username: args.bw_username,
+            url: args.bw_url,
+            notes: output::generated_notes(mixer.complexity),
+        };
+        let id = bitwarden::save(&item, password).unwrap_or_else(|err| fail(err));
+        let store = format!("bitwarden:{}", id);
+        history::record(args.expires, 1, &args.labels, Some(&store));
+        println!(
+            "{}",
+            i18n::trf("Created the Bitwarden item {0} ({1})", &[&item.name, &id])
+        );
+        return;
+    }
+
+    if args.split.is_some() && args.output.format != Format::Plain {
+        fail("--split can only be used with the plain output format");
+    }
+    if args.output.mask.is_some() && (args.split.is_some() || args.mnemonic.is_some()) {
+        fail("--mask can't be combined with --split or --mnemonic, which reveal the password");
+    }
+
+    #[cfg(feature = "vault")]
+    let store =
+        (args.vault_write.as_ref()).map(|target| format!("vault:{}#{}", target.path, target.key));
+    #[cfg(not(feature = "vault"))]
+    let store: Option<String> = None;
+    history::record(
+        args.expires,
+        passwords.len(),
+        &args.labels,
+        store.as_deref(),
+    );
+
+    let expires = args.expires.map(date::format);
+    let mut notes = output::generated_notes(mixer.complexity);
+    if let Some(expires) = &expires {
+        notes.push_str(&format!("\nExpires: {}", expires));
+    }
+    let strategy = audit::strategy(&mixer);
+    let policy = match (&args.policy_file, &args.site, args.style) {
+        (Some(path), ..) => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned()),
+        (None, Some(site), _) => Some(site.clone()),
+        (None, None, _) if args.template.is_some() => args.template.clone(),
+        (None, None, Some(style)) => Some(format!("{:?}", style).to_lowercase()),
+        (None, None, None) => None,
+    };
+    let mut labels = args.labels.into_iter();
+    let mut entries: Vec<Entry> = passwords
+        .into_iter()
+        .enumerate()
+        .map(|(i, password)| Entry {
+            index: i + 1,
+            label: labels.next(),
+            username: String::new(),
+            shares: match args.split {
+                Some((threshold, count)) => shamir::split(password.as_bytes(), threshold, count)
+                    .unwrap_or_else(|err| fail(err))
+                    .iter()
+                    .map(|share| share.to_string())
+                    .collect(),
+                None => Vec::new(),
+            },
+            hint: args.mnemonic.map(|style| match style {
+                MnemonicStyle::Random => hint::mnemonic(&password),
+                MnemonicStyle::Phonetic => hint::phonetic(&password),
+            }),
+            word_hint: None,
+            notes: notes.clone(),
+            expires: expires.clone(),
+            strategy,
+            policy: policy.clone(),
+            complexity: Some(mixer.complexity()),
+            entropy_bits: None,
+            password,
+        })
+        .collect();
+
+    if let Some(style) = args.hint {
+        for entry in &mut entries {
+            match hint::word_hint(&entry.password, &words, style) {
+                Some(found) if found.bits_after >= args.hint_min_bits => {
+                    entry.notes = format!("{}\nHint: {}", entry.notes, found.text);
+                    entry.word_hint = Some(found.text);
+                }
+                found => eprintln!(
+                    "{}",
+                    i18n::trf(
+                        "warning: no hint for {0}; it would leave {1} bits to guess, below {2}",
+                        &[
+                            &entry.title(),
+                            &format!("{:.1}", found.map_or(0.0, |found| found.bits_after)),
+                            &args.hint_min_bits,
+                        ],
+                    )
+                ),
+            }
+        }
+    }
+
+    // Outside the plain format, hints go into the exported notes only on request and
+    // are shown on stderr otherwise, so they never end up in a file by accident.
+    let tabbed = args.per_line && args.output.format == Format::Plain;
+    if args.output.format != Format::Plain || tabbed {
+        for entry in &mut entries {
+            let Some(hint) = entry.hint.take() else {
+                continue;
+            };
+            if args.store_mnemonic {
+                entry.notes = format!("{}\nHint: {}", entry.notes, hint);
+            } else {
+                eprintln!("{}: {}", entry.title(), hint);
+            }
+        }
+    }
+
+    if let Some((first, second)) = &args.split_halves {
+        output::write_halves(first, second, &entries);
+        if !args.quiet {
+            eprintln!(
+                "{}",
+                i18n::trf(
+                    "Wrote the first halves to {0} and the second halves to {1}",
+                    &[&first.display(), &second.display()]
+                )
+            );
+        }
+        return;
+    }
+
+    // --quiet only keeps the passwords off stdout; an --output file is still written, and
+    // rendering still writes the files of formats such as docker-secret.
+    let rendered = if tabbed && !args.output.null && !args.output.bare {
+        output::render_tabbed(&entries, &args.output)
+    } else {
+        output::render(&args.output, &entries)
+    };
+    if !args.quiet || args.output.output_file.is_some() {
+        output::emit(&args.output, &rendered);
+    }
+    if !args.quiet {
+        output::offer_reveal(&args.output, &entries);
+    }
+}
@@ -0,0 +1,83 @@
+//! Launcher integration for dmenu, rofi, and similar menus.
+//!
+//! `penguin menu` prints candidate passwords one per line, which is exactly what launchers
+//! read on stdin. The launcher's choice is written to stdout, so piping it into
+//! `penguin menu --select` completes the round trip by typing the password into the
+//! focused window (`wtype` under Wayland, `xdotool` under X11) or, with `--copy`, putting
+//! it on the clipboard:
+//!
+//! ```text
+//! penguin menu -n 5 | rofi -dmenu | penguin menu --select
+//! ```
+
+use crate::clipboard;
+use penguin::{mixer::PenguinMixer, pronounce};
+use std::io::{self, BufRead, Write};
+use std::process::{Command, Stdio};
+
+/// Prints `count` candidates, built from the mixer when words are given and
+/// pronounceable otherwise.
+pub fn candidates(words: &[String], mixer: &PenguinMixer, count: usize) {
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let mut stdout = io::stdout().lock();
+    for _ in 0..count {
+        let candidate = if words.is_empty() {
+            pronounce::generate(mixer.length).to_string()
+        } else {
+            mixer
+                .mix_password(&words)
+                .unwrap_or_else(|err| crate::fail(err.to_string()))
+        };
+        if writeln!(stdout, "{}", candidate).is_err() {
+            return;
+        }
+    }
+}
+
+/// Reads the chosen line from stdin and types it, or copies it when clipboard options
+/// are given.
+pub fn select(copy: Option<clipboard::ClipboardArgs>) {
+    let mut choice = String::new();
+    if let Err(err) = io::stdin().lock().read_line(&mut choice) {
+        crate::fail(format!("cannot read stdin: {}", err));
+    }
+    let choice = choice.trim_end_matches(['\r', '\n']);
+    if choice.is_empty() {
+        // The launcher was dismissed without a choice.
+        return;
+    }
+
+    let result = match copy {
+        Some(args) => clipboard::copy(choice, &args),
+        None => type_text(choice),
+    };
+    result.unwrap_or_else(|err| crate::fail(err));
+}
+
+/// Types the text into the focused window, feeding it on stdin to keep it off the
+/// process list.
+fn type_text(text: &str) -> Result<(), String> {
+    let (program, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wtype", &["-"])
+    } else {
+        ("xdotool", &["type", "--clearmodifiers", "--file", "-"])
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run {}: {}", program, err))?;
+    let written = child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(text.as_bytes());
+    let status = child
+        .wait()
+        .map_err(|err| format!("cannot run {}: {}", program, err))?;
+    if written.is_err() || !status.success() {
+        return Err(format!("{} failed to type the password", program));
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Prometheus metrics for the server modes, served at `/metrics` by `penguin serve` and
+//! by the `metrics` method of `penguin rpc` and `penguin daemon`.
+//!
+//! Only counts and timings are kept: calls by operation and outcome, how long generation
+//! took, how many candidates each constraint rejected, and the health of the entropy
+//! source, checked afresh on every scrape. No password, base word, or parameter value is
+//! ever recorded, and operation names other than the known ones are counted as
+//! `unknown` so a client can't fill the metrics with labels of its choosing.
+
+use penguin::{entropy, GenerationStats};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Upper bounds, in seconds, of the generation latency histogram's buckets.
+const LATENCY_BUCKETS: [f64; 9] = [0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0];
+
+/// The operations counted under their own name.
+const OPERATIONS: [&str; 4] = ["generate", "analyze", "passphrase", "metrics"];
+
+struct Metrics {
+    /// Calls by operation and whether they succeeded.
+    calls: BTreeMap<(&'static str, &'static str), u64>,
+    /// Generations per latency bucket, with the last slot for slower ones.
+    latency: [u64; LATENCY_BUCKETS.len() + 1],
+    latency_sum: f64,
+    passwords: u64,
+    attempts: u64,
+    rejections: BTreeMap<&'static str, u64>,
+}
+
+static METRICS: Mutex<Metrics> = Mutex::new(Metrics {
+    calls: BTreeMap::new(),
+    latency: [0; LATENCY_BUCKETS.len() + 1],
+    latency_sum: 0.0,
+    passwords: 0,
+    attempts: 0,
+    rejections: BTreeMap::new(),
+});
+
+fn metrics() -> std::sync::MutexGuard<'static, Metrics> {
+    METRICS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Counts a call of an operation.
+pub fn record_call(operation: &str, succeeded: bool) {
+    let operation = OPERATIONS
+        .iter()
+        .find(|&&known| known == operation)
+        .copied()
+        .unwrap_or("unknown");
+    let outcome = if succeeded { "ok" } else { "error" };
+    *metrics().calls.entry((operation, outcome)).or_default() += 1;
+}
+
+/// Adds a generated batch's latency, candidates, and rejections.
+pub fn record_generation(stats: &GenerationStats) {
+    let mut metrics = metrics();
+    let seconds = stats.elapsed.as_secs_f64();
+    let bucket = LATENCY_BUCKETS
+        .iter()
+        .position(|&bound| seconds <= bound)
+        .unwrap_or(LATENCY_BUCKETS.len());
+    metrics.latency[bucket] += 1;
+    metrics.latency_sum += seconds;
+    metrics.passwords += stats.passwords as u64;
+    metrics.attempts += stats.attempts as u64;
+    for &(reason, count) in &stats.rejections {
+        *metrics.rejections.entry(reason).or_default() += count as u64;
+    }
+}
+
+/// Renders every metric in the Prometheus text format.
+pub fn render() -> String {
+    let health = entropy::health_check();
+    let metrics = metrics();
+    let mut out = String::new();
+
+    out.push_str("# HELP penguin_requests_total Calls by operation and outcome.\n");
+    out.push_str("# TYPE penguin_requests_total counter\n");
+    for ((operation, outcome), count) in &metrics.calls {
+        let _ = writeln!(
+            out,
+            "penguin_requests_total{{operation=\"{}\",outcome=\"{}\"}} {}",
+            operation, outcome, count
+        );
+    }
+
+    out.push_str("# HELP penguin_generation_duration_seconds Time spent generating a batch.\n");
+    out.push_str("# TYPE penguin_generation_duration_seconds histogram\n");
+    let mut cumulative = 0;
+    for (bound, count) in LATENCY_BUCKETS.iter().zip(&metrics.latency) {
+        cumulative += count;
+        let _ = writeln!(
+            out,
+            "penguin_generation_duration_seconds_bucket{{le=\"{}\"}} {}",
+            bound, cumulative
+        );
+    }
+    let total: u64 = metrics.latency.iter().sum();
+    let _ = writeln!(
+        out,
+        "penguin_generation_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+        total
+    );
+    let _ = writeln!(
+        out,
+        "penguin_generation_duration_seconds_sum {}",
+        metrics.latency_sum
+    );
+    let _ = writeln!(out, "penguin_generation_duration_seconds_count {}", total);
+
+    out.push_str("# HELP penguin_generated_passwords_total Passwords returned.\n");
+    out.push_str("# TYPE penguin_generated_passwords_total counter\n");
+    let _ = writeln!(
+        out,
+        "penguin_generated_passwords_total {}",
+        metrics.passwords
+    );
+    out.push_str("# HELP penguin_generation_attempts_total Candidates generated, including the ones returned.\n");
+    out.push_str("# TYPE penguin_generation_attempts_total counter\n");
+    let _ = writeln!(
+        out,
+        "penguin_generation_attempts_total {}",
+        metrics.attempts
+    );
+    out.push_str("# HELP penguin_rejections_total Candidates rejected, by the constraint that rejected them.\n");
+    out.push_str("# TYPE penguin_rejections_total counter\n");
+    for (reason, count) in &metrics.rejections {
+        let _ = writeln!(
+            out,
+            "penguin_rejections_total{{reason=\"{}\"}} {}",
+            reason, count
+        );
+    }
+
+    out.push_str("# HELP penguin_entropy_source_healthy Whether the entropy source passed its health check.\n");
+    out.push_str("# TYPE penguin_entropy_source_healthy gauge\n");
+    let _ = writeln!(
+        out,
+        "penguin_entropy_source_healthy{{source=\"{}\"}} {}",
+        health.source,
+        u8::from(health.is_healthy())
+    );
+    out.push_str("# HELP penguin_entropy_health_check_seconds Time the entropy source took to fill the health check sample.\n");
+    out.push_str("# TYPE penguin_entropy_health_check_seconds gauge\n");
+    let _ = writeln!(
+        out,
+        "penguin_entropy_health_check_seconds {}",
+        health.latency.as_secs_f64()
+    );
+    out
+}
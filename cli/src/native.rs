@@ -0,0 +1,76 @@
+//! Native messaging host for browser extensions.
+//!
+//! Chrome and Firefox talk to native hosts over stdin/stdout, framing every message as a
+//! 32-bit length in native byte order followed by that many bytes of UTF-8 JSON. Requests
+//! look like `{"id": 1, "command": "generate", "params": {...}}` and are answered with
+//! `{"id": 1, "ok": true, "result": {...}}` or `{"id": 1, "ok": false, "error": "..."}`,
+//! using the same operations as the HTTP and JSON-RPC modes.
+
+use crate::api;
+use serde_json::{json, Value};
+use std::io::{self, Read, Write};
+
+/// Largest request accepted; generation requests are tiny, so anything bigger is a bug.
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// Browsers refuse messages from the host larger than this.
+const MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+/// Serves messages until the browser closes stdin.
+pub fn run() {
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+
+    loop {
+        let mut header = [0u8; 4];
+        match stdin.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return,
+            Err(err) => crate::fail(format!("cannot read stdin: {}", err)),
+        }
+        let length = u32::from_ne_bytes(header) as usize;
+        if length > MAX_REQUEST_BYTES {
+            crate::fail(format!("message of {} bytes is too large", length));
+        }
+
+        let mut message = vec![0u8; length];
+        if let Err(err) = stdin.read_exact(&mut message) {
+            crate::fail(format!("cannot read stdin: {}", err));
+        }
+
+        let mut response = respond(&message).to_string();
+        if response.len() > MAX_RESPONSE_BYTES {
+            response = json!({ "ok": false, "error": "response is too large" }).to_string();
+        }
+        let written = stdout
+            .write_all(&(response.len() as u32).to_ne_bytes())
+            .and_then(|_| stdout.write_all(response.as_bytes()))
+            .and_then(|_| stdout.flush());
+        if written.is_err() {
+            return;
+        }
+    }
+}
+
+fn respond(message: &[u8]) -> Value {
+    let request: Value = match serde_json::from_slice(message) {
+        Ok(request) => request,
+        Err(err) => return json!({ "ok": false, "error": err.to_string() }),
+    };
+
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(command) = request.get("command").and_then(Value::as_str) else {
+        return json!({ "id": id, "ok": false, "error": "expected a command" });
+    };
+    let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+
+    match api::call(command, params) {
+        Some(Ok(result)) => json!({ "id": id, "ok": true, "result": result }),
+        Some(Err(message)) => json!({ "id": id, "ok": false, "error": message }),
+        None => json!({
+            "id": id,
+            "ok": false,
+            "error": format!("unknown command '{}'", command),
+        }),
+    }
+}
@@ -0,0 +1,1350 @@
+//! Output formatting for the penguin command-line tool.
+//!
+//! Generated passwords are collected into [`Entry`] records first and rendered in one
+//! place, so every output format sees the same data. The plain format is meant for
+//! reading in a terminal, while the others produce files that can be imported into
+//! password managers without hand-editing.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use clap::{Args, ValueEnum};
+use penguin::mask;
+use penguin::mixer::ComplexityLevel;
+use penguin::strength;
+use serde_json::json;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Output formats supported by the generate command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Numbered list meant for reading in a terminal
+    Plain,
+    /// CSV importable into KeePass and KeePassXC (title, username, password, notes)
+    KeepassCsv,
+    /// JSON matching Bitwarden's unencrypted vault import schema
+    BitwardenJson,
+    /// CSV matching Bitwarden's individual vault import template
+    BitwardenCsv,
+    /// CSV matching 1Password's import template (title, website, username, password, notes)
+    #[value(name = "1password-csv")]
+    OnePasswordCsv,
+    /// NAME=password lines that can be appended to a .env file or sourced by a shell
+    Env,
+    /// Kubernetes Secret manifest ready for `kubectl apply -f -`
+    K8sSecret,
+    /// Raw credential for `systemd-creds encrypt` or a `LoadCredential=` file
+    SystemdCred,
+    /// One file per password under --secrets-dir, as Docker and Compose `secrets:` read them
+    DockerSecret,
+    /// Ansible Vault 1.1 encrypted YAML of label: password, for group_vars
+    AnsibleVault,
+    /// Terraform variable assignments (label = "password") for a .tfvars file
+    Tfvars,
+    /// SOPS-encrypted YAML (or JSON, for a .json --output) of label: password
+    Sops,
+    /// username:password lines for `chpasswd` (usernames from labels, or generated)
+    Chpasswd,
+    /// LDIF changes setting userPassword hashes, for `ldapmodify`
+    Ldif,
+    /// CREATE USER statements (ALTER USER with --alter) for the --dialect database
+    Sql,
+    /// .netrc stanzas (machine, login, password) for curl, git, ftp, and CI fetchers
+    Netrc,
+    /// Alfred/Raycast script filter JSON (items with title, subtitle, and arg)
+    ScriptFilter,
+    /// One JSON object per line with the password, label, entropy, policy, strategy, timestamp, and version
+    Jsonl,
+    /// JSON array of objects with the password, label, complexity, length, and entropy
+    Json,
+    /// CSV with a header row: password, label, complexity, length, entropy_bits
+    Csv,
+}
+
+/// Databases whose user statements the sql format writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Dialect {
+    /// PostgreSQL: CREATE USER "name" WITH PASSWORD '...'
+    Postgres,
+    /// MySQL and MariaDB: CREATE USER 'name'@'host' IDENTIFIED BY '...'
+    Mysql,
+}
+
+/// Command-line options controlling how generated passwords are written out.
+#[derive(Args)]
+pub struct OutputArgs {
+    /// Output format
+    #[arg(
+        short = 'f',
+        long,
+        value_enum,
+        default_value_t = Format::Plain,
+        env = "PENGUIN_FORMAT"
+    )]
+    pub format: Format,
+
+    /// Prefix for variable names in env output (names come from labels, or the index)
+    #[arg(long)]
+    pub var_prefix: Option<String>,
+
+    /// Name of the generated Kubernetes Secret, systemd credential, or Bitwarden item
+    #[arg(long, required_if_eq_any([("format", "k8s-secret"), ("format", "systemd-cred")]))]
+    pub name: Option<String>,
+
+    /// Secret data key for unlabeled passwords (labels are used as keys when given)
+    #[arg(long, default_value = "password")]
+    pub key: String,
+
+    /// Write plaintext values under stringData instead of base64-encoded data
+    #[arg(long)]
+    pub string_data: bool,
+
+    /// Encrypt the systemd credential with systemd-creds (for LoadCredentialEncrypted=)
+    #[arg(long)]
+    pub systemd_encrypt: bool,
+
+    /// Directory the docker-secret format writes one file per password into
+    #[arg(long, value_name = "DIR", required_if_eq("format", "docker-secret"))]
+    pub secrets_dir: Option<PathBuf>,
+
+    /// Print a Compose `secrets:` section pointing at the written files
+    #[arg(long, requires = "secrets_dir")]
+    pub compose: bool,
+
+    /// File holding the vault password the ansible-vault format encrypts with
+    #[arg(long, value_name = "FILE", required_if_eq("format", "ansible-vault"))]
+    pub vault_password_file: Option<PathBuf>,
+
+    /// Start tfvars output with a comment marking the values sensitive
+    #[arg(long)]
+    pub sensitive: bool,
+
+    /// Base DN the ldif format puts uid=<label> entries under, e.g. ou=people,dc=example,dc=com
+    #[arg(long, value_name = "DN", required_if_eq("format", "ldif"))]
+    pub base_dn: Option<String>,
+
+    /// Scheme of the userPassword hashes in ldif output
+    #[arg(long, value_enum, default_value_t = crate::ldap::Scheme::Ssha)]
+    pub ldap_hash: crate::ldap::Scheme,
+
+    /// Write ldif entries that add inetOrgPerson accounts instead of modifying existing ones
+    #[arg(long)]
+    pub ldif_add: bool,
+
+    /// Database the sql format writes statements for
+    #[arg(long, value_enum, default_value_t = Dialect::Postgres)]
+    pub dialect: Dialect,
+
+    /// Host part of MySQL accounts in sql output
+    #[arg(long, value_name = "HOST", default_value = "%")]
+    pub sql_host: String,
+
+    /// Write ALTER USER statements that rotate existing users instead of creating them
+    #[arg(long)]
+    pub alter: bool,
+
+    /// Host the netrc format writes a stanza for (labels name the hosts when unset)
+    #[arg(long, value_name = "HOST")]
+    pub machine: Option<String>,
+
+    /// Login written into netrc stanzas
+    #[arg(long, value_name = "USER")]
+    pub login: Option<String>,
+
+    /// age recipient the sops format encrypts its data key to (repeatable; needs the age feature)
+    #[arg(long, value_name = "RECIPIENT", value_delimiter = ',')]
+    pub age: Vec<String>,
+
+    /// AWS KMS key ARN the sops format encrypts its data key with (repeatable; needs the aws feature)
+    #[arg(long, value_name = "ARN", value_delimiter = ',')]
+    pub kms: Vec<String>,
+
+    /// Write the output to this file instead of stdout (created readable by the owner only)
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+
+    /// Append to the --output file instead of replacing it, making it readable by the owner only
+    #[arg(long, requires = "output_file")]
+    pub append: bool,
+
+    /// Encrypt the output to an age recipient (repeatable, or comma-separated)
+    #[cfg(feature = "age")]
+    #[arg(long, value_name = "RECIPIENT", value_delimiter = ',')]
+    pub encrypt_to: Vec<String>,
+
+    /// Encrypt the output with gpg to this key ID or user ID (repeatable, or comma-separated)
+    #[cfg_attr(feature = "age", arg(conflicts_with = "encrypt_to"))]
+    #[arg(long, value_name = "KEYID", value_delimiter = ',')]
+    pub gpg_recipient: Vec<String>,
+
+    /// Show passwords in groups of N characters, as in aX3f-9Qp2 (exported values stay ungrouped)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub group: Option<u64>,
+
+    /// Text shown between groups with --group
+    #[arg(long, default_value = "-", requires = "group")]
+    pub group_separator: String,
+
+    /// Show passwords masked as aB******3!, revealing the first and last 2 characters or
+    /// the given number (N for both ends, or FIRST,LAST)
+    #[arg(
+        long,
+        value_name = "FIRST,LAST",
+        num_args = 0..=1,
+        default_missing_value = "2",
+        value_parser = parse_mask
+    )]
+    pub mask: Option<(usize, usize)>,
+
+    /// Show plain-format passwords fully masked with their entropy, until --reveal or a keypress
+    #[arg(long, env = "PENGUIN_MASKED")]
+    pub masked: bool,
+
+    /// Show the passwords in full even when masked output is the default
+    #[arg(long)]
+    pub reveal: bool,
+
+    /// Print the bare passwords, or label and password pairs, each ended by a NUL byte for `xargs -0`
+    #[arg(short = '0', long)]
+    pub null: bool,
+
+    /// Print nothing but the passwords, one per line, without the header, numbers, labels, or hints
+    #[arg(long, conflicts_with = "null")]
+    pub bare: bool,
+
+    /// Print plain-format passwords even when stdout isn't a terminal, such as into a file or pipe
+    #[arg(long, env = "PENGUIN_FORCE_PLAIN")]
+    pub force_plain: bool,
+
+    /// Don't color the plain format (also off when NO_COLOR is set or stdout isn't a terminal)
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+impl OutputArgs {
+    /// Whether the plain format hides the passwords entirely, as set with `--masked` or
+    /// `masked = true` and not overridden by `--reveal` or `--mask`.
+    pub fn hides_plaintext(&self) -> bool {
+        self.masked
+            && !self.reveal
+            && !self.null
+            && !self.bare
+            && self.mask.is_none()
+            && self.format == Format::Plain
+    }
+}
+
+/// A single generated password together with the metadata exported alongside it.
+pub struct Entry {
+    /// One-based position of the password within its batch.
+    pub index: usize,
+    /// Name given with `--label`, identifying what the password is for.
+    pub label: Option<String>,
+    pub username: String,
+    /// Shamir shares of the password when it was split with `--split`.
+    pub shares: Vec<String>,
+    pub password: String,
+    /// Memory aid spelling out the password, shown with `--mnemonic`.
+    pub hint: Option<String>,
+    /// Hint describing the words of a whole-word password without naming them, from
+    /// `--hint`. Unlike the memory aid it is safe to store, and it is also in the notes.
+    pub word_hint: Option<String>,
+    pub notes: String,
+    /// Expiry date given with `--expires`, as `YYYY-MM-DD`.
+    pub expires: Option<String>,
+    /// How the password was built, as named by [`crate::audit::strategy`].
+    pub strategy: &'static str,
+    /// Name of the policy file or style the password was generated under.
+    pub policy: Option<String>,
+    /// Complexity level the password was generated at.
+    pub complexity: Option<ComplexityLevel>,
+    /// Entropy the password was drawn with, when it is known exactly as it is for
+    /// passphrases. Other passwords are estimated from their characters.
+    pub entropy_bits: Option<f64>,
+}
+
+impl Entry {
+    /// The name shown for this entry in exports, falling back to its position in
+    /// the batch when no label was given.
+    pub fn title(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => format!("Password {}", self.index),
+        }
+    }
+
+    /// The entropy reported for this entry: the exact one when known, or else the
+    /// estimate from its characters.
+    pub fn entropy(&self) -> f64 {
+        self.entropy_bits
+            .unwrap_or_else(|| strength::estimate_entropy(&self.password).entropy_bits)
+    }
+}
+
+/// The notes text attached to every exported entry, recording how it was generated.
+pub fn generated_notes(complexity: ComplexityLevel) -> String {
+    format!(
+        "Generated by penguin ({} complexity)",
+        format!("{:?}", complexity).to_lowercase()
+    )
+}
+
+/// Renders the entries in the requested format, ready to be written to stdout.
+pub fn render(args: &OutputArgs, entries: &[Entry]) -> String {
+    if args.mask.is_some() && !matches!(args.format, Format::Plain | Format::ScriptFilter) {
+        crate::fail("--mask only applies to the plain and script-filter formats");
+    }
+    if args.null && args.format != Format::Plain {
+        crate::fail("--null only applies to the plain format");
+    }
+    if args.bare && args.format != Format::Plain {
+        crate::fail("--bare only applies to the plain format");
+    }
+    match args.format {
+        Format::Plain if args.null => render_null(entries, args),
+        Format::Plain if args.bare => render_bare(entries, args),
+        Format::Plain => render_plain(entries, args, args.hides_plaintext()),
+        Format::KeepassCsv => render_keepass_csv(entries),
+        Format::BitwardenJson => render_bitwarden_json(entries),
+        Format::BitwardenCsv => render_bitwarden_csv(entries),
+        Format::OnePasswordCsv => render_1password_csv(entries),
+        Format::Env => render_env(entries, args.var_prefix.as_deref()),
+        Format::K8sSecret => render_k8s_secret(entries, args),
+        Format::SystemdCred => render_systemd_cred(entries),
+        Format::DockerSecret => write_docker_secrets(entries, args),
+        Format::AnsibleVault => render_ansible_vault(entries, args),
+        Format::Tfvars => render_tfvars(entries, args),
+        Format::Sops => render_sops(entries, args),
+        Format::Chpasswd => render_chpasswd(entries),
+        Format::Ldif => render_ldif(entries, args),
+        Format::Sql => render_sql(entries, args),
+        Format::Netrc => render_netrc(entries, args),
+        Format::ScriptFilter => render_script_filter(entries, args),
+        Format::Jsonl => render_jsonl(entries),
+        Format::Json => render_json(entries),
+        Format::Csv => render_csv(entries),
+    }
+}
+
+/// Whether the plain format should be colored: only when it is written unencrypted to a
+/// terminal and colors weren't turned off.
+fn colored(args: &OutputArgs) -> bool {
+    args.output_file.is_none() && !encrypted(args) && crate::color::enabled(args.no_color)
+}
+
+/// Whether the output is encrypted before it is written.
+fn encrypted(args: &OutputArgs) -> bool {
+    #[cfg(feature = "age")]
+    if !args.encrypt_to.is_empty() {
+        return true;
+    }
+    !args.gpg_recipient.is_empty() || args.systemd_encrypt
+}
+
+/// Refuses to print plaintext passwords to anything but a terminal unless forced, so a
+/// redirect or a pipe into a logging tool doesn't keep them by accident. Files named
+/// with `--output` and the export formats are deliberate, and never get here.
+fn guard_plaintext(force: bool, message: &'static str) {
+    use std::io::IsTerminal;
+    if !force && !std::io::stdout().is_terminal() {
+        crate::fail(crate::i18n::tr(message));
+    }
+}
+
+/// The `--force-plain` switch of subcommands that print their secrets straight to
+/// stdout rather than through [`OutputArgs`].
+#[derive(Args)]
+pub struct PlainArgs {
+    /// Print the secrets even when stdout isn't a terminal, such as into a file or pipe
+    #[arg(long, env = "PENGUIN_FORCE_PLAIN")]
+    pub force_plain: bool,
+}
+
+impl PlainArgs {
+    /// Fails unless stdout is a terminal or `--force-plain` was given, before anything
+    /// secret is generated or read.
+    pub fn guard(&self) {
+        guard_plaintext(
+            self.force_plain,
+            "refusing to print plaintext secrets to a file or pipe; pass --force-plain",
+        );
+    }
+}
+
+/// Fails when the output would put plaintext passwords on a stdout that isn't a
+/// terminal without `--force-plain`.
+pub fn guard(args: &OutputArgs) {
+    if args.output_file.is_none()
+        && args.format == Format::Plain
+        && args.mask.is_none()
+        && !args.null
+        && !args.bare
+        && !args.hides_plaintext()
+        && !encrypted(args)
+    {
+        guard_plaintext(
+            args.force_plain,
+            "refusing to print plaintext passwords to a file or pipe; \
+             pass --force-plain, write them with --output, or pick a --format",
+        );
+    }
+}
+
+/// Writes rendered output to the requested destination, encrypting it first when
+/// recipients were given.
+pub fn emit(args: &OutputArgs, rendered: &str) {
+    let armor = args.output_file.is_none();
+    guard(args);
+    let mut data = rendered.as_bytes().to_vec();
+    if args.systemd_encrypt {
+        if args.format != Format::SystemdCred {
+            crate::fail("--systemd-encrypt can only be used with --format systemd-cred");
+        }
+        let name = args.name.as_deref().unwrap_or_default();
+        data = crate::encrypt::systemd_creds(name, &data, armor)
+            .unwrap_or_else(|err| crate::fail(err));
+    }
+    #[cfg(feature = "age")]
+    if !args.encrypt_to.is_empty() {
+        data = crate::encrypt::age(&args.encrypt_to, &data, armor)
+            .unwrap_or_else(|err| crate::fail(err));
+    }
+    if !args.gpg_recipient.is_empty() {
+        data = crate::encrypt::gpg(&args.gpg_recipient, &data, armor)
+            .unwrap_or_else(|err| crate::fail(err));
+    }
+
+    match &args.output_file {
+        Some(path) if args.append => append_private(path, &data),
+        Some(path) => write_private(path, &data),
+        None => {
+            let mut stdout = std::io::stdout();
+            stdout
+                .write_all(&data)
+                .and_then(|()| stdout.flush())
+                .unwrap_or_else(|err| crate::fail(format!("cannot write output: {}", err)));
+        }
+    }
+}
+
+/// Writes `label<TAB>half` lines for `--split-halves`: the first half of every password
+/// (the shorter one, for odd lengths) to `first` and the rest to `second`, so neither
+/// file alone holds a whole credential.
+pub fn write_halves(first: &Path, second: &Path, entries: &[Entry]) {
+    let (mut head, mut tail) = (String::new(), String::new());
+    for entry in entries {
+        let middle = entry
+            .password
+            .char_indices()
+            .nth(entry.password.chars().count() / 2)
+            .map_or(entry.password.len(), |(i, _)| i);
+        let (left, right) = entry.password.split_at(middle);
+        head.push_str(&format!("{}\t{}\n", entry.title(), left));
+        tail.push_str(&format!("{}\t{}\n", entry.title(), right));
+    }
+    write_private(first, head.as_bytes());
+    write_private(second, tail.as_bytes());
+}
+
+/// Creates or replaces a file readable only by its owner, restricting an existing one
+/// before anything is written to it.
+pub fn write_private(path: &Path, data: &[u8]) {
+    let write = || -> std::io::Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path)?;
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        file.write_all(data)
+    };
+    write().unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+}
+
+/// Appends to a file, first restricting an existing one to its owner and starting on a
+/// new line when it doesn't end with one.
+fn append_private(path: &Path, data: &[u8]) {
+    use std::io::{Read, Seek, SeekFrom};
+    let append = || -> std::io::Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.read(true).append(true).create(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path)?;
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        let mut last = [0u8];
+        let ends_open = file
+            .seek(SeekFrom::End(-1))
+            .and_then(|_| file.read_exact(&mut last))
+            .is_ok_and(|()| last[0] != b'\n');
+        if ends_open {
+            file.write_all(b"\n")?;
+        }
+        file.write_all(data)
+    };
+    append().unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+}
+
+/// Renders the numbered list, with every password replaced by an asterisk per character
+/// and its entropy when `hide` is set.
+fn render_plain(entries: &[Entry], args: &OutputArgs, hide: bool) -> String {
+    let color = colored(args);
+    let mut out = format!("\n> {}\n", crate::i18n::tr("Generated passwords:"));
+    for entry in entries {
+        // A split password is only ever shown as its shares.
+        let value = if !entry.shares.is_empty() {
+            crate::i18n::tr("shares:").to_string()
+        } else if hide {
+            let bits = format!("{:.1}", entry.entropy());
+            format!(
+                "{} ({})",
+                mask::mask(&entry.password, 0, 0),
+                crate::i18n::trf("about {0} bits", &[&bits])
+            )
+        } else if color {
+            let groups: Vec<String> = chunks(&masked(&entry.password, args), args.group)
+                .into_iter()
+                .map(crate::color::highlight)
+                .collect();
+            format!(
+                "{} {}",
+                groups.join(&args.group_separator),
+                crate::color::badge(&entry.password)
+            )
+        } else {
+            grouped(&entry.password, args)
+        };
+        match &entry.label {
+            Some(label) => out.push_str(&format!("   {}. {}: {}\n", entry.index, label, value)),
+            None => out.push_str(&format!("   {}. {}\n", entry.index, value)),
+        }
+        for share in &entry.shares {
+            out.push_str(&format!("      {}\n", share));
+        }
+        if let Some(hint) = entry.hint.as_ref().or(entry.word_hint.as_ref()) {
+            out.push_str(&format!(
+                "      {}\n",
+                crate::i18n::trf("hint: {0}", &[hint])
+            ));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// After masked output on a terminal, offers to print the passwords in full when `r` is
+/// pressed. Any other key, or a stdin that isn't a terminal, leaves them hidden.
+pub fn offer_reveal(args: &OutputArgs, entries: &[Entry]) {
+    use std::io::IsTerminal;
+    if !args.hides_plaintext()
+        || args.output_file.is_some()
+        || !std::io::stdin().is_terminal()
+        || !std::io::stdout().is_terminal()
+    {
+        return;
+    }
+    let term = console::Term::stderr();
+    let _ = term.write_str(crate::i18n::tr(
+        "Press r to reveal the passwords, any other key to finish",
+    ));
+    let key = term.read_key();
+    let _ = term.clear_line();
+    if matches!(key, Ok(console::Key::Char('r' | 'R'))) {
+        print!("{}", render_plain(entries, args, false));
+    }
+}
+
+/// Splits a password into runs of `size` characters, or returns it whole without a size.
+fn chunks(password: &str, size: Option<u64>) -> Vec<&str> {
+    let Some(size) = size.map(|size| size as usize) else {
+        return vec![password];
+    };
+    let mut chunks = Vec::new();
+    let mut rest = password;
+    while !rest.is_empty() {
+        let end = rest.char_indices().nth(size).map_or(rest.len(), |(i, _)| i);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+/// The password as it is displayed with `--mask` and `--group`.
+fn grouped(password: &str, args: &OutputArgs) -> String {
+    chunks(&masked(password, args), args.group).join(&args.group_separator)
+}
+
+/// The password as it is displayed with `--mask`.
+fn masked(password: &str, args: &OutputArgs) -> String {
+    match args.mask {
+        Some((first, last)) => mask::mask(password, first, last),
+        None => password.to_string(),
+    }
+}
+
+fn parse_mask(value: &str) -> Result<(usize, usize), String> {
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("invalid character count '{}'", n))
+    };
+    match value.split_once(',') {
+        Some((first, last)) => Ok((parse(first)?, parse(last)?)),
+        None => parse(value).map(|n| (n, n)),
+    }
+}
+
+/// One `label<TAB>password` line per entry, for `--per-line`.
+pub fn render_tabbed(entries: &[Entry], args: &OutputArgs) -> String {
+    entries
+        .iter()
+        .map(|entry| format!("{}\t{}\n", entry.title(), masked(&entry.password, args)))
+        .collect()
+}
+
+/// Every value ends with a NUL byte, which no password holds, so any character in them
+/// survives `xargs -0`. Labeled batches give a label before each password, to be read in
+/// pairs with `xargs -0 -n 2`, and a split password gives its shares instead.
+fn render_null(entries: &[Entry], args: &OutputArgs) -> String {
+    let labeled = entries.iter().any(|entry| entry.label.is_some());
+    let mut out = String::new();
+    for entry in entries {
+        if labeled {
+            out.push_str(&format!("{}\0", entry.title()));
+        }
+        if entry.shares.is_empty() {
+            out.push_str(&format!("{}\0", masked(&entry.password, args)));
+        }
+        for share in &entry.shares {
+            out.push_str(&format!("{}\0", share));
+        }
+    }
+    out
+}
+
+/// One password per line and nothing else, for `while read` loops and `head -n 1`. A
+/// split password gives its shares instead.
+fn render_bare(entries: &[Entry], args: &OutputArgs) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        if entry.shares.is_empty() {
+            out.push_str(&format!("{}\n", masked(&entry.password, args)));
+        }
+        for share in &entry.shares {
+            out.push_str(&format!("{}\n", share));
+        }
+    }
+    out
+}
+
+/// KeePassXC maps these header names automatically in its CSV import dialog, and
+/// KeePass 2's generic CSV importer lets them be assigned in a single step.
+fn render_keepass_csv(entries: &[Entry]) -> String {
+    let mut out = csv_row(&["Title", "Username", "Password", "Notes"]);
+    for entry in entries {
+        out.push_str(&csv_row(&[
+            &entry.title(),
+            &entry.username,
+            &entry.password,
+            &entry.notes,
+        ]));
+    }
+    out
+}
+
+/// Bitwarden treats `type: 1` as a login item; folders are left out so the
+/// importer places items at the vault root.
+fn render_bitwarden_json(entries: &[Entry]) -> String {
+    let items: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "type": 1,
+                "name": entry.title(),
+                "notes": entry.notes,
+                "favorite": false,
+                "reprompt": 0,
+                "login": {
+                    "username": entry.username,
+                    "password": entry.password,
+                    "totp": null,
+                    "uris": [],
+                },
+            })
+        })
+        .collect();
+
+    let export = json!({
+        "encrypted": false,
+        "folders": [],
+        "items": items,
+    });
+    format!("{:#}\n", export)
+}
+
+/// Column order follows the template Bitwarden documents for individual vaults;
+/// columns penguin has no data for are left empty rather than omitted.
+fn render_bitwarden_csv(entries: &[Entry]) -> String {
+    let mut out = csv_row(&[
+        "folder",
+        "favorite",
+        "type",
+        "name",
+        "notes",
+        "fields",
+        "reprompt",
+        "login_uri",
+        "login_username",
+        "login_password",
+        "login_totp",
+    ]);
+    for entry in entries {
+        out.push_str(&csv_row(&[
+            "",
+            "",
+            "login",
+            &entry.title(),
+            &entry.notes,
+            "",
+            "",
+            "",
+            &entry.username,
+            &entry.password,
+            "",
+        ]));
+    }
+    out
+}
+
+/// 1Password's CSV importer recognises these headers and maps them to the Login
+/// fields of the same name, so no manual column assignment is needed.
+fn render_1password_csv(entries: &[Entry]) -> String {
+    let mut out = csv_row(&["Title", "Website", "Username", "Password", "Notes"]);
+    for entry in entries {
+        out.push_str(&csv_row(&[
+            &entry.title(),
+            "",
+            &entry.username,
+            &entry.password,
+            &entry.notes,
+        ]));
+    }
+    out
+}
+
+fn render_env(entries: &[Entry], prefix: Option<&str>) -> String {
+    let names: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            let name = match (&entry.label, prefix) {
+                (Some(label), prefix) => format!("{}{}", prefix.unwrap_or(""), label),
+                (None, Some(prefix)) => format!("{}{}", prefix, entry.index),
+                (None, None) => format!("PASSWORD_{}", entry.index),
+            };
+            env_var_name(&name)
+        })
+        .collect();
+    check_unique(entries, &names, "variable");
+    let mut out = String::new();
+    for (entry, name) in entries.iter().zip(&names) {
+        out.push_str(&format!("{}={}\n", name, shell_quote(&entry.password)));
+    }
+    out
+}
+
+/// Fails when two entries would be written under the same name, which would lose one
+/// of the passwords or make the file invalid. `what` says what the names are.
+fn check_unique(entries: &[Entry], names: &[String], what: &str) {
+    for (i, name) in names.iter().enumerate() {
+        if let Some(first) = names[..i].iter().position(|earlier| earlier == name) {
+            crate::fail(format!(
+                "'{}' and '{}' would both be written as the {} {}",
+                entries[first].title(),
+                entries[i].title(),
+                what,
+                name
+            ));
+        }
+    }
+}
+
+/// Turns a label such as `github token` into a conventional variable name like
+/// `GITHUB_TOKEN`; names must not start with a digit, so those get a leading `_`.
+fn env_var_name(name: &str) -> String {
+    let mut var: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if var.is_empty() || var.starts_with(|c: char| c.is_ascii_digit()) {
+        var.insert(0, '_');
+    }
+    var
+}
+
+/// Quotes a value so that both POSIX shells and dotenv parsers read it back verbatim.
+/// Single quotes disable every expansion, so they are used unless the value itself
+/// contains one, in which case double quotes with the active characters escaped are used.
+fn shell_quote(value: &str) -> String {
+    if !value.contains('\'') {
+        return format!("'{}'", value);
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn render_k8s_secret(entries: &[Entry], args: &OutputArgs) -> String {
+    let name = args.name.as_deref().unwrap_or_default();
+    if !is_dns_subdomain(name) {
+        crate::fail(format!(
+            "'{}' is not a valid Secret name; use lowercase letters, digits, '-' and '.'",
+            name
+        ));
+    }
+    let mut out = String::from("apiVersion: v1\nkind: Secret\nmetadata:\n");
+    out.push_str(&format!("  name: {}\n", name));
+    if let Some(expires) = entries.iter().find_map(|entry| entry.expires.as_ref()) {
+        out.push_str(&format!(
+            "  annotations:\n    penguin/expires: \"{}\"\n",
+            expires
+        ));
+    }
+    out.push_str("type: Opaque\n");
+    out.push_str(if args.string_data {
+        "stringData:\n"
+    } else {
+        "data:\n"
+    });
+
+    let mut keys: Vec<String> = Vec::new();
+    for entry in entries {
+        let key = match &entry.label {
+            Some(label) => secret_key(label),
+            None if entries.len() == 1 => args.key.clone(),
+            None => format!("{}-{}", args.key, entry.index),
+        };
+        if !is_secret_data_key(&key) {
+            crate::fail(format!(
+                "'{}' is not a valid Secret key; use letters, digits, '-', '_' and '.'",
+                key
+            ));
+        }
+        if keys.contains(&key) {
+            crate::fail(format!(
+                "two passwords would be stored under the Secret key {}",
+                key
+            ));
+        }
+        keys.push(key.clone());
+        let value = if args.string_data {
+            format!("'{}'", entry.password.replace('\'', "''"))
+        } else {
+            BASE64.encode(&entry.password)
+        };
+        out.push_str(&format!("  {}: {}\n", key, value));
+    }
+    out
+}
+
+/// Every record carries the same timestamp, so a batch can be told apart from the next
+/// one by it.
+fn render_jsonl(entries: &[Entry]) -> String {
+    let timestamp = crate::date::timestamp();
+    entries
+        .iter()
+        .map(|entry| {
+            let mut record = json!({
+                "password": entry.password,
+                "label": entry.label,
+                "entropy_bits": entropy_bits(entry),
+                "policy": entry.policy,
+                "strategy": entry.strategy,
+                "timestamp": timestamp,
+                "generator": format!("penguin {}", env!("CARGO_PKG_VERSION")),
+            });
+            if !entry.username.is_empty() {
+                record["username"] = json!(entry.username);
+            }
+            if let Some(expires) = &entry.expires {
+                record["expires"] = json!(expires);
+            }
+            if let Some(hint) = &entry.word_hint {
+                record["hint"] = json!(hint);
+            }
+            format!("{}\n", record)
+        })
+        .collect()
+}
+
+/// The complexity level as it is spelled on the command line, or empty when unknown.
+fn complexity_name(entry: &Entry) -> String {
+    entry
+        .complexity
+        .map(|complexity| format!("{:?}", complexity).to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Entropy rounded to a tenth of a bit, as the jsonl format gives it.
+fn entropy_bits(entry: &Entry) -> f64 {
+    (entry.entropy() * 10.0).round() / 10.0
+}
+
+fn render_json(entries: &[Entry]) -> String {
+    let records: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "password": entry.password,
+                "label": entry.label,
+                "complexity": entry.complexity.map(|_| complexity_name(entry)),
+                "length": entry.password.chars().count(),
+                "entropy_bits": entropy_bits(entry),
+            })
+        })
+        .collect();
+    let mut out = serde_json::to_string_pretty(&records).expect("JSON values always serialize");
+    out.push('\n');
+    out
+}
+
+fn render_csv(entries: &[Entry]) -> String {
+    let mut out = csv_row(&["password", "label", "complexity", "length", "entropy_bits"]);
+    for entry in entries {
+        out.push_str(&csv_row(&[
+            &entry.password,
+            entry.label.as_deref().unwrap_or_default(),
+            &complexity_name(entry),
+            &entry.password.chars().count().to_string(),
+            &entropy_bits(entry).to_string(),
+        ]));
+    }
+    out
+}
+
+/// A credential file holds the secret bytes verbatim, so there is no trailing newline.
+fn render_systemd_cred(entries: &[Entry]) -> String {
+    match entries {
+        [entry] => entry.password.clone(),
+        _ => crate::fail("a systemd credential holds a single password; drop --number and --label"),
+    }
+}
+
+/// Writes each password to a file named after its label, without a trailing newline
+/// since the file is the secret, and returns the Compose snippet when one was asked for.
+fn write_docker_secrets(entries: &[Entry], args: &OutputArgs) -> String {
+    let dir = args.secrets_dir.as_deref().unwrap_or(Path::new("."));
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder
+        .create(dir)
+        .unwrap_or_else(|err| crate::fail(format!("cannot create {}: {}", dir.display(), err)));
+
+    let mut names: Vec<String> = Vec::new();
+    for entry in entries {
+        let name = match &entry.label {
+            Some(label) => secret_key(label),
+            None if entries.len() == 1 => args.key.clone(),
+            None => format!("{}-{}", args.key, entry.index),
+        };
+        if names.contains(&name) {
+            crate::fail(format!(
+                "two passwords would be written to the secret file {}",
+                name
+            ));
+        }
+        write_private(&dir.join(&name), entry.password.as_bytes());
+        names.push(name);
+    }
+
+    if !args.compose {
+        return String::new();
+    }
+    let mut out = String::from("secrets:\n");
+    for name in &names {
+        out.push_str(&format!(
+            "  {}:\n    file: {}\n",
+            name,
+            dir.join(name).display()
+        ));
+    }
+    out
+}
+
+/// Variables are named after the labels like the env format names them, but in lowercase
+/// as Ansible variables usually are.
+fn render_ansible_vault(entries: &[Entry], args: &OutputArgs) -> String {
+    let path = args.vault_password_file.as_deref().unwrap_or(Path::new(""));
+    let password = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err)));
+    // Ansible strips the surrounding whitespace of a password file too.
+    let password = password.trim();
+    if password.is_empty() {
+        crate::fail(format!("{} holds no vault password", path.display()));
+    }
+
+    let names = variable_names(entries, args);
+    check_unique(entries, &names, "variable");
+    let mut yaml = String::from("---\n");
+    for (entry, name) in entries.iter().zip(&names) {
+        yaml.push_str(&format!(
+            "{}: '{}'\n",
+            name,
+            entry.password.replace('\'', "''")
+        ));
+    }
+    crate::encrypt::ansible_vault(password.as_bytes(), yaml.as_bytes())
+}
+
+fn render_tfvars(entries: &[Entry], args: &OutputArgs) -> String {
+    let mut out = String::new();
+    if args.sensitive {
+        out.push_str(
+            "# Sensitive values generated by penguin: declare these variables with\n\
+             # `sensitive = true` and keep this file out of version control.\n",
+        );
+    }
+    let names = variable_names(entries, args);
+    check_unique(entries, &names, "variable");
+    for (entry, name) in entries.iter().zip(&names) {
+        out.push_str(&format!("{} = {}\n", name, hcl_quote(&entry.password)));
+    }
+    out
+}
+
+/// Lowercase variable names for the tfvars and ansible-vault formats, taken from the
+/// labels like the env format's, or from `--key` and the index.
+fn variable_names(entries: &[Entry], args: &OutputArgs) -> Vec<String> {
+    entries
+        .iter()
+        .map(|entry| {
+            let name = match &entry.label {
+                Some(label) => label.clone(),
+                None if entries.len() == 1 => args.key.clone(),
+                None => format!("{}_{}", args.key, entry.index),
+            };
+            env_var_name(&name).to_lowercase()
+        })
+        .collect()
+}
+
+/// Renders `username:password` lines, taking each user name from the entry or its label
+/// and generating distinct ones for the rest.
+fn render_chpasswd(entries: &[Entry]) -> String {
+    let mut taken: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| login_name(entry, "chpasswd"))
+        .collect();
+    let mut out = String::new();
+    for entry in entries {
+        let user = login_name(entry, "chpasswd").unwrap_or_else(|| loop {
+            let candidate = penguin::username::generate(2);
+            if !taken.contains(&candidate) {
+                taken.push(candidate.clone());
+                break candidate;
+            }
+        });
+        if entry.password.contains('\n') {
+            crate::fail("chpasswd passwords can't contain line breaks");
+        }
+        out.push_str(&format!("{}:{}\n", user, entry.password));
+    }
+    out
+}
+
+/// Returns the user name an entry gives, from its username or label, failing when it
+/// isn't a valid one for the format: a lowercase letter or underscore followed by
+/// lowercase letters, digits, `_`, or `-`.
+fn login_name(entry: &Entry, format: &str) -> Option<String> {
+    let user = Some(entry.username.clone())
+        .filter(|user| !user.is_empty())
+        .or_else(|| entry.label.clone())?;
+    let valid = user.len() <= 32
+        && user.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && user
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if !valid {
+        crate::fail(format!(
+            "'{}' is not a valid user name for {}",
+            user, format
+        ));
+    }
+    Some(user)
+}
+
+/// Renders a `machine` stanza per entry for the `--machine` host or the entry's label.
+fn render_netrc(entries: &[Entry], args: &OutputArgs) -> String {
+    if args.machine.is_some() && entries.len() > 1 {
+        crate::fail("--machine names a single host; label the passwords to write several");
+    }
+    let mut out = String::new();
+    for entry in entries {
+        let machine = args
+            .machine
+            .as_deref()
+            .or(entry.label.as_deref())
+            .unwrap_or_else(|| {
+                crate::fail("netrc output needs --machine or a --label naming the host")
+            });
+        let login = args
+            .login
+            .as_deref()
+            .or(Some(entry.username.as_str()).filter(|user| !user.is_empty()));
+        out.push_str(&format!("machine {}\n", netrc_quote(machine)));
+        if let Some(login) = login {
+            out.push_str(&format!("  login {}\n", netrc_quote(login)));
+        }
+        out.push_str(&format!("  password {}\n", netrc_quote(&entry.password)));
+    }
+    out
+}
+
+/// Writes a netrc token, in double quotes with backslash escapes when it holds
+/// whitespace, quotes, or backslashes. curl and Python's netrc read quoted tokens; older
+/// ftp clients don't, so those characters are best left out of passwords for them.
+fn netrc_quote(value: &str) -> String {
+    if !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\\')
+    {
+        return value.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Renders one LDIF change record per entry, replacing the `userPassword` of
+/// `uid=<name>,<base DN>` or adding it as a new `inetOrgPerson` with `--ldif-add`.
+fn render_ldif(entries: &[Entry], args: &OutputArgs) -> String {
+    let base_dn = args.base_dn.as_deref().unwrap_or_default();
+    let mut out = String::from("version: 1\n");
+    for entry in entries {
+        let uid = login_name(entry, "ldif")
+            .unwrap_or_else(|| crate::fail("ldif entries need a --label naming their uid"));
+        let hash = crate::ldap::hash(args.ldap_hash, &entry.password);
+        out.push_str(&format!("\ndn: uid={},{}\n", uid, base_dn));
+        if args.ldif_add {
+            out.push_str(&format!(
+                "changetype: add\nobjectClass: inetOrgPerson\nuid: {0}\ncn: {0}\nsn: {0}\nuserPassword: {1}\n",
+                uid, hash
+            ));
+        } else {
+            out.push_str(&format!(
+                "changetype: modify\nreplace: userPassword\nuserPassword: {}\n-\n",
+                hash
+            ));
+        }
+    }
+    out
+}
+
+fn render_sops(entries: &[Entry], args: &OutputArgs) -> String {
+    let keys: Vec<String> = entries
+        .iter()
+        .map(|entry| match &entry.label {
+            Some(label) => secret_key(label),
+            None if entries.len() == 1 => args.key.clone(),
+            None => format!("{}-{}", args.key, entry.index),
+        })
+        .collect();
+    check_unique(entries, &keys, "SOPS key");
+    let secrets: Vec<(String, String)> = keys
+        .into_iter()
+        .zip(entries)
+        .map(|(key, entry)| (key, entry.password.clone()))
+        .collect();
+    let json = args
+        .output_file
+        .as_ref()
+        .and_then(|path| path.extension())
+        .is_some_and(|extension| extension == "json");
+    let recipients = crate::sops::Recipients {
+        age: &args.age,
+        kms: &args.kms,
+    };
+    crate::sops::encrypt(&secrets, recipients, json).unwrap_or_else(|err| crate::fail(err))
+}
+
+/// Renders one statement per entry, with the label or username as the database user.
+fn render_sql(entries: &[Entry], args: &OutputArgs) -> String {
+    let verb = if args.alter { "ALTER" } else { "CREATE" };
+    let mut out = String::new();
+    for entry in entries {
+        let user = Some(entry.username.as_str())
+            .filter(|user| !user.is_empty())
+            .or(entry.label.as_deref())
+            .unwrap_or_else(|| crate::fail("sql statements need a --label naming their user"));
+        let password = sql_quote(&entry.password, args.dialect);
+        out.push_str(&match args.dialect {
+            Dialect::Postgres => format!(
+                "{} USER \"{}\" WITH PASSWORD {};\n",
+                verb,
+                user.replace('"', "\"\""),
+                password
+            ),
+            Dialect::Mysql => format!(
+                "{} USER {}@{} IDENTIFIED BY {};\n",
+                verb,
+                sql_quote(user, args.dialect),
+                sql_quote(&args.sql_host, args.dialect),
+                password
+            ),
+        });
+    }
+    out
+}
+
+/// Quotes a value as a SQL string literal. Quotes are doubled, and so are backslashes
+/// for MySQL, which reads them as escapes in its default SQL mode.
+fn sql_quote(value: &str, dialect: Dialect) -> String {
+    let mut quoted = value.replace('\'', "''");
+    if dialect == Dialect::Mysql {
+        quoted = quoted.replace('\\', "\\\\");
+    }
+    format!("'{}'", quoted)
+}
+
+/// Quotes a value as an HCL string literal. Besides the usual backslash escapes, `${`
+/// and `%{` would start a template interpolation or directive, so their marker is
+/// doubled.
+fn hcl_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                quoted.push(c);
+                quoted.push(c);
+            }
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Launchers show the title and subtitle and pass `arg` on when an item is chosen, so a
+/// wrapper only has to copy or type it; `text` feeds the launcher's own copy action.
+fn render_script_filter(entries: &[Entry], args: &OutputArgs) -> String {
+    let items: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            let entropy = entry.entropy();
+            let shown = grouped(&entry.password, args);
+            json!({
+                "title": shown,
+                "subtitle": format!("{} · {:.0} bits of entropy", entry.title(), entropy),
+                "arg": entry.password,
+                "text": { "copy": entry.password, "largetype": shown },
+            })
+        })
+        .collect();
+    format!("{:#}\n", json!({ "items": items }))
+}
+
+/// Secret data keys may only contain alphanumerics, `-`, `_`, and `.`.
+fn secret_key(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Whether a name is a DNS subdomain, which Kubernetes requires of object names: at most
+/// 253 characters of dot-separated labels, each of lowercase letters, digits, and
+/// hyphens, starting and ending with a letter or digit.
+fn is_dns_subdomain(name: &str) -> bool {
+    name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+        })
+}
+
+/// Whether a key is allowed in a Secret's data: at most 253 of `[-._a-zA-Z0-9]`, and
+/// neither `.` nor starting with `..`.
+fn is_secret_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 253
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && key != "."
+        && !key.starts_with("..")
+}
+
+/// Renders text as a QR code made of Unicode half blocks, readable from a terminal.
+#[cfg(feature = "qr")]
+pub fn qr_code(text: &str) -> String {
+    use qrcode::render::unicode::Dense1x2;
+
+    match qrcode::QrCode::new(text) {
+        Ok(code) => code
+            .render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(true)
+            .build(),
+        Err(err) => crate::fail(format!("cannot encode QR code: {}", err)),
+    }
+}
+
+/// Builds a single CSV line with every field quoted, since generated passwords
+/// routinely contain commas and quotes.
+fn csv_row(fields: &[&str]) -> String {
+    let quoted: Vec<String> = fields
+        .iter()
+        .map(|field| format!("\"{}\"", field.replace('"', "\"\"")))
+        .collect();
+    format!("{}\n", quoted.join(","))
+}
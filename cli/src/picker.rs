@@ -0,0 +1,104 @@
+//! The fuzzy selector behind `penguin generate --pick`.
+//!
+//! The candidates are drawn on the terminal's alternate screen, which is thrown away when
+//! the selector closes, so the ones not chosen never reach the scrollback. Typing narrows
+//! the list to candidates containing the typed characters in order, ignoring case; the
+//! arrow keys move the selection, Enter picks it, and Escape or Ctrl-C gives up.
+
+use console::{Key, Term};
+
+/// How many candidates are generated when neither `--number` nor labels say otherwise.
+pub const CANDIDATES: usize = 10;
+
+/// Switches to the alternate screen, and back.
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+
+/// Lets the user choose one of the candidates and returns its index, or `None` when
+/// they give up.
+pub fn pick(candidates: &[String]) -> Option<usize> {
+    let term = Term::stderr();
+    if !term.is_term() {
+        crate::fail(crate::i18n::tr("--pick needs a terminal"));
+    }
+    let _ = term.write_str(ENTER_ALTERNATE_SCREEN);
+    let picked = run(&term, candidates);
+    let _ = term.show_cursor();
+    let _ = term.write_str(LEAVE_ALTERNATE_SCREEN);
+    let _ = term.flush();
+    picked.unwrap_or_else(|err| crate::fail(format!("cannot read the terminal: {}", err)))
+}
+
+fn run(term: &Term, candidates: &[String]) -> std::io::Result<Option<usize>> {
+    let mut query = String::new();
+    let mut selected = 0;
+    term.hide_cursor()?;
+    loop {
+        let matches: Vec<usize> = (0..candidates.len())
+            .filter(|&i| fuzzy_match(&query, &candidates[i]))
+            .collect();
+        selected = selected.min(matches.len().saturating_sub(1));
+        draw(term, candidates, &matches, selected, &query)?;
+
+        match term.read_key_raw()? {
+            Key::Enter => {
+                if let Some(&index) = matches.get(selected) {
+                    return Ok(Some(index));
+                }
+            }
+            Key::Escape | Key::CtrlC => return Ok(None),
+            Key::ArrowUp | Key::BackTab => selected = selected.saturating_sub(1),
+            Key::ArrowDown | Key::Tab => selected += 1,
+            Key::Backspace => {
+                query.pop();
+            }
+            Key::Char(c) if !c.is_control() => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Redraws the prompt and as many matching candidates as fit, keeping the selection in
+/// view.
+fn draw(
+    term: &Term,
+    candidates: &[String],
+    matches: &[usize],
+    selected: usize,
+    query: &str,
+) -> std::io::Result<()> {
+    let (rows, _) = term.size();
+    let visible = (rows as usize).saturating_sub(2).max(1);
+    let first = (selected + 1).saturating_sub(visible);
+    term.clear_screen()?;
+    term.write_line(&format!(
+        "{} {}/{}  > {}",
+        crate::i18n::tr("Pick a password:"),
+        matches.len(),
+        candidates.len(),
+        query
+    ))?;
+    for (row, &index) in matches.iter().enumerate().skip(first).take(visible) {
+        let marker = if row == selected { '>' } else { ' ' };
+        term.write_line(&format!(
+            "{} {:>3}. {}",
+            marker,
+            index + 1,
+            candidates[index]
+        ))?;
+    }
+    term.flush()
+}
+
+/// Returns whether the characters of the query appear in the candidate in order,
+/// ignoring case.
+fn fuzzy_match(query: &str, candidate: &str) -> bool {
+    let mut rest = candidate.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|wanted| rest.any(|c| c == wanted))
+}
@@ -0,0 +1,106 @@
+//! Batch generation for `penguin generate`, with a progress bar drawn on stderr.
+//!
+//! Big counts, or constraints that force many re-rolls, can keep the generator busy for
+//! a while with nothing on screen. Above [`THRESHOLD`] passwords the batch is spread
+//! over a rayon thread pool and generated behind a progress bar that also counts
+//! rejected candidates. The bar is skipped when stderr is not a terminal, so piped and
+//! scripted runs stay clean.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use penguin::mixer::PenguinMixer;
+use penguin::GenerationStats;
+use rayon::prelude::*;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Smallest batch that gets a progress bar and, unless `--jobs` says otherwise, runs on
+/// every core.
+pub const THRESHOLD: usize = 1000;
+
+/// How many passwords are generated between redraws.
+const STEP: usize = 100;
+
+/// Returns the number of worker threads to use for a batch: `jobs` when given, all
+/// available cores for large batches, and a single thread otherwise.
+pub fn jobs(jobs: Option<usize>, count: usize) -> usize {
+    match jobs {
+        Some(jobs) => jobs,
+        None if count >= THRESHOLD => std::thread::available_parallelism()
+            .map(|cores| cores.get())
+            .unwrap_or(1),
+        None => 1,
+    }
+}
+
+/// Generates `count` passwords from the base words on `jobs` threads, showing progress
+/// when the batch is large and `enabled` is set, and returns them with the stats of the
+/// whole batch, timed by the wall clock.
+pub fn generate(
+    words: &[&str],
+    mixer: &PenguinMixer,
+    count: usize,
+    jobs: usize,
+    enabled: bool,
+) -> (Vec<String>, GenerationStats) {
+    let bar = if enabled && count >= THRESHOLD && std::io::stderr().is_terminal() {
+        let bar = ProgressBar::new(count as u64);
+        bar.set_style(
+            ProgressStyle::with_template(
+                "{bar:40} {pos}/{len} passwords, {msg} [{elapsed}, eta {eta}]",
+            )
+            .expect("progress template is valid"),
+        );
+        bar.set_message("0 re-rolls");
+        bar
+    } else {
+        ProgressBar::hidden()
+    };
+
+    let started = Instant::now();
+    let done = AtomicUsize::new(0);
+    let rejected = AtomicUsize::new(0);
+    let generate_one = |_| {
+        let (password, stats) = mixer
+            .mix_password_stats(words)
+            .unwrap_or_else(|err| crate::fail(err.to_string()));
+        let rerolls = stats.rejected();
+        let rejected = rejected.fetch_add(rerolls, Ordering::Relaxed) + rerolls;
+        let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+        if done.is_multiple_of(STEP) {
+            bar.set_position(done as u64);
+            bar.set_message(format!("{} re-rolls", rejected));
+        }
+        (password, stats)
+    };
+
+    let generated: Vec<(String, GenerationStats)> = if jobs <= 1 {
+        (0..count).map(generate_one).collect()
+    } else {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .unwrap_or_else(|err| crate::fail(format!("cannot start {} threads: {}", jobs, err)))
+            .install(|| (0..count).into_par_iter().map(generate_one).collect())
+    };
+    bar.finish_and_clear();
+
+    let mut stats = GenerationStats::default();
+    let passwords = generated
+        .into_iter()
+        .map(|(password, generated)| {
+            stats.merge(&generated);
+            password
+        })
+        .collect();
+    stats.elapsed = started.elapsed();
+    tracing::info!(
+        count,
+        jobs,
+        rerolls = rejected.load(Ordering::Relaxed),
+        rng_bytes = stats.rng_bytes,
+        elapsed_ms = stats.elapsed.as_millis() as u64,
+        "generated a batch"
+    );
+    (passwords, stats)
+}
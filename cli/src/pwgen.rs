@@ -0,0 +1,171 @@
+//! pwgen-compatible command line.
+//!
+//! `penguin pwgen` accepts the flags and positional arguments of the classic `pwgen`
+//! tool, so scripts written against it keep working after switching. Like pwgen it prints
+//! a screenful of passwords in columns when writing to a terminal and a single password
+//! otherwise, though printing anywhere but a terminal needs `--force-plain`. Passwords
+//! are always drawn uniformly at random, which corresponds to pwgen's `-s` mode; the
+//! flag is accepted for compatibility.
+
+use clap::Args;
+use rand::Rng;
+use std::io::IsTerminal;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+/// The symbol set pwgen draws from with `-y`.
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+/// Characters pwgen considers easy to confuse, removed by `-B`.
+const AMBIGUOUS: &str = "B8G6I1l0OQDS5Z2";
+/// Characters removed by `-v`.
+const VOWELS: &str = "01aeiouyAEIOUY";
+
+/// Candidates are regenerated until they contain every required class.
+const MAX_ATTEMPTS: usize = 10_000;
+
+/// Terminal layout used when printing in columns.
+const SCREEN_WIDTH: usize = 80;
+const SCREEN_ROWS: usize = 20;
+
+#[derive(Args)]
+pub struct PwgenArgs {
+    /// Length of the generated passwords
+    pw_length: Option<usize>,
+
+    /// Number of passwords to generate
+    num_pw: Option<usize>,
+
+    /// Include at least one capital letter
+    #[arg(short = 'c', long = "capitalize")]
+    capitalize: bool,
+
+    /// Don't include capital letters
+    #[arg(short = 'A', long = "no-capitalize")]
+    no_capitalize: bool,
+
+    /// Include at least one number
+    #[arg(short = 'n', long = "numerals")]
+    numerals: bool,
+
+    /// Don't include numbers
+    #[arg(short = '0', long = "no-numerals")]
+    no_numerals: bool,
+
+    /// Include at least one special symbol
+    #[arg(short = 'y', long = "symbols")]
+    symbols: bool,
+
+    /// Remove characters from the set of characters to generate passwords
+    #[arg(short = 'r', long = "remove-chars", value_name = "CHARS")]
+    remove_chars: Option<String>,
+
+    /// Generate completely random passwords
+    #[arg(short = 's', long = "secure")]
+    secure: bool,
+
+    /// Don't include ambiguous characters in the password
+    #[arg(short = 'B', long = "ambiguous")]
+    ambiguous: bool,
+
+    /// Do not use any vowels so as to avoid accidental nasty words
+    #[arg(short = 'v', long = "no-vowels")]
+    no_vowels: bool,
+
+    /// Print the generated passwords in columns
+    #[arg(short = 'C')]
+    columns: bool,
+
+    /// Don't print the generated passwords in columns
+    #[arg(short = '1')]
+    one_per_line: bool,
+
+    /// Number of passwords to generate (alternative to the positional argument)
+    #[arg(short = 'N', long = "num-passwords", conflicts_with = "num_pw")]
+    num_passwords: Option<usize>,
+
+    #[command(flatten)]
+    pub plain: crate::output::PlainArgs,
+}
+
+/// Generates and prints passwords the way pwgen would.
+pub fn run(args: PwgenArgs) {
+    let length = args.pw_length.unwrap_or(8);
+    if length == 0 {
+        crate::fail("the password length must be at least 1");
+    }
+
+    let mut pool = String::from(LOWERCASE);
+    if !args.no_capitalize {
+        pool.push_str(UPPERCASE);
+    }
+    if !args.no_numerals {
+        pool.push_str(DIGITS);
+    }
+    if args.symbols {
+        pool.push_str(SYMBOLS);
+    }
+    let removed = |c: &char| {
+        (args.ambiguous && AMBIGUOUS.contains(*c))
+            || (args.no_vowels && VOWELS.contains(*c))
+            || args
+                .remove_chars
+                .as_deref()
+                .is_some_and(|chars| chars.contains(*c))
+    };
+    let pool: Vec<char> = pool.chars().filter(|c| !removed(c)).collect();
+    if pool.is_empty() {
+        crate::fail("no characters are left to generate passwords from");
+    }
+
+    // pwgen requires a capital and a digit by default, and a symbol when -y is given.
+    let mut required: Vec<&str> = Vec::new();
+    if (args.capitalize || !args.no_capitalize) && pool.iter().any(|c| UPPERCASE.contains(*c)) {
+        required.push(UPPERCASE);
+    }
+    if (args.numerals || !args.no_numerals) && pool.iter().any(|c| DIGITS.contains(*c)) {
+        required.push(DIGITS);
+    }
+    if args.symbols && pool.iter().any(|c| SYMBOLS.contains(*c)) {
+        required.push(SYMBOLS);
+    }
+    if required.len() > length {
+        required.clear();
+    }
+
+    let terminal = std::io::stdout().is_terminal();
+    let columns = !args.one_per_line && (args.columns || terminal);
+    let per_line = if columns {
+        (SCREEN_WIDTH / (length + 1)).max(1)
+    } else {
+        1
+    };
+    let count = args.num_pw.or(args.num_passwords).unwrap_or(if terminal {
+        per_line * SCREEN_ROWS
+    } else {
+        1
+    });
+
+    let passwords: Vec<String> = (0..count)
+        .map(|_| generate(&pool, &required, length))
+        .collect();
+    for line in passwords.chunks(per_line) {
+        println!("{}", line.join(" "));
+    }
+}
+
+fn generate(pool: &[char], required: &[&str], length: usize) -> String {
+    let mut rng = penguin::entropy::rng();
+    let mut password = String::with_capacity(length);
+    for _ in 0..MAX_ATTEMPTS {
+        password.clear();
+        password.extend((0..length).map(|_| pool[rng.gen_range(0..pool.len())]));
+        if required
+            .iter()
+            .all(|class| password.chars().any(|c| class.contains(c)))
+        {
+            break;
+        }
+    }
+    password
+}
@@ -0,0 +1,130 @@
+//! Generation receipts, written with `--receipt` and checked with `penguin receipt verify`.
+//!
+//! An audit often has to show that a password in use came out of a particular run,
+//! without anyone keeping the password. Every password generated with `--receipt <PATH>`
+//! adds one JSON line with the run it belongs to, when it was made, its label, the policy
+//! it was generated with, and a BLAKE3 hash of the run and the password. The run is a
+//! random ID shared by every password of one invocation, so the same password hashes
+//! differently in every run and a hash can't be looked up in a precomputed table.
+//!
+//! `penguin receipt verify <PATH>` reads a password and prints the receipts it matches,
+//! optionally only those of one run or label. The plaintext never reaches the file.
+
+use penguin::hex;
+use penguin::mixer::PenguinMixer;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Bytes of the random run ID.
+const RUN_LEN: usize = 16;
+
+/// One generated password as recorded in a receipt file.
+#[derive(Serialize, Deserialize)]
+struct Receipt {
+    run: String,
+    timestamp: String,
+    label: Option<String>,
+    policy: Value,
+    blake3: String,
+}
+
+/// Returns the ID of this run, the same for every receipt it writes.
+fn run() -> &'static str {
+    static RUN: OnceLock<String> = OnceLock::new();
+    RUN.get_or_init(|| {
+        let mut run = [0u8; RUN_LEN];
+        penguin::entropy::rng().fill_bytes(&mut run);
+        hex::encode(&run)
+    })
+}
+
+/// Appends one receipt per password, labeled in order, creating the file on first use.
+pub fn append(path: &Path, passwords: &[String], labels: &[String], mixer: &PenguinMixer) {
+    let timestamp = crate::date::timestamp();
+    let policy = crate::audit::policy(mixer);
+    let mut lines = String::new();
+    for (i, password) in passwords.iter().enumerate() {
+        let receipt = Receipt {
+            run: run().to_string(),
+            timestamp: timestamp.clone(),
+            label: labels.get(i).cloned(),
+            policy: policy.clone(),
+            blake3: hash(run(), password),
+        };
+        lines.push_str(&format!(
+            "{}\n",
+            serde_json::to_string(&receipt).unwrap_or_default()
+        ));
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+}
+
+/// Reads a password, at a hidden prompt on a terminal or as a line of stdin, and prints
+/// every receipt in the file it matches. Exits with status 1 when none does.
+pub fn verify(path: &Path, run: Option<&str>, label: Option<&str>) {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err)));
+    let password = read_password();
+
+    let mut matched = false;
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let receipt: Receipt = serde_json::from_str(line).unwrap_or_else(|err| {
+            crate::fail(format!("{}:{}: {}", path.display(), number + 1, err))
+        });
+        if run.is_some_and(|run| run != receipt.run)
+            || label.is_some_and(|label| receipt.label.as_deref() != Some(label))
+            || hash(&receipt.run, &password) != receipt.blake3
+        {
+            continue;
+        }
+        matched = true;
+        println!(
+            "{}  {}  {}",
+            receipt.timestamp,
+            receipt.run,
+            receipt.label.as_deref().unwrap_or("-")
+        );
+    }
+    if !matched {
+        eprintln!(
+            "{}",
+            crate::i18n::trf("The password matches no receipt in {0}", &[&path.display()])
+        );
+        std::process::exit(1);
+    }
+}
+
+fn read_password() -> String {
+    let password = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password(crate::i18n::tr("Password to verify: "))
+    } else {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).map(|_| line)
+    }
+    .unwrap_or_else(|err| crate::fail(format!("cannot read the password: {}", err)));
+    password.trim_end_matches(['\r', '\n']).to_string()
+}
+
+/// Hashes the password under the run it belongs to. The run ID has a fixed length, so
+/// the two can't run into each other.
+fn hash(run: &str, password: &str) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(run.as_bytes());
+    hasher.update(password.as_bytes());
+    hasher.finalize().to_hex().to_string()
+}
@@ -0,0 +1,140 @@
+//! The store of every password penguin has handed out, behind `--never-reuse`.
+//!
+//! A random generator can repeat itself, and a small charset or a short list of base
+//! words makes that likely. With `--never-reuse` (or `never_reuse = true` in the
+//! configuration), every password is checked against `$XDG_DATA_HOME/penguin/issued`
+//! (`~/.local/share/penguin/issued` when the variable is unset), candidates already in it
+//! are re-rolled, and the passwords handed out are added to it. Nothing is recorded
+//! without the flag.
+//!
+//! The store holds a random salt on its first line and a truncated HMAC-SHA256 of each
+//! password under that salt on the others, never the passwords. Anyone holding the file
+//! can still test guesses against it, so it is created readable by its owner only.
+
+use hmac::{Hmac, Mac};
+use penguin::hex;
+use rand::RngCore;
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Bytes of the salt the hashes are keyed with.
+const SALT_LEN: usize = 32;
+
+/// Bytes of each HMAC that are kept, so a million passwords take about 33 MB.
+const HASH_LEN: usize = 16;
+
+type Hash = [u8; HASH_LEN];
+
+struct Issued {
+    path: PathBuf,
+    salt: Vec<u8>,
+    /// Hashes read from the store or claimed by candidates of this run.
+    known: HashSet<Hash>,
+}
+
+static ISSUED: OnceLock<Mutex<Issued>> = OnceLock::new();
+
+/// Returns the path of the store, whether or not it exists.
+pub fn path() -> Option<PathBuf> {
+    match std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir).join("penguin/issued")),
+        None => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".local/share/penguin/issued")),
+    }
+}
+
+/// Loads the store, creating it with a fresh salt on first use, so that [`seen`] and
+/// [`record`] take effect.
+pub fn enable() {
+    let path = path().unwrap_or_else(|| {
+        crate::fail("cannot locate the data directory; set XDG_DATA_HOME or HOME")
+    });
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let mut salt = [0u8; SALT_LEN];
+            penguin::entropy::rng().fill_bytes(&mut salt);
+            let header = format!("{}\n", hex::encode(&salt));
+            append(&path, &header);
+            header
+        }
+        Err(err) => crate::fail(format!("cannot read {}: {}", path.display(), err)),
+    };
+
+    let corrupt = |number: usize| -> ! {
+        crate::fail(format!(
+            "{}:{}: not a penguin password store",
+            path.display(),
+            number + 1
+        ))
+    };
+    let mut lines = contents.lines();
+    let salt = lines
+        .next()
+        .and_then(hex::decode)
+        .filter(|salt| salt.len() == SALT_LEN)
+        .unwrap_or_else(|| corrupt(0));
+    let mut known = HashSet::new();
+    for (number, line) in lines.enumerate() {
+        let hash = hex::decode(line)
+            .and_then(|bytes| Hash::try_from(bytes).ok())
+            .unwrap_or_else(|| corrupt(number + 1));
+        known.insert(hash);
+    }
+    let _ = ISSUED.set(Mutex::new(Issued { path, salt, known }));
+}
+
+/// Returns whether the password was handed out before or already claimed by another
+/// candidate of this run, and claims it otherwise. Pushed onto the mixer's `reject_if`.
+pub fn seen(password: &str) -> bool {
+    let Some(issued) = ISSUED.get() else {
+        return false;
+    };
+    let mut issued = issued
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let hash = hash(&issued.salt, password);
+    !issued.known.insert(hash)
+}
+
+/// Adds the passwords handed out to the store. Does nothing unless [`enable`] was called.
+pub fn record(passwords: &[String]) {
+    let Some(issued) = ISSUED.get() else {
+        return;
+    };
+    let issued = issued
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let lines: String = passwords
+        .iter()
+        .map(|password| format!("{}\n", hex::encode(&hash(&issued.salt, password))))
+        .collect();
+    append(&issued.path, &lines);
+}
+
+fn hash(salt: &[u8], password: &str) -> Hash {
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt).expect("HMAC takes keys of any length");
+    mac.update(password.as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let mut hash = [0u8; HASH_LEN];
+    hash.copy_from_slice(&digest[..HASH_LEN]);
+    hash
+}
+
+fn append(path: &std::path::Path, lines: &str) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|err| crate::fail(format!("cannot create {}: {}", dir.display(), err)));
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .unwrap_or_else(|err| crate::fail(format!("cannot write {}: {}", path.display(), err)));
+}
@@ -0,0 +1,205 @@
+//! The `penguin rotate` command, which replaces every password of an earlier export.
+//!
+//! Scheduled rotation means generating a new password for each account under the same
+//! rules as the last one. `penguin rotate old.jsonl -o new.jsonl --mapping map.jsonl`
+//! reads a JSON Lines export (`--format jsonl`) and runs `penguin generate` once per
+//! entry, under the policy the entry records: the style, template, or site it names, or
+//! a policy file of that name in `--policy-dir`. Entries without one get the strategy,
+//! length, and character classes of their old password. The new export keeps the labels,
+//! usernames, and expiry spans, so it can be rotated again in turn, and the mapping pairs
+//! every old password with its replacement for the scripts that change them.
+//!
+//! Both files hold plaintext passwords and are created readable by their owner only.
+
+use clap::ValueEnum;
+use penguin::mixer::CharClass;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// An entry of the old export, as written by `--format jsonl`.
+#[derive(Deserialize)]
+struct Record {
+    password: String,
+    label: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    policy: Option<String>,
+    strategy: Option<String>,
+    timestamp: Option<String>,
+    expires: Option<String>,
+}
+
+/// Where the old export's passwords were generated from.
+pub struct Sources<'a> {
+    /// Base words for the entries that were built from words.
+    pub words: &'a [String],
+    pub wordlist: Option<&'a Path>,
+    /// Directory holding the policy files the entries' policies are named after.
+    pub policy_dir: Option<&'a Path>,
+}
+
+/// Rotates every entry of `old`, writing the new export to `output` and the old→new
+/// pairs to `mapping`.
+pub fn run(old: &Path, output: &Path, mapping: &Path, sources: &Sources) {
+    let contents = std::fs::read_to_string(old)
+        .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", old.display(), err)));
+    let mut export = String::new();
+    let mut pairs = String::new();
+    let mut rotated = 0;
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = serde_json::from_str(line).unwrap_or_else(|err| {
+            crate::fail(format!("{}:{}: {}", old.display(), number + 1, err))
+        });
+        let name = record
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("line {}", number + 1));
+        let mut fresh = generate(&record, sources)
+            .unwrap_or_else(|err| crate::fail(format!("entry '{}': {}", name, err)));
+        // Labels go in here rather than on the command line, where a comma would split one.
+        fresh["label"] = json!(record.label);
+        if let Some(username) = &record.username {
+            fresh["username"] = json!(username);
+        }
+        pairs.push_str(&format!(
+            "{}\n",
+            json!({
+                "label": record.label,
+                "username": record.username,
+                "old": record.password,
+                "new": fresh["password"],
+            })
+        ));
+        export.push_str(&format!("{}\n", fresh));
+        rotated += 1;
+    }
+    if rotated == 0 {
+        crate::fail(format!("{} has no entries to rotate", old.display()));
+    }
+
+    crate::output::write_private(output, export.as_bytes());
+    crate::output::write_private(mapping, pairs.as_bytes());
+    println!(
+        "{}",
+        crate::i18n::trf(
+            "Rotated {0} passwords into {1}; the old and new pairs are in {2}",
+            &[&rotated, &output.display(), &mapping.display()],
+        )
+    );
+}
+
+/// Runs `penguin generate` for the entry and returns the JSON record it prints.
+fn generate(record: &Record, sources: &Sources) -> Result<Value, String> {
+    let mut args: Vec<String> = vec!["generate".into(), "--format".into(), "jsonl".into()];
+    if let Some(span) = expiry_span(record) {
+        args.extend(["--expires".into(), format!("{}d", span)]);
+    }
+
+    let mut shaped = false;
+    if let Some(name) = &record.policy {
+        let (policy, sets_shape) = policy_args(name, sources).ok_or_else(|| {
+            format!(
+                "no style, template, site, or --policy-dir file is named '{}'",
+                name
+            )
+        })?;
+        args.extend(policy);
+        shaped = sets_shape;
+    }
+    let strategy = record.strategy.as_deref().unwrap_or("mixed");
+    if !shaped {
+        args.extend(shape_args(&record.password, strategy));
+    }
+    if strategy != "random" && strategy != "pronounceable" {
+        if sources.words.is_empty() && sources.wordlist.is_none() {
+            return Err(String::from(
+                "it was generated from base words; give them with -w or --wordlist",
+            ));
+        }
+        if !sources.words.is_empty() {
+            args.extend(["--words".into(), sources.words.join(",")]);
+        }
+        if let Some(path) = sources.wordlist {
+            args.push("--wordlist".into());
+            args.push(path.display().to_string());
+        }
+    }
+
+    let exe = std::env::current_exe().map_err(|err| format!("cannot run penguin: {}", err))?;
+    let output = Command::new(exe)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|err| format!("cannot run penguin: {}", err))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|err| format!("cannot read the generated password: {}", err))
+}
+
+/// Returns the options that select a policy by name, and whether it also sets the
+/// length and complexity, or `None` when nothing has that name.
+fn policy_args(name: &str, sources: &Sources) -> Option<(Vec<String>, bool)> {
+    if crate::style::Style::from_str(name, true).is_ok() {
+        return Some((vec!["--style".into(), name.into()], true));
+    }
+    let template = crate::template::dir().map(|dir| dir.join(format!("{}.toml", name)));
+    if template.is_some_and(|path| path.is_file()) {
+        return Some((vec!["--template".into(), name.into()], true));
+    }
+    let file: Option<PathBuf> = sources
+        .policy_dir
+        .map(|dir| dir.join(format!("{}.toml", name)));
+    if let Some(file) = file.filter(|file| file.is_file()) {
+        return Some((
+            vec!["--policy-file".into(), file.display().to_string()],
+            false,
+        ));
+    }
+    if crate::sites::load().get(name).is_some() {
+        return Some((vec!["--site".into(), name.into()], false));
+    }
+    None
+}
+
+/// Returns the options that reproduce the strategy, length, and character classes of a
+/// password generated without a shaping policy.
+fn shape_args(password: &str, strategy: &str) -> Vec<String> {
+    let specials = password
+        .chars()
+        .any(|c| CharClass::of(c) == Some(CharClass::Specials));
+    let uppercase = password
+        .chars()
+        .any(|c| CharClass::of(c) == Some(CharClass::Uppercase));
+    let complexity = match strategy {
+        "random" => "penguin",
+        "pronounceable" if uppercase => "medium",
+        "whole-words" if specials => "medium",
+        "mixed" if specials => "hard",
+        _ => "basic",
+    };
+    let mut args = vec!["--complexity".into(), complexity.into()];
+    match strategy {
+        "whole-words" => args.push("--whole-words".into()),
+        "pronounceable" => args.push("--pronounceable".into()),
+        _ => {}
+    }
+    if strategy != "random" {
+        args.extend(["--length".into(), password.chars().count().to_string()]);
+    }
+    args
+}
+
+/// Returns how many days the old password was valid for, to give the new one as long.
+fn expiry_span(record: &Record) -> Option<i64> {
+    let created = crate::date::parse(record.timestamp.as_deref()?.get(..10)?)?;
+    let expires = crate::date::parse(record.expires.as_deref()?)?;
+    Some((expires - created).max(1))
+}
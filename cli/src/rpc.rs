@@ -0,0 +1,89 @@
+//! JSON-RPC 2.0 over stdin/stdout.
+//!
+//! Each line on stdin holds one request object and each response is written as a single
+//! line on stdout, which keeps framing trivial for wrappers driving penguin as a
+//! long-lived child process. Requests without an `id` are notifications and get no reply.
+//! `penguin daemon` speaks the same protocol on each connection to a unix socket. Besides
+//! the API operations, the `metrics` method returns the Prometheus metrics as a string.
+
+use crate::api;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serves requests until stdin is closed.
+pub fn run() {
+    if let Err(err) = serve(io::stdin().lock(), io::stdout().lock()) {
+        crate::fail(format!("cannot read stdin: {}", err));
+    }
+}
+
+/// Answers the requests read from `input` on `output` until the input ends or the
+/// output is closed.
+pub fn serve(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = respond(&line) {
+            if writeln!(output, "{}", response)
+                .and_then(|_| output.flush())
+                .is_err()
+            {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn respond(line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => return Some(error(Value::Null, PARSE_ERROR, &err.to_string())),
+    };
+
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(Value::as_str);
+    let version = request.get("jsonrpc").and_then(Value::as_str);
+    let (Some(method), Some("2.0")) = (method, version) else {
+        return Some(error(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            "expected a JSON-RPC 2.0 request object",
+        ));
+    };
+
+    let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+    let outcome = if method == "metrics" {
+        crate::metrics::record_call(method, true);
+        Some(Ok(Value::String(crate::metrics::render())))
+    } else {
+        api::call(method, params)
+    };
+
+    let id = id?;
+    Some(match outcome {
+        Some(Ok(result)) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Some(Err(message)) => error(id, INVALID_PARAMS, &message),
+        None => error(
+            id,
+            METHOD_NOT_FOUND,
+            &format!("unknown method '{}'", method),
+        ),
+    })
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
@@ -0,0 +1,118 @@
+//! Composition rules given on the command line, such as `--min-digits 2`.
+//!
+//! The flags describe the same rules as a policy file and end up in one
+//! [`PasswordPolicy`]: on their own they make a policy, and next to `--policy-file`,
+//! `--site`, or a template they tighten its rules. Minimums are raised and maximums
+//! lowered to whichever is stricter, `--exclude-chars` adds to the excluded characters,
+//! and `--special-chars` replaces the specials to pick from. Every password is checked
+//! against the result, so those that slip past the mixer are generated again.
+
+use clap::Args;
+use penguin::policy::PasswordPolicy;
+
+/// Options that set composition rules for the generated passwords.
+#[derive(Args, Default)]
+pub struct RuleArgs {
+    /// Lowercase letters every password contains at least
+    #[arg(long, value_name = "N")]
+    pub min_lowercase: Option<usize>,
+
+    /// Uppercase letters every password contains at least
+    #[arg(long, value_name = "N")]
+    pub min_uppercase: Option<usize>,
+
+    /// Digits every password contains at least
+    #[arg(long, value_name = "N")]
+    pub min_digits: Option<usize>,
+
+    /// Special characters every password contains at least
+    #[arg(long, value_name = "N")]
+    pub min_specials: Option<usize>,
+
+    /// Lowercase letters a password contains at most (0 leaves them out)
+    #[arg(long, value_name = "N")]
+    pub max_lowercase: Option<usize>,
+
+    /// Uppercase letters a password contains at most (0 leaves them out)
+    #[arg(long, value_name = "N")]
+    pub max_uppercase: Option<usize>,
+
+    /// Digits a password contains at most (0 leaves them out)
+    #[arg(long, value_name = "N")]
+    pub max_digits: Option<usize>,
+
+    /// Special characters a password contains at most (0 leaves them out)
+    #[arg(long, value_name = "N")]
+    pub max_specials: Option<usize>,
+
+    /// Characters that never appear, such as the easily confused l1IO0
+    #[arg(long, value_name = "CHARS", allow_hyphen_values = true)]
+    pub exclude_chars: Option<String>,
+
+    /// The only special characters to use, such as '-_!'
+    #[arg(long, value_name = "CHARS", allow_hyphen_values = true)]
+    pub special_chars: Option<String>,
+}
+
+impl RuleArgs {
+    fn is_empty(&self) -> bool {
+        [
+            self.min_lowercase,
+            self.min_uppercase,
+            self.min_digits,
+            self.min_specials,
+            self.max_lowercase,
+            self.max_uppercase,
+            self.max_digits,
+            self.max_specials,
+        ]
+        .iter()
+        .all(Option::is_none)
+            && self.exclude_chars.is_none()
+            && self.special_chars.is_none()
+    }
+
+    /// Applies the rules to the policy, or to a new one when there is none. Returns the
+    /// policy unchanged when no rule was given, and fails when the rules contradict each
+    /// other or the policy.
+    pub fn apply(&self, policy: Option<PasswordPolicy>) -> Option<PasswordPolicy> {
+        if self.is_empty() {
+            return policy;
+        }
+        let mut policy = policy.unwrap_or_default();
+        let raise = |minimum: &mut usize, flag: Option<usize>| {
+            *minimum = flag.map_or(*minimum, |flag| flag.max(*minimum));
+        };
+        raise(&mut policy.min_lowercase, self.min_lowercase);
+        raise(&mut policy.min_uppercase, self.min_uppercase);
+        raise(&mut policy.min_digits, self.min_digits);
+        raise(&mut policy.min_specials, self.min_specials);
+        let lower = |maximum: &mut Option<usize>, flag: Option<usize>| {
+            *maximum = match (*maximum, flag) {
+                (Some(maximum), Some(flag)) => Some(maximum.min(flag)),
+                (maximum, flag) => maximum.or(flag),
+            };
+        };
+        lower(&mut policy.max_lowercase, self.max_lowercase);
+        lower(&mut policy.max_uppercase, self.max_uppercase);
+        lower(&mut policy.max_digits, self.max_digits);
+        lower(&mut policy.max_specials, self.max_specials);
+        if let Some(excluded) = &self.exclude_chars {
+            policy.excluded_chars.push_str(excluded);
+        }
+        if let Some(specials) = &self.special_chars {
+            if let Some(c) = specials
+                .chars()
+                .find(|c| c.is_alphanumeric() || c.is_whitespace())
+            {
+                crate::fail(format!(
+                    "--special-chars takes special characters only, not '{}'",
+                    c
+                ));
+            }
+            policy.special_chars = Some(specials.clone());
+        }
+        policy.validate().unwrap_or_else(|err| crate::fail(err));
+        Some(policy)
+    }
+}
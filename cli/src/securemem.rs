@@ -0,0 +1,45 @@
+//! Locked, self-wiping heap memory for the command-line tool, enabled by the
+//! `secure-mem` feature.
+//!
+//! Passwords and the base words they come from pass through many short-lived heap
+//! buffers: the generated strings, their rendered output, clipboard and encryption
+//! copies. Rather than tracking each of them, this module installs a global allocator
+//! that locks every allocation into RAM with `mlock` (`VirtualLock` on Windows), so no
+//! page holding a secret is swapped to disk, and overwrites every allocation with zeros
+//! before handing it back to the system allocator.
+//!
+//! Pages are never unlocked explicitly. A page usually holds several allocations, and
+//! since `munlock` is not reference counted, unlocking one freed buffer would unlock
+//! its live neighbors too. Freed memory is wiped and reused instead, and the locks are
+//! released when the process exits. Locking is best effort: once the `RLIMIT_MEMLOCK`
+//! budget is used up, further allocations are still wiped but no longer locked.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+
+struct LockingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: LockingAllocator = LockingAllocator;
+
+unsafe impl GlobalAlloc for LockingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            memsec::mlock(ptr, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            memsec::mlock(ptr, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        memsec::memzero(ptr, layout.size());
+        System.dealloc(ptr, layout);
+    }
+}
@@ -0,0 +1,158 @@
+//! Statistical self-test of the random number generators penguin relies on.
+//!
+//! `penguin selftest` checks that the operating system entropy source and the source
+//! chosen with `--entropy` answer, runs the FIPS 140-2 monobit, runs, and long-run tests
+//! on a 20,000-bit sample of the generator used for passwords, and runs a chi-square test over the character frequencies of a
+//! large batch of penguin-level passwords. A healthy generator fails any of these only
+//! with negligible probability, so a failure is evidence that something is degraded.
+
+use penguin::mixer::{ComplexityLevel, PenguinMixer};
+use rand::{rngs::OsRng, RngCore};
+use std::collections::HashMap;
+
+/// Sample size the FIPS 140-2 tests are defined for.
+const SAMPLE_BITS: usize = 20_000;
+
+/// Acceptance intervals for the number of runs of each length (6 stands for 6 and longer).
+const RUN_BOUNDS: [(usize, usize); 6] = [
+    (2315, 2685),
+    (1114, 1386),
+    (527, 723),
+    (240, 384),
+    (103, 209),
+    (103, 209),
+];
+
+/// Any run this long or longer fails the long-run test.
+const LONG_RUN: usize = 26;
+
+/// Number of penguin-level passwords sampled for the chi-square test.
+const CHI_SQUARE_PASSWORDS: usize = 2_000;
+
+/// Standard normal quantile for the chi-square critical value (p = 0.0001).
+const CHI_SQUARE_Z: f64 = 3.719;
+
+/// Runs every check, printing one line per check, and exits non-zero if any failed.
+pub fn run() {
+    let checks = [os_entropy(), monobit_and_runs(), chi_square()];
+    let mut failed = false;
+    for (passed, line) in checks.into_iter().flatten() {
+        println!("{} {}", if passed { "PASS" } else { "FAIL" }, line);
+        failed |= !passed;
+    }
+
+    if failed {
+        eprintln!(
+            "{}",
+            crate::i18n::trf(
+                "error: {0}",
+                &[&crate::i18n::tr(
+                    "the random number generator failed its self-test"
+                )],
+            )
+        );
+        std::process::exit(1);
+    }
+}
+
+fn os_entropy() -> Vec<(bool, String)> {
+    let mut buf = [0u8; 32];
+    let line = match OsRng.try_fill_bytes(&mut buf) {
+        Ok(()) if buf.iter().any(|&byte| byte != 0) => {
+            (true, String::from("os entropy: source available"))
+        }
+        Ok(()) => (
+            false,
+            String::from("os entropy: source returned only zeros"),
+        ),
+        Err(err) => (false, format!("os entropy: {}", err)),
+    };
+
+    let source = penguin::entropy::source();
+    let active = match penguin::entropy::rng().try_fill_bytes(&mut buf) {
+        Ok(()) => (
+            true,
+            format!("entropy source: {} ({})", source, source.backend()),
+        ),
+        Err(err) => (false, format!("entropy source: {}: {}", source, err)),
+    };
+    vec![line, active]
+}
+
+fn monobit_and_runs() -> Vec<(bool, String)> {
+    let mut bytes = vec![0u8; SAMPLE_BITS / 8];
+    penguin::entropy::rng().fill_bytes(&mut bytes);
+    let bits: Vec<bool> = bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| byte >> i & 1 == 1))
+        .collect();
+
+    let ones = bits.iter().filter(|&&bit| bit).count();
+    let mut lines = vec![(
+        (9725..=10275).contains(&ones),
+        format!("monobit: {} ones in {} bits", ones, SAMPLE_BITS),
+    )];
+
+    // runs[value][length - 1] counts runs of zeros (value 0) and ones (value 1).
+    let mut runs = [[0usize; 6]; 2];
+    let mut longest = 0;
+    for run in bits.chunk_by(|a, b| a == b) {
+        runs[run[0] as usize][run.len().min(6) - 1] += 1;
+        longest = longest.max(run.len());
+    }
+    let runs_ok = runs.iter().all(|counts| {
+        counts
+            .iter()
+            .zip(RUN_BOUNDS)
+            .all(|(count, (low, high))| (low..=high).contains(count))
+    });
+    lines.push((
+        runs_ok,
+        format!(
+            "runs: zeros {:?}, ones {:?} (lengths 1-6+)",
+            runs[0], runs[1]
+        ),
+    ));
+    lines.push((
+        longest < LONG_RUN,
+        format!("long run: longest run is {} bits", longest),
+    ));
+    lines
+}
+
+fn chi_square() -> Vec<(bool, String)> {
+    let mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 0);
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut total = 0;
+    for _ in 0..CHI_SQUARE_PASSWORDS {
+        for c in mixer.mix_password(&[]).unwrap_or_default().chars() {
+            *counts.entry(c).or_default() += 1;
+            total += 1;
+        }
+    }
+    if total == 0 {
+        return vec![(false, String::from("chi-square: no characters generated"))];
+    }
+
+    let categories = counts.len() as f64;
+    let expected = total as f64 / categories;
+    let statistic: f64 = counts
+        .values()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+
+    // Wilson-Hilferty approximation of the chi-square quantile.
+    let df = categories - 1.0;
+    let term = 2.0 / (9.0 * df);
+    let critical = df * (1.0 - term + CHI_SQUARE_Z * term.sqrt()).powi(3);
+
+    vec![(
+        statistic < critical,
+        format!(
+            "chi-square: {:.1} over {} characters (critical value {:.1})",
+            statistic,
+            counts.len(),
+            critical
+        ),
+    )]
+}
@@ -0,0 +1,190 @@
+//! HTTP server mode exposing password generation and analysis as a small JSON API.
+//!
+//! Every request must carry `Authorization: Bearer <token>`, and with a `rate_limit` in
+//! the configuration file each client gets that many requests per minute, answered with
+//! 429 beyond it. The server only ever logs
+//! the method, path, and status of a request, never request or response bodies, since
+//! both contain secret material. `GET /metrics` serves the Prometheus metrics, which hold
+//! only counts and timings.
+
+use crate::api;
+use penguin::Charset;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Requests larger than this are rejected before their body is read.
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+/// How many clients and addresses the rate limiter tracks before forgetting idle ones.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Starts the server and handles requests until the process is terminated.
+///
+/// Clients authenticate with the token given on the command line or with one of the
+/// named tokens in the `[serve]` table of the configuration file. When neither provides
+/// one a random token is generated and printed to stderr, so the API is never reachable
+/// without authentication.
+pub fn run(listen: SocketAddr, token: Option<String>) {
+    let settings = crate::config::serve();
+    let mut clients: Vec<(String, String)> = settings.tokens.into_iter().collect();
+    if let Some(token) = token {
+        clients.push((String::from("default"), token));
+    }
+    if clients.is_empty() {
+        let token = penguin::random_password(32, &Charset::ALPHANUMERIC);
+        eprintln!("penguin: generated API token: {}", token);
+        clients.push((String::from("default"), token));
+    }
+    if settings.rate_limit == Some(0) {
+        crate::fail("rate_limit in the [serve] table must be at least 1");
+    }
+    let mut limiter = settings.rate_limit.map(RateLimiter::new);
+
+    let server = Server::http(listen)
+        .unwrap_or_else(|err| crate::fail(format!("cannot listen on {}: {}", listen, err)));
+    eprintln!("penguin: listening on http://{}", listen);
+    if !listen.ip().is_loopback() {
+        eprintln!(
+            "penguin: warning: serving plain HTTP beyond localhost; put a TLS proxy in front of it"
+        );
+    }
+
+    for request in server.incoming_requests() {
+        handle(request, &clients, limiter.as_mut());
+    }
+}
+
+fn handle(mut request: Request, clients: &[(String, String)], limiter: Option<&mut RateLimiter>) {
+    let method = request.method().clone();
+    let path = request.url().to_string();
+    let client = authorized(&request, clients);
+
+    // Unauthenticated requests are limited by address, so guessing tokens is too.
+    let key = match (client, request.remote_addr()) {
+        (Some(name), _) => format!("client {}", name),
+        (None, Some(addr)) => format!("address {}", addr.ip()),
+        (None, None) => String::from("unknown"),
+    };
+    if let Some(wait) = limiter.and_then(|limiter| limiter.check(&key)) {
+        eprintln!("penguin: {} {} 429", method, path);
+        let retry_after = wait.as_secs().max(1).to_string();
+        let response = Response::from_string(json!({ "error": "rate limit exceeded" }).to_string())
+            .with_status_code(429)
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+            .with_header(Header::from_bytes("Retry-After", retry_after).unwrap());
+        let _ = request.respond(response);
+        return;
+    }
+
+    if path == "/metrics" && method == Method::Get && client.is_some() {
+        crate::metrics::record_call("metrics", true);
+        eprintln!("penguin: {} {} 200", method, path);
+        let response = Response::from_string(crate::metrics::render())
+            .with_header(Header::from_bytes("Content-Type", "text/plain; version=0.0.4").unwrap());
+        let _ = request.respond(response);
+        return;
+    }
+
+    let (status, body) = if client.is_none() {
+        (401, json!({ "error": "missing or invalid bearer token" }))
+    } else if request.body_length().unwrap_or(0) as u64 > MAX_BODY_BYTES {
+        (413, json!({ "error": "request body too large" }))
+    } else {
+        let mut raw = String::new();
+        match request
+            .as_reader()
+            .take(MAX_BODY_BYTES)
+            .read_to_string(&mut raw)
+        {
+            Ok(_) => route(&method, &path, &raw),
+            Err(_) => (400, json!({ "error": "request body is not valid UTF-8" })),
+        }
+    };
+
+    eprintln!("penguin: {} {} {}", method, path, status);
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    let _ = request.respond(response);
+}
+
+/// Returns the name of the client whose token the request carries.
+fn authorized<'a>(request: &Request, clients: &'a [(String, String)]) -> Option<&'a str> {
+    let given = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().strip_prefix("Bearer "))?;
+    // Every token is compared, so the time taken doesn't tell which one came close.
+    clients.iter().fold(None, |found, (name, token)| {
+        let matches = penguin::compare(given, token);
+        found.or(matches.then_some(name.as_str()))
+    })
+}
+
+/// A token bucket per client: each holds up to a minute's worth of requests and refills
+/// continuously.
+struct RateLimiter {
+    per_minute: f64,
+    buckets: HashMap<String, (f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn new(per_minute: u32) -> Self {
+        RateLimiter {
+            per_minute: f64::from(per_minute),
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Takes a request from the client's bucket, or returns how long until one is
+    /// available.
+    fn check(&mut self, key: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let per_second = self.per_minute / 60.0;
+        if self.buckets.len() >= MAX_TRACKED_CLIENTS {
+            // Buckets that would have refilled by now are no different from new ones.
+            let full = Duration::from_secs(60);
+            self.buckets
+                .retain(|_, (_, last)| now.duration_since(*last) < full);
+        }
+        let (tokens, last) = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert((self.per_minute, now));
+        *tokens =
+            (*tokens + now.duration_since(*last).as_secs_f64() * per_second).min(self.per_minute);
+        *last = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - *tokens) / per_second))
+        }
+    }
+}
+
+fn route(method: &Method, path: &str, body: &str) -> (u16, Value) {
+    let operation = match path {
+        "/generate" => "generate",
+        "/analyze" => "analyze",
+        _ => return (404, json!({ "error": "not found" })),
+    };
+    if *method != Method::Post {
+        return (405, json!({ "error": "method not allowed" }));
+    }
+
+    let params = match serde_json::from_str(body) {
+        Ok(params) => params,
+        Err(err) => return (400, json!({ "error": err.to_string() })),
+    };
+    match api::call(operation, params) {
+        Some(Ok(result)) => (200, result),
+        Some(Err(message)) => (400, json!({ "error": message })),
+        None => (404, json!({ "error": "not found" })),
+    }
+}
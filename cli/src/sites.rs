@@ -0,0 +1,61 @@
+//! Site password rules for `--site` and the `penguin site-rules` command.
+//!
+//! The rules that ship with the library are extended by `site-rules.txt` next to the
+//! configuration file (`~/.config/penguin/site-rules.txt` by default), in the same
+//! `domain rules` format, one site per line. Entries there replace built-in ones, so a
+//! site that changed its limits can be fixed without waiting for a release.
+
+use penguin::policy::PasswordPolicy;
+use penguin::siterules::SiteRules;
+use std::path::PathBuf;
+
+/// Returns the path of the user's site rules, whether or not it exists.
+pub fn path() -> Option<PathBuf> {
+    crate::config::path().map(|config| config.with_file_name("site-rules.txt"))
+}
+
+/// Loads the built-in rules and the user's overrides.
+pub fn load() -> SiteRules {
+    let mut rules = SiteRules::embedded();
+    let Some(path) = path() else {
+        return rules;
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => rules
+            .extend(&contents)
+            .unwrap_or_else(|err| crate::fail(format!("{}: {}", path.display(), err))),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => crate::fail(format!("cannot read {}: {}", path.display(), err)),
+    }
+    rules
+}
+
+/// Returns the policy for a site, failing when no rules are known for it.
+pub fn policy(site: &str) -> PasswordPolicy {
+    load().get(site).cloned().unwrap_or_else(|| {
+        let hint = path().map_or_else(String::new, |path| {
+            format!("; add them to {}", path.display())
+        });
+        crate::fail(format!("no password rules known for {}{}", site, hint))
+    })
+}
+
+/// Prints the rules that apply to a site, or every known site's rules.
+pub fn show(site: Option<&str>) {
+    let rules = load();
+    match site {
+        Some(site) => match rules.rules(site) {
+            Some((domain, text)) => println!("{}  {}", domain, text),
+            None => crate::fail(format!("no password rules known for {}", site)),
+        },
+        None => {
+            let domains = rules.domains();
+            let width = domains.iter().map(|domain| domain.len()).max().unwrap_or(0);
+            for domain in domains {
+                if let Some((_, text)) = rules.rules(domain) {
+                    println!("{:width$}  {}", domain, text);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! The `sops` output format: a SOPS-encrypted file of generated secrets.
+//!
+//! Every password is encrypted on its own with AES-256-GCM under a fresh data key, bound
+//! to its key name, and stored as an `ENC[AES256_GCM,...]` value. The data key itself is
+//! encrypted to each `--age` recipient (in builds with the `age` feature) and each
+//! `--kms` key (with the `aws` feature) in the `sops` metadata, alongside the encrypted MAC over all
+//! values that `sops` checks before decrypting. The result can be edited and decrypted
+//! with `sops` like any file it wrote itself. It is YAML unless the output file ends in
+//! `.json`, as `sops` decides too.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sha2::{Digest, Sha512};
+
+/// The SOPS version the metadata claims, which decides how it is read back.
+const SOPS_VERSION: &str = "3.9.0";
+
+/// The key suffix that, by SOPS convention, leaves a value unencrypted.
+const UNENCRYPTED_SUFFIX: &str = "_unencrypted";
+
+/// Where the data key is encrypted to.
+pub struct Recipients<'a> {
+    /// age recipients, as `age1...` public keys.
+    pub age: &'a [String],
+    /// AWS KMS key ARNs.
+    pub kms: &'a [String],
+}
+
+/// Encrypts the named secrets into the text of a SOPS file.
+pub fn encrypt(
+    secrets: &[(String, String)],
+    recipients: Recipients,
+    json: bool,
+) -> Result<String, String> {
+    if recipients.age.is_empty() && recipients.kms.is_empty() {
+        return Err(String::from(
+            "--format sops needs an --age recipient or a --kms key",
+        ));
+    }
+    let mut data_key = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut penguin::entropy::rng(), &mut data_key);
+    let lastmodified = crate::date::timestamp();
+    let (values, mac) = seal(secrets, &data_key, &lastmodified)?;
+
+    let mut age = Vec::new();
+    for recipient in recipients.age {
+        age.push((recipient.clone(), age_encrypt(recipient, &data_key)?));
+    }
+    let mut kms = Vec::new();
+    for arn in recipients.kms {
+        kms.push((arn.clone(), kms_encrypt(arn, &data_key)?));
+    }
+
+    Ok(if json {
+        render_json(&values, &age, &kms, &lastmodified, &mac)
+    } else {
+        render_yaml(&values, &age, &kms, &lastmodified, &mac)
+    })
+}
+
+/// Encrypted values by key, in the order the secrets were given.
+type Values<'a> = Vec<(&'a String, String)>;
+
+/// Encrypts every value under the data key, along with the MAC over them that is bound
+/// to the modification time.
+fn seal<'a>(
+    secrets: &'a [(String, String)],
+    data_key: &[u8; 32],
+    lastmodified: &str,
+) -> Result<(Values<'a>, String), String> {
+    let mut mac = Sha512::new();
+    let mut values = Vec::new();
+    for (key, value) in secrets {
+        if key == "sops" {
+            return Err(String::from(
+                "the key sops is taken by the SOPS metadata; pick another label",
+            ));
+        }
+        if key.ends_with(UNENCRYPTED_SUFFIX) {
+            return Err(format!(
+                "the key {} ends in {} and would be left unencrypted",
+                key, UNENCRYPTED_SUFFIX
+            ));
+        }
+        mac.update(value.as_bytes());
+        values.push((key, encrypt_value(data_key, value, &format!("{}:", key))));
+    }
+    let mac = format!("{:X}", mac.finalize());
+    Ok((values, encrypt_value(data_key, &mac, lastmodified)))
+}
+
+/// Encrypts a string the way SOPS does: AES-256-GCM with a 32-byte nonce and the value's
+/// path as additional data.
+fn encrypt_value(key: &[u8; 32], value: &str, aad: &str) -> String {
+    let mut iv = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut penguin::entropy::rng(), &mut iv);
+    encrypt_value_with_iv(key, &iv, value, aad)
+}
+
+fn encrypt_value_with_iv(key: &[u8; 32], iv: &[u8; 32], value: &str, aad: &str) -> String {
+    use aes_gcm::aead::{consts::U32, Aead, KeyInit, Payload};
+
+    let cipher = aes_gcm::AesGcm::<aes::Aes256, U32>::new(key.into());
+    let sealed = cipher
+        .encrypt(
+            iv.into(),
+            Payload {
+                msg: value.as_bytes(),
+                aad: aad.as_bytes(),
+            },
+        )
+        .expect("AES-GCM encrypts messages of this size");
+    let (data, tag) = sealed.split_at(sealed.len() - 16);
+    format!(
+        "ENC[AES256_GCM,data:{},iv:{},tag:{},type:str]",
+        BASE64.encode(data),
+        BASE64.encode(iv),
+        BASE64.encode(tag)
+    )
+}
+
+#[cfg(feature = "age")]
+fn age_encrypt(recipient: &str, data_key: &[u8]) -> Result<String, String> {
+    let armored = crate::encrypt::age(&[recipient.to_string()], data_key, true)?;
+    String::from_utf8(armored).map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "age"))]
+fn age_encrypt(_: &str, _: &[u8]) -> Result<String, String> {
+    Err(String::from(
+        "--age needs penguin-cli built with the age feature",
+    ))
+}
+
+#[cfg(feature = "aws")]
+fn kms_encrypt(arn: &str, data_key: &[u8]) -> Result<String, String> {
+    crate::aws::kms_encrypt(arn, data_key).map(|blob| BASE64.encode(blob))
+}
+
+#[cfg(not(feature = "aws"))]
+fn kms_encrypt(_: &str, _: &[u8]) -> Result<String, String> {
+    Err(String::from(
+        "--kms needs penguin-cli built with the aws feature",
+    ))
+}
+
+fn render_yaml(
+    values: &[(&String, String)],
+    age: &[(String, String)],
+    kms: &[(String, String)],
+    lastmodified: &str,
+    mac: &str,
+) -> String {
+    let mut out = String::new();
+    for (key, value) in values {
+        out.push_str(&format!("{}: {}\n", key, value));
+    }
+    out.push_str("sops:\n");
+    if !kms.is_empty() {
+        out.push_str("    kms:\n");
+        for (arn, enc) in kms {
+            out.push_str(&format!(
+                "        - arn: {}\n          created_at: \"{}\"\n          enc: {}\n          aws_profile: \"\"\n",
+                arn, lastmodified, enc
+            ));
+        }
+    }
+    if !age.is_empty() {
+        out.push_str("    age:\n");
+        for (recipient, enc) in age {
+            out.push_str(&format!(
+                "        - recipient: {}\n          enc: |\n",
+                recipient
+            ));
+            for line in enc.lines() {
+                out.push_str(&format!("            {}\n", line));
+            }
+        }
+    }
+    out.push_str(&format!("    lastmodified: \"{}\"\n", lastmodified));
+    out.push_str(&format!("    mac: {}\n", mac));
+    out.push_str(&format!("    unencrypted_suffix: {}\n", UNENCRYPTED_SUFFIX));
+    out.push_str(&format!("    version: {}\n", SOPS_VERSION));
+    out
+}
+
+fn render_json(
+    values: &[(&String, String)],
+    age: &[(String, String)],
+    kms: &[(String, String)],
+    lastmodified: &str,
+    mac: &str,
+) -> String {
+    let mut metadata = serde_json::json!({
+        "lastmodified": lastmodified,
+        "mac": mac,
+        "unencrypted_suffix": UNENCRYPTED_SUFFIX,
+        "version": SOPS_VERSION,
+    });
+    if !kms.is_empty() {
+        metadata["kms"] = kms
+            .iter()
+            .map(|(arn, enc)| {
+                serde_json::json!({
+                    "arn": arn,
+                    "created_at": lastmodified,
+                    "enc": enc,
+                    "aws_profile": "",
+                })
+            })
+            .collect();
+    }
+    if !age.is_empty() {
+        metadata["age"] = age
+            .iter()
+            .map(|(recipient, enc)| serde_json::json!({ "recipient": recipient, "enc": enc }))
+            .collect();
+    }
+
+    // The values are written by hand to keep them in order, which the MAC depends on.
+    let mut out = String::from("{\n");
+    for (key, value) in values {
+        out.push_str(&format!(
+            "  {}: {},\n",
+            serde_json::Value::from(key.as_str()),
+            serde_json::Value::from(value.as_str())
+        ));
+    }
+    let metadata = serde_json::to_string_pretty(&metadata)
+        .expect("JSON values serialize")
+        .replace('\n', "\n  ");
+    out.push_str(&format!("  \"sops\": {}\n}}\n", metadata));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes_gcm::aead::{consts::U32, Aead, KeyInit, Payload};
+
+    const KEY: [u8; 32] = [7; 32];
+    const LASTMODIFIED: &str = "2024-05-01T12:30:00Z";
+
+    /// Decrypts an `ENC[AES256_GCM,...]` value the way `sops` does, checking its tag
+    /// against the additional data.
+    fn decrypt_value(key: &[u8; 32], value: &str, aad: &str) -> String {
+        let inner = value
+            .strip_prefix("ENC[AES256_GCM,")
+            .and_then(|rest| rest.strip_suffix(",type:str]"))
+            .expect("an encrypted string value");
+        let field = |name: &str| {
+            let field = inner
+                .split(',')
+                .find_map(|part| part.strip_prefix(name))
+                .expect("the field is present");
+            BASE64.decode(field).expect("the field is base64")
+        };
+        let (mut sealed, iv) = (field("data:"), field("iv:"));
+        sealed.extend(field("tag:"));
+        let cipher = aes_gcm::AesGcm::<aes::Aes256, U32>::new(key.into());
+        let plain = cipher
+            .decrypt(
+                iv.as_slice().into(),
+                Payload {
+                    msg: &sealed,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .expect("the value decrypts");
+        String::from_utf8(plain).expect("the value is UTF-8")
+    }
+
+    /// Made by Python's `cryptography` with AES-GCM under the key 0..32 and the nonce
+    /// 100..132, as Go's `NewGCMWithNonceSize(32)` in `sops` seals values.
+    #[test]
+    fn values_match_standard_gcm() {
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let iv: [u8; 32] = std::array::from_fn(|i| 100 + i as u8);
+        assert_eq!(
+            encrypt_value_with_iv(&key, &iv, "hunter2", "db:"),
+            "ENC[AES256_GCM,data:ojS9sy1swQ==,\
+             iv:ZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXp7fH1+f4CBgoM=,\
+             tag:g7nYHx/TAG1eFlhxZPJO3g==,type:str]"
+        );
+    }
+
+    #[test]
+    fn seal_round_trips_with_mac() {
+        let secrets = vec![
+            (String::from("db"), String::from("hunter2")),
+            (String::from("api"), String::from("p@ss word")),
+        ];
+        let (values, mac) = seal(&secrets, &KEY, LASTMODIFIED).unwrap();
+        for ((key, value), (name, password)) in values.iter().zip(&secrets) {
+            assert_eq!(*key, name);
+            assert_eq!(decrypt_value(&KEY, value, &format!("{}:", name)), *password);
+        }
+        let expected = format!("{:X}", Sha512::digest(b"hunter2p@ss word"));
+        assert_eq!(decrypt_value(&KEY, &mac, LASTMODIFIED), expected);
+    }
+
+    #[test]
+    fn seal_refuses_unencrypted_keys() {
+        let secrets = vec![(String::from("db_unencrypted"), String::from("hunter2"))];
+        assert!(seal(&secrets, &KEY, LASTMODIFIED).is_err());
+    }
+
+    #[test]
+    fn seal_refuses_the_metadata_key() {
+        let secrets = vec![(String::from("sops"), String::from("hunter2"))];
+        assert!(seal(&secrets, &KEY, LASTMODIFIED).is_err());
+    }
+
+    #[test]
+    fn yaml_has_the_sops_layout() {
+        let db = String::from("db");
+        let values = vec![(&db, String::from("ENC[db]"))];
+        let age = vec![(
+            String::from("age1recipient"),
+            String::from(
+                "-----BEGIN AGE ENCRYPTED FILE-----\nYWJj\n-----END AGE ENCRYPTED FILE-----\n",
+            ),
+        )];
+        assert_eq!(
+            render_yaml(&values, &age, &[], LASTMODIFIED, "ENC[mac]"),
+            "db: ENC[db]\n\
+             sops:\n\
+             \x20   age:\n\
+             \x20       - recipient: age1recipient\n\
+             \x20         enc: |\n\
+             \x20           -----BEGIN AGE ENCRYPTED FILE-----\n\
+             \x20           YWJj\n\
+             \x20           -----END AGE ENCRYPTED FILE-----\n\
+             \x20   lastmodified: \"2024-05-01T12:30:00Z\"\n\
+             \x20   mac: ENC[mac]\n\
+             \x20   unencrypted_suffix: _unencrypted\n\
+             \x20   version: 3.9.0\n"
+        );
+    }
+
+    #[test]
+    fn json_keeps_values_in_order() {
+        let (b, a) = (String::from("b"), String::from("a"));
+        let values = vec![(&b, String::from("ENC[b]")), (&a, String::from("ENC[a]"))];
+        let kms = vec![(String::from("arn:aws:kms:k"), String::from("YmxvYg=="))];
+        let out = render_json(&values, &[], &kms, LASTMODIFIED, "ENC[mac]");
+        assert!(out.find("\"b\"").unwrap() < out.find("\"a\"").unwrap());
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["a"], "ENC[a]");
+        assert_eq!(parsed["sops"]["kms"][0]["arn"], "arn:aws:kms:k");
+        assert_eq!(parsed["sops"]["mac"], "ENC[mac]");
+        assert_eq!(parsed["sops"]["lastmodified"], LASTMODIFIED);
+    }
+}
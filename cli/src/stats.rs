@@ -0,0 +1,149 @@
+//! Statistics report for a generated batch, printed with `--stats`.
+//!
+//! The report summarizes what a reviewer would otherwise check by hand before handing a
+//! batch out: how the characters are spread over the classes, how long the passwords
+//! are, whether any repeat, how much entropy the weakest one has, and how many satisfy
+//! every constraint the batch was generated with. Both `--stats` and `--verbose` also
+//! show how the batch was generated: the candidates it took, what rejected them, the
+//! random bytes drawn, and the time spent.
+
+use crate::i18n::{tr, trf};
+use penguin::mixer::{CharClass, PenguinMixer};
+use penguin::{strength, GenerationStats};
+use std::collections::{BTreeMap, HashSet};
+
+const CLASSES: [(CharClass, &str); 4] = [
+    (CharClass::Lowercase, "lowercase"),
+    (CharClass::Uppercase, "uppercase"),
+    (CharClass::Digits, "digits"),
+    (CharClass::Specials, "specials"),
+];
+
+/// Width of the longest bar in the length histogram.
+const BAR_WIDTH: usize = 40;
+
+/// Renders the statistics for the batch, checking compliance against the mixer it came from.
+pub fn report(passwords: &[String], mixer: &PenguinMixer) -> String {
+    let mut out = format!(
+        "\n> {}\n",
+        trf("Batch statistics ({0} passwords):", &[&passwords.len()])
+    );
+    if passwords.is_empty() {
+        return out;
+    }
+
+    let total_chars: usize = passwords.iter().map(|p| p.chars().count()).sum();
+    out.push_str(&format!("   {}\n", tr("character classes:")));
+    for (class, name) in CLASSES {
+        let chars = passwords
+            .iter()
+            .flat_map(|p| p.chars())
+            .filter(|&c| CharClass::of(c) == Some(class))
+            .count();
+        let containing = passwords
+            .iter()
+            .filter(|p| p.chars().any(|c| CharClass::of(c) == Some(class)))
+            .count();
+        out.push_str(&format!(
+            "      {:<10} {:>5.1}% of characters, in {:>5.1}% of passwords\n",
+            name,
+            percent(chars, total_chars),
+            percent(containing, passwords.len())
+        ));
+    }
+    let other = passwords
+        .iter()
+        .flat_map(|p| p.chars())
+        .filter(|&c| CharClass::of(c).is_none())
+        .count();
+    if other > 0 {
+        out.push_str(&format!(
+            "      {:<10} {:>5.1}% of characters\n",
+            "other",
+            percent(other, total_chars)
+        ));
+    }
+
+    let mut lengths: BTreeMap<usize, usize> = BTreeMap::new();
+    for password in passwords {
+        *lengths.entry(password.chars().count()).or_default() += 1;
+    }
+    let most = lengths.values().copied().max().unwrap_or(1);
+    out.push_str(&format!("   {}\n", tr("length histogram:")));
+    for (length, count) in &lengths {
+        let bar = "#".repeat((count * BAR_WIDTH).div_ceil(most));
+        out.push_str(&format!("      {:>4} | {} {}\n", length, bar, count));
+    }
+
+    let mut seen = HashSet::new();
+    let duplicates = passwords.iter().filter(|p| !seen.insert(*p)).count();
+    out.push_str(&format!("   {}\n", trf("duplicates: {0}", &[&duplicates])));
+
+    let entropies: Vec<f64> = passwords
+        .iter()
+        .map(|p| strength::estimate_entropy(p).entropy_bits)
+        .collect();
+    let min = entropies.iter().copied().fold(f64::INFINITY, f64::min);
+    let avg = entropies.iter().sum::<f64>() / entropies.len() as f64;
+    out.push_str(&format!(
+        "   {}\n",
+        trf(
+            "entropy: min {0} bits, avg {1} bits",
+            &[&format!("{:.1}", min), &format!("{:.1}", avg)],
+        )
+    ));
+
+    let compliant = passwords.iter().filter(|p| mixer.complies(p)).count();
+    out.push_str(&format!(
+        "   {}\n",
+        trf(
+            "policy compliance: {0}/{1} ({2}%)",
+            &[
+                &compliant,
+                &passwords.len(),
+                &format!("{:.1}", percent(compliant, passwords.len())),
+            ],
+        )
+    ));
+    out
+}
+
+/// Renders how the batch was generated, printed with `--stats` or `--verbose`.
+pub fn generation(stats: &GenerationStats) -> String {
+    let mut out = format!("\n> {}\n", tr("Generation:"));
+    out.push_str(&format!(
+        "   {}\n",
+        trf(
+            "attempts: {0} for {1} passwords",
+            &[&stats.attempts, &stats.passwords]
+        )
+    ));
+    let rejections = if stats.rejections.is_empty() {
+        String::from("0")
+    } else {
+        stats
+            .rejections
+            .iter()
+            .map(|(reason, count)| format!("{} {}", count, reason))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    out.push_str(&format!("   {}\n", trf("rejections: {0}", &[&rejections])));
+    out.push_str(&format!(
+        "   {}\n",
+        trf("random bytes drawn: {0}", &[&stats.rng_bytes])
+    ));
+    out.push_str(&format!(
+        "   {}\n",
+        trf("elapsed: {0}", &[&format!("{:.2?}", stats.elapsed)])
+    ));
+    out
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
@@ -0,0 +1,47 @@
+//! Named presets for the generate command.
+//!
+//! A style bundles complexity, length, separator, and character-class choices into a
+//! recipe, so a sensible result doesn't require knowing every flag. Presets only fill in
+//! options that were not given on the command line; an explicit flag always wins.
+
+use clap::ValueEnum;
+
+use crate::GenerateArgs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Style {
+    /// Long run of whole words joined by hyphens, as in the xkcd comic
+    Xkcd,
+    /// 16 mixed characters with digits and symbols, accepted by typical password policies
+    Corporate,
+    /// 8 letters and digits for old systems that reject symbols and long passwords
+    Legacy,
+    /// The 64-character penguin complexity level
+    Maximum,
+}
+
+impl Style {
+    /// Fills in the options this style controls that were left unset.
+    pub fn apply(self, args: &mut GenerateArgs) {
+        let (complexity, length) = match self {
+            Style::Xkcd => ("basic", 28),
+            Style::Corporate => ("hard", 16),
+            Style::Legacy => ("hard", 8),
+            Style::Maximum => ("penguin", 64),
+        };
+        args.complexity
+            .get_or_insert_with(|| complexity.to_string());
+        args.length.get_or_insert(length);
+
+        match self {
+            Style::Xkcd => {
+                args.whole_words = true;
+                if args.separator_classes.is_empty() && args.separator_count.is_none() {
+                    args.separator.get_or_insert_with(|| "-".to_string());
+                }
+            }
+            Style::Legacy => args.no_special = true,
+            Style::Corporate | Style::Maximum => {}
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Reusable password shapes for `penguin generate --template`.
+//!
+//! Requirements that come up again and again, like a bank's, can be written down once in
+//! `$XDG_CONFIG_HOME/penguin/templates/<name>.toml` (`~/.config/penguin/templates` when
+//! the variable is unset) and used as `penguin g --template <name>`. A template holds a
+//! pattern the passwords are generated to match, a policy they are checked against, and
+//! any of a few generation settings:
+//!
+//! ```toml
+//! description = "Online banking"
+//! pattern = "[A-Za-z][A-Za-z0-9]{11,15}"
+//! length = 14
+//! safe_for = "shell"
+//!
+//! [policy]
+//! min_digits = 2
+//! forbidden = ["bank"]
+//! ```
+//!
+//! Like a style, a template only fills in options that were not given on the command
+//! line; an explicit flag always wins.
+
+use penguin::policy::PasswordPolicy;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::GenerateArgs;
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct File {
+    description: Option<String>,
+    pattern: Option<String>,
+    complexity: Option<String>,
+    length: Option<usize>,
+    whole_words: Option<bool>,
+    charset: Option<String>,
+    safe_for: Option<String>,
+    policy: Option<toml::Table>,
+}
+
+/// Returns the directory templates are loaded from, whether or not it exists.
+pub fn dir() -> Option<PathBuf> {
+    crate::config::path().and_then(|path| path.parent().map(|dir| dir.join("templates")))
+}
+
+/// Loads the named template and fills in the options it sets that were left unset.
+pub fn apply(name: &str, args: &mut GenerateArgs) {
+    let dir = dir().unwrap_or_else(|| {
+        crate::fail("cannot locate the config directory; set XDG_CONFIG_HOME or HOME")
+    });
+    let path = dir.join(format!("{}.toml", name));
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => crate::fail(format!(
+            "no template named '{}' (looked for {})",
+            name,
+            path.display()
+        )),
+        Err(err) => crate::fail(format!("cannot read {}: {}", path.display(), err)),
+    };
+    let file: File = toml::from_str(&contents)
+        .unwrap_or_else(|err| crate::fail(format!("invalid template {}: {}", path.display(), err)));
+    let invalid = |err: String| -> ! { crate::fail(format!("{}: {}", path.display(), err)) };
+    tracing::debug!(
+        template = name,
+        description = file.description.as_deref().unwrap_or_default(),
+        "loaded a template"
+    );
+
+    if args.pattern.is_none() {
+        args.pattern = file.pattern;
+    }
+    if args.complexity.is_none() {
+        args.complexity = file.complexity;
+    }
+    if args.length.is_none() {
+        args.length = file.length;
+    }
+    if file.whole_words == Some(true) {
+        args.whole_words = true;
+    }
+    if args.charset.is_none() {
+        if let Some(charset) = file.charset {
+            args.charset = Some(charset.parse().unwrap_or_else(|err| invalid(err)));
+        }
+    }
+    if args.safe_for.is_none() {
+        if let Some(format) = file.safe_for {
+            args.safe_for = Some(format.parse().unwrap_or_else(|err| invalid(err)));
+        }
+    }
+    if args.policy_file.is_none() && args.site.is_none() {
+        if let Some(policy) = file.policy {
+            let policy: PasswordPolicy = toml::to_string(&policy)
+                .map_err(|err| err.to_string())
+                .and_then(|text| {
+                    text.parse()
+                        .map_err(|err: penguin::policy::PolicyError| err.to_string())
+                })
+                .unwrap_or_else(|err| invalid(err));
+            args.template_policy = Some(policy);
+        }
+    }
+}
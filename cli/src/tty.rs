@@ -0,0 +1,44 @@
+//! The controlling terminal, for helpers whose stdin and stdout belong to another program.
+//!
+//! Git, ssh, and sudo run their helpers with pipes for stdin and stdout, so questions for
+//! the person at the keyboard go to `/dev/tty` (the console on Windows) instead.
+
+use std::io::{self, BufRead, Write};
+
+/// An open handle on the terminal.
+pub struct Terminal {
+    input: io::BufReader<std::fs::File>,
+    output: std::fs::File,
+}
+
+impl Terminal {
+    /// Opens the terminal, or returns `None` when the process has none.
+    pub fn open() -> Option<Self> {
+        let path = if cfg!(windows) { "CONIN$" } else { "/dev/tty" };
+        let input = std::fs::File::open(path).ok()?;
+        let output = std::fs::OpenOptions::new()
+            .write(true)
+            .open(if cfg!(windows) { "CONOUT$" } else { path })
+            .ok()?;
+        Some(Terminal {
+            input: io::BufReader::new(input),
+            output,
+        })
+    }
+
+    /// Writes text to the terminal, ignoring errors.
+    pub fn say(&mut self, text: &str) {
+        let _ = self.output.write_all(text.as_bytes());
+        let _ = self.output.flush();
+    }
+
+    /// Prints the prompt and returns the trimmed answer, or `None` at end of input.
+    pub fn ask(&mut self, prompt: &str) -> Option<String> {
+        self.say(prompt);
+        let mut answer = String::new();
+        match self.input.read_line(&mut answer) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(answer.trim().to_string()),
+        }
+    }
+}
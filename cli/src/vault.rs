@@ -0,0 +1,85 @@
+//! Writing generated passwords into HashiCorp Vault.
+//!
+//! With the `vault` feature, `--vault-write <path>#<key>` stores the password under the
+//! key of a KV version 2 secret, e.g. `secret/data/ci/db#password`. The server address and
+//! token come from `VAULT_ADDR` and `VAULT_TOKEN`, and `VAULT_NAMESPACE` is honoured for
+//! Vault Enterprise, just like the `vault` CLI.
+//!
+//! Existing secrets are patched, so other keys stored alongside survive; a secret that
+//! does not exist yet is created. With `--expires`, the expiry date also goes into the
+//! secret's custom metadata as `expires`.
+
+use serde_json::json;
+
+/// A KV v2 location given as `<path>#<key>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultTarget {
+    /// API path below `/v1/`, including the `data/` segment of KV v2 mounts.
+    pub path: String,
+    pub key: String,
+}
+
+/// Parses `<path>#<key>`; the key defaults to `password` when omitted.
+pub fn parse_target(value: &str) -> Result<VaultTarget, String> {
+    let (path, key) = value.split_once('#').unwrap_or((value, "password"));
+    let path = path.trim_matches('/');
+    if path.is_empty() || key.is_empty() {
+        return Err(String::from("expected <mount>/data/<path>#<key>"));
+    }
+    if !path.contains("/data/") {
+        let (mount, rest) = path.split_once('/').unwrap_or(("secret", path));
+        return Err(format!(
+            "'{}' is not a KV v2 data path (e.g. {}/data/{})",
+            path, mount, rest
+        ));
+    }
+    Ok(VaultTarget {
+        path: path.to_string(),
+        key: key.to_string(),
+    })
+}
+
+/// Writes the password to the target using the address and token from the environment,
+/// recording the expiry date, if any, in the secret's custom metadata.
+pub fn write(target: &VaultTarget, password: &str, expires: Option<&str>) -> Result<(), String> {
+    let address = std::env::var("VAULT_ADDR")
+        .map_err(|_| String::from("VAULT_ADDR must be set to write to Vault"))?;
+    let token = std::env::var("VAULT_TOKEN")
+        .map_err(|_| String::from("VAULT_TOKEN must be set to write to Vault"))?;
+    let url = format!("{}/v1/{}", address.trim_end_matches('/'), target.path);
+    let body = json!({ "data": { target.key.as_str(): password } });
+
+    let request = |method: &str, url: &str| {
+        let request = ureq::request(method, url).set("X-Vault-Token", &token);
+        match std::env::var("VAULT_NAMESPACE") {
+            Ok(namespace) => request.set("X-Vault-Namespace", &namespace),
+            Err(_) => request,
+        }
+    };
+
+    let patched = request("PATCH", &url)
+        .set("Content-Type", "application/merge-patch+json")
+        .send_string(&body.to_string());
+    let result = match patched {
+        Err(ureq::Error::Status(404, _)) => request("POST", &url).send_json(&body),
+        other => other,
+    };
+    let result = match (result, expires) {
+        (Ok(_), Some(expires)) => request("PATCH", &url.replacen("/data/", "/metadata/", 1))
+            .set("Content-Type", "application/merge-patch+json")
+            .send_string(&json!({ "custom_metadata": { "expires": expires } }).to_string()),
+        (result, _) => result,
+    };
+    match result {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(status, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            Err(format!(
+                "Vault rejected the write ({}): {}",
+                status,
+                detail.trim()
+            ))
+        }
+        Err(err) => Err(format!("cannot reach Vault: {}", err)),
+    }
+}
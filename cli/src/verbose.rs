@@ -0,0 +1,71 @@
+//! Per-password composition breakdown, printed with `--verbose`.
+//!
+//! When a site rejects a password it rarely says which rule failed. The breakdown lists,
+//! for each password, everything such a rule could look at: the length, how many
+//! characters of each class it holds, which base words went into it, how it was built,
+//! and its estimated entropy.
+
+use penguin::mixer::{CharClass, ComplexityLevel, PenguinMixer};
+use penguin::strength;
+
+/// Renders the breakdown of every password, numbered like the plain output.
+pub fn report(passwords: &[String], words: &[&str], mixer: &PenguinMixer) -> String {
+    let strategy = crate::audit::strategy(mixer);
+    let mut out = String::from("\n> Composition:\n");
+    for (i, password) in passwords.iter().enumerate() {
+        let count = |class| {
+            password
+                .chars()
+                .filter(|&c| CharClass::of(c) == Some(class))
+                .count()
+        };
+        let used = words_used(password, words, mixer);
+        out.push_str(&format!(
+            "   {}. length {}: {} lowercase, {} uppercase, {} digits, {} specials\n",
+            i + 1,
+            password.chars().count(),
+            count(CharClass::Lowercase),
+            count(CharClass::Uppercase),
+            count(CharClass::Digits),
+            count(CharClass::Specials),
+        ));
+        out.push_str(&format!(
+            "      strategy: {}, words: {}\n",
+            strategy,
+            if used.is_empty() {
+                String::from("none")
+            } else {
+                used.join(", ")
+            }
+        ));
+        out.push_str(&format!(
+            "      entropy: {:.1} bits\n",
+            strength::estimate_entropy(password).entropy_bits
+        ));
+    }
+    out.push('\n');
+    out
+}
+
+/// The base words that went into a password. Whole words count when they appear in it;
+/// mixed characters are drawn from every word, so a word counts when any of its letters
+/// made it in.
+fn words_used<'a>(password: &str, words: &[&'a str], mixer: &PenguinMixer) -> Vec<&'a str> {
+    if matches!(mixer.complexity, ComplexityLevel::Penguin) {
+        return Vec::new();
+    }
+    let lowered = password.to_lowercase();
+    words
+        .iter()
+        .copied()
+        .filter(|word| {
+            let word = word.to_lowercase();
+            if mixer.use_whole_words {
+                lowered.contains(&word)
+            } else {
+                word.chars()
+                    .any(|c| c.is_alphabetic() && lowered.contains(c))
+            }
+        })
+        .collect()
+}
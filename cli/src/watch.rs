@@ -0,0 +1,77 @@
+//! The `--watch` mode of `penguin generate`, for kiosks, demos, and brainstorming.
+//!
+//! The screen is cleared and a fresh batch drawn every interval, or as soon as a key is
+//! pressed when stdin is a terminal; `q`, Escape, or Ctrl-C stop it. Nothing shown is
+//! recorded in the history, the audit log, or receipts, since none of it is meant to be
+//! kept.
+
+use console::{Key, Term};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Parses an interval written as `30s`, `5m`, or `1h`, or as a number of seconds.
+pub fn parse_interval(value: &str) -> Result<Duration, String> {
+    let invalid = || {
+        format!(
+            "invalid interval '{}'; use seconds, minutes, or hours as in 30s, 5m, or 1h",
+            value
+        )
+    };
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (count, unit) = value.split_at(split);
+    let count: u64 = count.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "" | "s" => count,
+        "m" => count * 60,
+        "h" => count * 3600,
+        _ => return Err(invalid()),
+    };
+    if seconds == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Clears the screen and calls `draw` every interval and on every keypress, until the
+/// user quits or `draw` can no longer write, as when the reader of a pipe goes away.
+pub fn run(interval: Duration, mut draw: impl FnMut() -> std::io::Result<()>) -> ! {
+    let term = Term::stdout();
+    let keys = term.is_term().then(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let term = Term::stdout();
+            while let Ok(key) = term.read_key_raw() {
+                if sender.send(key).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
+    });
+
+    loop {
+        let _ = term.clear_screen();
+        if draw().is_err() {
+            std::process::exit(0);
+        }
+        if keys.is_some() {
+            eprintln!(
+                "\n{}",
+                crate::i18n::tr("Press any key for new passwords, or q to quit")
+            );
+        }
+        let key = match &keys {
+            Some(keys) => keys.recv_timeout(interval).ok(),
+            None => {
+                std::thread::sleep(interval);
+                None
+            }
+        };
+        if matches!(key, Some(Key::Char('q') | Key::Escape | Key::CtrlC)) {
+            let _ = term.clear_screen();
+            std::process::exit(0);
+        }
+    }
+}
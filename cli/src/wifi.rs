@@ -0,0 +1,94 @@
+//! WiFi passphrase generation and `WIFI:` QR payloads.
+//!
+//! WPA passphrases must be 8 to 63 printable ASCII characters. The payload follows the
+//! `WIFI:S:<ssid>;T:<security>;P:<password>;;` convention that phone cameras recognise,
+//! so guests can join a network by scanning a code instead of typing the passphrase.
+
+use clap::ValueEnum;
+use penguin::mixer::CharClass;
+use rand::Rng;
+
+/// Network security types supported by the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Security {
+    Wpa,
+    Wpa2,
+    Wpa3,
+    /// Open network without a passphrase
+    None,
+}
+
+/// Passphrase length limits imposed by WPA.
+const MIN_LENGTH: usize = 8;
+const MAX_LENGTH: usize = 63;
+
+/// Generates a passphrase and prints it along with the scannable payload.
+pub fn run(ssid: &str, security: Security, length: usize, hidden: bool, qr: bool) {
+    let passphrase = match security {
+        Security::None => None,
+        _ => {
+            if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+                crate::fail(format!(
+                    "WPA passphrases must be between {} and {} characters long",
+                    MIN_LENGTH, MAX_LENGTH
+                ));
+            }
+            Some(passphrase(length))
+        }
+    };
+
+    let payload = payload(ssid, security, passphrase.as_deref(), hidden);
+    if let Some(passphrase) = &passphrase {
+        println!("passphrase: {}", passphrase);
+    }
+    println!("payload:    {}", payload);
+
+    if qr {
+        #[cfg(feature = "qr")]
+        println!("\n{}", crate::output::qr_code(&payload));
+    }
+}
+
+/// Draws a passphrase from letters, digits, and the mixer's special characters, all of
+/// which are printable ASCII.
+fn passphrase(length: usize) -> String {
+    let chars: Vec<char> = [
+        CharClass::Lowercase,
+        CharClass::Uppercase,
+        CharClass::Digits,
+        CharClass::Specials,
+    ]
+    .iter()
+    .flat_map(|class| class.chars().chars())
+    .collect();
+
+    let mut rng = penguin::entropy::rng();
+    (0..length)
+        .map(|_| chars[rng.gen_range(0..chars.len())])
+        .collect()
+}
+
+fn payload(ssid: &str, security: Security, passphrase: Option<&str>, hidden: bool) -> String {
+    let mut payload = format!("WIFI:S:{};", escape(ssid));
+    match (security, passphrase) {
+        (Security::None, _) | (_, None) => payload.push_str("T:nopass;"),
+        (_, Some(passphrase)) => payload.push_str(&format!("T:WPA;P:{};", escape(passphrase))),
+    }
+    if hidden {
+        payload.push_str("H:true;");
+    }
+    payload.push(';');
+    payload
+}
+
+/// Backslash-escapes the characters that delimit fields in the payload.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
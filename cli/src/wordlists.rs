@@ -0,0 +1,69 @@
+//! The `penguin wordlist` commands for checking and cleaning up word lists.
+//!
+//! `lint` reports what weakens a list: entries that repeat once case and accents are
+//! ignored, words outside the wanted lengths, and how much entropy per word that costs.
+//! It exits with status 1 when it finds anything, so it can guard a list in CI.
+//! `normalize` prints the cleaned-up list.
+
+use penguin::wordlist::{normalize_word, Wordlist};
+use std::path::Path;
+
+/// How many duplicate groups are listed before the rest are only counted.
+const SHOWN_DUPLICATES: usize = 20;
+
+fn load(path: &Path) -> Wordlist {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| crate::fail(format!("cannot read {}: {}", path.display(), err)));
+    Wordlist::parse(&contents)
+}
+
+/// Prints a report on the list at `path` and exits with status 1 when it has problems.
+pub fn lint(path: &Path, min_length: usize, max_length: Option<usize>) {
+    let list = load(path);
+    let max = max_length.unwrap_or(usize::MAX);
+    let fits = |word: &str| (min_length..=max).contains(&word.chars().count());
+    let duplicates = list.duplicates();
+    let repeats: usize = duplicates.iter().map(|group| group.len() - 1).sum();
+    let outside = list
+        .words()
+        .iter()
+        .filter(|word| !fits(&normalize_word(word)))
+        .count();
+    let clean = list.normalize(min_length..=max);
+
+    println!("{}: {} entries", path.display(), list.len());
+    println!(
+        "   duplicates: {} words repeat, adding {} entries",
+        duplicates.len(),
+        repeats
+    );
+    for group in duplicates.iter().take(SHOWN_DUPLICATES) {
+        println!("      {}: {}", normalize_word(group[0]), group.join(", "));
+    }
+    if duplicates.len() > SHOWN_DUPLICATES {
+        println!("      ... and {} more", duplicates.len() - SHOWN_DUPLICATES);
+    }
+    let bounds = match max_length {
+        Some(max) => format!("{}-{}", min_length, max),
+        None => format!("{}+", min_length),
+    };
+    println!("   outside {} characters: {}", bounds, outside);
+    println!(
+        "   entropy per word: {:.2} bits as listed, {:.2} bits really, {:.2} bits normalized ({} words)",
+        list.entropy_bits(),
+        list.effective_entropy_bits(),
+        clean.entropy_bits(),
+        clean.len()
+    );
+    if repeats > 0 || outside > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Prints the normalized list at `path`, one word per line.
+pub fn normalize(path: &Path, min_length: usize, max_length: Option<usize>) {
+    let max = max_length.unwrap_or(usize::MAX);
+    for word in load(path).normalize(min_length..=max).words() {
+        println!("{}", word);
+    }
+}
@@ -0,0 +1,31 @@
+//! Generates passwords with arbitrary mixer settings and base words, including huge
+//! and empty words, control characters, multi-byte characters, and zero lengths, and
+//! checks that every password returned honors the settings that must always hold.
+
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use penguin::mixer::PenguinMixer;
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Unstructured::new(data);
+    let (Ok(mixer), Ok(words)) = (
+        PenguinMixer::arbitrary(&mut input),
+        Vec::<String>::arbitrary(&mut input),
+    ) else {
+        return;
+    };
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+    let Ok(password) = mixer.mix_password(&words) else {
+        return;
+    };
+    let lowered = password.to_lowercase();
+    for substring in mixer.forbidden_substrings.iter().filter(|s| !s.is_empty()) {
+        assert!(!lowered.contains(&substring.to_lowercase()));
+    }
+    if let Some(policy) = &mixer.policy {
+        assert!(policy.check(&password));
+    }
+});
@@ -0,0 +1,35 @@
+//! Parses arbitrary text as a policy file and as site rules, and checks arbitrary
+//! passwords against arbitrary policies, none of which may panic.
+
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use penguin::mixer::PenguinMixer;
+use penguin::policy::PasswordPolicy;
+use penguin::siterules::{self, SiteRules};
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Unstructured::new(data);
+    let Ok(text) = <&str>::arbitrary(&mut input) else {
+        return;
+    };
+    let _ = text.parse::<PasswordPolicy>();
+    let _ = siterules::parse(text);
+    let mut sites = SiteRules::default();
+    if sites.extend(text).is_ok() {
+        let _ = sites.get(text);
+    }
+
+    let (Ok(policy), Ok(mut mixer)) = (
+        PasswordPolicy::arbitrary(&mut input),
+        PenguinMixer::arbitrary(&mut input),
+    ) else {
+        return;
+    };
+    let _ = policy.check(text);
+    policy.configure(&mut mixer);
+    if let Ok(password) = mixer.mix_password(&[text]) {
+        assert!(policy.check(&password));
+    }
+});
@@ -0,0 +1,130 @@
+//! Recovery codes with a check character for the Penguin password generator.
+//!
+//! Backup codes get printed once, filed away, and typed back in months later, often by
+//! someone locked out of their account and in a hurry. Each code ends in a check
+//! character computed with the Luhn mod N algorithm over the code's alphabet, so a
+//! single mistyped character or a swap of two neighbors is caught by [`verify`] before
+//! the code is spent on the real service.
+//!
+//! Codes use uppercase letters and digits without the characters that are easily
+//! mistaken for one another, and are shown in hyphenated groups of four. Hyphens,
+//! spaces, and case are ignored when a code is verified.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::backupcode::{generate, verify, CodeError};
+//!
+//! let code = generate(8);
+//! assert_eq!(code.len(), 11);
+//! assert_eq!(verify(&code), Ok(()));
+//! assert_eq!(verify(&code.to_lowercase().replace('-', " ")), Ok(()));
+//!
+//! assert_eq!(verify("ACDE-FGHJ-H"), Ok(()));
+//! assert_eq!(verify("ACDE-FGHJ-K"), Err(CodeError::Checksum));
+//! assert_eq!(verify("ACDE-FGJH-H"), Err(CodeError::Checksum));
+//! assert_eq!(verify("ACDE-FGH0-H"), Err(CodeError::InvalidChar('0')));
+//! ```
+
+use rand::Rng;
+use std::fmt;
+
+/// Every character a code can use.
+pub const ALPHABET: &str = "ACDEFGHJKMNPQRTUVWXY34679";
+
+/// Number of characters between the hyphens of a displayed code.
+const GROUP_SIZE: usize = 4;
+
+/// The ways a code can fail verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    /// The code has no characters besides the check character.
+    TooShort,
+    /// The code contains a character outside [`ALPHABET`].
+    InvalidChar(char),
+    /// The check character does not match the rest of the code.
+    Checksum,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::TooShort => write!(f, "the code is too short"),
+            CodeError::InvalidChar(c) => write!(f, "'{}' never appears in a backup code", c),
+            CodeError::Checksum => {
+                write!(f, "the check character doesn't match; the code has a typo")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+/// Generates a code of `length` random characters followed by its check character,
+/// grouped with hyphens.
+pub fn generate(length: usize) -> String {
+    let alphabet: Vec<char> = ALPHABET.chars().collect();
+    let mut rng = crate::entropy::rng();
+    let mut code: String = (0..length)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+        .collect();
+    code.push(check_char(&code).unwrap_or_default());
+
+    let chars: Vec<char> = code.chars().collect();
+    chars
+        .chunks(GROUP_SIZE)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Returns the entropy of a code with `length` random characters, in bits. The check
+/// character adds none.
+pub fn entropy_bits(length: usize) -> f64 {
+    length as f64 * (ALPHABET.len() as f64).log2()
+}
+
+/// Computes the check character for the characters of a code, or `None` when one of
+/// them is outside [`ALPHABET`].
+///
+/// ```
+/// use penguin::backupcode::check_char;
+///
+/// assert_eq!(check_char("ACDEFGHJ"), Some('H'));
+/// assert_eq!(check_char("ACDEFGHO"), None);
+/// ```
+pub fn check_char(body: &str) -> Option<char> {
+    let sum = luhn_sum(body, 2).ok()?;
+    let n = ALPHABET.len();
+    ALPHABET.chars().nth((n - sum % n) % n)
+}
+
+/// Checks a code's check character, ignoring hyphens, spaces, and case.
+pub fn verify(code: &str) -> Result<(), CodeError> {
+    let code: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    if code.chars().count() < 2 {
+        return Err(CodeError::TooShort);
+    }
+    match luhn_sum(&code, 1)? % ALPHABET.len() {
+        0 => Ok(()),
+        _ => Err(CodeError::Checksum),
+    }
+}
+
+/// Sums the Luhn addends of `code` from the right, weighting the rightmost character
+/// by `factor` and alternating between doubling and not from there.
+fn luhn_sum(code: &str, mut factor: usize) -> Result<usize, CodeError> {
+    let n = ALPHABET.len();
+    let mut sum = 0;
+    for c in code.chars().rev() {
+        let value = ALPHABET.find(c).ok_or(CodeError::InvalidChar(c))?;
+        let addend = value * factor;
+        sum += addend / n + addend % n;
+        factor = 3 - factor;
+    }
+    Ok(sum)
+}
@@ -0,0 +1,176 @@
+//! Character sets for the random passwords of the Penguin password generator.
+//!
+//! A [`Charset`] is the list of characters [`random_password`](crate::random_password)
+//! draws from. Each character is listed once, so every one of them is equally likely, and
+//! every character of a password adds [`bits_per_char`](Charset::bits_per_char) bits of
+//! entropy.
+//!
+//! The presets cover the usual target formats, and parse from their names (`alphanumeric`,
+//! `base58`, `url-safe`, and `printable`):
+//!
+//! - [`Charset::ALPHANUMERIC`]: letters of both cases and digits, 62 characters.
+//! - [`Charset::BASE58`]: the Bitcoin base58 alphabet, which leaves out `0`, `O`, `I`, and
+//!   `l`, 58 characters.
+//! - [`Charset::URL_SAFE`]: the base64url alphabet, letters, digits, `-`, and `_`, which
+//!   need no escaping in URLs and file names, 64 characters.
+//! - [`Charset::PRINTABLE_ASCII`]: every printable ASCII character except space, 94
+//!   characters.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::Charset;
+//!
+//! let hex = Charset::new("0123456789abcdef");
+//! assert_eq!(hex.len(), 16);
+//! assert_eq!(hex.bits_per_char(), 4.0);
+//!
+//! // Repeated characters are only kept once.
+//! assert_eq!(Charset::new("aabbc").as_str(), "abc");
+//!
+//! let base58: Charset = "base58".parse().unwrap();
+//! assert_eq!(base58, Charset::BASE58);
+//! assert!(!base58.contains('0'));
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::mixer::CharClass;
+
+/// A set of characters to draw random passwords from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Charset {
+    chars: Cow<'static, str>,
+}
+
+impl Charset {
+    /// Letters of both cases and digits.
+    pub const ALPHANUMERIC: Charset =
+        Charset::preset("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789");
+
+    /// The Bitcoin base58 alphabet: alphanumerics without `0`, `O`, `I`, and `l`.
+    pub const BASE58: Charset =
+        Charset::preset("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+    /// The base64url alphabet: alphanumerics, `-`, and `_`.
+    pub const URL_SAFE: Charset =
+        Charset::preset("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+
+    /// Every printable ASCII character except space.
+    pub const PRINTABLE_ASCII: Charset = Charset::preset(
+        "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~",
+    );
+
+    /// The presets with the names they parse from.
+    pub const PRESETS: [(&'static str, Charset); 4] = [
+        ("alphanumeric", Charset::ALPHANUMERIC),
+        ("base58", Charset::BASE58),
+        ("url-safe", Charset::URL_SAFE),
+        ("printable", Charset::PRINTABLE_ASCII),
+    ];
+
+    /// A charset of characters known to be listed once each.
+    const fn preset(chars: &'static str) -> Self {
+        Charset {
+            chars: Cow::Borrowed(chars),
+        }
+    }
+
+    /// Creates a charset of the given characters, keeping the first occurrence of each.
+    pub fn new(chars: &str) -> Self {
+        let mut unique = String::with_capacity(chars.len());
+        for c in chars.chars() {
+            if !unique.contains(c) {
+                unique.push(c);
+            }
+        }
+        Charset {
+            chars: Cow::Owned(unique),
+        }
+    }
+
+    /// Creates a charset of every character of the given classes.
+    ///
+    /// ```
+    /// use penguin::{mixer::CharClass, Charset};
+    ///
+    /// let charset = Charset::from_classes(&[CharClass::Uppercase, CharClass::Digits]);
+    /// assert_eq!(charset.len(), 36);
+    /// ```
+    pub fn from_classes(classes: &[CharClass]) -> Self {
+        let chars: String = classes.iter().map(|class| class.chars()).collect();
+        Charset::new(&chars)
+    }
+
+    /// Returns the characters of the set, in order.
+    pub fn as_str(&self) -> &str {
+        &self.chars
+    }
+
+    /// Returns the number of characters in the set.
+    pub fn len(&self) -> usize {
+        self.chars.chars().count()
+    }
+
+    /// Returns whether the set has no characters.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns whether the set contains a character.
+    pub fn contains(&self, c: char) -> bool {
+        self.chars.contains(c)
+    }
+
+    /// Returns the entropy, in bits, of one character drawn at random from the set.
+    pub fn bits_per_char(&self) -> f64 {
+        (self.len() as f64).log2().max(0.0)
+    }
+}
+
+/// The characters of the penguin complexity level: letters of both cases, digits, and the
+/// default specials.
+impl Default for Charset {
+    fn default() -> Self {
+        Charset::from_classes(&[
+            CharClass::Lowercase,
+            CharClass::Uppercase,
+            CharClass::Digits,
+            CharClass::Specials,
+        ])
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.chars)
+    }
+}
+
+/// Parses the name of a preset, ignoring case; `printable-ascii` and `urlsafe` are
+/// accepted too.
+impl FromStr for Charset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = match s.to_lowercase().as_str() {
+            "printable-ascii" => String::from("printable"),
+            "urlsafe" => String::from("url-safe"),
+            name => name.to_string(),
+        };
+        Charset::PRESETS
+            .into_iter()
+            .find(|(preset, _)| *preset == name)
+            .map(|(_, charset)| charset)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Charset::PRESETS.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "unknown charset '{}'; expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,120 @@
+//! Date detection for the Penguin password generator.
+//!
+//! Birthdays, anniversaries, and years are among the first digits people put in a
+//! password, so cracking tools try them long before other numbers. [`find_dates`] looks
+//! at every run of four digits for a year from 1900 to 2099, a day followed by its month
+//! (`DDMM`), and a month followed by a two-digit year (`MMYY`).
+//!
+//! Random digits hit one of those shapes fairly often, since any run starting with `01`
+//! to `12` reads as a month and year. Avoiding them costs a little entropy in exchange
+//! for digits that never look like a date to a guesser.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::dates::{contains_date, find_dates, DateKind};
+//!
+//! let dates = find_dates("glacier2512-1987");
+//! assert_eq!(dates.len(), 2);
+//! assert_eq!((dates[0].text.as_str(), dates[0].kind), ("2512", DateKind::DayMonth));
+//! assert_eq!((dates[1].text.as_str(), dates[1].kind), ("1987", DateKind::Year));
+//! assert!(!contains_date("glacier-4471"));
+//! ```
+
+use std::fmt;
+
+/// How many digits every date shape spans.
+const DATE_DIGITS: usize = 4;
+
+/// The shape that makes a run of digits look like a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateKind {
+    /// A year from 1900 to 2099.
+    Year,
+    /// A day of the month followed by the month, like `2512`.
+    DayMonth,
+    /// A month followed by a two-digit year, like `0387`.
+    MonthYear,
+}
+
+impl fmt::Display for DateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DateKind::Year => "year",
+            DateKind::DayMonth => "day and month",
+            DateKind::MonthYear => "month and year",
+        })
+    }
+}
+
+/// Digits in a password that read as a date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateMatch {
+    /// Where the digits start in the password, counted in characters.
+    pub start: usize,
+    /// The digits, as they appear.
+    pub text: String,
+    /// The shape they match.
+    pub kind: DateKind,
+}
+
+/// Returns the date-like runs of four digits in a password, in order and without
+/// overlaps. Where a run matches more than one shape, a year is preferred over a day and
+/// month, and that over a month and year.
+///
+/// ```
+/// use penguin::dates::{find_dates, DateKind};
+///
+/// assert_eq!(find_dates("x2023")[0].kind, DateKind::Year);
+/// assert_eq!(find_dates("0599!")[0].kind, DateKind::MonthYear);
+/// assert!(find_dates("3213").is_empty());
+/// assert!(find_dates("20-23").is_empty());
+/// ```
+pub fn find_dates(password: &str) -> Vec<DateMatch> {
+    let chars: Vec<char> = password.chars().collect();
+    let mut dates = Vec::new();
+    let mut start = 0;
+    while start + DATE_DIGITS <= chars.len() {
+        let window = &chars[start..start + DATE_DIGITS];
+        let kind = window
+            .iter()
+            .all(char::is_ascii_digit)
+            .then(|| date_kind(window))
+            .flatten();
+        match kind {
+            Some(kind) => {
+                dates.push(DateMatch {
+                    start,
+                    text: window.iter().collect(),
+                    kind,
+                });
+                start += DATE_DIGITS;
+            }
+            None => start += 1,
+        }
+    }
+    dates
+}
+
+/// Returns whether a password has digits that read as a date; see [`find_dates`].
+pub fn contains_date(password: &str) -> bool {
+    !find_dates(password).is_empty()
+}
+
+/// Returns the date shape four ASCII digits match, if any.
+fn date_kind(digits: &[char]) -> Option<DateKind> {
+    let pair = |i: usize| {
+        digits[i].to_digit(10).unwrap_or_default() * 10
+            + digits[i + 1].to_digit(10).unwrap_or_default()
+    };
+    let (first, second) = (pair(0), pair(2));
+    if first == 19 || first == 20 {
+        Some(DateKind::Year)
+    } else if (1..=31).contains(&first) && (1..=12).contains(&second) {
+        Some(DateKind::DayMonth)
+    } else if (1..=12).contains(&first) {
+        Some(DateKind::MonthYear)
+    } else {
+        None
+    }
+}
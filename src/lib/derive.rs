@@ -0,0 +1,548 @@
+//! Site passwords derived from a master password, the same on every run.
+//!
+//! Like LessPass, [`derive`] turns a master password, a site name, and a counter into a
+//! password without storing anything: the same inputs give the same password on any
+//! machine and in any version of penguin, and raising the counter rotates a site's
+//! password. PBKDF2-HMAC-SHA256 with [`ITERATIONS`] rounds stretches the master password
+//! into a 256-bit key salted with the site and counter, and that key seeds the ChaCha20
+//! keystream the characters are picked with. The site is trimmed and lowercased first,
+//! so `GitHub.com` and `github.com` share a password.
+//!
+//! The [`ComplexityLevel`] picks the character classes, each of which appears at least
+//! once: lowercase letters and digits for basic, uppercase letters as well for medium, and
+//! special characters too for hard and penguin, which is always 64 characters long. A
+//! [`PasswordPolicy`] shapes the password the way it does generated ones. Its minimums
+//! add classes and characters, a maximum caps its class and leaves it out at zero, its
+//! special characters replace the default ones, excluded characters and those outside
+//! its allowed set are never picked, and candidates that still fail its
+//! [`check`](PasswordPolicy::check) are drawn again from the keystream.
+//!
+//! Provisioning jobs that need many passwords from one secret use a stream instead:
+//! [`seed`] stretches the master password and site once, and [`derive_nth`] computes any
+//! password of the stream from the seed and its index without the ones before it, as
+//! each index keys a ChaCha20 stream of its own. A batch can be split into shards by
+//! index range, and a job that stops can resume where it left off.
+//!
+//! A typo in the master password gives a different password rather than an error, so
+//! [`verification`] makes a tag that can be saved on disk, and [`verify`] checks a
+//! master password against it before anything is derived. The tag is keyed with the
+//! master password the same way the passwords are, under a salt of its own, so it is no
+//! quicker to attack than a derived password.
+//!
+//! Everything a password depends on, from the KDF parameters to the alphabets and the way
+//! characters are picked and shuffled, is fixed here rather than borrowed from the mixer,
+//! so changes to generation never change a derived password. [`KNOWN_ANSWERS`] lists
+//! passwords every build of this [`VERSION`] derives, and [`KnownAnswer::passes`]
+//! checks one, so packagers and downstream ports can confirm that their build agrees.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::derive::{derive, DeriveOptions};
+//! use penguin::mixer::ComplexityLevel;
+//!
+//! let options = DeriveOptions::default();
+//! let password = derive("correct horse battery staple", "github.com", 1, &options).unwrap();
+//! assert_eq!(password, "@EjSY!6t*bP7V6wP");
+//! assert_eq!(derive("correct horse battery staple", " GitHub.com", 1, &options).unwrap(), password);
+//! assert_ne!(derive("correct horse battery staple", "github.com", 2, &options).unwrap(), password);
+//!
+//! let basic = DeriveOptions { complexity: ComplexityLevel::Basic, length: 10, ..options };
+//! let pin_like = derive("correct horse battery staple", "bank.example", 1, &basic).unwrap();
+//! assert_eq!(pin_like.len(), 10);
+//! assert!(pin_like.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+//! ```
+
+use crate::hex;
+use crate::mixer::{CharClass, ComplexityLevel, MIN_LENGTH};
+use crate::policy::PasswordPolicy;
+use crate::PenguinError;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::Sha256;
+use zeroize::{Zeroize, Zeroizing};
+
+/// The version of the derivation, named in its salts. A change to any derived password
+/// starts a new version, with known answers of its own.
+pub const VERSION: u32 = 1;
+
+/// The known answers of this version, in the format [`known_answers`] reads.
+pub const KNOWN_ANSWERS: &str = include_str!("data/derive/vectors-v1.txt");
+
+/// PBKDF2 rounds spent stretching the master password, as many as LessPass spends.
+pub const ITERATIONS: u32 = 100_000;
+
+/// Starts every salt, keeping penguin's keys apart from other uses of the same master
+/// password and naming the version of the derivation.
+const DOMAIN: &[u8] = b"penguin-derive-v1";
+
+/// Starts the salts of stream seeds, which are kept apart from those of single passwords.
+const STREAM_DOMAIN: &[u8] = b"penguin-stream-v1";
+
+/// Starts verification tags and their salts.
+const VERIFY_DOMAIN: &str = "penguin-verify-v1";
+
+/// Random bytes in the salt of a verification tag.
+const VERIFY_SALT_LEN: usize = 16;
+
+/// Candidates drawn before a policy is given up on.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// The characters of each class, in the order the classes are filled.
+const ALPHABETS: [(CharClass, &str); 4] = [
+    (CharClass::Lowercase, "abcdefghijklmnopqrstuvwxyz"),
+    (CharClass::Uppercase, "ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+    (CharClass::Digits, "0123456789"),
+    (CharClass::Specials, "!@#$%^&*"),
+];
+
+/// What a derived password looks like.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeriveOptions {
+    /// The character classes the password is made of.
+    pub complexity: ComplexityLevel,
+    /// Characters in the password, which the penguin level ignores.
+    pub length: usize,
+    /// Rules the password follows.
+    pub policy: Option<PasswordPolicy>,
+}
+
+/// Hard complexity and 16 characters.
+impl Default for DeriveOptions {
+    fn default() -> Self {
+        DeriveOptions {
+            complexity: ComplexityLevel::Hard,
+            length: 16,
+            policy: None,
+        }
+    }
+}
+
+/// How a known answer is derived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// With [`derive`], taking the index as the counter.
+    Counter,
+    /// With [`derive_nth`], from the [`seed`] of the master password and site.
+    Stream,
+}
+
+/// A password that given inputs derive on every build of this version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownAnswer {
+    /// How the password is derived.
+    pub mode: Mode,
+    /// The master password.
+    pub master: String,
+    /// The site.
+    pub site: String,
+    /// The counter, or the index in the stream.
+    pub index: u64,
+    /// The complexity and length; known answers have no policy.
+    pub options: DeriveOptions,
+    /// The password the inputs derive.
+    pub expected: String,
+}
+
+impl KnownAnswer {
+    /// Derives the password on this build.
+    pub fn derive(&self) -> Result<String, PenguinError> {
+        match self.mode {
+            Mode::Counter => {
+                let counter = u32::try_from(self.index).map_err(|_| {
+                    PenguinError::InvalidConfig(format!("counter {} is too large", self.index))
+                })?;
+                derive(&self.master, &self.site, counter, &self.options)
+            }
+            Mode::Stream => {
+                let seed = seed(&self.master, &self.site)?;
+                derive_nth(&seed, self.index, &self.options)
+            }
+        }
+    }
+
+    /// Whether this build derives the expected password.
+    pub fn passes(&self) -> bool {
+        self.derive()
+            .is_ok_and(|password| password == self.expected)
+    }
+}
+
+/// Reads known answers, one per line with the tab-separated fields described in
+/// [`KNOWN_ANSWERS`], skipping blank lines and those starting with `#`. Fails with
+/// [`PenguinError::InvalidConfig`] naming the first line that can't be read.
+///
+/// ```
+/// use penguin::derive::{known_answers, Mode, KNOWN_ANSWERS};
+///
+/// let answers = known_answers(KNOWN_ANSWERS).unwrap();
+/// assert!(answers.iter().any(|answer| answer.mode == Mode::Stream));
+/// assert!(answers.iter().all(|answer| answer.passes()));
+///
+/// let wrong = "counter\tmaster\tgithub.com\t1\thard\t16\tnot-the-password";
+/// assert!(!known_answers(wrong).unwrap()[0].passes());
+/// assert!(known_answers("counter\tmaster").is_err());
+/// ```
+pub fn known_answers(text: &str) -> Result<Vec<KnownAnswer>, PenguinError> {
+    let mut answers = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |details: &str| {
+            PenguinError::InvalidConfig(format!("known answer on line {}: {}", number + 1, details))
+        };
+        let fields: Vec<&str> = line.trim_end_matches('\r').split('\t').collect();
+        let [mode, master, site, index, complexity, length, expected] = fields[..] else {
+            return Err(invalid("expected 7 tab-separated fields"));
+        };
+        let mode = match mode {
+            "counter" => Mode::Counter,
+            "stream" => Mode::Stream,
+            _ => return Err(invalid("the mode must be counter or stream")),
+        };
+        let complexity = match complexity {
+            "basic" => ComplexityLevel::Basic,
+            "medium" => ComplexityLevel::Medium,
+            "hard" => ComplexityLevel::Hard,
+            "penguin" => ComplexityLevel::Penguin,
+            _ => return Err(invalid("unknown complexity")),
+        };
+        answers.push(KnownAnswer {
+            mode,
+            master: master.to_string(),
+            site: site.to_string(),
+            index: index
+                .parse()
+                .map_err(|_| invalid("the index is not a number"))?,
+            options: DeriveOptions {
+                complexity,
+                length: length
+                    .parse()
+                    .map_err(|_| invalid("the length is not a number"))?,
+                policy: None,
+            },
+            expected: expected.to_string(),
+        });
+    }
+    Ok(answers)
+}
+
+/// A class the password is drawn from.
+struct Class {
+    chars: Vec<char>,
+    /// Characters the password has at least.
+    min: usize,
+    /// Characters the password has at most.
+    max: usize,
+}
+
+/// Derives the password for the site and counter from the master password.
+///
+/// Fails with [`PenguinError::InvalidConfig`] when the master password or site is empty,
+/// when the length is below [`MIN_LENGTH`] or can't fit the classes, or when the policy
+/// can't be satisfied, and with [`PenguinError::Exhausted`] when no candidate passes the
+/// policy's check.
+pub fn derive(
+    master: &str,
+    site: &str,
+    counter: u32,
+    options: &DeriveOptions,
+) -> Result<String, PenguinError> {
+    check_inputs(master, site)?;
+    let (policy, classes, length) = prepare(options)?;
+    let mut key = key(master, site, &counter.to_be_bytes(), DOMAIN);
+    let mut rng = ChaCha20Rng::from_seed(key);
+    key.zeroize();
+    draw_checked(&mut rng, &policy, &classes, length)
+}
+
+/// Stretches the master password and site into the seed of their password stream,
+/// which [`derive_nth`] computes passwords from. The seed is wiped when dropped.
+///
+/// Fails with [`PenguinError::InvalidConfig`] when the master password or site is empty.
+pub fn seed(master: &str, site: &str) -> Result<Zeroizing<[u8; 32]>, PenguinError> {
+    check_inputs(master, site)?;
+    Ok(Zeroizing::new(key(master, site, &[], STREAM_DOMAIN)))
+}
+
+/// Derives the password at index `n` of the stream keyed by the seed, without deriving
+/// the ones before it. Any 32 secret bytes can be the seed; [`seed`] makes one from a
+/// master password and site.
+///
+/// Fails like [`derive`] when the options or policy can't be satisfied.
+///
+/// ```
+/// use penguin::derive::{derive_nth, seed, DeriveOptions};
+///
+/// let options = DeriveOptions::default();
+/// let seed = seed("correct horse battery staple", "provisioning.example").unwrap();
+/// let shard: Vec<String> = (1000..1003)
+///     .map(|n| derive_nth(&seed, n, &options).unwrap())
+///     .collect();
+/// assert_eq!(shard[1], derive_nth(&seed, 1001, &options).unwrap());
+/// assert_ne!(shard[0], shard[1]);
+/// assert!(shard.iter().all(|password| password.len() == 16));
+/// ```
+pub fn derive_nth(
+    seed: &[u8; 32],
+    n: u64,
+    options: &DeriveOptions,
+) -> Result<String, PenguinError> {
+    let (policy, classes, length) = prepare(options)?;
+    let mut rng = ChaCha20Rng::from_seed(*seed);
+    rng.set_stream(n);
+    draw_checked(&mut rng, &policy, &classes, length)
+}
+
+fn check_inputs(master: &str, site: &str) -> Result<(), PenguinError> {
+    let invalid = |details: &str| Err(PenguinError::InvalidConfig(String::from(details)));
+    if master.is_empty() {
+        return invalid("the master password is empty");
+    }
+    if site.trim().is_empty() {
+        return invalid("the site name is empty");
+    }
+    Ok(())
+}
+
+/// Returns the policy, the classes, and the length of the passwords the options
+/// describe, once they are known to be satisfiable.
+fn prepare(options: &DeriveOptions) -> Result<(PasswordPolicy, Vec<Class>, usize), PenguinError> {
+    let policy = options.policy.clone().unwrap_or_default();
+    policy
+        .validate()
+        .map_err(|err| PenguinError::InvalidConfig(err.to_string()))?;
+    let length = policy.clamp_length(match options.complexity {
+        ComplexityLevel::Penguin => 64,
+        _ => options.length,
+    });
+    if length < MIN_LENGTH {
+        return Err(PenguinError::InvalidConfig(format!(
+            "length must be at least {}, got {}",
+            MIN_LENGTH, length
+        )));
+    }
+    let classes = classes(options.complexity, &policy)?;
+    let needed: usize = classes.iter().map(|class| class.min).sum();
+    let room = classes
+        .iter()
+        .fold(0usize, |room, class| room.saturating_add(class.max));
+    if needed > length || room < length {
+        return Err(PenguinError::InvalidConfig(format!(
+            "the character classes can't fill exactly {} characters",
+            length
+        )));
+    }
+    Ok((policy, classes, length))
+}
+
+/// Draws candidates from the keystream until one passes the policy's check.
+fn draw_checked(
+    rng: &mut ChaCha20Rng,
+    policy: &PasswordPolicy,
+    classes: &[Class],
+    length: usize,
+) -> Result<String, PenguinError> {
+    for _ in 0..MAX_ATTEMPTS {
+        let mut candidate = draw(rng, classes, length);
+        if policy.check(&candidate) {
+            return Ok(candidate);
+        }
+        candidate.zeroize();
+    }
+    Err(PenguinError::Exhausted {
+        attempts: MAX_ATTEMPTS,
+        rejections: vec![("policy violation", MAX_ATTEMPTS)],
+    })
+}
+
+/// Stretches the master password into a key salted with the domain, the site, and
+/// what follows it.
+fn key(master: &str, site: &str, suffix: &[u8], domain: &[u8]) -> [u8; 32] {
+    let site = site.trim().to_lowercase();
+    let mut salt = Vec::with_capacity(domain.len() + 4 + site.len() + suffix.len());
+    salt.extend_from_slice(domain);
+    salt.extend_from_slice(&(site.len() as u32).to_be_bytes());
+    salt.extend_from_slice(site.as_bytes());
+    salt.extend_from_slice(suffix);
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(master.as_bytes(), &salt, ITERATIONS, &mut key);
+    key
+}
+
+/// Returns the classes of the level and those the policy asks for, with the characters
+/// the policy allows of each.
+fn classes(level: ComplexityLevel, policy: &PasswordPolicy) -> Result<Vec<Class>, PenguinError> {
+    let levels = match level {
+        ComplexityLevel::Basic => &[CharClass::Lowercase, CharClass::Digits][..],
+        ComplexityLevel::Medium => &[
+            CharClass::Lowercase,
+            CharClass::Uppercase,
+            CharClass::Digits,
+        ][..],
+        ComplexityLevel::Hard | ComplexityLevel::Penguin => &[
+            CharClass::Lowercase,
+            CharClass::Uppercase,
+            CharClass::Digits,
+            CharClass::Specials,
+        ][..],
+    };
+    let mut classes = Vec::new();
+    for (class, alphabet) in ALPHABETS {
+        let minimum = match class {
+            CharClass::Lowercase => policy.min_lowercase,
+            CharClass::Uppercase => policy.min_uppercase,
+            CharClass::Digits => policy.min_digits,
+            CharClass::Specials => policy.min_specials,
+        };
+        let maximum = policy.max_count(class).unwrap_or(usize::MAX);
+        if maximum == 0 || (minimum == 0 && !levels.contains(&class)) {
+            continue;
+        }
+        let alphabet = match (class, &policy.special_chars) {
+            (CharClass::Specials, Some(specials)) => specials.as_str(),
+            _ => alphabet,
+        };
+        let mut chars: Vec<char> = Vec::new();
+        for c in alphabet.chars() {
+            let usable = CharClass::of(c) == Some(class)
+                && !policy.excluded_chars.contains(c)
+                && policy
+                    .allowed
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(c));
+            if usable && !chars.contains(&c) {
+                chars.push(c);
+            }
+        }
+        if chars.is_empty() {
+            if minimum > 0 {
+                let name = match class {
+                    CharClass::Lowercase => "lowercase",
+                    CharClass::Uppercase => "uppercase",
+                    CharClass::Digits => "digit",
+                    CharClass::Specials => "special",
+                };
+                return Err(PenguinError::InvalidConfig(format!(
+                    "the policy leaves no usable {} characters",
+                    name
+                )));
+            }
+            continue;
+        }
+        classes.push(Class {
+            chars,
+            min: minimum.max(1),
+            max: maximum,
+        });
+    }
+    if classes.is_empty() {
+        return Err(PenguinError::InvalidConfig(String::from(
+            "the policy leaves no usable characters",
+        )));
+    }
+    Ok(classes)
+}
+
+/// Draws a candidate: the minimum of every class, then characters from every class below
+/// its maximum until the length is reached, then a shuffle.
+fn draw(rng: &mut ChaCha20Rng, classes: &[Class], length: usize) -> String {
+    let mut picked = Vec::with_capacity(length);
+    let mut counts: Vec<usize> = classes.iter().map(|class| class.min).collect();
+    for class in classes {
+        for _ in 0..class.min {
+            picked.push(class.chars[uniform(rng, class.chars.len())]);
+        }
+    }
+    while picked.len() < length {
+        let pool: Vec<(usize, char)> = classes
+            .iter()
+            .enumerate()
+            .filter(|(index, class)| counts[*index] < class.max)
+            .flat_map(|(index, class)| class.chars.iter().map(move |&c| (index, c)))
+            .collect();
+        let (index, c) = pool[uniform(rng, pool.len())];
+        counts[index] += 1;
+        picked.push(c);
+    }
+    for i in (1..picked.len()).rev() {
+        picked.swap(i, uniform(rng, i + 1));
+    }
+    picked.into_iter().collect()
+}
+
+/// Draws a number below `n` uniformly, throwing away the draws that would bias it.
+fn uniform(rng: &mut ChaCha20Rng, n: usize) -> usize {
+    let n = n as u64;
+    let limit = u64::MAX - u64::MAX % n;
+    loop {
+        let value = rng.next_u64();
+        if value < limit {
+            return (value % n) as usize;
+        }
+    }
+}
+
+/// Makes a tag that [`verify`] accepts only for this master password, such as
+/// `penguin-verify-v1:4f1c...:9a07...`, from a random salt and a key stretched like the ones
+/// passwords are derived from.
+///
+/// ```
+/// use penguin::derive::{verification, verify};
+///
+/// let tag = verification("correct horse battery staple");
+/// assert!(tag.starts_with("penguin-verify-v1:"));
+/// assert!(verify("correct horse battery staple", &tag).unwrap());
+/// assert!(!verify("correct horse battery stapel", &tag).unwrap());
+/// assert_ne!(tag, verification("correct horse battery staple"));
+/// assert!(verify("correct horse battery staple", "not a tag").is_err());
+/// ```
+pub fn verification(master: &str) -> String {
+    let mut salt = [0u8; VERIFY_SALT_LEN];
+    crate::entropy::rng().fill_bytes(&mut salt);
+    let mut tag = verification_key(master, &salt);
+    let encoded = format!(
+        "{}:{}:{}",
+        VERIFY_DOMAIN,
+        hex::encode(&salt),
+        hex::encode(&tag)
+    );
+    tag.zeroize();
+    encoded
+}
+
+/// Checks the master password against a tag from [`verification`], failing with
+/// [`PenguinError::InvalidConfig`] when the tag is malformed.
+pub fn verify(master: &str, tag: &str) -> Result<bool, PenguinError> {
+    let malformed = || PenguinError::InvalidConfig(String::from("not a penguin verification tag"));
+    let mut parts = tag.trim().split(':');
+    let (Some(VERIFY_DOMAIN), Some(salt), Some(expected), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(malformed());
+    };
+    let salt = hex::decode(salt).filter(|salt| salt.len() == VERIFY_SALT_LEN);
+    let Some(salt) = salt else {
+        return Err(malformed());
+    };
+    if expected.len() != 64 {
+        return Err(malformed());
+    }
+    let mut key = verification_key(master, &salt);
+    let mut actual = hex::encode(&key);
+    key.zeroize();
+    let matches = crate::compare(&actual, expected.to_ascii_lowercase());
+    actual.zeroize();
+    Ok(matches)
+}
+
+/// Stretches the master password into the key of a verification tag.
+fn verification_key(master: &str, salt: &[u8]) -> [u8; 32] {
+    let mut salted = Vec::with_capacity(VERIFY_DOMAIN.len() + salt.len());
+    salted.extend_from_slice(VERIFY_DOMAIN.as_bytes());
+    salted.extend_from_slice(salt);
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(master.as_bytes(), &salted, ITERATIONS, &mut key);
+    key
+}
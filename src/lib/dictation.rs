@@ -0,0 +1,65 @@
+//! Checks for passwords that will be read aloud over the phone.
+//!
+//! Support staff who read a password to a user spell it out character by character, and
+//! over a phone line several letter names are easy to mishear: "bee", "dee", "pee",
+//! "tee", and "vee" blur together, as do "em" and "en" or "ef" and "es". A listener can
+//! usually recover one unclear letter from context, but not two confusable letters in a
+//! row. The letter O and the digit zero are the other classic trap, since many people
+//! read both as "oh".
+//!
+//! [`is_dictation_safe`] rejects passwords with either problem and plugs into the
+//! mixer's rejection predicates.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::dictation;
+//! use penguin::mixer::{ComplexityLevel, PenguinMixer};
+//!
+//! let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+//! mixer.reject_if.push(|password| !dictation::is_dictation_safe(password));
+//! let password = mixer.mix_password(&["penguin"]).unwrap();
+//! assert!(dictation::is_dictation_safe(&password));
+//! ```
+
+/// Groups of letters whose spoken names are easily confused on a phone line.
+const SOUND_ALIKE: [&str; 7] = ["bdptv", "cz", "mn", "fsx", "ajk", "iy", "qu"];
+
+/// Returns whether two different letters have names that sound alike, ignoring case.
+///
+/// ```
+/// use penguin::dictation::sounds_alike;
+///
+/// assert!(sounds_alike('b', 'D'));
+/// assert!(!sounds_alike('b', 'b'));
+/// assert!(!sounds_alike('b', 'm'));
+/// ```
+pub fn sounds_alike(a: char, b: char) -> bool {
+    let (a, b) = (a.to_ascii_lowercase(), b.to_ascii_lowercase());
+    a != b
+        && SOUND_ALIKE
+            .iter()
+            .any(|group| group.contains(a) && group.contains(b))
+}
+
+/// Returns whether a password can be read aloud without likely mishearings.
+///
+/// A password is unsafe when two sound-alike letters are next to each other or when it
+/// mixes the letter O with the digit zero.
+///
+/// ```
+/// use penguin::dictation::is_dictation_safe;
+///
+/// assert!(is_dictation_safe("penguin42"));
+/// assert!(!is_dictation_safe("bdx7"));
+/// assert!(!is_dictation_safe("polar08"));
+/// ```
+pub fn is_dictation_safe(password: &str) -> bool {
+    let has_letter_o = password.contains(['o', 'O']);
+    if has_letter_o && password.contains('0') {
+        return false;
+    }
+
+    let chars: Vec<char> = password.chars().collect();
+    !chars.windows(2).any(|pair| sounds_alike(pair[0], pair[1]))
+}
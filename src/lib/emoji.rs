@@ -0,0 +1,75 @@
+//! Emoji passphrases for the Penguin password generator.
+//!
+//! An emoji passphrase is a handful of emoji picked uniformly from a curated set, with
+//! digits optionally mixed into the pool. A sequence of pictures is remarkably easy to
+//! remember, and several services accept them as passwords.
+//!
+//! The set only contains emoji that are a single code point with emoji presentation by
+//! default, so each one is exactly one grapheme on every platform: no skin-tone
+//! modifiers, variation selectors, or joiner sequences that a keyboard or a server
+//! might normalize differently. Look-alike pairs (dog face and poodle, peach and mango)
+//! are left out so the passphrase can be read back without doubt.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::emoji::generate;
+//!
+//! let passphrase = generate(6, false);
+//! assert_eq!(passphrase.symbols.len(), 6);
+//! assert!(passphrase.entropy_bits > 40.0);
+//! println!("{}", passphrase);
+//! ```
+
+use rand::Rng;
+use std::fmt;
+
+/// The curated emoji set.
+pub const EMOJI: &[&str] = &[
+    "🐶", "🐱", "🐭", "🐰", "🦊", "🐻", "🐼", "🐨", "🐯", "🦁", "🐮", "🐷", "🐸", "🐵", "🐔", "🐧",
+    "🦆", "🦅", "🦉", "🦇", "🐺", "🐴", "🦄", "🐝", "🐛", "🦋", "🐌", "🐞", "🐜", "🦂", "🐢", "🐍",
+    "🦎", "🦖", "🐙", "🦑", "🦐", "🦀", "🐠", "🐬", "🐳", "🦈", "🐊", "🦓", "🦍", "🐘", "🦛", "🦏",
+    "🐪", "🦒", "🦘", "🐑", "🦙", "🐐", "🦌", "🦃", "🦚", "🦜", "🦢", "🦩", "🦝", "🦨", "🦦", "🦥",
+    "🍎", "🍐", "🍊", "🍋", "🍌", "🍉", "🍇", "🍓", "🍒", "🍑", "🍍", "🥥", "🥝", "🍅", "🍆", "🥑",
+    "🥦", "🥕", "🌽", "🥔", "🍞", "🥐", "🥨", "🧀", "🥚", "🥞", "🥓", "🍔", "🍟", "🍕", "🌭", "🌮",
+    "🍿", "🍩", "🍪", "🎂", "🧁", "🍫", "🍭", "🍯", "⚽", "🏀", "🏈", "⚾", "🎾", "🎱", "🚗", "🚌",
+    "🚑", "🚒", "🚜", "🚲", "🚀", "🛸", "🚁", "⛵", "⚓", "🎸", "🎺", "🎻", "🥁", "🎲", "🎯", "🔑",
+    "🔔", "💡", "📷", "📚", "🌵", "🌻", "🍄", "🌈", "⛄", "🔥", "🌙",
+];
+
+const DIGITS: &[&str] = &["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// A generated emoji passphrase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmojiPassphrase {
+    /// The chosen symbols, one grapheme each.
+    pub symbols: Vec<&'static str>,
+    /// Total entropy of the passphrase in bits.
+    pub entropy_bits: f64,
+}
+
+impl fmt::Display for EmojiPassphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbols.concat())
+    }
+}
+
+/// Generates a passphrase of `count` symbols, drawn from the emoji set and, when
+/// `with_digits` is set, the digits 0 to 9 as well.
+pub fn generate(count: usize, with_digits: bool) -> EmojiPassphrase {
+    let pool: Vec<&'static str> = if with_digits {
+        EMOJI.iter().chain(DIGITS).copied().collect()
+    } else {
+        EMOJI.to_vec()
+    };
+
+    let mut rng = crate::entropy::rng();
+    let symbols = (0..count)
+        .map(|_| pool[rng.gen_range(0..pool.len())])
+        .collect();
+
+    EmojiPassphrase {
+        symbols,
+        entropy_bits: count as f64 * (pool.len() as f64).log2(),
+    }
+}
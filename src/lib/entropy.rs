@@ -0,0 +1,616 @@
+//! Sources of randomness for the Penguin password generator.
+//!
+//! Everything penguin draws at random, from the characters of a password to the
+//! coefficients of a secret share, comes from [`rng`]. It returns a generator backed by
+//! the active [`EntropySource`], so the guarantees in one place cover every generator in
+//! the crate, and [`EntropySource::backend`] says where the randomness really comes from.
+//!
+//! The system source is `OsRng`, which asks the operating system through `getrandom`
+//! for every draw, so no generator state lives in the process. In WebAssembly builds for
+//! the browser or Node.js (`wasm32-unknown-unknown`), `getrandom` calls the Web Crypto
+//! API's `crypto.getRandomValues`, and JavaScript callers can confirm which backend is
+//! active with the exported `entropyBackend()` function.
+//!
+//! The hardware source reads the CPU's RDSEED or RDRAND instructions on x86-64, or the
+//! kernel's `/dev/hwrng` device elsewhere, for compliance regimes that require
+//! hardware-derived entropy. [`set_source`] falls back to the system source on machines
+//! without one and returns the source it settled on.
+//!
+//! The `chacha20` source is a deterministic random bit generator with a construction
+//! simple enough to cite in a security review: the keystream of the ChaCha20 stream
+//! cipher (20 rounds, as in RFC 8439), keyed with 256 bits from the operating system and
+//! rekeyed with fresh ones after every [`reseed_interval`] bytes of output. One
+//! generator serves the whole process, so the interval counts every draw from it.
+//!
+//! [`with_rng`] hands a generator of the caller's own to everything run inside it, on
+//! the current thread, in place of the source, to make tests reproducible. It seeds a
+//! ChaCha20 keystream from that generator, so a seeded generator gives the same
+//! passwords every time, and one that is secure, such as `OsRng`, gives secure ones.
+//! Seeded passwords follow the mixer, so another version of penguin may give different
+//! ones for the same seed; those of `penguin::derive` stay the same across versions.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::entropy::{self, EntropySource};
+//! use rand::Rng;
+//!
+//! let roll = entropy::rng().gen_range(1..=6);
+//! assert!((1..=6).contains(&roll));
+//! assert_eq!(entropy::source(), EntropySource::System);
+//!
+//! let used = entropy::set_source(EntropySource::Hardware);
+//! assert_eq!(used == EntropySource::Hardware, entropy::hardware_available());
+//! assert!((1..=6).contains(&entropy::rng().gen_range(1..=6)));
+//!
+//! entropy::set_reseed_interval(1024);
+//! assert_eq!(entropy::set_source(EntropySource::ChaCha20), EntropySource::ChaCha20);
+//! assert_eq!(EntropySource::ChaCha20.backend(), "ChaCha20 DRBG reseeded from getrandom");
+//! assert!((1..=6).contains(&entropy::rng().gen_range(1..=6)));
+//! ```
+
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+/// Where the random numbers behind generated passwords come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropySource {
+    /// The operating system's random number generator, read for every draw.
+    System,
+    /// The machine's hardware random number generator, read directly for every draw.
+    Hardware,
+    /// A ChaCha20 keystream shared by the whole process and rekeyed from the operating
+    /// system every [`reseed_interval`] bytes.
+    ChaCha20,
+}
+
+impl EntropySource {
+    /// Returns the name of the source.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EntropySource::System => "system",
+            EntropySource::Hardware => "hw",
+            EntropySource::ChaCha20 => "chacha20",
+        }
+    }
+
+    /// Describes the generator that supplies the source's entropy on this platform.
+    ///
+    /// ```
+    /// use penguin::entropy::EntropySource;
+    ///
+    /// assert!(EntropySource::System.backend().contains("getrandom"));
+    /// ```
+    pub fn backend(&self) -> &'static str {
+        match self {
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            EntropySource::System => "getrandom (Web Crypto crypto.getRandomValues)",
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            EntropySource::System => "getrandom (operating system)",
+            EntropySource::Hardware => match hardware::detect() {
+                Some(generator) => generator.name(),
+                None => "unavailable",
+            },
+            EntropySource::ChaCha20 => "ChaCha20 DRBG reseeded from getrandom",
+        }
+    }
+}
+
+impl fmt::Display for EntropySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for EntropySource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "system" | "os" => Ok(EntropySource::System),
+            "hw" | "hardware" => Ok(EntropySource::Hardware),
+            "chacha20" => Ok(EntropySource::ChaCha20),
+            _ => Err(format!(
+                "unknown entropy source '{}'; expected system, hw, or chacha20",
+                s
+            )),
+        }
+    }
+}
+
+/// The source [`rng`] draws from, as its position in [`SOURCES`].
+static SOURCE: AtomicU8 = AtomicU8::new(0);
+
+const SOURCES: [EntropySource; 3] = [
+    EntropySource::System,
+    EntropySource::Hardware,
+    EntropySource::ChaCha20,
+];
+
+/// Returns the source that [`rng`] draws from.
+pub fn source() -> EntropySource {
+    SOURCES[usize::from(SOURCE.load(Ordering::Relaxed))]
+}
+
+/// Makes [`rng`] draw from `source` for the rest of the process, and returns the source
+/// it draws from, which is the system source when a hardware one was asked for but the
+/// machine has none.
+pub fn set_source(source: EntropySource) -> EntropySource {
+    let source = match source {
+        EntropySource::Hardware if !hardware_available() => EntropySource::System,
+        source => source,
+    };
+    let index = SOURCES
+        .iter()
+        .position(|known| *known == source)
+        .unwrap_or(0);
+    SOURCE.store(index as u8, Ordering::Relaxed);
+    source
+}
+
+/// Whether the machine has a hardware random number generator penguin can read.
+pub fn hardware_available() -> bool {
+    hardware::detect().is_some()
+}
+
+/// Bytes the `chacha20` source outputs between reseeds unless configured otherwise,
+/// matching the threshold of `rand`'s thread-local generator.
+pub const DEFAULT_RESEED_INTERVAL: u64 = 64 * 1024;
+
+/// Returns how many bytes the `chacha20` source outputs before it rekeys.
+pub fn reseed_interval() -> u64 {
+    drbg::INTERVAL.load(Ordering::Relaxed)
+}
+
+/// Makes the `chacha20` source rekey after every `bytes` bytes of output, and at least
+/// after every byte.
+pub fn set_reseed_interval(bytes: u64) {
+    drbg::INTERVAL.store(bytes.max(1), Ordering::Relaxed);
+}
+
+/// Returns a cryptographically secure generator backed by the active [`source`], or by
+/// the generator of the enclosing [`with_rng`].
+pub fn rng() -> EntropyRng {
+    if INJECTED.with(|injected| injected.borrow().is_some()) {
+        return EntropyRng {
+            inner: Inner::Injected,
+        };
+    }
+    let inner = match source() {
+        EntropySource::System => Inner::System(OsRng),
+        EntropySource::Hardware => match hardware::detect() {
+            Some(generator) => Inner::Hardware(generator),
+            None => Inner::System(OsRng),
+        },
+        EntropySource::ChaCha20 => Inner::ChaCha20,
+    };
+    EntropyRng { inner }
+}
+
+thread_local! {
+    /// Bytes drawn through [`rng`] on this thread.
+    static DRAWN: Cell<u64> = const { Cell::new(0) };
+
+    /// The keystream of the innermost [`with_rng`] on this thread.
+    static INJECTED: RefCell<Option<ChaCha20Rng>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with every draw through [`rng`] on this thread coming from a ChaCha20
+/// keystream seeded from `rng`, and returns what it returns. The generator is advanced
+/// by the 32 bytes of the seed. Calls nest, and the previous generator is back in place
+/// afterwards, even when `f` panics.
+///
+/// ```
+/// use penguin::entropy;
+/// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+///
+/// let mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+/// let generate = |seed| {
+///     let mut rng = ChaCha20Rng::seed_from_u64(seed);
+///     entropy::with_rng(&mut rng, || mixer.mix_password(&["glacier"]).unwrap())
+/// };
+/// assert_eq!(generate(7), generate(7));
+/// assert_ne!(generate(7), generate(8));
+/// ```
+pub fn with_rng<R: RngCore + CryptoRng, T>(rng: &mut R, f: impl FnOnce() -> T) -> T {
+    /// Puts the previous generator back when dropped.
+    struct Restore(Option<ChaCha20Rng>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            INJECTED.with(|injected| *injected.borrow_mut() = previous);
+        }
+    }
+
+    let mut seed = <ChaCha20Rng as SeedableRng>::Seed::default();
+    rng.fill_bytes(&mut seed);
+    let seeded = ChaCha20Rng::from_seed(seed);
+    let _restore = Restore(INJECTED.with(|injected| injected.borrow_mut().replace(seeded)));
+    f()
+}
+
+/// Returns how many random bytes generators from [`rng`] have handed out on the current
+/// thread, so the cost of an operation is the difference between two readings.
+///
+/// ```
+/// use penguin::entropy;
+/// use rand::RngCore;
+///
+/// let before = entropy::bytes_drawn();
+/// entropy::rng().fill_bytes(&mut [0u8; 48]);
+/// assert_eq!(entropy::bytes_drawn() - before, 48);
+/// ```
+pub fn bytes_drawn() -> u64 {
+    DRAWN.with(Cell::get)
+}
+
+fn count(bytes: usize) {
+    DRAWN.with(|drawn| drawn.set(drawn.get() + bytes as u64));
+}
+
+/// A cryptographically secure random number generator returned by [`rng`].
+#[derive(Clone, Debug)]
+pub struct EntropyRng {
+    inner: Inner,
+}
+
+#[derive(Clone, Debug)]
+enum Inner {
+    System(OsRng),
+    Hardware(hardware::Generator),
+    ChaCha20,
+    Injected,
+}
+
+impl RngCore for EntropyRng {
+    fn next_u32(&mut self) -> u32 {
+        match &mut self.inner {
+            Inner::System(rng) => {
+                count(4);
+                rng.next_u32()
+            }
+            Inner::Hardware(_) | Inner::ChaCha20 | Inner::Injected => {
+                let mut bytes = [0u8; 4];
+                self.fill_bytes(&mut bytes);
+                u32::from_le_bytes(bytes)
+            }
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match &mut self.inner {
+            Inner::System(rng) => {
+                count(8);
+                rng.next_u64()
+            }
+            Inner::Hardware(_) | Inner::ChaCha20 | Inner::Injected => {
+                let mut bytes = [0u8; 8];
+                self.fill_bytes(&mut bytes);
+                u64::from_le_bytes(bytes)
+            }
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(err) = self.try_fill_bytes(dest) {
+            // Like `OsRng`, fail loudly; quietly switching generators would defeat
+            // choosing one.
+            panic!("cannot draw random bytes: {}", err);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        count(dest.len());
+        match &mut self.inner {
+            Inner::System(rng) => rng.try_fill_bytes(dest),
+            Inner::Hardware(generator) => generator.fill(dest).map_err(rand::Error::new),
+            Inner::ChaCha20 => drbg::fill(dest),
+            Inner::Injected => INJECTED.with(|injected| match injected.borrow_mut().as_mut() {
+                Some(rng) => rng.try_fill_bytes(dest),
+                // Only reachable by keeping the generator past its `with_rng`.
+                None => OsRng.try_fill_bytes(dest),
+            }),
+        }
+    }
+}
+
+impl CryptoRng for EntropyRng {}
+
+/// Bytes drawn from the active source by [`health_check`].
+const HEALTH_SAMPLE_BYTES: usize = 4096;
+
+/// The outcome of [`health_check`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// The source that was checked.
+    pub source: EntropySource,
+    /// Whether the operating system's generator answered with nonzero bytes.
+    pub os_available: bool,
+    /// How long drawing the sample from the source took.
+    pub latency: Duration,
+    /// How many bits of the sample were ones, out of `8 * 4096`.
+    pub ones: usize,
+    /// What is wrong with the source, empty when it is healthy.
+    pub problems: Vec<String>,
+}
+
+impl HealthReport {
+    /// Whether every check passed.
+    pub fn is_healthy(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}): {} bytes in {:?}, {} of {} bits set",
+            self.source,
+            self.source.backend(),
+            HEALTH_SAMPLE_BYTES,
+            self.latency,
+            self.ones,
+            HEALTH_SAMPLE_BYTES * 8
+        )?;
+        match self.problems.is_empty() {
+            true => write!(f, ", healthy"),
+            false => write!(f, "; {}", self.problems.join("; ")),
+        }
+    }
+}
+
+/// Checks that the operating system's generator answers and that the active [`source`]
+/// produces plausible output, timing how long it takes to.
+///
+/// The checks catch environments that are broken rather than subtly biased, such as a
+/// container without access to the kernel's generator or a hardware generator stuck on
+/// one value: the source has to answer, not repeat itself, and set close to half of the
+/// sample's bits. A healthy source fails only with negligible probability. For the
+/// statistical tests of FIPS 140-2, see `penguin selftest`.
+///
+/// ```
+/// use penguin::entropy;
+///
+/// let report = entropy::health_check();
+/// assert!(report.is_healthy(), "{}", report);
+/// assert!(report.os_available);
+/// ```
+pub fn health_check() -> HealthReport {
+    let mut problems = Vec::new();
+
+    let mut probe = [0u8; 32];
+    let os_available = match OsRng.try_fill_bytes(&mut probe) {
+        Ok(()) if probe.iter().any(|&byte| byte != 0) => true,
+        Ok(()) => {
+            problems.push(String::from(
+                "the operating system generator returned only zeros",
+            ));
+            false
+        }
+        Err(err) => {
+            problems.push(format!("the operating system generator failed: {}", err));
+            false
+        }
+    };
+
+    let source = source();
+    let mut sample = vec![0u8; HEALTH_SAMPLE_BYTES];
+    let started = Instant::now();
+    let drawn = rng().try_fill_bytes(&mut sample);
+    let latency = started.elapsed();
+    if let Err(err) = &drawn {
+        problems.push(format!("the {} source failed: {}", source, err));
+    } else {
+        let (first, second) = sample.split_at(HEALTH_SAMPLE_BYTES / 2);
+        if first == second || first.chunks(8).zip(second.chunks(8)).any(|(a, b)| a == b) {
+            problems.push(format!("the {} source repeats its output", source));
+        }
+    }
+
+    // Six standard deviations from the mean, which a working generator essentially
+    // never strays as far as.
+    let ones: usize = sample.iter().map(|byte| byte.count_ones() as usize).sum();
+    let bits = HEALTH_SAMPLE_BYTES * 8;
+    let tolerance = 3 * (bits as f64).sqrt() as usize;
+    if drawn.is_ok() && ones.abs_diff(bits / 2) > tolerance {
+        problems.push(format!(
+            "the {} source set {} of {} bits",
+            source, ones, bits
+        ));
+    }
+
+    HealthReport {
+        source,
+        os_available,
+        latency,
+        ones,
+        problems,
+    }
+}
+
+/// Describes the active entropy backend to JavaScript callers of a WebAssembly build, so
+/// they can check that passwords are drawn from Web Crypto.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen::prelude::wasm_bindgen(js_name = entropyBackend)]
+pub fn entropy_backend() -> String {
+    format!("{}: {}", source(), source().backend())
+}
+
+/// The process-wide ChaCha20 generator behind the `chacha20` source.
+mod drbg {
+    use rand::rngs::OsRng;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    pub static INTERVAL: AtomicU64 = AtomicU64::new(super::DEFAULT_RESEED_INTERVAL);
+
+    struct State {
+        rng: ChaCha20Rng,
+        /// Bytes output since the last reseed.
+        output: u64,
+    }
+
+    /// The generator, seeded on first use.
+    static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+    /// Fills `dest` from the keystream, rekeying from the operating system whenever the
+    /// interval runs out, including in the middle of `dest`.
+    pub fn fill(mut dest: &mut [u8]) -> Result<(), rand::Error> {
+        let mut state = STATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while !dest.is_empty() {
+            let interval = INTERVAL.load(Ordering::Relaxed);
+            let current = match state.as_mut() {
+                Some(current) if current.output < interval => current,
+                _ => state.insert(State {
+                    rng: ChaCha20Rng::from_rng(OsRng)?,
+                    output: 0,
+                }),
+            };
+            let available = usize::try_from(interval - current.output).unwrap_or(usize::MAX);
+            let (now, rest) = dest.split_at_mut(available.min(dest.len()));
+            current.rng.fill_bytes(now);
+            current.output += now.len() as u64;
+            dest = rest;
+        }
+        Ok(())
+    }
+}
+
+/// Detection of and draws from hardware random number generators.
+mod hardware {
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::sync::{Mutex, OnceLock};
+
+    /// The kernel's interface to a hardware random number generator, on Linux.
+    const HWRNG: &str = "/dev/hwrng";
+
+    /// A hardware random number generator found on this machine.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Generator {
+        RdSeed,
+        RdRand,
+        HwRng,
+    }
+
+    impl Generator {
+        pub fn name(&self) -> &'static str {
+            match self {
+                Generator::RdSeed => "RDSEED instruction",
+                Generator::RdRand => "RDRAND instruction",
+                Generator::HwRng => "/dev/hwrng",
+            }
+        }
+
+        pub fn fill(&self, dest: &mut [u8]) -> io::Result<()> {
+            match self {
+                #[cfg(target_arch = "x86_64")]
+                Generator::RdSeed | Generator::RdRand => {
+                    let step = if *self == Generator::RdSeed {
+                        rdseed
+                    } else {
+                        rdrand
+                    };
+                    for chunk in dest.chunks_mut(8) {
+                        // SAFETY: `detect` only returns a CPU generator after checking
+                        // that the CPU supports its instruction.
+                        let value = unsafe { step() }.ok_or_else(|| {
+                            io::Error::other(format!("{} returned no value", self.name()))
+                        })?;
+                        chunk.copy_from_slice(&value.to_le_bytes()[..chunk.len()]);
+                    }
+                    Ok(())
+                }
+                #[cfg(not(target_arch = "x86_64"))]
+                Generator::RdSeed | Generator::RdRand => {
+                    Err(io::Error::from(io::ErrorKind::Unsupported))
+                }
+                Generator::HwRng => match device() {
+                    Some(device) => device
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .read_exact(dest),
+                    None => Err(io::Error::from(io::ErrorKind::NotFound)),
+                },
+            }
+        }
+    }
+
+    /// Returns the best hardware generator on this machine, checking once per process.
+    pub fn detect() -> Option<Generator> {
+        static GENERATOR: OnceLock<Option<Generator>> = OnceLock::new();
+        *GENERATOR.get_or_init(|| {
+            [Generator::RdSeed, Generator::RdRand, Generator::HwRng]
+                .into_iter()
+                .find(|generator| supported(*generator) && works(*generator))
+        })
+    }
+
+    fn supported(generator: Generator) -> bool {
+        match generator {
+            #[cfg(target_arch = "x86_64")]
+            Generator::RdSeed => std::arch::is_x86_feature_detected!("rdseed"),
+            #[cfg(target_arch = "x86_64")]
+            Generator::RdRand => std::arch::is_x86_feature_detected!("rdrand"),
+            #[cfg(not(target_arch = "x86_64"))]
+            Generator::RdSeed | Generator::RdRand => false,
+            Generator::HwRng => device().is_some(),
+        }
+    }
+
+    /// Draws twice and rejects generators that fail or repeat themselves, such as CPUs
+    /// whose RDRAND returns all ones after a suspend.
+    fn works(generator: Generator) -> bool {
+        let (mut first, mut second) = ([0u8; 8], [0u8; 8]);
+        generator.fill(&mut first).is_ok() && generator.fill(&mut second).is_ok() && first != second
+    }
+
+    /// The hardware generator device, opened once per process.
+    fn device() -> Option<&'static Mutex<File>> {
+        static DEVICE: OnceLock<Option<Mutex<File>>> = OnceLock::new();
+        DEVICE
+            .get_or_init(|| File::open(HWRNG).ok().map(Mutex::new))
+            .as_ref()
+    }
+
+    /// Reads 64 bits from RDSEED, retrying while the entropy pool refills.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "rdseed")]
+    unsafe fn rdseed() -> Option<u64> {
+        let mut value = 0;
+        for _ in 0..100 {
+            if std::arch::x86_64::_rdseed64_step(&mut value) == 1 {
+                return Some(value);
+            }
+            std::hint::spin_loop();
+        }
+        None
+    }
+
+    /// Reads 64 bits from RDRAND, retrying ten times as Intel recommends.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "rdrand")]
+    unsafe fn rdrand() -> Option<u64> {
+        let mut value = 0;
+        for _ in 0..10 {
+            if std::arch::x86_64::_rdrand64_step(&mut value) == 1 {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,47 @@
+//! Lowercase hexadecimal encoding for the Penguin password generator.
+//!
+//! Salts, tags, and hashes that end up in text files are written as hex, by the
+//! [`derive`](crate::derive) verification tags here and by the command-line tool's audit
+//! log, receipts, reuse store, and vault files. Keeping one encoder and one strict
+//! decoder means every one of those files reads back the same way.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::hex;
+//!
+//! assert_eq!(hex::encode(&[0x00, 0x7f, 0xff]), "007fff");
+//! assert_eq!(hex::decode("007FFF"), Some(vec![0x00, 0x7f, 0xff]));
+//! ```
+
+/// Encodes bytes as lowercase hex, two digits per byte.
+pub fn encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes hex in either case, or returns `None` unless the text is an even number of
+/// hex digits and nothing else: no signs, whitespace, or prefix.
+///
+/// ```
+/// use penguin::hex;
+///
+/// assert_eq!(hex::decode(""), Some(Vec::new()));
+/// assert_eq!(hex::decode("abc"), None);
+/// assert_eq!(hex::decode("+f"), None);
+/// assert_eq!(hex::decode("é0"), None);
+/// ```
+pub fn decode(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    let digits: Vec<u8> = text
+        .chars()
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair[1])
+            .collect(),
+    )
+}
@@ -0,0 +1,335 @@
+//! Memory aids for randomly generated passwords.
+//!
+//! Mixed-character passwords are hard to remember, but they become much easier once each
+//! character is tied to a word. [`mnemonic`] spells a password out as a sequence of words:
+//! lowercase letters become a noun starting with that letter, uppercase letters become the
+//! capitalized NATO alphabet word, and digits and symbols are kept as they are.
+//!
+//! The nouns are picked at random, so the same password gets a different mnemonic every
+//! time. [`phonetic`] maps every character to a fixed word instead, so its spelling
+//! follows from the password alone and [`from_phonetic`] turns it back into the password.
+//!
+//! A mnemonic reveals the password completely, so it should be treated like the password
+//! itself. Systems that ask for a hint to store next to the password need something
+//! weaker: [`word_hint`] describes a whole-word password by the first letters or the
+//! parts of speech of its words, and reports how many bits of the password that leaves
+//! for a guesser to find.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::hint::mnemonic;
+//!
+//! let hint = mnemonic("k9Tw");
+//! let words: Vec<&str> = hint.split(' ').collect();
+//! assert!(words[0].starts_with('k'));
+//! assert_eq!(words[1], "9");
+//! assert_eq!(words[2], "Tango");
+//! assert!(words[3].starts_with('w'));
+//! ```
+
+use rand::seq::SliceRandom;
+use std::str::FromStr;
+
+use crate::wordlist::Category;
+
+const NATO: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "Xray", "Yankee", "Zulu",
+];
+
+/// The fixed words [`phonetic`] spells lowercase letters with.
+const LOWERCASE_WORDS: [&str; 26] = [
+    "apple",
+    "banana",
+    "cherry",
+    "dragon",
+    "eagle",
+    "falcon",
+    "grape",
+    "harbor",
+    "igloo",
+    "jungle",
+    "kite",
+    "lemon",
+    "mango",
+    "nectar",
+    "orange",
+    "piano",
+    "quartz",
+    "rocket",
+    "salmon",
+    "tiger",
+    "umbrella",
+    "violin",
+    "walrus",
+    "xylophone",
+    "yogurt",
+    "zebra",
+];
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The names [`phonetic`] spells ASCII symbols with.
+const SYMBOL_WORDS: [(char, &str); 33] = [
+    (' ', "space"),
+    ('!', "bang"),
+    ('"', "quote"),
+    ('#', "hash"),
+    ('$', "dollar"),
+    ('%', "percent"),
+    ('&', "ampersand"),
+    ('\'', "apostrophe"),
+    ('(', "open-paren"),
+    (')', "close-paren"),
+    ('*', "star"),
+    ('+', "plus"),
+    (',', "comma"),
+    ('-', "dash"),
+    ('.', "dot"),
+    ('/', "slash"),
+    (':', "colon"),
+    (';', "semicolon"),
+    ('<', "less-than"),
+    ('=', "equals"),
+    ('>', "greater-than"),
+    ('?', "question"),
+    ('@', "at"),
+    ('[', "open-bracket"),
+    ('\\', "backslash"),
+    (']', "close-bracket"),
+    ('^', "caret"),
+    ('_', "underscore"),
+    ('`', "backtick"),
+    ('{', "open-brace"),
+    ('|', "pipe"),
+    ('}', "close-brace"),
+    ('~', "tilde"),
+];
+
+/// Maps every character of the password to a word, joined with spaces.
+pub fn mnemonic(password: &str) -> String {
+    let mut rng = crate::entropy::rng();
+    let nouns = Category::Noun.words();
+
+    password
+        .chars()
+        .map(|c| {
+            if c.is_ascii_uppercase() {
+                return NATO[(c as u8 - b'A') as usize].to_string();
+            }
+            let candidates: Vec<&str> = nouns
+                .iter()
+                .copied()
+                .filter(|word| c.is_ascii_lowercase() && word.starts_with(c))
+                .collect();
+            match candidates.choose(&mut rng) {
+                Some(word) => word.to_string(),
+                None => c.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Spells a password out with one fixed word per character, joined with spaces.
+///
+/// Lowercase letters become a lowercase word starting with them, uppercase letters the
+/// NATO alphabet word, digits their English names, and ASCII symbols their names, so
+/// `k9T!` is always `kite nine Tango bang`. Other characters are kept as they are. For
+/// fully random passwords this gives something to memorize without anything extra to
+/// store: the words can be worked out again from the password whenever it's needed.
+///
+/// ```
+/// use penguin::hint::{from_phonetic, phonetic};
+///
+/// assert_eq!(phonetic("k9T!"), "kite nine Tango bang");
+/// assert_eq!(from_phonetic("kite nine Tango bang").unwrap(), "k9T!");
+/// assert_eq!(from_phonetic(&phonetic("x_Q{7é")).unwrap(), "x_Q{7é");
+/// ```
+pub fn phonetic(password: &str) -> String {
+    password
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => LOWERCASE_WORDS[(c as u8 - b'a') as usize].to_string(),
+            'A'..='Z' => NATO[(c as u8 - b'A') as usize].to_string(),
+            '0'..='9' => DIGIT_WORDS[(c as u8 - b'0') as usize].to_string(),
+            _ => SYMBOL_WORDS
+                .iter()
+                .find(|(symbol, _)| *symbol == c)
+                .map_or_else(|| c.to_string(), |(_, word)| word.to_string()),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Turns a spelling from [`phonetic`] back into the password, or returns `None` when a
+/// word is not one it uses.
+pub fn from_phonetic(spelling: &str) -> Option<String> {
+    spelling
+        .split(' ')
+        .map(|word| {
+            let position = |list: &[&str]| list.iter().position(|known| *known == word);
+            if let Some(i) = position(&LOWERCASE_WORDS) {
+                Some((b'a' + i as u8) as char)
+            } else if let Some(i) = position(&NATO) {
+                Some((b'A' + i as u8) as char)
+            } else if let Some(i) = position(&DIGIT_WORDS) {
+                Some((b'0' + i as u8) as char)
+            } else if let Some((symbol, _)) = SYMBOL_WORDS.iter().find(|(_, name)| *name == word) {
+                Some(*symbol)
+            } else {
+                let mut chars = word.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            }
+        })
+        .collect()
+}
+
+/// How [`word_hint`] describes the words of a password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintStyle {
+    /// The first letter of every word, as in `g..., p...`.
+    FirstLetters,
+    /// The part of speech of every word, as in `adjective, noun`, looked up in the embedded
+    /// word lists. Words that aren't in them are just `word`.
+    Categories,
+}
+
+impl FromStr for HintStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "first-letters" | "letters" => Ok(HintStyle::FirstLetters),
+            "categories" | "category" => Ok(HintStyle::Categories),
+            _ => Err(format!(
+                "unknown hint style '{}' (expected first-letters or categories)",
+                s
+            )),
+        }
+    }
+}
+
+/// A hint for a whole-word password and what it gives away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordHint {
+    /// The hint, naming nothing but first letters or parts of speech.
+    pub text: String,
+    /// Bits a guesser who knows the base words has to find without the hint.
+    pub bits_before: f64,
+    /// Bits left to find once the hint is known.
+    pub bits_after: f64,
+}
+
+/// Describes the base words a whole-word password is built from, in order, without
+/// naming them, or returns `None` when none of the words is in it.
+///
+/// The bits assume the worst case, a guesser who knows every base word and how
+/// passwords are put together: each word in the password is one pick from the distinct
+/// base words and every other character one pick from its class (26 letters, 10 digits,
+/// or 33 symbols). The hint narrows each word down to the base words with the same first
+/// letter or part of speech. Check [`bits_after`](WordHint::bits_after) against a
+/// threshold before storing the hint.
+///
+/// ```
+/// use penguin::hint::{word_hint, HintStyle};
+///
+/// let words = ["glacier", "penguin", "tundra", "pebble"];
+/// let hint = word_hint("glacier-Penguin-42", &words, HintStyle::FirstLetters).unwrap();
+/// assert_eq!(hint.text, "g..., p...");
+/// assert_eq!(hint.bits_before - hint.bits_after, 3.0);
+///
+/// let hint = word_hint("penguin.glacier", &words, HintStyle::Categories).unwrap();
+/// assert_eq!(hint.text, "noun, noun");
+/// assert!(word_hint("x7!Qk2", &words, HintStyle::Categories).is_none());
+/// ```
+pub fn word_hint(password: &str, words: &[&str], style: HintStyle) -> Option<WordHint> {
+    let mut pool: Vec<String> = Vec::new();
+    for word in words {
+        let word = word.trim().to_lowercase();
+        if !word.is_empty() && !pool.contains(&word) {
+            pool.push(word);
+        }
+    }
+    let lowered: Vec<char> = password
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+
+    let mut found: Vec<&str> = Vec::new();
+    let mut other_bits = 0.0;
+    let mut i = 0;
+    while i < lowered.len() {
+        let longest = pool
+            .iter()
+            .filter(|word| {
+                let word: Vec<char> = word.chars().collect();
+                lowered[i..].starts_with(&word)
+            })
+            .max_by_key(|word| word.chars().count());
+        match longest {
+            Some(word) => {
+                found.push(word);
+                i += word.chars().count();
+            }
+            None => {
+                other_bits += class_bits(lowered[i]);
+                i += 1;
+            }
+        }
+    }
+    if found.is_empty() {
+        return None;
+    }
+
+    let describe = |word: &str| -> String {
+        match style {
+            HintStyle::FirstLetters => word.chars().take(1).collect::<String>() + "...",
+            HintStyle::Categories => category_name(word).to_string(),
+        }
+    };
+    let bits_before = other_bits + found.len() as f64 * (pool.len() as f64).log2();
+    let bits_after = other_bits
+        + found
+            .iter()
+            .map(|word| {
+                let described = describe(word);
+                let alike = pool.iter().filter(|other| describe(other) == described);
+                (alike.count() as f64).log2()
+            })
+            .sum::<f64>();
+    Some(WordHint {
+        text: found
+            .iter()
+            .map(|word| describe(word))
+            .collect::<Vec<_>>()
+            .join(", "),
+        bits_before,
+        bits_after,
+    })
+}
+
+/// Bits of one character picked from its class.
+fn class_bits(c: char) -> f64 {
+    match c {
+        c if c.is_ascii_alphabetic() => 26f64.log2(),
+        c if c.is_ascii_digit() => 10f64.log2(),
+        _ => 33f64.log2(),
+    }
+}
+
+/// Names the part of speech of a lowercase word, by the embedded list it is on.
+fn category_name(word: &str) -> &'static str {
+    [
+        (Category::Adjective, "adjective"),
+        (Category::Noun, "noun"),
+        (Category::Verb, "verb"),
+    ]
+    .iter()
+    .find(|(category, _)| category.words().contains(&word))
+    .map_or("word", |&(_, name)| name)
+}
@@ -0,0 +1,410 @@
+//! Keyboard-layout-aware symbol pools for the Penguin password generator.
+//!
+//! The default symbols `!@#$%^&*` all sit on shifted number-row keys of a US keyboard,
+//! but other layouts move them around. On a French AZERTY keyboard `@` and `#` need
+//! AltGr and `^` is a dead key, so typing them means an awkward chord or a second
+//! keystroke that is easy to get wrong.
+//!
+//! A [`Layout`] knows how each symbol is reached. [`Layout::special_chars`] drops the
+//! ones behind dead keys or AltGr chords and can weight the rest by reachability, so
+//! symbols on unmodified keys come up twice as often as shifted ones.
+//!
+//! A layout also knows which hand and finger presses each key, which
+//! [`Layout::typing_score`] uses to rate how easily a password flows under the fingers:
+//! alternating hands is fast, while striking two different keys with the same finger is
+//! slow and a common source of typos.
+//!
+//! Keys that sit next to each other make keyboard walks like `qwerty`, `asdfgh`, or
+//! `1qaz2wsx`, which attackers try early because people type them to satisfy length
+//! rules. [`keyboard_walks`] finds them on every layout, with Shift ignored, so `!@#$` is
+//! the same walk as `1234`.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::layout::Layout;
+//!
+//! let azerty: Layout = "azerty".parse().unwrap();
+//! let symbols = azerty.special_chars(false);
+//! assert!(!symbols.contains('@') && !symbols.contains('#'));
+//! assert!(symbols.contains('!'));
+//! ```
+
+use std::str::FromStr;
+
+/// The symbols the mixer draws from by default.
+const SYMBOLS: &str = "!@#$%^&*";
+
+/// The rows of each layout, as unshifted and shifted characters by column, top row first.
+const QWERTY_ROWS: [(&str, &str); 4] = [
+    ("1234567890-=", "!@#$%^&*()_+"),
+    ("qwertyuiop[]", "QWERTYUIOP{}"),
+    ("asdfghjkl;'", "ASDFGHJKL:\""),
+    ("zxcvbnm,./", "ZXCVBNM<>?"),
+];
+const AZERTY_ROWS: [(&str, &str); 4] = [
+    ("&é\"'(-è_çà)=", "1234567890°+"),
+    ("azertyuiop^$", "AZERTYUIOP¨£"),
+    ("qsdfghjklmù*", "QSDFGHJKLM%µ"),
+    ("wxcvbn,;:!", "WXCVBN?./§"),
+];
+const QWERTZ_ROWS: [(&str, &str); 4] = [
+    ("1234567890ß", "!\"§$%&/()=?"),
+    ("qwertzuiopü+", "QWERTZUIOPÜ*"),
+    ("asdfghjklöä#", "ASDFGHJKLÖÄ'"),
+    ("yxcvbnm,.-", "YXCVBNM;:_"),
+];
+const DVORAK_ROWS: [(&str, &str); 4] = [
+    ("1234567890[]", "!@#$%^&*(){}"),
+    ("',.pyfgcrl/=", "\"<>PYFGCRL?+"),
+    ("aoeuidhtns-", "AOEUIDHTNS_"),
+    (";qjkxbmwvz", ":QJKXBMWVZ"),
+];
+
+/// Most same-finger bigrams, as a share of all bigrams, a comfortable password may have.
+const MAX_SAME_FINGER: f64 = 0.1;
+
+/// Fewest hand alternations, as a share of all bigrams, a comfortable password may have.
+const MIN_ALTERNATION: f64 = 0.5;
+
+/// A run of neighbouring keys typed one after another.
+///
+/// Besides keys side by side and keys diagonally above or below each other, a step from
+/// the bottom row back up to the number row in the same or the next column continues a
+/// walk, so column walks like `1qaz2wsx` count as one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardWalk {
+    /// Where the walk starts in the password, counted in characters.
+    pub start: usize,
+    /// The characters of the walk, as typed.
+    pub keys: String,
+    /// The layout the keys are neighbours on.
+    pub layout: Layout,
+}
+
+impl KeyboardWalk {
+    /// Returns how many keys the walk is long.
+    pub fn len(&self) -> usize {
+        self.keys.chars().count()
+    }
+
+    /// Returns whether the walk has no keys, which never happens for walks that were found.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// How a symbol is typed on a given layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reach {
+    /// A key pressed on its own.
+    Direct,
+    /// A key pressed together with Shift.
+    Shift,
+    /// An AltGr chord or a dead key that waits for the next keystroke.
+    Awkward,
+}
+
+/// The hand that presses a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// The hand and finger that press a key in touch typing.
+///
+/// Fingers are numbered from the pinky, so `0` is the pinky and `3` the index finger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Finger {
+    pub hand: Hand,
+    pub index: u8,
+}
+
+/// How comfortably a password types on a layout.
+///
+/// Only bigrams whose keys are both on the layout are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypingScore {
+    pub bigrams: usize,
+    /// Bigrams typed with one hand after the other.
+    pub alternations: usize,
+    /// Bigrams of two different keys struck with the same finger.
+    pub same_finger: usize,
+}
+
+impl TypingScore {
+    /// Returns whether the password alternates hands on at least half of its bigrams
+    /// and uses the same finger twice in a row on at most a tenth of them.
+    pub fn is_comfortable(&self) -> bool {
+        if self.bigrams == 0 {
+            return true;
+        }
+        let bigrams = self.bigrams as f64;
+        self.same_finger as f64 / bigrams <= MAX_SAME_FINGER
+            && self.alternations as f64 / bigrams >= MIN_ALTERNATION
+    }
+}
+
+/// A physical keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+}
+
+impl Layout {
+    /// Every layout, in the order [`keyboard_walks`] prefers them when walks tie.
+    pub const ALL: [Layout; 4] = [
+        Layout::Qwerty,
+        Layout::Azerty,
+        Layout::Qwertz,
+        Layout::Dvorak,
+    ];
+
+    /// Returns how a symbol is typed on this layout.
+    ///
+    /// Letters and digits are always reachable and count as direct. Symbols the layout
+    /// does not know about are treated as awkward.
+    pub fn reach(self, c: char) -> Reach {
+        if c.is_ascii_alphanumeric() {
+            return Reach::Direct;
+        }
+        match (self, c) {
+            (Layout::Qwerty | Layout::Dvorak, '!' | '@' | '#' | '$' | '%' | '^' | '&' | '*') => {
+                Reach::Shift
+            }
+            (Layout::Azerty, '!' | '$' | '&' | '*') => Reach::Direct,
+            (Layout::Azerty, '%') => Reach::Shift,
+            (Layout::Qwertz, '#') => Reach::Direct,
+            (Layout::Qwertz, '!' | '$' | '%' | '&' | '*') => Reach::Shift,
+            _ => Reach::Awkward,
+        }
+    }
+
+    /// Returns the row and column of the key that types a character on this layout,
+    /// counting from the top row and the leftmost key, or `None` when the character is
+    /// not on one of its main keys. A character and its shifted form share a key.
+    ///
+    /// ```
+    /// use penguin::layout::Layout;
+    ///
+    /// assert_eq!(Layout::Qwerty.key('q'), Some((1, 0)));
+    /// assert_eq!(Layout::Qwerty.key('!'), Layout::Qwerty.key('1'));
+    /// assert_eq!(Layout::Qwerty.key('é'), None);
+    /// ```
+    pub fn key(self, c: char) -> Option<(usize, usize)> {
+        let rows = match self {
+            Layout::Qwerty => &QWERTY_ROWS,
+            Layout::Azerty => &AZERTY_ROWS,
+            Layout::Qwertz => &QWERTZ_ROWS,
+            Layout::Dvorak => &DVORAK_ROWS,
+        };
+        rows.iter().enumerate().find_map(|(row, (plain, shifted))| {
+            plain
+                .chars()
+                .position(|key| key == c)
+                .or_else(|| shifted.chars().position(|key| key == c))
+                .map(|column| (row, column))
+        })
+    }
+
+    /// Returns whether two characters are typed on different keys that a keyboard walk
+    /// can step between on this layout.
+    ///
+    /// ```
+    /// use penguin::layout::Layout;
+    ///
+    /// assert!(Layout::Qwerty.adjacent('q', 'w'));
+    /// assert!(Layout::Qwerty.adjacent('w', 's') && Layout::Qwerty.adjacent('s', 'x'));
+    /// assert!(Layout::Qwerty.adjacent('z', '2'));
+    /// assert!(!Layout::Qwerty.adjacent('q', 'e'));
+    /// assert!(!Layout::Qwerty.adjacent('a', 'A'));
+    /// ```
+    pub fn adjacent(self, a: char, b: char) -> bool {
+        let (Some((row, column)), Some((other_row, other_column))) = (self.key(a), self.key(b))
+        else {
+            return false;
+        };
+        match row.abs_diff(other_row) {
+            0 => column.abs_diff(other_column) == 1,
+            1 => {
+                // Each row sits about half a key to the right of the one above it.
+                let (upper, lower) = if row < other_row {
+                    (column, other_column)
+                } else {
+                    (other_column, column)
+                };
+                upper == lower || upper == lower + 1
+            }
+            _ => {
+                row.min(other_row) == 0
+                    && row.max(other_row) == QWERTY_ROWS.len() - 1
+                    && column.abs_diff(other_column) <= 1
+            }
+        }
+    }
+
+    /// Returns the keyboard walks of at least `min_keys` keys in a password on this
+    /// layout, longest runs only, in order.
+    pub fn walks(self, password: &str, min_keys: usize) -> Vec<KeyboardWalk> {
+        let chars: Vec<char> = password.chars().collect();
+        let mut walks = Vec::new();
+        let mut start = 0;
+        for end in 1..=chars.len() {
+            if end < chars.len() && self.adjacent(chars[end - 1], chars[end]) {
+                continue;
+            }
+            if end - start >= min_keys.max(2) {
+                walks.push(KeyboardWalk {
+                    start,
+                    keys: chars[start..end].iter().collect(),
+                    layout: self,
+                });
+            }
+            start = end;
+        }
+        walks
+    }
+
+    /// Returns the finger that types a character on this layout, or `None` when the
+    /// character is not on one of its main keys.
+    pub fn finger(self, c: char) -> Option<Finger> {
+        let (_, column) = self.key(c)?;
+        let (hand, index) = match column {
+            0..=3 => (Hand::Left, column as u8),
+            4 => (Hand::Left, 3),
+            5 | 6 => (Hand::Right, 3),
+            7 => (Hand::Right, 2),
+            8 => (Hand::Right, 1),
+            _ => (Hand::Right, 0),
+        };
+        Some(Finger { hand, index })
+    }
+
+    /// Scores how comfortably a password types on this layout.
+    ///
+    /// ```
+    /// use penguin::layout::Layout;
+    ///
+    /// let score = Layout::Qwerty.typing_score("sidekick");
+    /// assert_eq!(score.bigrams, 7);
+    /// assert!(Layout::Qwerty.typing_score("visual").is_comfortable());
+    /// assert!(!Layout::Qwerty.typing_score("deceduct").is_comfortable());
+    /// ```
+    pub fn typing_score(self, password: &str) -> TypingScore {
+        let mut score = TypingScore {
+            bigrams: 0,
+            alternations: 0,
+            same_finger: 0,
+        };
+        let chars: Vec<char> = password.chars().collect();
+        for pair in chars.windows(2) {
+            let (Some(first), Some(second)) = (self.finger(pair[0]), self.finger(pair[1])) else {
+                continue;
+            };
+            score.bigrams += 1;
+            if first.hand != second.hand {
+                score.alternations += 1;
+            } else if first.index == second.index && pair[0] != pair[1] {
+                score.same_finger += 1;
+            }
+        }
+        score
+    }
+
+    /// Returns the default symbols that are comfortable to type on this layout.
+    ///
+    /// When `weighted` is set, symbols on unmodified keys appear twice in the result, so
+    /// picking uniformly from it favors them over shifted symbols.
+    ///
+    /// ```
+    /// use penguin::layout::Layout;
+    ///
+    /// assert_eq!(Layout::Qwertz.special_chars(false), "!#$%&*");
+    /// assert_eq!(Layout::Qwertz.special_chars(true), "!##$%&*");
+    /// ```
+    pub fn special_chars(self, weighted: bool) -> String {
+        let mut chars = String::new();
+        for c in SYMBOLS.chars() {
+            match self.reach(c) {
+                Reach::Direct if weighted => chars.extend([c, c]),
+                Reach::Direct | Reach::Shift => chars.push(c),
+                Reach::Awkward => {}
+            }
+        }
+        chars
+    }
+}
+
+/// Returns the keyboard walks of at least `min_keys` keys in a password on any layout.
+///
+/// Walks found on several layouts are reported once, and where walks overlap only the
+/// longest is kept, so the walks returned never share a character. They are in the order
+/// they appear.
+///
+/// ```
+/// use penguin::layout::{keyboard_walks, Layout};
+///
+/// let walks = keyboard_walks("1qaz2wsx-penguin-azerty", 4);
+/// assert_eq!(walks.len(), 2);
+/// assert_eq!(walks[0].keys, "1qaz2wsx");
+/// assert_eq!((walks[1].keys.as_str(), walks[1].layout), ("azerty", Layout::Azerty));
+/// assert!(keyboard_walks("glacier", 4).is_empty());
+/// ```
+pub fn keyboard_walks(password: &str, min_keys: usize) -> Vec<KeyboardWalk> {
+    let mut found: Vec<KeyboardWalk> = Layout::ALL
+        .iter()
+        .flat_map(|layout| layout.walks(password, min_keys))
+        .collect();
+    // A stable sort keeps the preferred layout first among walks of the same length.
+    found.sort_by_key(|walk| std::cmp::Reverse(walk.len()));
+    let mut walks: Vec<KeyboardWalk> = Vec::new();
+    for walk in found {
+        let overlaps = walks.iter().any(|kept| {
+            walk.start < kept.start + kept.len() && kept.start < walk.start + walk.len()
+        });
+        if !overlaps {
+            walks.push(walk);
+        }
+    }
+    walks.sort_by_key(|walk| walk.start);
+    walks
+}
+
+/// Returns how many keys the longest keyboard walk in a password is long on any layout,
+/// or 0 when no two neighbouring characters are neighbouring keys.
+///
+/// ```
+/// use penguin::layout::longest_walk;
+///
+/// assert_eq!(longest_walk("Summer-qwerty!"), 6);
+/// assert_eq!(longest_walk("%"), 0);
+/// ```
+pub fn longest_walk(password: &str) -> usize {
+    Layout::ALL
+        .iter()
+        .flat_map(|layout| layout.walks(password, 2))
+        .map(|walk| walk.len())
+        .max()
+        .unwrap_or(0)
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "qwerty" => Ok(Layout::Qwerty),
+            "azerty" => Ok(Layout::Azerty),
+            "qwertz" => Ok(Layout::Qwertz),
+            "dvorak" => Ok(Layout::Dvorak),
+            _ => Err(format!(
+                "unknown layout '{}' (expected qwerty, azerty, qwertz, or dvorak)",
+                s
+            )),
+        }
+    }
+}
@@ -12,39 +12,295 @@
 //! Generate passwords using default settings (medium complexity, whole words, 12 characters):
 //!
 //! ```
-//! use penguin::Penguin;
+//! use penguin::{GenerateOptions, Penguin};
 //!
 //! let penguin = Penguin::new(vec!["hello", "world"]);
-//! let passwords = penguin.generate_password(3, None, None, None);
+//! let passwords = penguin.generate(&GenerateOptions::new().count(3)).unwrap();
 //! ```
 //!
 //! Generate passwords with custom settings for more security:
 //!
 //! ```
-//! use penguin::{Penguin, mixer::ComplexityLevel};
+//! use penguin::{mixer::ComplexityLevel, GenerateOptions, Penguin};
 //!
 //! let penguin = Penguin::new(vec!["secure", "password"]);
-//! let passwords = penguin.generate_password(
-//!     2,                              // Generate 2 passwords
-//!     Some(ComplexityLevel::Hard),    // Use hard complexity
-//!     Some(false),                    // Mix characters instead of whole words
-//!     Some(16)                        // Make them 16 characters long
-//! );
+//! let options = GenerateOptions::new()
+//!     .count(2)                            // Generate 2 passwords
+//!     .complexity(ComplexityLevel::Hard)   // Use hard complexity
+//!     .whole_words(false)                  // Mix characters instead of whole words
+//!     .length(16);                         // Make them 16 characters long
+//! let passwords = penguin.generate(&options).unwrap();
+//! let more = penguin.generate(&options).unwrap();
 //! ```
 
+pub use charset::Charset;
 use mixer::{ComplexityLevel, PenguinMixer};
+pub use options::GenerateOptions;
+use rand::{CryptoRng, Rng, RngCore};
+use std::fmt;
+use std::time::{Duration, Instant};
 
+pub mod backupcode;
+pub mod charset;
+pub mod dates;
+#[cfg(feature = "derive")]
+pub mod derive;
+pub mod dictation;
+pub mod emoji;
+pub mod entropy;
+pub mod hex;
+#[cfg(feature = "wordlists")]
+pub mod hint;
+pub mod layout;
+#[cfg(feature = "markov")]
+pub mod markov;
+pub mod mask;
 pub mod mixer;
+pub mod options;
+#[cfg(feature = "otp")]
+pub mod otp;
+#[cfg(feature = "wordlists")]
+pub mod passphrase;
+pub mod personal;
+pub mod policy;
+pub mod productkey;
+#[cfg(feature = "profanity")]
+pub mod profanity;
+pub mod pronounce;
+pub mod regexgen;
+#[cfg(feature = "analysis")]
+pub mod render;
+pub mod safe;
+pub mod schneier;
+pub mod secret;
+#[cfg(feature = "wordlists")]
+pub mod sentence;
+pub mod shamir;
+pub mod siterules;
+#[cfg(feature = "analysis")]
+pub mod strength;
+pub mod tokenize;
+#[cfg(feature = "wordlists")]
+pub mod username;
+pub mod variants;
+#[cfg(feature = "wordlists")]
+pub mod wordlist;
+
+/// Generates a random password of `length` characters drawn from `charset`.
+///
+/// Every character is picked independently and uniformly from the set, so the password
+/// has `length` times [`Charset::bits_per_char`] bits of entropy. No base words are
+/// involved; this is what the penguin complexity level does underneath. An empty charset
+/// yields an empty password.
+///
+/// ```
+/// use penguin::{random_password, Charset};
+///
+/// let password = random_password(64, &Charset::default());
+/// assert_eq!(password.chars().count(), 64);
+///
+/// let pin = random_password(6, &Charset::new("0123456789"));
+/// assert!(pin.len() == 6 && pin.chars().all(|c| c.is_ascii_digit()));
+/// ```
+pub fn random_password(length: usize, charset: &Charset) -> String {
+    let chars: Vec<char> = charset.as_str().chars().collect();
+    sample(&chars, length)
+}
+
+/// Draws `length` characters uniformly from `chars`, which may list a character more than
+/// once to make it more likely.
+pub(crate) fn sample(chars: &[char], length: usize) -> String {
+    if chars.is_empty() {
+        return String::new();
+    }
+    let mut rng = entropy::rng();
+    (0..length)
+        .map(|_| chars[rng.gen_range(0..chars.len())])
+        .collect()
+}
+
+/// Maps characters commonly used as stand-ins for letters back to those letters.
+pub(crate) fn undo_substitution(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        '8' => 'b',
+        _ => c,
+    }
+}
+
+/// Compares two secrets for equality in constant time.
+///
+/// The time taken depends only on the lengths of the inputs, never on where they first
+/// differ, so services that check tokens or passwords with it don't leak how much of a
+/// guess was right. Inputs of different lengths compare unequal.
+///
+/// ```
+/// assert!(penguin::compare(b"s3cret", b"s3cret"));
+/// assert!(!penguin::compare(b"s3cret", b"s3creT"));
+/// assert!(!penguin::compare(b"s3cret", b"s3c"));
+/// ```
+pub fn compare(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    if a.len() != b.len() {
+        return false;
+    }
+    // black_box keeps the optimizer from turning the fold into an early-exit loop.
+    let difference = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |acc, (x, y)| std::hint::black_box(acc | (x ^ y)));
+    difference == 0
+}
+
+/// Errors produced when generating passwords.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PenguinError {
+    /// A word-based complexity level was given no words, or only blank ones.
+    NoWords,
+    /// The settings can't produce a proper password; the message says which one.
+    InvalidConfig(String),
+    /// Every candidate within the mixer's attempt budget was rejected.
+    Exhausted {
+        attempts: usize,
+        /// The constraints that rejected candidates, each with how many it rejected.
+        rejections: Vec<(&'static str, usize)>,
+    },
+}
+
+impl fmt::Display for PenguinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PenguinError::NoWords => write!(
+                f,
+                "no usable base words; word-based complexity levels need at least one"
+            ),
+            PenguinError::InvalidConfig(details) => write!(f, "invalid configuration: {}", details),
+            PenguinError::Exhausted {
+                attempts,
+                rejections,
+            } => {
+                let rejections: Vec<String> = rejections
+                    .iter()
+                    .map(|(reason, count)| format!("{} {}", count, reason))
+                    .collect();
+                write!(
+                    f,
+                    "no candidate satisfied the constraints in {} attempts (rejected: {})",
+                    attempts,
+                    rejections.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PenguinError {}
+
+/// How the passwords of a generation came about: how many candidates it took, which
+/// constraints threw candidates away, how much randomness it used, and how long it ran.
+///
+/// When generation is slow or fails, the rejections show which constraints conflict.
+///
+/// ```
+/// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+///
+/// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 8);
+/// mixer.reject_if.push(|password| !password.starts_with('!'));
+/// let (password, stats) = mixer.mix_password_stats(&["glacier"]).unwrap();
+/// assert!(password.starts_with('!'));
+/// assert_eq!(stats.passwords, 1);
+/// assert_eq!(stats.attempts, stats.rejected() + 1);
+/// assert!(stats.rng_bytes > 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationStats {
+    /// Passwords returned.
+    pub passwords: usize,
+    /// Candidates generated, including the ones returned.
+    pub attempts: usize,
+    /// The constraints that rejected candidates, each with how many it rejected.
+    pub rejections: Vec<(&'static str, usize)>,
+    /// Random bytes drawn from the [`entropy`] generators.
+    pub rng_bytes: u64,
+    /// Time spent generating.
+    pub elapsed: Duration,
+}
+
+impl GenerationStats {
+    /// Returns how many candidates were rejected.
+    pub fn rejected(&self) -> usize {
+        self.rejections.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Counts one more rejection for `reason`.
+    pub fn reject(&mut self, reason: &'static str) {
+        match self
+            .rejections
+            .iter_mut()
+            .find(|(known, _)| *known == reason)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.rejections.push((reason, 1)),
+        }
+    }
+
+    /// Adds the counts of another generation to these, as when combining the stats of
+    /// a batch generated in parallel. The elapsed times are added up as well, which
+    /// overstates the wall-clock time of parallel work.
+    pub fn merge(&mut self, other: &GenerationStats) {
+        self.passwords += other.passwords;
+        self.attempts += other.attempts;
+        for &(reason, count) in &other.rejections {
+            match self
+                .rejections
+                .iter_mut()
+                .find(|(known, _)| *known == reason)
+            {
+                Some((_, total)) => *total += count,
+                None => self.rejections.push((reason, count)),
+            }
+        }
+        self.rng_bytes += other.rng_bytes;
+        self.elapsed += other.elapsed;
+    }
+}
 
 /// The main struct for generating passwords from a set of base words.
 ///
 /// The Penguin struct takes a set of base words during initialization and uses them
 /// to generate passwords. The base words serve as the foundation for password generation,
 /// either being used whole or mixed character by character depending on the settings.
+///
+/// The word set can grow after construction, with [`add_words`](Self::add_words) or
+/// [`Extend`], and a Penguin can be collected straight from an iterator of words.
+///
+/// ```
+/// use penguin::Penguin;
+///
+/// let mut penguin: Penguin = "glacier iceberg".split(' ').collect();
+/// penguin.extend(["   ", "floe"]);
+/// assert_eq!(penguin.words(), ["glacier", "iceberg", "floe"]);
+/// ```
+///
+/// The base words are often personal, so the `Debug` output only says how many there are.
+#[derive(Clone, Default, PartialEq, Eq)]
 pub struct Penguin<'a> {
     base_input: Vec<&'a str>,
 }
 
+impl fmt::Debug for Penguin<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Penguin")
+            .field("words", &self.base_input.len())
+            .finish()
+    }
+}
+
 impl<'a> Penguin<'a> {
     /// Creates a new Penguin instance with the provided base words.
     ///
@@ -56,6 +312,55 @@ impl<'a> Penguin<'a> {
         Self { base_input }
     }
 
+    /// Creates a new Penguin instance with the words of a phrase, split by the tokenizer.
+    ///
+    /// ```
+    /// use penguin::{tokenize::Tokenizer, Penguin};
+    ///
+    /// let penguin = Penguin::from_phrase("the quick brown fox", Tokenizer::Whitespace);
+    /// assert_eq!(penguin.words(), ["the", "quick", "brown", "fox"]);
+    /// ```
+    pub fn from_phrase(phrase: &'a str, tokenizer: tokenize::Tokenizer) -> Self {
+        Self::new(tokenizer.split(phrase))
+    }
+
+    /// Returns the base words, in the order they were given.
+    pub fn words(&self) -> &[&'a str] {
+        &self.base_input
+    }
+
+    /// Adds base words to the set.
+    ///
+    /// Fails with [`PenguinError::InvalidConfig`], adding none of the words, when one of
+    /// them is empty or only whitespace.
+    ///
+    /// ```
+    /// use penguin::{Penguin, PenguinError};
+    ///
+    /// let mut penguin = Penguin::new(vec!["glacier"]);
+    /// penguin.add_words(["iceberg", "floe"]).unwrap();
+    /// assert_eq!(penguin.words().len(), 3);
+    /// assert!(matches!(
+    ///     penguin.add_words(["krill", " "]),
+    ///     Err(PenguinError::InvalidConfig(_))
+    /// ));
+    /// assert_eq!(penguin.words().len(), 3);
+    /// ```
+    pub fn add_words<I>(&mut self, words: I) -> Result<(), PenguinError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let words: Vec<&'a str> = words.into_iter().collect();
+        if let Some(position) = words.iter().position(|word| word.trim().is_empty()) {
+            return Err(PenguinError::InvalidConfig(format!(
+                "base word {} is blank",
+                position + 1
+            )));
+        }
+        self.base_input.extend(words);
+        Ok(())
+    }
+
     /// Generates multiple passwords with customizable settings.
     ///
     /// This method generates a specified number of passwords using the base words provided
@@ -77,27 +382,309 @@ impl<'a> Penguin<'a> {
     ///
     /// If no customization options are provided (all None), the generator uses medium complexity,
     /// whole words, and 12-character length as defaults.
+    ///
+    /// Fails with [`PenguinError::NoWords`] when a word-based complexity level has no
+    /// base words to work with, and with [`PenguinError::InvalidConfig`] when the length
+    /// is too short.
+    #[deprecated(note = "use `generate` with `GenerateOptions`, which names each setting")]
     pub fn generate_password(
-        self,
+        &self,
         count: usize,
         complexity: Option<ComplexityLevel>,
         use_whole_words: Option<bool>,
         length: Option<usize>,
-    ) -> Vec<String> {
-        let mixer = match (complexity, use_whole_words, length) {
-            (None, None, None) => PenguinMixer::default(),
-            (complexity, use_whole_words, length) => PenguinMixer::new(
-                complexity.unwrap_or(ComplexityLevel::Medium),
-                use_whole_words.unwrap_or(true),
-                length.unwrap_or(12),
-            ),
+    ) -> Result<Vec<String>, PenguinError> {
+        let mut options = GenerateOptions::new().count(count);
+        if let Some(complexity) = complexity {
+            options = options.complexity(complexity);
+        }
+        if let Some(use_whole_words) = use_whole_words {
+            options = options.whole_words(use_whole_words);
+        }
+        if let Some(length) = length {
+            options = options.length(length);
+        }
+        self.generate(&options)
+    }
+
+    /// Generates the passwords the options ask for. The Penguin is only borrowed, so it
+    /// can generate any number of batches.
+    ///
+    /// Fails with [`PenguinError::NoWords`] when a word-based complexity level has no
+    /// base words to work with, and with [`PenguinError::InvalidConfig`] when the
+    /// settings or the policy can't produce a password.
+    ///
+    /// ```
+    /// use penguin::{mixer::ComplexityLevel, GenerateOptions, Penguin, PenguinError};
+    ///
+    /// let penguin = Penguin::new(vec!["glacier", "penguin"]);
+    /// let passwords = penguin.generate(&GenerateOptions::new().count(4)).unwrap();
+    /// assert_eq!(passwords.len(), 4);
+    ///
+    /// let too_short = GenerateOptions::new().length(2);
+    /// assert!(matches!(penguin.generate(&too_short), Err(PenguinError::InvalidConfig(_))));
+    /// let no_words = GenerateOptions::new();
+    /// assert_eq!(Penguin::default().generate(&no_words), Err(PenguinError::NoWords));
+    /// ```
+    pub fn generate(&self, options: &GenerateOptions) -> Result<Vec<String>, PenguinError> {
+        self.generate_with_mixer(options.count, &options.to_mixer()?)
+    }
+
+    /// Generates multiple passwords using a fully configured mixer.
+    ///
+    /// Use this instead of [`generate`](Self::generate) to reuse a mixer that is already
+    /// configured.
+    ///
+    /// ```
+    /// use penguin::{Penguin, mixer::{ComplexityLevel, PenguinMixer}};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Medium, true, 16);
+    /// mixer.suffix = String::from("!x");
+    /// let passwords = Penguin::new(vec!["hello", "world"])
+    ///     .generate_with_mixer(2, &mixer)
+    ///     .unwrap();
+    /// assert!(passwords.iter().all(|p| p.ends_with("!x")));
+    /// ```
+    pub fn generate_with_mixer(
+        &self,
+        count: usize,
+        mixer: &PenguinMixer,
+    ) -> Result<Vec<String>, PenguinError> {
+        self.generate_with_stats(count, mixer)
+            .map(|(passwords, _)| passwords)
+    }
+
+    /// Generates multiple passwords like [`generate_with_mixer`](Self::generate_with_mixer),
+    /// drawing every random choice from `rng`, so a seeded generator gives the same batch
+    /// every time; see [`with_rng`](entropy::with_rng).
+    ///
+    /// ```
+    /// use penguin::{Penguin, mixer::{ComplexityLevel, PenguinMixer}};
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha20Rng;
+    ///
+    /// let penguin = Penguin::new(vec!["glacier", "penguin"]);
+    /// let mixer = PenguinMixer::new(ComplexityLevel::Medium, true, 16);
+    /// let batch = |seed| {
+    ///     let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    ///     penguin.generate_with_rng(3, &mixer, &mut rng).unwrap()
+    /// };
+    /// assert_eq!(batch(1), batch(1));
+    /// ```
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        count: usize,
+        mixer: &PenguinMixer,
+        rng: &mut R,
+    ) -> Result<Vec<String>, PenguinError> {
+        entropy::with_rng(rng, || self.generate_with_mixer(count, mixer))
+    }
+
+    /// Generates multiple passwords like [`generate_with_mixer`](Self::generate_with_mixer)
+    /// as [`SecretString`](secret::SecretString)s, which are zeroed when dropped.
+    ///
+    /// ```
+    /// use penguin::{Penguin, mixer::PenguinMixer};
+    ///
+    /// let secrets = Penguin::new(vec!["glacier"])
+    ///     .generate_secrets(2, &PenguinMixer::default())
+    ///     .unwrap();
+    /// assert!(secrets.iter().all(|secret| secret.expose().contains(char::is_alphabetic)));
+    /// ```
+    pub fn generate_secrets(
+        &self,
+        count: usize,
+        mixer: &PenguinMixer,
+    ) -> Result<Vec<secret::SecretString>, PenguinError> {
+        (0..count)
+            .map(|_| mixer.mix_secret(&self.base_input))
+            .collect()
+    }
+
+    /// Generates multiple passwords like [`generate_with_mixer`](Self::generate_with_mixer),
+    /// each paired with its strength report, estimated with the base words by
+    /// [`estimate_entropy_with_words`](strength::estimate_entropy_with_words).
+    ///
+    /// ```
+    /// use penguin::{Penguin, mixer::{ComplexityLevel, PenguinMixer}};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 20);
+    /// mixer.min_entropy = Some(90.0);
+    /// let reported = Penguin::new(vec!["glacier", "penguin"])
+    ///     .generate_with_reports(3, &mixer)
+    ///     .unwrap();
+    /// assert!(reported.iter().all(|(_, report)| report.entropy_bits >= 90.0));
+    /// ```
+    #[cfg(feature = "analysis")]
+    pub fn generate_with_reports(
+        &self,
+        count: usize,
+        mixer: &PenguinMixer,
+    ) -> Result<Vec<(String, strength::StrengthReport)>, PenguinError> {
+        let passwords = self.generate_with_mixer(count, mixer)?;
+        Ok(passwords
+            .into_iter()
+            .map(|password| {
+                let report = strength::estimate_entropy_with_words(&password, &self.base_input);
+                (password, report)
+            })
+            .collect())
+    }
+
+    /// Generates multiple passwords that satisfy a policy, starting from the default
+    /// settings with the policy carried over by
+    /// [`PasswordPolicy::configure`](policy::PasswordPolicy::configure). Use
+    /// [`generate_with_mixer`](Self::generate_with_mixer) with a configured mixer to start
+    /// from other settings.
+    ///
+    /// Fails with [`PenguinError::InvalidConfig`] when no password can satisfy the policy.
+    ///
+    /// ```
+    /// use penguin::{Penguin, policy::PasswordPolicy};
+    ///
+    /// let policy = PasswordPolicy {
+    ///     min_digits: 2,
+    ///     max_specials: Some(0),
+    ///     excluded_chars: String::from("l1IO0"),
+    ///     ..PasswordPolicy::default()
+    /// };
+    /// let passwords = Penguin::new(vec!["glacier", "penguin"])
+    ///     .generate_with_policy(3, &policy)
+    ///     .unwrap();
+    /// assert!(passwords.iter().all(|p| policy.check(p)));
+    ///
+    /// let impossible = PasswordPolicy { min_digits: 3, max_digits: Some(2), ..policy };
+    /// assert!(Penguin::new(vec!["glacier"]).generate_with_policy(1, &impossible).is_err());
+    /// ```
+    pub fn generate_with_policy(
+        &self,
+        count: usize,
+        policy: &policy::PasswordPolicy,
+    ) -> Result<Vec<String>, PenguinError> {
+        policy
+            .validate()
+            .map_err(|err| PenguinError::InvalidConfig(err.to_string()))?;
+        let mut mixer = PenguinMixer::default();
+        policy.configure(&mut mixer);
+        self.generate_with_mixer(count, &mixer)
+    }
+
+    /// Generates diceware passphrases of `words_per_phrase` words each, joined by the
+    /// separator. The words are picked from the base words, or from the embedded
+    /// [`eff_large`](wordlist::eff_large) list when there are none, so no words have to
+    /// be supplied at all.
+    ///
+    /// Fails with [`PenguinError::InvalidConfig`] when `words_per_phrase` is zero.
+    ///
+    /// ```
+    /// use penguin::{passphrase::Capitalization, Penguin};
+    ///
+    /// let passphrases = Penguin::default()
+    ///     .generate_passphrase(3, 5, "-", Capitalization::Lower)
+    ///     .unwrap();
+    /// assert_eq!(passphrases.len(), 3);
+    /// assert!(passphrases.iter().all(|p| p.split('-').count() >= 5));
+    ///
+    /// let own = Penguin::new(vec!["ice", "floe"])
+    ///     .generate_passphrase(1, 4, " ", Capitalization::Upper)
+    ///     .unwrap();
+    /// assert!(own[0].split(' ').all(|word| word == "ICE" || word == "FLOE"));
+    /// ```
+    #[cfg(feature = "wordlists")]
+    pub fn generate_passphrase(
+        &self,
+        count: usize,
+        words_per_phrase: usize,
+        separator: &str,
+        capitalization: passphrase::Capitalization,
+    ) -> Result<Vec<String>, PenguinError> {
+        if words_per_phrase == 0 {
+            return Err(PenguinError::InvalidConfig(String::from(
+                "a passphrase needs at least one word",
+            )));
+        }
+        let list = if self.base_input.is_empty() {
+            wordlist::eff_large()
+        } else {
+            &self.base_input
         };
+        Ok((0..count)
+            .map(|_| passphrase::generate(list, words_per_phrase, capitalization).join(separator))
+            .collect())
+    }
 
+    /// Derives the password for a site from the master password with
+    /// [`derive::derive`]. The same master password, site, counter, and options always
+    /// give the same password, so nothing has to be stored. The master password is taken
+    /// as given, not split into base words, and both it and the password are
+    /// [`SecretString`](secret::SecretString)s, which are zeroed when dropped.
+    ///
+    /// ```
+    /// use penguin::{derive::DeriveOptions, secret::SecretString, Penguin};
+    ///
+    /// let master = SecretString::from(String::from("correct horse battery staple"));
+    /// let options = DeriveOptions::default();
+    /// let password = Penguin::derive_password(&master, "github.com", 1, &options).unwrap();
+    /// assert_eq!(password.expose(), "@EjSY!6t*bP7V6wP");
+    /// assert_eq!(password, Penguin::derive_password(&master, "github.com", 1, &options).unwrap());
+    /// ```
+    #[cfg(feature = "derive")]
+    pub fn derive_password(
+        master: &secret::SecretString,
+        site: &str,
+        counter: u32,
+        options: &derive::DeriveOptions,
+    ) -> Result<secret::SecretString, PenguinError> {
+        derive::derive(master.expose(), site, counter, options).map(secret::SecretString::new)
+    }
+
+    /// Generates multiple passwords like [`generate_with_mixer`](Self::generate_with_mixer)
+    /// and also returns how the batch was generated.
+    ///
+    /// ```
+    /// use penguin::{Penguin, mixer::{ComplexityLevel, PenguinMixer}};
+    ///
+    /// let mixer = PenguinMixer::new(ComplexityLevel::Medium, true, 16);
+    /// let (passwords, stats) = Penguin::new(vec!["hello", "world"])
+    ///     .generate_with_stats(3, &mixer)
+    ///     .unwrap();
+    /// assert_eq!(passwords.len(), 3);
+    /// assert_eq!(stats.passwords, 3);
+    /// assert!(stats.attempts >= 3);
+    /// ```
+    pub fn generate_with_stats(
+        &self,
+        count: usize,
+        mixer: &PenguinMixer,
+    ) -> Result<(Vec<String>, GenerationStats), PenguinError> {
+        let started = Instant::now();
         let mut collected = Vec::with_capacity(count);
+        let mut stats = GenerationStats::default();
         for _ in 0..count {
-            collected.push(mixer.mix_password(&self.base_input));
+            let (password, generated) = mixer.mix_password_stats(&self.base_input)?;
+            collected.push(password);
+            stats.merge(&generated);
         }
+        stats.elapsed = started.elapsed();
+
+        Ok((collected, stats))
+    }
+}
+
+/// Adds the words, skipping the ones that are empty or only whitespace. Use
+/// [`Penguin::add_words`] to have blank words reported instead.
+impl<'a> Extend<&'a str> for Penguin<'a> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        self.base_input
+            .extend(words.into_iter().filter(|word| !word.trim().is_empty()));
+    }
+}
 
-        collected
+/// Collects the words into a Penguin, skipping the ones that are empty or only whitespace.
+impl<'a> FromIterator<&'a str> for Penguin<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(words: I) -> Self {
+        let mut penguin = Penguin::default();
+        penguin.extend(words);
+        penguin
     }
 }
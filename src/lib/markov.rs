@@ -0,0 +1,135 @@
+//! Markov-model strength estimation for the Penguin password generator.
+//!
+//! Charset entropy assumes every character was chosen at random, which wildly overstates
+//! the strength of passwords built from words and common patterns. This module estimates
+//! how many guesses a password would take an attacker using a character-level Markov model
+//! trained on real leaked passwords, which is how modern crackers order their guesses.
+//!
+//! The model is a bigram table over lowercase letters, digits, and a single bucket for
+//! symbols, trained on an embedded sample of the most common leaked passwords plus the
+//! crate's own word lists. A password that appears in the sample itself is rated by its
+//! rank there, since crackers try those first. Otherwise the probability of a password under the model approximates the
+//! fraction of an attacker's guesses spent before reaching it, so `-log2` of it
+//! approximates the number of guesses in bits. Every uppercase letter costs one additional
+//! bit and every symbol five more, and the estimate never exceeds the charset entropy from
+//! [`strength::estimate_entropy`](crate::strength::estimate_entropy), since brute force
+//! is always available. The tables are only compiled in with the `markov` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::markov::guess_bits;
+//! use penguin::strength::estimate_entropy;
+//!
+//! // Charset entropy rates a common password as strong...
+//! assert!(estimate_entropy("password123").entropy_bits > 50.0);
+//! // ...but it follows the patterns a trained model expects.
+//! assert!(guess_bits("password123") < 10.0);
+//! assert!(guess_bits("penguinglacier") < estimate_entropy("penguinglacier").entropy_bits);
+//! assert!(guess_bits("password123") < guess_bits("xq7vzj2kpw9"));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::strength::estimate_entropy;
+use crate::wordlist::Category;
+
+/// Sample of the most frequent passwords in public breach corpora, one per line.
+const CORPUS: &str = include_str!("data/markov/passwords.txt");
+
+/// Letters, digits, the symbol bucket, and the start and end markers.
+const STATES: usize = 26 + 10 + 3;
+const SYMBOL: usize = 36;
+const START: usize = 37;
+const END: usize = 38;
+
+/// Added to every transition count so unseen transitions keep a small probability.
+const SMOOTHING: f64 = 0.01;
+
+/// Extra cost of choosing which of the roughly 33 symbols was used.
+const SYMBOL_BITS: f64 = 5.0;
+
+struct Model {
+    /// Negative log2 probability of moving from one state to the next.
+    bits: [[f64; STATES]; STATES],
+    /// Position of each sample password in the corpus, most common first.
+    ranks: HashMap<&'static str, usize>,
+}
+
+fn state(c: char) -> usize {
+    match c.to_ascii_lowercase() {
+        c @ 'a'..='z' => c as usize - 'a' as usize,
+        c @ '0'..='9' => 26 + c as usize - '0' as usize,
+        _ => SYMBOL,
+    }
+}
+
+fn model() -> &'static Model {
+    static MODEL: OnceLock<Model> = OnceLock::new();
+    MODEL.get_or_init(|| {
+        let mut counts = [[0u32; STATES]; STATES];
+        let samples = CORPUS
+            .lines()
+            .chain(Category::Adjective.words().iter().copied())
+            .chain(Category::Noun.words().iter().copied())
+            .chain(Category::Verb.words().iter().copied())
+            .map(str::trim)
+            .filter(|sample| !sample.is_empty());
+        for sample in samples {
+            let mut previous = START;
+            for c in sample.chars() {
+                let next = state(c);
+                counts[previous][next] += 1;
+                previous = next;
+            }
+            counts[previous][END] += 1;
+        }
+
+        let mut bits = [[0.0; STATES]; STATES];
+        for (from, row) in counts.iter().enumerate() {
+            let total: f64 =
+                row.iter().map(|&count| count as f64).sum::<f64>() + SMOOTHING * STATES as f64;
+            for (to, &count) in row.iter().enumerate() {
+                bits[from][to] = -((count as f64 + SMOOTHING) / total).log2();
+            }
+        }
+        let mut ranks = HashMap::new();
+        for (rank, sample) in CORPUS.lines().map(str::trim).enumerate() {
+            ranks.entry(sample).or_insert(rank + 1);
+        }
+        Model { bits, ranks }
+    })
+}
+
+/// Estimates the number of guesses needed to find the password, in bits.
+///
+/// An attacker following the model would need roughly `2^bits` guesses. An empty
+/// password needs none.
+pub fn guess_bits(password: &str) -> f64 {
+    if password.is_empty() {
+        return 0.0;
+    }
+
+    let model = model();
+    if let Some(&rank) = model.ranks.get(password.to_lowercase().as_str()) {
+        return (rank as f64).log2() + password.chars().filter(|c| c.is_uppercase()).count() as f64;
+    }
+
+    let mut bits = 0.0;
+    let mut previous = START;
+    for c in password.chars() {
+        let next = state(c);
+        bits += model.bits[previous][next];
+        if c.is_uppercase() {
+            bits += 1.0;
+        }
+        if next == SYMBOL {
+            bits += SYMBOL_BITS;
+        }
+        previous = next;
+    }
+    bits += model.bits[previous][END];
+
+    bits.min(estimate_entropy(password).entropy_bits)
+}
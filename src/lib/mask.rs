@@ -0,0 +1,47 @@
+//! Masked display of passwords for the Penguin password generator.
+//!
+//! Screenshots, demos, and logs often need to show that a password exists and what it
+//! looks like without showing the password itself. [`mask`] keeps the first and last few
+//! characters and replaces every other one with [`MASK_CHAR`], so the length stays
+//! visible while the secret doesn't.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::mask::mask;
+//!
+//! assert_eq!(mask("aBcdefgh3!", 2, 2), "aB******3!");
+//! assert_eq!(mask("aBcdefgh3!", 0, 0), "**********");
+//! ```
+
+/// The character shown in place of each hidden character.
+pub const MASK_CHAR: char = '*';
+
+/// Masks all but the first `first` and last `last` characters of a password.
+///
+/// At least half of the password always stays hidden: when the requested characters
+/// would reveal more, fewer are shown, taking them from the end first.
+///
+/// ```
+/// use penguin::mask::mask;
+///
+/// assert_eq!(mask("hunter2!", 3, 3), "hun****!");
+/// assert_eq!(mask("abc", 2, 2), "a**");
+/// assert_eq!(mask("", 2, 2), "");
+/// ```
+pub fn mask(password: &str, first: usize, last: usize) -> String {
+    let length = password.chars().count();
+    let first = first.min(length / 2);
+    let last = last.min(length / 2 - first);
+    password
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if i < first || i >= length - last {
+                c
+            } else {
+                MASK_CHAR
+            }
+        })
+        .collect()
+}
@@ -11,7 +11,9 @@
 //! uses complete words from the input and adds separators between them (numbers and/or
 //! special characters). This creates more memorable passwords while maintaining security.
 //! The second approach breaks words into individual characters and mixes them randomly
-//! with numbers and special characters, providing higher entropy and randomness.
+//! with numbers and special characters, providing higher entropy and randomness. With
+//! [`GenerationStyle::Pronounceable`], a third approach builds pseudo-words from syllables
+//! anyone can read aloud and joins them the way the first approach joins words.
 //!
 //! # Complexity Levels
 //!
@@ -29,28 +31,43 @@
 //! use penguin::mixer::{PenguinMixer, ComplexityLevel};
 //!
 //! let mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 12);
-//! let password = mixer.mix_password(&vec!["hello", "world"]);
+//! let password = mixer.mix_password(&vec!["hello", "world"]).unwrap();
 //! // Might generate: "hello2world5"
 //! ```
 //!
 //! For a more secure password using character mixing:
 //!
 //! ```
+//! use penguin::mixer::{PenguinMixer, ComplexityLevel};
+//!
 //! let mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
-//! let password = mixer.mix_password(&vec!["penguin", "secure"]);
+//! let password = mixer.mix_password(&vec!["penguin", "secure"]).unwrap();
 //! // Might generate: "p3n@gu1nS#cur3"
 //! ```
 //!
 //! And for maximum security with a random password:
 //!
 //! ```
+//! use penguin::mixer::{PenguinMixer, ComplexityLevel};
+//!
 //! let mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 64);
-//! let password = mixer.mix_password(&vec!["not", "used"]);
+//! let password = mixer.mix_password(&vec!["not", "used"]).unwrap();
 //! // Generates a 64-character random string using all possible characters
 //! ```
 
 use rand::prelude::SliceRandom;
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+use crate::charset::Charset;
+use crate::personal::PersonalInfo;
+use crate::policy::PasswordPolicy;
+use crate::pronounce;
+use crate::secret::SecretString;
+use crate::{GenerationStats, PenguinError};
 
 // Character sets used for password generation
 const NUMBERS: &str = "0123456789";
@@ -58,6 +75,15 @@ const SPECIAL_CHARS: &str = "!@#$%^&*";
 const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
 const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
+/// Shortest length accepted for the word-based complexity levels.
+pub const MIN_LENGTH: usize = 4;
+
+/// Length of passwords generated at the Penguin complexity level.
+const PENGUIN_LENGTH: usize = 64;
+
+/// How many candidates are generated before giving up on avoiding forbidden substrings.
+const MAX_ATTEMPTS: usize = 1000;
+
 /// Defines the complexity level for password generation.
 ///
 /// The complexity levels provide different balances between security and memorability.
@@ -65,7 +91,10 @@ const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 /// level offers a good balance of security and usability for most purposes. Hard
 /// level provides high security while maintaining some structure. The Penguin level
 /// ensures maximum security through complete randomness.
-#[derive(Debug, Clone, Copy)]
+///
+/// Levels are ordered by strength, so `Basic` compares lowest and `Penguin` highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ComplexityLevel {
     Basic,   // Uses whole words + numbers
     Medium,  // Uses whole words + numbers + special chars
@@ -73,15 +102,571 @@ pub enum ComplexityLevel {
     Penguin, // Ultimate 64-char random password with all possible combinations
 }
 
+impl ComplexityLevel {
+    /// Every complexity level, weakest first.
+    ///
+    /// ```
+    /// use penguin::mixer::ComplexityLevel;
+    ///
+    /// for level in ComplexityLevel::ALL {
+    ///     println!("{:?}: {}", level, level.description());
+    /// }
+    /// assert!(ComplexityLevel::ALL.is_sorted());
+    /// assert!(ComplexityLevel::Hard > ComplexityLevel::Medium);
+    /// ```
+    pub const ALL: [ComplexityLevel; 4] = [
+        ComplexityLevel::Basic,
+        ComplexityLevel::Medium,
+        ComplexityLevel::Hard,
+        ComplexityLevel::Penguin,
+    ];
+
+    /// Describes what the level builds passwords from.
+    pub fn description(self) -> &'static str {
+        match self {
+            ComplexityLevel::Basic => "whole words and numbers",
+            ComplexityLevel::Medium => "whole words, numbers, and special characters",
+            ComplexityLevel::Hard => {
+                "characters mixed from the words, numbers, and special characters"
+            }
+            ComplexityLevel::Penguin => "64 fully random characters",
+        }
+    }
+}
+
+/// How the word-based complexity levels build a password; the Penguin level always
+/// draws random characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum GenerationStyle {
+    /// From the base words, whole or mixed as
+    /// [`use_whole_words`](PenguinMixer::use_whole_words) says.
+    #[default]
+    Words,
+    /// From pronounceable pseudo-words of two or three syllables, such as `brafoo`, joined
+    /// by the separators. Each syllable is one or two consonants followed by one or two
+    /// vowels, taken from the base words' own syllables half of the time when there are
+    /// any, and a pseudo-word may end in a consonant or two. Medium capitalizes every
+    /// pseudo-word and Hard a random third of the syllables. No base words are needed.
+    Pronounceable,
+}
+
+/// A class of characters the mixer can draw from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CharClass {
+    Lowercase,
+    Uppercase,
+    Digits,
+    Specials,
+}
+
+/// Every character class, in the order their characters are pooled.
+const ALL_CLASSES: [CharClass; 4] = [
+    CharClass::Lowercase,
+    CharClass::Uppercase,
+    CharClass::Digits,
+    CharClass::Specials,
+];
+
+impl CharClass {
+    /// Returns the class a character belongs to. Any character that is not a letter or
+    /// digit counts as special, while non-ASCII letters and digits belong to no class.
+    pub fn of(c: char) -> Option<CharClass> {
+        if c.is_ascii_lowercase() {
+            Some(CharClass::Lowercase)
+        } else if c.is_ascii_uppercase() {
+            Some(CharClass::Uppercase)
+        } else if c.is_ascii_digit() {
+            Some(CharClass::Digits)
+        } else if !c.is_alphanumeric() {
+            Some(CharClass::Specials)
+        } else {
+            None
+        }
+    }
+
+    /// Returns every character belonging to this class.
+    pub fn chars(self) -> &'static str {
+        match self {
+            CharClass::Lowercase => LOWERCASE,
+            CharClass::Uppercase => UPPERCASE,
+            CharClass::Digits => NUMBERS,
+            CharClass::Specials => SPECIAL_CHARS,
+        }
+    }
+}
+
+/// Parses the lowercase class names `lowercase`, `uppercase`, `digits`, and `specials`.
+impl FromStr for CharClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lowercase" => Ok(CharClass::Lowercase),
+            "uppercase" => Ok(CharClass::Uppercase),
+            "digits" => Ok(CharClass::Digits),
+            "specials" => Ok(CharClass::Specials),
+            _ => Err(format!(
+                "unknown character class '{}' (expected lowercase, uppercase, digits, or specials)",
+                s
+            )),
+        }
+    }
+}
+
+/// Controls what is inserted after each word when generating whole-word passwords.
+///
+/// By default the separator follows the complexity level: a digit for Basic, and a
+/// special character followed by a digit for Medium and Hard. A fixed separator inserts
+/// the same text every time, while a random separator inserts `count` characters drawn
+/// from the given classes.
+///
+/// ```
+/// use penguin::mixer::{ComplexityLevel, PenguinMixer, SeparatorPolicy};
+///
+/// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 11);
+/// mixer.separator = SeparatorPolicy::Fixed(String::from("-"));
+/// let password = mixer.mix_password(&["hello", "world"]).unwrap();
+/// assert!(password == "hello-world" || password == "world-hello");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SeparatorPolicy {
+    #[default]
+    ByComplexity,
+    Fixed(String),
+    Random {
+        classes: Vec<CharClass>,
+        count: usize,
+    },
+}
+
+/// Separators from fuzz input, with at most 255 random characters so a single
+/// candidate stays cheap to build.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SeparatorPolicy {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => SeparatorPolicy::ByComplexity,
+            1 => SeparatorPolicy::Fixed(u.arbitrary()?),
+            _ => SeparatorPolicy::Random {
+                classes: u.arbitrary()?,
+                count: usize::from(u.arbitrary::<u8>()?),
+            },
+        })
+    }
+}
+
+/// What whole-word mode does when every base word is longer than the length allows, so
+/// no word fits whole.
+///
+/// ```
+/// use penguin::mixer::{ComplexityLevel, PenguinMixer, ShortLength};
+///
+/// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 6);
+/// assert!(mixer.overflows(&["antarctica", "snowstorm"]));
+/// mixer.on_short_length = ShortLength::Error;
+/// assert!(mixer.validate(&["antarctica", "snowstorm"]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ShortLength {
+    /// Cut the first word at the length.
+    #[default]
+    Cut,
+    /// Shorten the word to its leading syllables, as
+    /// [`abbreviate`](PenguinMixer::abbreviate) does.
+    Abbreviate,
+    /// Mix the words' characters instead, as if whole words weren't requested.
+    Mix,
+    /// Fail with [`PenguinError::InvalidConfig`].
+    Error,
+}
+
+/// Parses `cut`, `abbreviate`, `mix`, and `error`.
+impl FromStr for ShortLength {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cut" => Ok(ShortLength::Cut),
+            "abbreviate" => Ok(ShortLength::Abbreviate),
+            "mix" => Ok(ShortLength::Mix),
+            "error" => Ok(ShortLength::Error),
+            _ => Err(format!(
+                "unknown short-length handling '{}' (expected cut, abbreviate, mix, or error)",
+                s
+            )),
+        }
+    }
+}
+
 /// Main password mixer struct that handles password generation with various settings.
 ///
 /// The PenguinMixer combines the input words and complexity settings to generate
 /// passwords. It can be configured to use either whole words or mix individual
 /// characters, and supports different complexity levels and length requirements.
+///
+/// The prefix and suffix are attached to every generated password and count towards
+/// its length, so a 16 character password with a 3 character prefix has 13 generated
+/// characters. In whole-word mode, the separator policy decides what follows each word.
+///
+/// Excluded character classes never appear in the output: they are left out of every
+/// character pool, and their characters are dropped from the base words as well. This
+/// makes it possible to target systems that only accept alphanumeric passwords.
+///
+/// ```
+/// use penguin::mixer::{CharClass, ComplexityLevel, PenguinMixer};
+///
+/// let mut mixer = PenguinMixer::new(ComplexityLevel::Medium, false, 20);
+/// mixer.excluded_classes = vec![CharClass::Specials];
+/// let password = mixer.mix_password(&["snow", "fall!"]).unwrap();
+/// assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+/// ```
+///
+/// Each entry of `required_chars` is a group of characters of which at least one must
+/// appear in every password. A group of a single character requires that exact
+/// character. Every group is matched by a character of its own, so listing a group
+/// twice requires two of its characters. Missing groups are satisfied by replacing
+/// randomly chosen characters of the generated part, so the password keeps its length.
+///
+/// ```
+/// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+///
+/// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, false, 12);
+/// mixer.required_chars = vec![String::from("@"), String::from("XYZ")];
+/// let password = mixer.mix_password(&["glacier"]).unwrap();
+/// assert!(password.contains('@'));
+/// assert!(password.contains(|c| "XYZ".contains(c)));
+/// assert_eq!(password.len(), 12);
+/// ```
+///
+/// Mixers can be cloned and compared, so an application can keep profiles of settings
+/// and tell them apart. Their `Debug` output leaves out the prefix, suffix, forbidden
+/// substrings, and personal details, which end up in or describe the passwords, so a
+/// mixer can be logged.
+///
+/// ```
+/// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+///
+/// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+/// mixer.prefix = String::from("acme-");
+/// let profile = mixer.clone();
+/// assert_eq!(profile, mixer);
+/// assert_eq!(profile.length(), 16);
+/// assert_eq!(profile.complexity(), ComplexityLevel::Hard);
+/// assert!(!format!("{:?}", profile).contains("acme"));
+/// ```
+#[derive(Clone)]
 pub struct PenguinMixer {
     pub length: usize,
     pub complexity: ComplexityLevel,
     pub use_whole_words: bool,
+    pub prefix: String,
+    pub suffix: String,
+    pub separator: SeparatorPolicy,
+    pub excluded_classes: Vec<CharClass>,
+    pub required_chars: Vec<String>,
+    pub forbidden_substrings: Vec<String>,
+    pub reject_if: Vec<fn(&str) -> bool>,
+    /// The characters drawn from for the specials class; empty to leave the class out.
+    pub special_chars: String,
+    /// Characters that never appear in generated passwords, such as the easily confused
+    /// `l1IO0`. They are left out of every class and dropped from the base words.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 64);
+    /// mixer.excluded_chars = String::from("l1IO0");
+    /// let password = mixer.mix_password(&[]).unwrap();
+    /// assert!(!password.contains(|c| "l1IO0".contains(c)));
+    /// ```
+    pub excluded_chars: String,
+    /// The characters the penguin level draws from instead of its character classes, such
+    /// as [`Charset::BASE58`]. Characters of excluded classes are still left out.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    /// use penguin::Charset;
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 64);
+    /// mixer.charset = Some(Charset::BASE58);
+    /// let password = mixer.mix_password(&[]).unwrap();
+    /// assert_eq!(password.len(), 64);
+    /// assert!(password.chars().all(|c| Charset::BASE58.contains(c)));
+    /// ```
+    pub charset: Option<Charset>,
+    /// Reorders the generated part so letters come first, then digits, then specials,
+    /// each keeping their relative order. This minimizes layer switches on phone
+    /// keyboards at the cost of the entropy carried by the character order.
+    pub group_classes: bool,
+    /// A policy every returned password must pass; see
+    /// [`PasswordPolicy::configure`](crate::policy::PasswordPolicy::configure).
+    pub policy: Option<PasswordPolicy>,
+    /// In whole-word mode, picks words whose lengths, each with its separator, add up to
+    /// exactly the target length instead of filling up with random characters and
+    /// cutting the last word. Use [`fits_exactly`](Self::fits_exactly) to find out
+    /// beforehand whether any combination of the base words does.
+    pub exact_fit: bool,
+    /// Whether the password is built from the base words or from pronounceable syllables.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, GenerationStyle, PenguinMixer};
+    /// use penguin::pronounce::is_pronounceable;
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Medium, false, 16);
+    /// mixer.generation = GenerationStyle::Pronounceable;
+    /// for _ in 0..50 {
+    ///     let password = mixer.mix_password(&[]).unwrap();
+    ///     assert_eq!(password.len(), 16);
+    ///     assert!(password.starts_with(|c: char| c.is_ascii_uppercase()));
+    ///     assert!(is_pronounceable(&password), "{}", password);
+    /// }
+    ///
+    /// let seeded = mixer.mix_password(&["glacier", "penguin"]).unwrap();
+    /// assert!(is_pronounceable(&seeded));
+    /// ```
+    pub generation: GenerationStyle,
+    /// In whole-word mode, lets a password run up to this many characters past the target
+    /// length so the last word stays whole; words that would run further are skipped.
+    /// `None` cuts every password to the target length, splitting the last word if needed.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer, SeparatorPolicy};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 10);
+    /// mixer.separator = SeparatorPolicy::Fixed(String::from("-"));
+    /// assert_eq!(mixer.mix_password(&["glacier", "icefloe"]).unwrap().len(), 10);
+    ///
+    /// mixer.max_overshoot = Some(6);
+    /// let password = mixer.mix_password(&["glacier", "icefloe"]).unwrap();
+    /// assert!(password == "glacier-icefloe-" || password == "icefloe-glacier-");
+    /// ```
+    pub max_overshoot: Option<usize>,
+    /// In whole-word mode, shortens a word that would run past the length budget to its
+    /// leading syllables, or failing that its first characters, instead of cutting it or
+    /// falling back to random filler. Abbreviations are at least two characters long.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer, SeparatorPolicy};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 12);
+    /// mixer.separator = SeparatorPolicy::Fixed(String::from("-"));
+    /// mixer.abbreviate = true;
+    /// let password = mixer.mix_password(&["glacier", "penguin"]).unwrap();
+    /// assert!(password == "glacier-pen-" || password == "penguin-gla-");
+    /// ```
+    pub abbreviate: bool,
+    /// In whole-word mode, the fewest base words each password is built from. Words keep
+    /// being added until there are this many, even past the target length.
+    pub min_words: usize,
+    /// What whole-word mode does when no base word fits in the length; see
+    /// [`overflows`](Self::overflows).
+    pub on_short_length: ShortLength,
+    /// The most keys a keyboard walk like `qwerty` or `1qaz` may be long; candidates with
+    /// longer walks on any layout are generated again. See
+    /// [`keyboard_walks`](crate::layout::keyboard_walks).
+    ///
+    /// ```
+    /// use penguin::layout::longest_walk;
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+    /// mixer.max_walk = Some(3);
+    /// let password = mixer.mix_password(&["glacier"]).unwrap();
+    /// assert!(longest_walk(&password) <= 3);
+    /// assert!(!mixer.complies("qwer-glacier-Ab1"));
+    /// ```
+    pub max_walk: Option<usize>,
+    /// Generates candidates again while they contain digits that read as a year, a day
+    /// and month, or a month and year; see [`find_dates`](crate::dates::find_dates).
+    pub avoid_dates: bool,
+    /// Names, birthdays, and other details no password may contain, even in another
+    /// case or with digits and symbols standing in for letters.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+    /// mixer.personal.add("Jane Doe");
+    /// let password = mixer.mix_password(&["jane", "glacier"]).unwrap();
+    /// assert!(!mixer.personal.contains(&password));
+    /// assert!(!mixer.complies("j4ne-glacier-Ab1"));
+    /// ```
+    pub personal: PersonalInfo,
+    /// How many candidates to generate before giving up on the forbidden substrings,
+    /// `reject_if` predicates, and policy; a thousand by default.
+    pub max_attempts: usize,
+    /// The fewest bits of entropy a password may have, estimated with the base words it
+    /// is generated from by
+    /// [`estimate_entropy_with_words`](crate::strength::estimate_entropy_with_words);
+    /// weaker candidates are generated again.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    /// use penguin::strength::estimate_entropy_with_words;
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+    /// mixer.min_entropy = Some(80.0);
+    /// let password = mixer.mix_password(&["glacier"]).unwrap();
+    /// assert!(estimate_entropy_with_words(&password, &["glacier"]).entropy_bits >= 80.0);
+    /// ```
+    #[cfg(feature = "analysis")]
+    pub min_entropy: Option<f64>,
+    /// A regular expression every password is generated to match, in place of the
+    /// complexity level and base words; see
+    /// [`generate_matching`](crate::regexgen::generate_matching). The length serves as the
+    /// hint for open-ended repetitions. The prefix, suffix, and required characters don't
+    /// apply, while forbidden substrings, `reject_if`, and the policy still do.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, false, 12);
+    /// mixer.pattern = Some(String::from("[A-Z][a-z]+[0-9]{2}"));
+    /// let password = mixer.mix_password(&[]).unwrap();
+    /// assert_eq!(password.len(), 12);
+    /// assert!(mixer.complies(&password));
+    /// ```
+    pub pattern: Option<String>,
+}
+
+/// Mixers from fuzz input, for exercising [`mix_password`](PenguinMixer::mix_password)
+/// with hostile settings. Lengths, word counts, and the attempt budget stay below 256 so
+/// a single call stays fast, and `reject_if` is always empty.
+///
+/// ```
+/// # #[cfg(feature = "arbitrary")] {
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use penguin::mixer::PenguinMixer;
+///
+/// let mut input = Unstructured::new(&[7; 64]);
+/// let mixer = PenguinMixer::arbitrary(&mut input).unwrap();
+/// assert!(mixer.length < 256 && mixer.max_attempts < 256);
+/// let _ = mixer.mix_password(&["glacier", "\u{0}\u{7f}"]);
+/// # }
+/// ```
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PenguinMixer {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let small = |u: &mut arbitrary::Unstructured<'a>| u.arbitrary::<u8>().map(usize::from);
+        Ok(PenguinMixer {
+            length: small(u)?,
+            complexity: u.arbitrary()?,
+            use_whole_words: u.arbitrary()?,
+            prefix: u.arbitrary()?,
+            suffix: u.arbitrary()?,
+            separator: u.arbitrary()?,
+            excluded_classes: u.arbitrary()?,
+            required_chars: u.arbitrary()?,
+            forbidden_substrings: u.arbitrary()?,
+            reject_if: Vec::new(),
+            special_chars: u.arbitrary()?,
+            excluded_chars: u.arbitrary()?,
+            charset: u
+                .arbitrary::<Option<String>>()?
+                .map(|chars| Charset::new(&chars)),
+            group_classes: u.arbitrary()?,
+            policy: u.arbitrary()?,
+            exact_fit: u.arbitrary()?,
+            generation: u.arbitrary()?,
+            max_overshoot: u.arbitrary::<Option<u8>>()?.map(usize::from),
+            abbreviate: u.arbitrary()?,
+            min_words: small(u)?,
+            on_short_length: u.arbitrary()?,
+            max_walk: u.arbitrary::<Option<u8>>()?.map(usize::from),
+            avoid_dates: u.arbitrary()?,
+            personal: {
+                let mut personal = PersonalInfo::new();
+                for detail in u.arbitrary::<Vec<&str>>()? {
+                    personal.add(detail);
+                }
+                personal
+            },
+            max_attempts: small(u)?,
+            #[cfg(feature = "analysis")]
+            min_entropy: u.arbitrary::<Option<u8>>()?.map(f64::from),
+            pattern: None,
+        })
+    }
+}
+
+impl fmt::Debug for PenguinMixer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = |text: &str| if text.is_empty() { "" } else { "<redacted>" };
+        let mut debug = f.debug_struct("PenguinMixer");
+        debug
+            .field("length", &self.length)
+            .field("complexity", &self.complexity)
+            .field("use_whole_words", &self.use_whole_words)
+            .field("prefix", &redacted(&self.prefix))
+            .field("suffix", &redacted(&self.suffix))
+            .field("separator", &self.separator)
+            .field("excluded_classes", &self.excluded_classes)
+            .field("required_chars", &self.required_chars)
+            .field("forbidden_substrings", &self.forbidden_substrings.len())
+            .field("reject_if", &self.reject_if.len())
+            .field("special_chars", &self.special_chars)
+            .field("excluded_chars", &self.excluded_chars)
+            .field("charset", &self.charset)
+            .field("group_classes", &self.group_classes)
+            .field("policy", &self.policy)
+            .field("exact_fit", &self.exact_fit)
+            .field("generation", &self.generation)
+            .field("max_overshoot", &self.max_overshoot)
+            .field("abbreviate", &self.abbreviate)
+            .field("min_words", &self.min_words)
+            .field("on_short_length", &self.on_short_length)
+            .field("max_walk", &self.max_walk)
+            .field("avoid_dates", &self.avoid_dates)
+            .field("personal", &self.personal)
+            .field("max_attempts", &self.max_attempts);
+        #[cfg(feature = "analysis")]
+        debug.field("min_entropy", &self.min_entropy);
+        debug.field("pattern", &self.pattern).finish()
+    }
+}
+
+/// The `reject_if` predicates compare by address, so two mixers holding the same
+/// function agree while two identical closures may not.
+impl PartialEq for PenguinMixer {
+    fn eq(&self, other: &Self) -> bool {
+        let same = self.length == other.length
+            && self.complexity == other.complexity
+            && self.use_whole_words == other.use_whole_words
+            && self.prefix == other.prefix
+            && self.suffix == other.suffix
+            && self.separator == other.separator
+            && self.excluded_classes == other.excluded_classes
+            && self.required_chars == other.required_chars
+            && self.forbidden_substrings == other.forbidden_substrings
+            && self.reject_if.len() == other.reject_if.len()
+            && self
+                .reject_if
+                .iter()
+                .zip(&other.reject_if)
+                .all(|(a, b)| std::ptr::fn_addr_eq(*a, *b))
+            && self.special_chars == other.special_chars
+            && self.excluded_chars == other.excluded_chars
+            && self.charset == other.charset
+            && self.group_classes == other.group_classes
+            && self.policy == other.policy
+            && self.exact_fit == other.exact_fit
+            && self.generation == other.generation
+            && self.max_overshoot == other.max_overshoot
+            && self.abbreviate == other.abbreviate
+            && self.min_words == other.min_words
+            && self.on_short_length == other.on_short_length
+            && self.max_walk == other.max_walk
+            && self.avoid_dates == other.avoid_dates
+            && self.personal == other.personal
+            && self.max_attempts == other.max_attempts
+            && self.pattern == other.pattern;
+        #[cfg(feature = "analysis")]
+        let same = same && self.min_entropy == other.min_entropy;
+        same
+    }
 }
 
 /// Default implementation providing medium complexity with whole words and 12 character length.
@@ -96,101 +681,988 @@ impl Default for PenguinMixer {
             length: 12,
             complexity: ComplexityLevel::Medium,
             use_whole_words: true,
+            prefix: String::new(),
+            suffix: String::new(),
+            separator: SeparatorPolicy::default(),
+            excluded_classes: Vec::new(),
+            required_chars: Vec::new(),
+            forbidden_substrings: Vec::new(),
+            reject_if: Vec::new(),
+            special_chars: String::from(SPECIAL_CHARS),
+            excluded_chars: String::new(),
+            charset: None,
+            group_classes: false,
+            policy: None,
+            exact_fit: false,
+            generation: GenerationStyle::Words,
+            max_overshoot: None,
+            abbreviate: false,
+            min_words: 0,
+            on_short_length: ShortLength::Cut,
+            max_walk: None,
+            avoid_dates: false,
+            personal: PersonalInfo::new(),
+            max_attempts: MAX_ATTEMPTS,
+            #[cfg(feature = "analysis")]
+            min_entropy: None,
+            pattern: None,
         }
     }
 }
 
 impl PenguinMixer {
-    /// Creates a new PenguinMixer with custom settings.
+    /// Returns the target length of the passwords.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the complexity level passwords are generated at.
+    pub fn complexity(&self) -> ComplexityLevel {
+        self.complexity
+    }
+
+    /// Returns whether passwords are built from whole base words.
+    pub fn whole_words(&self) -> bool {
+        self.use_whole_words
+    }
+
+    /// Creates a new PenguinMixer with custom settings, without checking them; see
+    /// [`try_new`](Self::try_new).
     pub fn new(complexity: ComplexityLevel, use_whole_words: bool, length: usize) -> Self {
         Self {
             complexity,
             use_whole_words,
             length,
+            prefix: String::new(),
+            suffix: String::new(),
+            separator: SeparatorPolicy::default(),
+            excluded_classes: Vec::new(),
+            required_chars: Vec::new(),
+            forbidden_substrings: Vec::new(),
+            reject_if: Vec::new(),
+            special_chars: String::from(SPECIAL_CHARS),
+            excluded_chars: String::new(),
+            charset: None,
+            group_classes: false,
+            policy: None,
+            exact_fit: false,
+            generation: GenerationStyle::Words,
+            max_overshoot: None,
+            abbreviate: false,
+            min_words: 0,
+            on_short_length: ShortLength::Cut,
+            max_walk: None,
+            avoid_dates: false,
+            personal: PersonalInfo::new(),
+            max_attempts: MAX_ATTEMPTS,
+            #[cfg(feature = "analysis")]
+            min_entropy: None,
+            pattern: None,
         }
     }
 
+    /// Creates a new PenguinMixer like [`new`](Self::new), failing with
+    /// [`PenguinError::InvalidConfig`] when the length is below [`MIN_LENGTH`].
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// assert!(PenguinMixer::try_new(ComplexityLevel::Hard, false, 16).is_ok());
+    /// assert!(PenguinMixer::try_new(ComplexityLevel::Hard, false, 0).is_err());
+    /// ```
+    pub fn try_new(
+        complexity: ComplexityLevel,
+        use_whole_words: bool,
+        length: usize,
+    ) -> Result<Self, PenguinError> {
+        let mixer = Self::new(complexity, use_whole_words, length);
+        mixer.check_length()?;
+        Ok(mixer)
+    }
+
+    /// Returns whether some combination of the base words, each followed by its
+    /// separator, fills the target length exactly, as [`exact_fit`](Self::exact_fit)
+    /// needs.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer, SeparatorPolicy};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 12);
+    /// mixer.separator = SeparatorPolicy::Fixed(String::from("-"));
+    /// mixer.exact_fit = true;
+    /// assert!(mixer.fits_exactly(&["ice", "floe", "penguin"]));
+    /// let password = mixer.mix_password(&["ice", "floe", "penguin"]).unwrap();
+    /// assert!(password == "ice-penguin-" || password == "penguin-ice-");
+    ///
+    /// mixer.length = 7;
+    /// assert!(!mixer.fits_exactly(&["ice", "floe", "penguin"]));
+    /// ```
+    pub fn fits_exactly(&self, base_input: &[&str]) -> bool {
+        let length = self
+            .length
+            .saturating_sub(self.prefix.len() + self.suffix.len());
+        let order: Vec<usize> = (0..base_input.len()).collect();
+        self.fit_words(base_input, &order, length)
+            .is_some_and(|reachable| reachable[0][length])
+    }
+
+    /// Returns the characters this mixer draws from for the given class, without the
+    /// excluded ones.
+    fn class_chars(&self, class: CharClass) -> Cow<'_, str> {
+        let chars = match class {
+            CharClass::Specials => &self.special_chars,
+            class => class.chars(),
+        };
+        if chars.contains(|c| self.excluded_chars.contains(c)) {
+            Cow::Owned(
+                chars
+                    .chars()
+                    .filter(|c| !self.excluded_chars.contains(*c))
+                    .collect(),
+            )
+        } else {
+            Cow::Borrowed(chars)
+        }
+    }
+
+    /// Returns whether characters of the given class may appear in generated passwords.
+    /// An empty [`special_chars`](Self::special_chars) leaves the specials class out.
+    pub fn allows(&self, class: CharClass) -> bool {
+        !self.excluded_classes.contains(&class) && !self.class_chars(class).is_empty()
+    }
+
     /// Main password generation method that handles both Penguin and regular complexity levels.
-    /// Returns an empty string if no input words are provided.
-    pub fn mix_password(&self, base_input: &Vec<&str>) -> String {
-        if base_input.is_empty() {
-            return String::new();
+    ///
+    /// Blank base words are ignored. The word-based levels fail with
+    /// [`PenguinError::NoWords`] when no word has a character they may use; the Penguin
+    /// level doesn't need any words.
+    ///
+    /// The prefix and suffix are attached after generation, and the generated part is
+    /// shortened by their combined length so the whole password keeps its target length.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+    /// mixer.prefix = String::from("A-");
+    /// let password = mixer.mix_password(&["glacier"]).unwrap();
+    /// assert!(password.starts_with("A-"));
+    /// assert_eq!(password.len(), 16);
+    /// ```
+    ///
+    /// Candidates containing any of the forbidden substrings, compared case-insensitively,
+    /// matching any of the `reject_if` predicates, or failing the policy are discarded and
+    /// generated again. If no acceptable candidate turns up within
+    /// [`max_attempts`](Self::max_attempts), for example because a whole base word
+    /// contains a forbidden substring, [`PenguinError::Exhausted`] says which constraints
+    /// rejected the candidates.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    /// use penguin::PenguinError;
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Medium, false, 16);
+    /// mixer.forbidden_substrings = vec![String::from("ACME")];
+    /// let password = mixer.mix_password(&["acme", "corp"]).unwrap();
+    /// assert!(!password.to_lowercase().contains("acme"));
+    ///
+    /// mixer.reject_if.push(|password| !password.contains('!'));
+    /// assert!(mixer.mix_password(&["acme", "corp"]).unwrap().contains('!'));
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 12);
+    /// mixer.forbidden_substrings = vec![String::from("acme")];
+    /// mixer.max_attempts = 50;
+    /// assert_eq!(
+    ///     mixer.mix_password(&["acme"]),
+    ///     Err(PenguinError::Exhausted {
+    ///         attempts: 50,
+    ///         rejections: vec![("forbidden substring", 50)],
+    ///     })
+    /// );
+    /// ```
+    pub fn mix_password(&self, base_input: &[&str]) -> Result<String, PenguinError> {
+        self.mix_password_counted(base_input)
+            .map(|(password, _)| password)
+    }
+
+    /// Generates a password like [`mix_password`](Self::mix_password), drawing every
+    /// random choice from `rng` instead of the active entropy source; see
+    /// [`with_rng`](crate::entropy::with_rng).
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha20Rng;
+    ///
+    /// let mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 64);
+    /// let first = mixer.mix_password_with_rng(&[], &mut ChaCha20Rng::seed_from_u64(42));
+    /// let again = mixer.mix_password_with_rng(&[], &mut ChaCha20Rng::seed_from_u64(42));
+    /// assert_eq!(first.unwrap(), again.unwrap());
+    ///
+    /// let os = mixer.mix_password_with_rng(&[], &mut rand::rngs::OsRng).unwrap();
+    /// assert_eq!(os.len(), 64);
+    /// ```
+    pub fn mix_password_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        base_input: &[&str],
+        rng: &mut R,
+    ) -> Result<String, PenguinError> {
+        crate::entropy::with_rng(rng, || self.mix_password(base_input))
+    }
+
+    /// Generates a password like [`mix_password`](Self::mix_password) as a
+    /// [`SecretString`], which is zeroed when dropped.
+    pub fn mix_secret(&self, base_input: &[&str]) -> Result<SecretString, PenguinError> {
+        self.mix_password(base_input).map(SecretString::new)
+    }
+
+    /// Generates a password like [`mix_password`](Self::mix_password) and also returns
+    /// how many candidates were rejected along the way.
+    ///
+    /// Every rejection is reported as a `tracing` event at the trace level, with the
+    /// reason it was rejected; the candidates themselves are never logged.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 8);
+    /// mixer.reject_if.push(|password| !password.starts_with('!'));
+    /// let (password, rejected) = mixer.mix_password_counted(&["glacier"]).unwrap();
+    /// assert!(password.starts_with('!'));
+    /// assert!(rejected < 1000);
+    /// ```
+    pub fn mix_password_counted(
+        &self,
+        base_input: &[&str],
+    ) -> Result<(String, usize), PenguinError> {
+        self.mix_password_stats(base_input)
+            .map(|(password, stats)| (password, stats.rejected()))
+    }
+
+    /// Generates a password like [`mix_password`](Self::mix_password) and also returns
+    /// how it was generated; see [`GenerationStats`].
+    pub fn mix_password_stats(
+        &self,
+        base_input: &[&str],
+    ) -> Result<(String, GenerationStats), PenguinError> {
+        self.validate(base_input)?;
+        let words: Vec<&str> = base_input
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let base_input = words.as_slice();
+
+        let forbidden: Vec<String> = self
+            .forbidden_substrings
+            .iter()
+            .filter(|substring| !substring.is_empty())
+            .map(|substring| substring.to_lowercase())
+            .collect();
+
+        let started = std::time::Instant::now();
+        let drawn = crate::entropy::bytes_drawn();
+        let mut stats = GenerationStats::default();
+        for attempt in 0..self.max_attempts {
+            stats.attempts += 1;
+            let mut password = self.generate_candidate(base_input);
+            let mut lowered = password.to_lowercase();
+            let rejection = if forbidden
+                .iter()
+                .any(|substring| lowered.contains(substring))
+            {
+                Some("forbidden substring")
+            } else if self.reject_if.iter().any(|reject| reject(&password)) {
+                Some("filter")
+            } else if self.walks_too_far(&password) {
+                Some("keyboard walk")
+            } else if self.has_avoided_date(&password) {
+                Some("date")
+            } else if self.personal.contains(&password) {
+                Some("personal information")
+            } else if self.too_weak(&password, base_input) {
+                Some("low entropy")
+            } else if self
+                .policy
+                .as_ref()
+                .is_some_and(|policy| !policy.check(&password))
+            {
+                Some("policy violation")
+            } else {
+                None
+            };
+            // Candidates are wiped once judged, so rejected ones don't linger in memory.
+            lowered.zeroize();
+            match rejection {
+                Some(reason) => {
+                    tracing::trace!(attempt, reason, "rejected a candidate");
+                    stats.reject(reason);
+                    password.zeroize();
+                }
+                None => {
+                    tracing::debug!(
+                        attempts = attempt + 1,
+                        length = password.chars().count(),
+                        "generated a password"
+                    );
+                    stats.passwords = 1;
+                    stats.rng_bytes = crate::entropy::bytes_drawn() - drawn;
+                    stats.elapsed = started.elapsed();
+                    return Ok((password, stats));
+                }
+            }
+        }
+        tracing::warn!(
+            attempts = self.max_attempts,
+            "gave up: every candidate was rejected"
+        );
+        Err(PenguinError::Exhausted {
+            attempts: self.max_attempts,
+            rejections: stats.rejections,
+        })
+    }
+
+    /// Checks that the settings and base words can produce proper passwords: the length
+    /// is at least [`MIN_LENGTH`] and leaves room after the prefix and suffix, and at
+    /// least one word (at least [`min_words`](Self::min_words) in whole-word mode) has a
+    /// non-blank character of an allowed class. The Penguin level ignores both the length
+    /// and the words.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    /// use penguin::PenguinError;
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Basic, true, 12);
+    /// assert_eq!(mixer.validate(&["  ", ""]), Err(PenguinError::NoWords));
+    /// assert!(mixer.validate(&["glacier"]).is_ok());
+    ///
+    /// mixer.min_words = 2;
+    /// assert!(matches!(mixer.validate(&["glacier"]), Err(PenguinError::InvalidConfig(_))));
+    ///
+    /// let mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 64);
+    /// assert_eq!(mixer.mix_password(&[]).unwrap().len(), 64);
+    /// ```
+    pub fn validate(&self, base_input: &[&str]) -> Result<(), PenguinError> {
+        if let Some(pattern) = &self.pattern {
+            return crate::regexgen::generate_matching(pattern, self.length)
+                .map(|_| ())
+                .map_err(|err| PenguinError::InvalidConfig(format!("pattern: {}", err)));
+        }
+        if matches!(self.complexity, ComplexityLevel::Penguin) {
+            return Ok(());
+        }
+        self.check_length()?;
+
+        if self.generation == GenerationStyle::Pronounceable {
+            let (onsets, nuclei, _) = self.syllable_tables();
+            if onsets.is_empty() || nuclei.is_empty() {
+                return Err(PenguinError::InvalidConfig(String::from(
+                    "pronounceable passwords need consonants and vowels that aren't excluded",
+                )));
+            }
+            return Ok(());
         }
 
-        match self.complexity {
-            ComplexityLevel::Penguin => Self::generate_penguin_password(),
-            _ => self.generate_regular_password(base_input),
+        let usable = base_input
+            .iter()
+            .filter(|word| {
+                word.chars()
+                    .any(|c| !c.is_whitespace() && self.allows_char(c))
+            })
+            .count();
+        if usable == 0 {
+            return Err(PenguinError::NoWords);
         }
+        if self.on_short_length == ShortLength::Error && self.overflows(base_input) {
+            return Err(PenguinError::InvalidConfig(format!(
+                "every base word is longer than the {} characters available for it",
+                self.length - self.prefix.len() - self.suffix.len()
+            )));
+        }
+        if self.use_whole_words && self.min_words > usable {
+            return Err(PenguinError::InvalidConfig(format!(
+                "min_words is {} but only {} usable base words were given",
+                self.min_words, usable
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns whether whole-word mode would have no base word that fits: every word
+    /// with usable characters is longer than the length left after the prefix and suffix.
+    /// [`on_short_length`](Self::on_short_length) decides what happens then.
+    pub fn overflows(&self, base_input: &[&str]) -> bool {
+        let budget = self
+            .length
+            .saturating_sub(self.prefix.len() + self.suffix.len());
+        self.overflows_budget(base_input, budget)
     }
 
-    /// Generates a maximum-security 64-character password using all possible character types.
+    fn overflows_budget(&self, base_input: &[&str], budget: usize) -> bool {
+        if !self.use_whole_words
+            || self.generation == GenerationStyle::Pronounceable
+            || matches!(self.complexity, ComplexityLevel::Penguin)
+        {
+            return false;
+        }
+        let mut lengths = base_input
+            .iter()
+            .map(|word| {
+                word.trim()
+                    .chars()
+                    .filter(|c| self.allows_char(*c))
+                    .map(char::len_utf8)
+                    .sum::<usize>()
+            })
+            .filter(|&length| length > 0)
+            .peekable();
+        lengths.peek().is_some() && lengths.all(|length| length > budget)
+    }
+
+    /// Checks the length on its own, for construction time.
+    fn check_length(&self) -> Result<(), PenguinError> {
+        if matches!(self.complexity, ComplexityLevel::Penguin) {
+            return Ok(());
+        }
+        if self.length < MIN_LENGTH {
+            return Err(PenguinError::InvalidConfig(format!(
+                "length must be at least {}, got {}",
+                MIN_LENGTH, self.length
+            )));
+        }
+        let reserved = self.prefix.len() + self.suffix.len();
+        if reserved >= self.length {
+            return Err(PenguinError::InvalidConfig(format!(
+                "the prefix and suffix take up {} of the {} characters, leaving none to generate",
+                reserved, self.length
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns whether a password satisfies every constraint of this mixer: the target
+    /// length, the affixes, the excluded classes, the required characters, the forbidden
+    /// substrings, the longest keyboard walk, dates, personal information, and the
+    /// `reject_if` predicates.
+    ///
+    /// Passwords from [`mix_password`](Self::mix_password) comply by construction; this
+    /// is meant for auditing batches of passwords or checking ones produced elsewhere.
+    ///
+    /// ```
+    /// use penguin::mixer::{CharClass, ComplexityLevel, PenguinMixer};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 8);
+    /// mixer.excluded_classes.push(CharClass::Digits);
+    /// assert!(mixer.complies("ab!cd@ef"));
+    /// assert!(!mixer.complies("ab1cd@ef"));
+    /// assert!(!mixer.complies("ab!cd@e"));
+    /// ```
+    pub fn complies(&self, password: &str) -> bool {
+        let lowered = password.to_lowercase();
+        let shaped = match &self.pattern {
+            Some(pattern) => crate::regexgen::is_match(pattern, password),
+            None => self.has_shape(password),
+        };
+        shaped
+            && !self.forbidden_substrings.iter().any(|substring| {
+                !substring.is_empty() && lowered.contains(&substring.to_lowercase())
+            })
+            && !self.reject_if.iter().any(|reject| reject(password))
+            && !self.walks_too_far(password)
+            && !self.has_avoided_date(password)
+            && !self.personal.contains(password)
+            && self
+                .policy
+                .as_ref()
+                .is_none_or(|policy| policy.check(password))
+    }
+
+    /// Returns whether a password has a keyboard walk longer than [`max_walk`](Self::max_walk).
+    fn walks_too_far(&self, password: &str) -> bool {
+        self.max_walk
+            .is_some_and(|max| crate::layout::longest_walk(password) > max)
+    }
+
+    /// Returns whether a password falls short of [`min_entropy`](Self::min_entropy).
+    #[cfg(feature = "analysis")]
+    fn too_weak(&self, password: &str, base_input: &[&str]) -> bool {
+        self.min_entropy.is_some_and(|min| {
+            crate::strength::estimate_entropy_with_words(password, base_input).entropy_bits < min
+        })
+    }
+
+    #[cfg(not(feature = "analysis"))]
+    fn too_weak(&self, _password: &str, _base_input: &[&str]) -> bool {
+        false
+    }
+
+    /// Returns whether a password has a date while [`avoid_dates`](Self::avoid_dates) is set.
+    fn has_avoided_date(&self, password: &str) -> bool {
+        self.avoid_dates && crate::dates::contains_date(password)
+    }
+
+    /// Returns whether a password has the length, characters, and required characters
+    /// the settings call for.
+    fn has_shape(&self, password: &str) -> bool {
+        let target = match self.complexity {
+            ComplexityLevel::Penguin => PENGUIN_LENGTH,
+            _ => self.length,
+        };
+        let Some(body) = password
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|rest| rest.strip_suffix(self.suffix.as_str()))
+        else {
+            return false;
+        };
+
+        let target = target.max(self.prefix.len() + self.suffix.len());
+        let ceiling = match self.complexity {
+            ComplexityLevel::Penguin => target,
+            _ if self.use_whole_words && self.min_words > 0 => usize::MAX,
+            _ => target + self.overshoot(),
+        };
+
+        (target..=ceiling).contains(&password.len())
+            && body.chars().all(|c| self.allows_char(c))
+            && self
+                .required_chars
+                .iter()
+                .all(|group| group.is_empty() || group.chars().any(|c| password.contains(c)))
+    }
+
+    /// Generates a single password, without checking it against forbidden substrings.
+    fn generate_candidate(&self, base_input: &[&str]) -> String {
+        if let Some(pattern) = &self.pattern {
+            // validate has already shown that the pattern can be matched.
+            return crate::regexgen::generate_matching(pattern, self.length)
+                .map(|matched| matched.password)
+                .unwrap_or_default();
+        }
+        let reserved = self.prefix.len() + self.suffix.len();
+        let password = match self.complexity {
+            ComplexityLevel::Penguin => {
+                self.generate_penguin_password(PENGUIN_LENGTH.saturating_sub(reserved))
+            }
+            _ if self.generation == GenerationStyle::Pronounceable => self
+                .generate_pronounceable_password(base_input, self.length.saturating_sub(reserved)),
+            _ => self.generate_regular_password(base_input, self.length.saturating_sub(reserved)),
+        };
+        let mut password = self.insert_required_chars(self.cap_classes(password));
+        if self.group_classes {
+            password = group_by_class(password);
+        }
+
+        // Every step above wipes the string it replaces, and this last copy is no
+        // exception, so only the candidate holds the password.
+        let mut candidate =
+            String::with_capacity(self.prefix.len() + password.len() + self.suffix.len());
+        candidate.push_str(&self.prefix);
+        candidate.push_str(&password);
+        candidate.push_str(&self.suffix);
+        password.zeroize();
+        candidate
+    }
+
+    /// Replaces randomly chosen characters of classes over the policy's maximums with
+    /// characters of classes still under theirs, so the password keeps its length.
+    fn cap_classes(&self, password: String) -> String {
+        let Some(policy) = &self.policy else {
+            return password;
+        };
+        if ALL_CLASSES
+            .iter()
+            .all(|class| policy.max_count(*class).is_none())
+        {
+            return password;
+        }
+
+        let mut rng = crate::entropy::rng();
+        let mut chars = into_chars(password, 0);
+        let mut counts = ALL_CLASSES.map(|class| {
+            chars
+                .iter()
+                .filter(|c| CharClass::of(**c) == Some(class))
+                .count()
+        });
+        let room = |counts: &[usize; 4], i: usize| {
+            policy
+                .max_count(ALL_CLASSES[i])
+                .is_none_or(|max| counts[i] < max)
+        };
+        let mut positions: Vec<usize> = (0..chars.len()).collect();
+        positions.shuffle(&mut rng);
+        for position in positions {
+            let Some(over) = CharClass::of(chars[position])
+                .map(|class| ALL_CLASSES.iter().position(|c| *c == class).unwrap_or(0))
+                .filter(|&i| {
+                    policy
+                        .max_count(ALL_CLASSES[i])
+                        .is_some_and(|max| counts[i] > max)
+                })
+            else {
+                continue;
+            };
+            let open: Vec<usize> = (0..ALL_CLASSES.len())
+                .filter(|&i| i != over && room(&counts, i) && self.allows(ALL_CLASSES[i]))
+                .collect();
+            let Some(&under) = open.choose(&mut rng) else {
+                break;
+            };
+            chars[position] = random_char(&self.class_chars(ALL_CLASSES[under]), &mut rng);
+            counts[over] -= 1;
+            counts[under] += 1;
+        }
+        from_chars(chars)
+    }
+
+    /// Makes sure every required group is represented in the password.
+    ///
+    /// One existing occurrence of each satisfied group is protected, then every missing
+    /// group replaces a random unprotected character. Groups already present in the
+    /// prefix or suffix need no replacement. If the password runs out of characters to
+    /// replace, the remaining groups are appended.
+    fn insert_required_chars(&self, password: String) -> String {
+        if self.required_chars.is_empty() {
+            return password;
+        }
+
+        let mut rng = crate::entropy::rng();
+        let mut chars = into_chars(password, self.required_chars.len());
+        let mut protected = vec![false; chars.len()];
+        let mut missing = Vec::new();
+
+        for group in self.required_chars.iter().filter(|group| !group.is_empty()) {
+            let in_affix = group
+                .chars()
+                .any(|c| self.prefix.contains(c) || self.suffix.contains(c));
+            if in_affix {
+                continue;
+            }
+            let present = (0..chars.len()).find(|&i| !protected[i] && group.contains(chars[i]));
+            match present {
+                Some(position) => protected[position] = true,
+                None => missing.push(group.as_str()),
+            }
+        }
+
+        let mut free: Vec<usize> = (0..chars.len()).filter(|&i| !protected[i]).collect();
+        free.shuffle(&mut rng);
+        for group in missing {
+            let replacement = random_char(group, &mut rng);
+            match free.pop() {
+                Some(position) => chars[position] = replacement,
+                None => chars.push(replacement),
+            }
+        }
+
+        from_chars(chars)
+    }
+
+    /// Collects the characters of the given classes, leaving out excluded classes.
+    fn pool(&self, classes: &[CharClass]) -> Vec<char> {
+        classes
+            .iter()
+            .filter(|class| self.allows(**class))
+            .flat_map(|class| self.class_chars(*class).chars().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Returns whether a character taken from the base words may be used.
+    fn allows_char(&self, c: char) -> bool {
+        !self.excluded_chars.contains(c) && CharClass::of(c).is_none_or(|class| self.allows(class))
+    }
+
+    /// Generates a maximum-security password using all possible character types.
     /// This method ignores the input words and generates a completely random password.
-    fn generate_penguin_password() -> String {
-        let mut rng = rand::thread_rng();
-        let all_chars = format!("{}{}{}{}", LOWERCASE, UPPERCASE, NUMBERS, SPECIAL_CHARS);
-        let chars: Vec<char> = all_chars.chars().collect();
+    fn generate_penguin_password(&self, length: usize) -> String {
+        let chars = match &self.charset {
+            Some(charset) => charset
+                .as_str()
+                .chars()
+                .filter(|&c| self.allows_char(c))
+                .collect(),
+            None => self.pool(&ALL_CLASSES),
+        };
+        crate::sample(&chars, length)
+    }
+
+    /// How far past the target length a password may run.
+    fn overshoot(&self) -> usize {
+        match self.max_overshoot {
+            Some(extra) if self.use_whole_words && !self.exact_fit => extra,
+            _ => 0,
+        }
+    }
+
+    /// Returns how many characters [`push_separator`](Self::push_separator) adds.
+    fn separator_len(&self) -> usize {
+        match &self.separator {
+            SeparatorPolicy::ByComplexity => match self.complexity {
+                ComplexityLevel::Basic => usize::from(self.allows(CharClass::Digits)),
+                ComplexityLevel::Medium | ComplexityLevel::Hard => {
+                    usize::from(self.allows(CharClass::Specials))
+                        + usize::from(self.allows(CharClass::Digits))
+                }
+                ComplexityLevel::Penguin => 0,
+            },
+            SeparatorPolicy::Fixed(separator) => separator.len(),
+            SeparatorPolicy::Random { classes, count } => {
+                if self.pool(classes).is_empty() {
+                    0
+                } else {
+                    *count
+                }
+            }
+        }
+    }
 
-        let mut password = String::with_capacity(64);
-        for _ in 0..64 {
-            password.push(chars[rng.gen_range(0..chars.len())]);
+    /// Tabulates which lengths the words can still fill: `reachable[i][n]` is whether
+    /// some of the words `order[i..]`, each followed by its separator, add up to exactly
+    /// `n` characters. Returns `None` when only the empty combination is possible.
+    fn fit_words(
+        &self,
+        base_input: &[&str],
+        order: &[usize],
+        length: usize,
+    ) -> Option<Vec<Vec<bool>>> {
+        let separator = self.separator_len();
+        let costs: Vec<usize> = order
+            .iter()
+            .map(|&i| {
+                base_input[i]
+                    .chars()
+                    .filter(|c| self.allows_char(*c))
+                    .map(char::len_utf8)
+                    .sum::<usize>()
+                    + separator
+            })
+            .collect();
+        if costs.iter().all(|&cost| cost == 0) {
+            return None;
+        }
+
+        let mut reachable = vec![vec![false; length + 1]; order.len() + 1];
+        reachable[order.len()][0] = true;
+        for i in (0..order.len()).rev() {
+            for n in 0..=length {
+                reachable[i][n] = reachable[i + 1][n]
+                    || (costs[i] > 0 && costs[i] <= n && reachable[i + 1][n - costs[i]]);
+            }
+        }
+        Some(reachable)
+    }
+
+    /// Builds a whole-word password of exactly `length` characters, or an empty string
+    /// when no combination of the words fits.
+    fn generate_fitted_password(&self, base_input: &[&str], length: usize) -> String {
+        let mut rng = crate::entropy::rng();
+        let mut order: Vec<usize> = (0..base_input.len()).collect();
+        order.shuffle(&mut rng);
+        let Some(reachable) = self.fit_words(base_input, &order, length) else {
+            return String::new();
+        };
+        if !reachable[0][length] {
+            return String::new();
+        }
+
+        let separator = self.separator_len();
+        let mut password = String::with_capacity(length);
+        let mut remaining = length;
+        for (i, &index) in order.iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+            let word: String = base_input[index]
+                .chars()
+                .filter(|c| self.allows_char(*c))
+                .collect();
+            let cost = word.len() + separator;
+            let take = cost > 0 && cost <= remaining && reachable[i + 1][remaining - cost];
+            let skip = reachable[i + 1][remaining];
+            if take && (!skip || rng.gen_bool(0.5)) {
+                password.push_str(&word);
+                self.push_separator(&mut password, &mut rng);
+                remaining -= cost;
+            }
+        }
+        password
+    }
+
+    /// Appends the separator that follows each word in whole-word mode.
+    fn push_separator(&self, password: &mut String, rng: &mut impl Rng) {
+        match &self.separator {
+            SeparatorPolicy::ByComplexity => match self.complexity {
+                ComplexityLevel::Basic => {
+                    if self.allows(CharClass::Digits) {
+                        password.push(random_char(&self.class_chars(CharClass::Digits), rng));
+                    }
+                }
+                ComplexityLevel::Medium | ComplexityLevel::Hard => {
+                    if self.allows(CharClass::Specials) {
+                        password.push(random_char(&self.class_chars(CharClass::Specials), rng));
+                    }
+                    if self.allows(CharClass::Digits) {
+                        password.push(random_char(&self.class_chars(CharClass::Digits), rng));
+                    }
+                }
+                ComplexityLevel::Penguin => unreachable!(),
+            },
+            SeparatorPolicy::Fixed(separator) => password.push_str(separator),
+            SeparatorPolicy::Random { classes, count } => {
+                let chars = self.pool(classes);
+                if chars.is_empty() {
+                    return;
+                }
+                for _ in 0..*count {
+                    password.push(chars[rng.gen_range(0..chars.len())]);
+                }
+            }
+        }
+    }
+
+    /// Returns the onsets, nuclei, and codas of [`pronounce`] without any excluded character.
+    fn syllable_tables(&self) -> (Vec<&'static str>, Vec<&'static str>, Vec<&'static str>) {
+        let usable = |pieces: &[&'static str]| -> Vec<&'static str> {
+            pieces
+                .iter()
+                .copied()
+                .filter(|piece| piece.chars().all(|c| self.allows_char(c)))
+                .collect()
+        };
+        (
+            usable(&pronounce::ONSETS),
+            usable(&pronounce::NUCLEI),
+            usable(&pronounce::CODAS),
+        )
+    }
+
+    /// Builds a password of pronounceable pseudo-words joined by separators, as described
+    /// at [`GenerationStyle::Pronounceable`], and cuts it to the length.
+    fn generate_pronounceable_password(&self, base_input: &[&str], length: usize) -> String {
+        let mut rng = crate::entropy::rng();
+        let (onsets, nuclei, codas) = self.syllable_tables();
+        let seeded: Vec<String> = base_input
+            .iter()
+            .flat_map(|word| pronounce::syllables_of(word))
+            .filter(|syllable| syllable.chars().all(|c| self.allows_char(c)))
+            .collect();
+        let capitals = self.allows(CharClass::Uppercase);
+        let capitalize = |syllable: &str| {
+            let mut chars = syllable.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        };
+
+        let mut password = String::new();
+        // The coda ending the last pseudo-word, dropped when no separator follows it so
+        // that it can't run into the next onset.
+        let mut coda = "";
+        while password.chars().count() < length {
+            if !password.is_empty() {
+                let before = password.len();
+                self.push_separator(&mut password, &mut rng);
+                if password.len() == before {
+                    password.truncate(password.len() - coda.len());
+                }
+            }
+            for i in 0..rng.gen_range(2..=3) {
+                let syllable = if !seeded.is_empty() && rng.gen_bool(0.5) {
+                    seeded[rng.gen_range(0..seeded.len())].clone()
+                } else {
+                    format!(
+                        "{}{}",
+                        onsets[rng.gen_range(0..onsets.len())],
+                        nuclei[rng.gen_range(0..nuclei.len())]
+                    )
+                };
+                let capital = capitals
+                    && match self.complexity {
+                        ComplexityLevel::Medium => i == 0,
+                        ComplexityLevel::Hard => rng.gen_bool(1.0 / 3.0),
+                        _ => false,
+                    };
+                if capital {
+                    password.push_str(&capitalize(&syllable));
+                } else {
+                    password.push_str(&syllable);
+                }
+            }
+            coda = if !codas.is_empty() && rng.gen_bool(0.5) {
+                codas[rng.gen_range(0..codas.len())]
+            } else {
+                ""
+            };
+            password.push_str(coda);
+        }
+        if let Some((cut, _)) = password.char_indices().nth(length) {
+            password.truncate(cut);
         }
         password
     }
 
     /// Generates passwords based on input words with various complexity levels.
     /// Supports both whole-word and character mixing approaches.
-    fn generate_regular_password(&self, base_input: &Vec<&str>) -> String {
-        let mut rng = rand::thread_rng();
+    fn generate_regular_password(&self, base_input: &[&str], length: usize) -> String {
+        let mut rng = crate::entropy::rng();
         let mut password = String::new();
+        // The end of the last word that `min_words` requires, which is never cut off.
+        let mut floor = 0;
+
+        let overflow = self.overflows_budget(base_input, length);
+        let whole_words =
+            self.use_whole_words && !(overflow && self.on_short_length == ShortLength::Mix);
+        let abbreviate =
+            self.abbreviate || (overflow && self.on_short_length == ShortLength::Abbreviate);
 
-        if self.use_whole_words {
+        if whole_words && self.exact_fit {
+            password = self.generate_fitted_password(base_input, length);
+        } else if whole_words {
             // Create a vector of available indices
             let mut available_indices: Vec<usize> = (0..base_input.len()).collect();
             available_indices.shuffle(&mut rng);
 
-            // Use whole words approach
+            // Use whole words approach, dropping characters of excluded classes
+            let separator = self.separator_len();
+            let limit = length + self.overshoot();
             let mut index = 0;
-            while password.len() < self.length && index < available_indices.len() {
+            let mut used = 0;
+            while (password.len() < length || used < self.min_words)
+                && index < available_indices.len()
+            {
                 let word = base_input[available_indices[index]];
-                password.push_str(word);
                 index += 1;
-
-                // Add separators based on complexity
-                match self.complexity {
-                    ComplexityLevel::Basic => {
-                        password.push(
-                            NUMBERS
-                                .chars()
-                                .nth(rng.gen_range(0..NUMBERS.len()))
-                                .unwrap(),
-                        );
+                let mut word: String = word.chars().filter(|c| self.allows_char(*c)).collect();
+                if used < self.min_words {
+                    if !word.is_empty() {
+                        used += 1;
                     }
-                    ComplexityLevel::Medium | ComplexityLevel::Hard => {
-                        password.push(
-                            SPECIAL_CHARS
-                                .chars()
-                                .nth(rng.gen_range(0..SPECIAL_CHARS.len()))
-                                .unwrap(),
-                        );
-                        password.push(
-                            NUMBERS
-                                .chars()
-                                .nth(rng.gen_range(0..NUMBERS.len()))
-                                .unwrap(),
-                        );
+                    password.push_str(&word);
+                    floor = password.len();
+                    self.push_separator(&mut password, &mut rng);
+                    continue;
+                }
+                if password.len() + word.len() + separator > limit {
+                    if abbreviate {
+                        let budget = limit.saturating_sub(password.len() + separator);
+                        word = abbreviation(&word, budget).to_string();
+                        if word.is_empty() {
+                            continue;
+                        }
+                    } else if self.max_overshoot.is_some() {
+                        continue;
                     }
-                    ComplexityLevel::Penguin => unreachable!(),
                 }
+                password.push_str(&word);
+
+                self.push_separator(&mut password, &mut rng);
             }
 
             // If we've used all words but still haven't reached desired length,
             // fill the rest with random characters
-            if password.len() < self.length {
-                let all_chars = format!("{}{}{}{}", LOWERCASE, UPPERCASE, NUMBERS, SPECIAL_CHARS);
-                let chars: Vec<char> = all_chars.chars().collect();
-                while password.len() < self.length {
-                    password.push(chars[rng.gen_range(0..chars.len())]);
-                }
+            let chars = self.pool(&ALL_CLASSES);
+            while password.len() < length && !chars.is_empty() {
+                password.push(chars[rng.gen_range(0..chars.len())]);
             }
         } else {
             // Mix characters approach
@@ -202,70 +1674,116 @@ impl PenguinMixer {
             for &idx in &available_indices {
                 combined.push_str(base_input[idx]);
             }
-            let chars: Vec<char> = combined.chars().collect();
-
-            while password.len() < self.length {
-                match self.complexity {
-                    ComplexityLevel::Basic => {
-                        if password.len() % 4 == 0 {
-                            password.push(
-                                NUMBERS
-                                    .chars()
-                                    .nth(rng.gen_range(0..NUMBERS.len()))
-                                    .unwrap(),
-                            );
-                        } else if !chars.is_empty() {
-                            password.push(chars[rng.gen_range(0..chars.len())]);
-                        } else {
-                            // If we've used all chars, use random ones
-                            password.push(
-                                LOWERCASE
-                                    .chars()
-                                    .nth(rng.gen_range(0..LOWERCASE.len()))
-                                    .unwrap(),
-                            );
-                        }
+            let chars: Vec<char> = combined.chars().filter(|c| self.allows_char(*c)).collect();
+
+            // If we've used all chars, use random ones, preferring lowercase letters
+            let mut fallback = self.pool(&[CharClass::Lowercase]);
+            if fallback.is_empty() {
+                fallback = self.pool(&ALL_CLASSES);
+            }
+
+            while password.len() < length {
+                let slot = match self.complexity {
+                    ComplexityLevel::Basic if password.len().is_multiple_of(4) => {
+                        Some(CharClass::Digits)
                     }
+                    ComplexityLevel::Basic => None,
                     ComplexityLevel::Medium | ComplexityLevel::Hard => match password.len() % 4 {
-                        0 => password.push(
-                            SPECIAL_CHARS
-                                .chars()
-                                .nth(rng.gen_range(0..SPECIAL_CHARS.len()))
-                                .unwrap(),
-                        ),
-                        1 => password.push(
-                            NUMBERS
-                                .chars()
-                                .nth(rng.gen_range(0..NUMBERS.len()))
-                                .unwrap(),
-                        ),
-                        _ => {
-                            if !chars.is_empty() {
-                                password.push(chars[rng.gen_range(0..chars.len())]);
-                            } else {
-                                // If we've used all chars, use random ones
-                                password.push(
-                                    LOWERCASE
-                                        .chars()
-                                        .nth(rng.gen_range(0..LOWERCASE.len()))
-                                        .unwrap(),
-                                );
-                            }
-                        }
+                        0 => Some(CharClass::Specials),
+                        1 => Some(CharClass::Digits),
+                        _ => None,
                     },
                     ComplexityLevel::Penguin => unreachable!(),
+                };
+
+                match slot.filter(|class| self.allows(*class)) {
+                    Some(class) => password.push(random_char(&self.class_chars(class), &mut rng)),
+                    None if !chars.is_empty() => {
+                        password.push(chars[rng.gen_range(0..chars.len())])
+                    }
+                    None if !fallback.is_empty() => {
+                        password.push(fallback[rng.gen_range(0..fallback.len())])
+                    }
+                    None => break,
                 }
             }
         }
 
-        // Trim to exact length and shuffle if using Hard complexity
-        password.truncate(self.length);
+        // Trim to exact length, keeping multi-byte characters whole, and shuffle if using
+        // Hard complexity
+        let mut cut = (length + self.overshoot()).max(floor);
+        if cut < password.len() {
+            while !password.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            password.truncate(cut);
+        }
         if matches!(self.complexity, ComplexityLevel::Hard) {
-            let mut password_chars: Vec<char> = password.chars().collect();
-            password_chars.shuffle(&mut rng);
-            password = password_chars.into_iter().collect();
+            let mut chars = into_chars(password, 0);
+            chars.shuffle(&mut rng);
+            password = from_chars(chars);
         }
 
         password
     }
 }
+
+/// Stable-sorts characters into letters, then digits, then everything else.
+fn group_by_class(password: String) -> String {
+    let mut chars = into_chars(password, 0);
+    chars.sort_by_key(|c| match CharClass::of(*c) {
+        Some(CharClass::Lowercase | CharClass::Uppercase) | None => 0,
+        Some(CharClass::Digits) => 1,
+        Some(CharClass::Specials) => 2,
+    });
+    from_chars(chars)
+}
+
+/// Moves the characters of the password into a vector with room for `extra` more, and
+/// wipes the string.
+fn into_chars(mut password: String, extra: usize) -> Vec<char> {
+    let mut chars = Vec::with_capacity(password.len() + extra);
+    chars.extend(password.chars());
+    password.zeroize();
+    chars
+}
+
+/// Collects the characters into a string allocated at their size, so that growing it
+/// leaves no copies behind, and wipes the vector.
+fn from_chars(mut chars: Vec<char>) -> String {
+    let mut password = String::with_capacity(chars.iter().map(|c| c.len_utf8()).sum());
+    password.extend(chars.iter());
+    chars.zeroize();
+    password
+}
+
+/// Returns the longest prefix of `word` that fits in `budget` bytes, preferring one that
+/// ends on a syllable boundary (a consonant after a vowel), or an empty string when fewer
+/// than two characters fit.
+fn abbreviation(word: &str, budget: usize) -> &str {
+    let is_vowel = |c: char| "aeiouyAEIOUY".contains(c);
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    let fitting = chars
+        .iter()
+        .map(|&(i, c)| i + c.len_utf8())
+        .take_while(|&end| end <= budget)
+        .count();
+    if fitting < 2 {
+        return "";
+    }
+
+    let syllable = (2..=fitting)
+        .rev()
+        .find(|&n| !is_vowel(chars[n - 1].1) && is_vowel(chars[n - 2].1));
+    let end = syllable.unwrap_or(fitting);
+    let (start, c) = chars[end - 1];
+    &word[..start + c.len_utf8()]
+}
+
+/// Picks a random character from a character set.
+fn random_char(chars: &str, rng: &mut impl Rng) -> char {
+    chars
+        .chars()
+        .nth(rng.gen_range(0..chars.chars().count()))
+        .unwrap()
+}
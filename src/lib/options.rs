@@ -0,0 +1,153 @@
+//! Settings for a batch of passwords, given one at a time.
+//!
+//! [`GenerateOptions`] names every setting it changes, so a call reads as what it asks
+//! for, and a new setting is one more method instead of one more argument for every
+//! caller to fill in. It starts from the same defaults as
+//! [`PenguinMixer::default`]: one password of medium complexity, built from whole words,
+//! 12 characters long. Options are set by value and can be built once and passed to
+//! [`Penguin::generate`](crate::Penguin::generate) as often as needed.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::{mixer::ComplexityLevel, GenerateOptions, Penguin};
+//!
+//! let penguin = Penguin::new(vec!["glacier", "penguin"]);
+//! let options = GenerateOptions::new()
+//!     .count(3)
+//!     .complexity(ComplexityLevel::Hard)
+//!     .whole_words(false)
+//!     .length(16);
+//! let first = penguin.generate(&options).unwrap();
+//! let second = penguin.generate(&options.clone().count(2)).unwrap();
+//! assert_eq!(first.len(), 3);
+//! assert_eq!(second.len(), 2);
+//! assert!(first.iter().all(|password| password.len() == 16));
+//! ```
+
+use crate::mixer::{ComplexityLevel, PenguinMixer, SeparatorPolicy};
+use crate::policy::PasswordPolicy;
+use crate::PenguinError;
+
+/// The settings of a batch of passwords.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateOptions {
+    pub(crate) count: usize,
+    mixer: PenguinMixer,
+    policy: Option<PasswordPolicy>,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            count: 1,
+            mixer: PenguinMixer::default(),
+            policy: None,
+        }
+    }
+}
+
+impl GenerateOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many passwords are generated.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the complexity level.
+    pub fn complexity(mut self, complexity: ComplexityLevel) -> Self {
+        self.mixer.complexity = complexity;
+        self
+    }
+
+    /// Sets the length of the passwords.
+    pub fn length(mut self, length: usize) -> Self {
+        self.mixer.length = length;
+        self
+    }
+
+    /// Sets whether passwords are built from whole base words or from their characters.
+    pub fn whole_words(mut self, whole_words: bool) -> Self {
+        self.mixer.use_whole_words = whole_words;
+        self
+    }
+
+    /// Sets what goes between the words of whole-word passwords.
+    ///
+    /// ```
+    /// use penguin::{mixer::{ComplexityLevel, SeparatorPolicy}, GenerateOptions, Penguin};
+    ///
+    /// let options = GenerateOptions::new()
+    ///     .complexity(ComplexityLevel::Basic)
+    ///     .separator(SeparatorPolicy::Fixed(String::from("-")));
+    /// let passwords = Penguin::new(vec!["ice", "floe"]).generate(&options).unwrap();
+    /// assert!(passwords[0].contains('-'));
+    /// ```
+    pub fn separator(mut self, separator: SeparatorPolicy) -> Self {
+        self.mixer.separator = separator;
+        self
+    }
+
+    /// Sets text every password starts with.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.mixer.prefix = prefix.into();
+        self
+    }
+
+    /// Sets text every password ends with.
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.mixer.suffix = suffix.into();
+        self
+    }
+
+    /// Sets a policy every password satisfies, carried over to the mixer with
+    /// [`PasswordPolicy::configure`] after the other settings.
+    ///
+    /// ```
+    /// use penguin::{policy::PasswordPolicy, GenerateOptions, Penguin};
+    ///
+    /// let policy = PasswordPolicy { min_digits: 3, ..PasswordPolicy::default() };
+    /// let options = GenerateOptions::new().count(2).policy(policy.clone());
+    /// let passwords = Penguin::new(vec!["glacier"]).generate(&options).unwrap();
+    /// assert!(passwords.iter().all(|password| policy.check(password)));
+    /// ```
+    pub fn policy(mut self, policy: PasswordPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Replaces every setting but the count and policy with those of the mixer, for
+    /// settings without a method of their own.
+    ///
+    /// ```
+    /// use penguin::{mixer::{ComplexityLevel, PenguinMixer}, GenerateOptions, Penguin};
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 12);
+    /// mixer.avoid_dates = true;
+    /// let options = GenerateOptions::new().mixer(mixer).suffix("!x");
+    /// let passwords = Penguin::new(vec!["glacier"]).generate(&options).unwrap();
+    /// assert!(passwords[0].ends_with("!x"));
+    /// ```
+    pub fn mixer(mut self, mixer: PenguinMixer) -> Self {
+        self.mixer = mixer;
+        self
+    }
+
+    /// Builds the mixer the passwords are generated with, failing with
+    /// [`PenguinError::InvalidConfig`] when the policy can't be satisfied.
+    pub fn to_mixer(&self) -> Result<PenguinMixer, PenguinError> {
+        let mut mixer = self.mixer.clone();
+        if let Some(policy) = &self.policy {
+            policy
+                .validate()
+                .map_err(|err| PenguinError::InvalidConfig(err.to_string()))?;
+            policy.configure(&mut mixer);
+        }
+        Ok(mixer)
+    }
+}
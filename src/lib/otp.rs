@@ -0,0 +1,191 @@
+//! One-time password secrets and codes for two-factor authentication.
+//!
+//! This module generates TOTP shared secrets (RFC 6238) and builds the `otpauth://` URIs
+//! that authenticator apps accept, either pasted directly or scanned from a QR code.
+//! Secrets are drawn from the [`entropy`](crate::entropy) generator and encoded
+//! as unpadded base32, the encoding authenticator apps expect. It can also compute the
+//! codes for a secret, which is handy for scripted logins where a phone isn't available.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::otp::{generate_secret, TotpUri};
+//!
+//! let secret = generate_secret();
+//! let uri = TotpUri::new(&secret, "Acme", "alice@acme.test").to_string();
+//! assert!(uri.starts_with("otpauth://totp/Acme:alice%40acme.test?secret="));
+//! ```
+
+use crate::PenguinError;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use std::fmt;
+
+/// Secret size in bytes; RFC 4226 recommends 160 bits, matching the HMAC-SHA1 block.
+const SECRET_BYTES: usize = 20;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Generates a new random TOTP secret, encoded as unpadded base32.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    crate::entropy::rng().fill_bytes(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// Encodes bytes as RFC 4648 base32 without padding.
+///
+/// ```
+/// assert_eq!(penguin::otp::base32_encode(b"foobar"), "MZXW6YTBOI");
+/// ```
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+/// Decodes RFC 4648 base32, ignoring case, spaces, hyphens, and trailing padding.
+/// Returns `None` if the text contains characters outside the base32 alphabet.
+///
+/// ```
+/// assert_eq!(penguin::otp::base32_decode("mzxw 6ytb oi======").unwrap(), b"foobar");
+/// ```
+pub fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.trim_end_matches('=').chars() {
+        if c == ' ' || c == '-' {
+            continue;
+        }
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&symbol| symbol as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+/// Longest code [`hotp`] computes. Codes are taken from a 31-bit number, so longer ones
+/// would only add leading zeros.
+pub const MAX_DIGITS: u32 = 10;
+
+/// Computes an HOTP code (RFC 4226) for the given key and counter.
+///
+/// Fails with [`PenguinError::InvalidConfig`] unless `digits` is between 1 and
+/// [`MAX_DIGITS`].
+pub fn hotp(key: &[u8], counter: u64, digits: u32) -> Result<String, PenguinError> {
+    if !(1..=MAX_DIGITS).contains(&digits) {
+        return Err(PenguinError::InvalidConfig(format!(
+            "OTP codes have 1 to {} digits, not {}",
+            MAX_DIGITS, digits
+        )));
+    }
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    let code = binary as u64 % 10u64.pow(digits);
+    Ok(format!("{:0width$}", code, width = digits as usize))
+}
+
+/// Computes the TOTP code (RFC 6238) valid at the given Unix time.
+///
+/// Fails with [`PenguinError::InvalidConfig`] when `period` is zero, or when `digits`
+/// is out of range as for [`hotp`].
+///
+/// ```
+/// use penguin::otp::totp;
+///
+/// // Test vector from RFC 6238, appendix B.
+/// assert_eq!(totp(b"12345678901234567890", 59, 30, 8).unwrap(), "94287082");
+/// assert!(totp(b"12345678901234567890", 59, 0, 8).is_err());
+/// assert!(totp(b"12345678901234567890", 59, 30, 20).is_err());
+/// ```
+pub fn totp(key: &[u8], unix_time: u64, period: u64, digits: u32) -> Result<String, PenguinError> {
+    if period == 0 {
+        return Err(PenguinError::InvalidConfig(String::from(
+            "the TOTP period must be at least one second",
+        )));
+    }
+    hotp(key, unix_time / period, digits)
+}
+
+/// The parameters of a TOTP enrollment, rendered as an `otpauth://totp/` URI.
+///
+/// Digits and period default to the values every authenticator app supports: six
+/// digit codes that change every 30 seconds, computed with HMAC-SHA1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TotpUri {
+    pub secret: String,
+    pub issuer: String,
+    pub account: String,
+    pub digits: u32,
+    pub period: u64,
+}
+
+impl TotpUri {
+    /// Creates an enrollment URI with the default six digits and 30 second period.
+    pub fn new(secret: &str, issuer: &str, account: &str) -> Self {
+        Self {
+            secret: secret.to_string(),
+            issuer: issuer.to_string(),
+            account: account.to_string(),
+            digits: 6,
+            period: 30,
+        }
+    }
+}
+
+impl fmt::Display for TotpUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            percent_encode(&self.issuer),
+            percent_encode(&self.account),
+            self.secret,
+            percent_encode(&self.issuer),
+            self.digits,
+            self.period
+        )
+    }
+}
+
+/// Percent-encodes everything except RFC 3986 unreserved characters.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
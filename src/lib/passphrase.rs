@@ -0,0 +1,130 @@
+//! Diceware passphrases for the Penguin password generator.
+//!
+//! A passphrase is a few words picked independently and uniformly from a list, like
+//! `cactus-oblong-ashtray-pulse-widget`. It needs no base words: the list defaults to the
+//! embedded [`eff_large`](crate::wordlist::eff_large), and any other list, such as one
+//! read with [`Wordlist::parse`](crate::wordlist::Wordlist::parse), works the same way.
+//!
+//! Because every word is drawn on its own, the entropy is exact: `log2` of the list size
+//! per word, plus one bit per word when the [`Capitalization`] is random. Entries that
+//! repeat count as separate words, so normalize a hand-made list first.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::passphrase::{generate, Capitalization};
+//! use penguin::wordlist::eff_large;
+//!
+//! let passphrase = generate(eff_large(), 5, Capitalization::Title);
+//! assert_eq!(passphrase.words.len(), 5);
+//! assert!(passphrase.words.iter().all(|word| word.starts_with(char::is_uppercase)));
+//! assert!((passphrase.entropy_bits - 64.6).abs() < 0.1);
+//! println!("{}", passphrase.join("-"));
+//! ```
+
+use rand::Rng;
+use std::str::FromStr;
+
+/// How the letters of every word are cased.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Capitalization {
+    /// All lowercase, `glacier`.
+    #[default]
+    Lower,
+    /// All uppercase, `GLACIER`.
+    Upper,
+    /// The first letter uppercase, `Glacier`.
+    Title,
+    /// Lowercase or title case, chosen at random for each word.
+    Random,
+}
+
+impl Capitalization {
+    /// Cases the word, drawing from `rng` for [`Random`](Self::Random).
+    ///
+    /// ```
+    /// use penguin::passphrase::Capitalization;
+    ///
+    /// let mut rng = penguin::entropy::rng();
+    /// assert_eq!(Capitalization::Title.apply("t-shirt", &mut rng), "T-shirt");
+    /// assert_eq!(Capitalization::Upper.apply("floe", &mut rng), "FLOE");
+    /// ```
+    pub fn apply(self, word: &str, rng: &mut impl Rng) -> String {
+        let title = |word: &str| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+        };
+        match self {
+            Capitalization::Lower => word.to_lowercase(),
+            Capitalization::Upper => word.to_uppercase(),
+            Capitalization::Title => title(word),
+            Capitalization::Random if rng.gen_bool(0.5) => title(word),
+            Capitalization::Random => word.to_lowercase(),
+        }
+    }
+
+    /// Bits of entropy the casing adds to each word.
+    pub fn entropy_bits(self) -> f64 {
+        match self {
+            Capitalization::Random => 1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+impl FromStr for Capitalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "lower" | "lowercase" => Ok(Capitalization::Lower),
+            "upper" | "uppercase" => Ok(Capitalization::Upper),
+            "title" => Ok(Capitalization::Title),
+            "random" => Ok(Capitalization::Random),
+            _ => Err(format!(
+                "unknown capitalization '{}' (expected lower, upper, title, or random)",
+                s
+            )),
+        }
+    }
+}
+
+/// A generated passphrase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passphrase {
+    /// The words, cased and in order.
+    pub words: Vec<String>,
+    /// Total entropy of the passphrase in bits.
+    pub entropy_bits: f64,
+}
+
+impl Passphrase {
+    /// Joins the words with the separator.
+    pub fn join(&self, separator: &str) -> String {
+        self.words.join(separator)
+    }
+}
+
+/// Picks `words` words from the list, each uniformly and independently, and cases them.
+/// An empty list gives an empty passphrase.
+pub fn generate(list: &[&str], words: usize, capitalization: Capitalization) -> Passphrase {
+    if list.is_empty() {
+        return Passphrase {
+            words: Vec::new(),
+            entropy_bits: 0.0,
+        };
+    }
+    let mut rng = crate::entropy::rng();
+    let picked = (0..words)
+        .map(|_| capitalization.apply(list[rng.gen_range(0..list.len())], &mut rng))
+        .collect();
+    Passphrase {
+        words: picked,
+        entropy_bits: words as f64 * ((list.len() as f64).log2() + capitalization.entropy_bits()),
+    }
+}
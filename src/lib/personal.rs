@@ -0,0 +1,166 @@
+//! Personal information to keep out of passwords, for the Penguin password generator.
+//!
+//! Targeted attacks start from what is known about the victim: their name, birthday,
+//! employer, and email address. A [`PersonalInfo`] turns those details into the tokens a
+//! guesser would try and finds them in a password, even in another case or with common
+//! digit and symbol substitutions (`J0hn`, `$mith`, `4cme`).
+//!
+//! Each detail is split into its words, and words shorter than three characters are
+//! dropped since they would rule out too much. An email address contributes its local
+//! part, and a birthday like `1990-05-25` or `25.05.1990` the year and every common way
+//! of writing the day and month with or without it.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::personal::PersonalInfo;
+//!
+//! let mut info = PersonalInfo::new();
+//! info.add("John Smith");
+//! info.add("j.smith@acme.example");
+//! info.add("1990-05-25");
+//! assert!(info.contains("Sm1th!2024"));
+//! assert!(info.contains("glacier2505"));
+//! assert!(!info.contains("glacier-floe7"));
+//! ```
+
+/// Words of a detail shorter than this are not looked for.
+const MIN_TOKEN_LEN: usize = 3;
+
+/// The tokens derived from what is known about a person.
+///
+/// Its `Debug` output gives only how many tokens there are, never the tokens.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct PersonalInfo {
+    tokens: Vec<String>,
+}
+
+impl std::fmt::Debug for PersonalInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersonalInfo")
+            .field("tokens", &self.tokens.len())
+            .finish()
+    }
+}
+
+impl PersonalInfo {
+    /// Creates an empty set of personal information.
+    pub fn new() -> Self {
+        PersonalInfo::default()
+    }
+
+    /// Adds a detail: a name, an employer, an email address, a birthday, or any other
+    /// text. Addresses are recognized by their `@` and birthdays by three numbers, one of
+    /// them a four-digit year, separated by `-`, `.`, `/`, or spaces.
+    ///
+    /// ```
+    /// use penguin::personal::PersonalInfo;
+    ///
+    /// let mut info = PersonalInfo::new();
+    /// info.add("Mary-Jo O'Neil");
+    /// assert_eq!(info.tokens(), ["mary", "neil", "maryjooneil"]);
+    /// info.add("05/25/1990");
+    /// assert!(info.tokens().contains(&String::from("1990")));
+    /// assert!(info.tokens().contains(&String::from("2505")));
+    /// ```
+    pub fn add(&mut self, detail: &str) {
+        let detail = detail.trim();
+        let tokens = match detail.split_once('@') {
+            Some((local, _)) => words(local),
+            None => date_forms(detail).unwrap_or_else(|| words(detail)),
+        };
+        for token in tokens {
+            if token.chars().count() >= MIN_TOKEN_LEN && !self.tokens.contains(&token) {
+                self.tokens.push(token);
+            }
+        }
+    }
+
+    /// Returns the lowercase tokens that are looked for.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Returns whether there are no tokens to look for.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns the tokens a password contains, ignoring case and common substitutions.
+    pub fn find(&self, password: &str) -> Vec<&str> {
+        let lowered = password.to_lowercase();
+        let decoded: String = lowered.chars().map(crate::undo_substitution).collect();
+        // `1`, `!`, and `|` stand in for `l` as often as for `i`.
+        let decoded_l: String = lowered
+            .chars()
+            .map(|c| match c {
+                '1' | '!' | '|' => 'l',
+                c => crate::undo_substitution(c),
+            })
+            .collect();
+        self.tokens
+            .iter()
+            .filter(|token| {
+                [&lowered, &decoded, &decoded_l]
+                    .iter()
+                    .any(|text| text.contains(token.as_str()))
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns whether a password contains any of the tokens; see [`find`](Self::find).
+    pub fn contains(&self, password: &str) -> bool {
+        !self.find(password).is_empty()
+    }
+}
+
+/// Returns the lowercase words of a detail, followed by all of them run together when
+/// there are several.
+fn words(detail: &str) -> Vec<String> {
+    let mut words: Vec<String> = detail
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() > 1 {
+        words.push(words.concat());
+    }
+    words
+}
+
+/// Returns the ways of writing a birthday, or `None` when the detail isn't one.
+///
+/// The order of day and month can't be told apart in general, so both are covered.
+fn date_forms(detail: &str) -> Option<Vec<String>> {
+    let parts: Vec<&str> = detail
+        .split(|c: char| matches!(c, '-' | '.' | '/') || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.len() != 3
+        || !parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    let year = parts.iter().position(|part| part.len() == 4)?;
+    let short: Vec<String> = parts
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != year)
+        .map(|(_, part)| format!("{:0>2}", part))
+        .collect();
+    if short.iter().any(|part| part.len() != 2) {
+        return None;
+    }
+    let (first, second, year) = (&short[0], &short[1], parts[year]);
+    let mut forms = vec![year.to_string()];
+    for (a, b) in [(first, second), (second, first)] {
+        forms.push(format!("{}{}", a, b));
+        forms.push(format!("{}{}{}", a, b, &year[2..]));
+        forms.push(format!("{}{}{}", a, b, year));
+        forms.push(format!("{}{}{}", year, a, b));
+    }
+    Some(forms)
+}
@@ -0,0 +1,523 @@
+//! Shareable password policies for the Penguin password generator.
+//!
+//! A policy collects the rules a target system puts on its passwords: length bounds,
+//! minimum and maximum counts per character class, character sets to draw from, the
+//! characters allowed at all and the ones never used, the special characters to pick
+//! from, a limit on repeated characters, forbidden substrings, whether digits may read
+//! as a date, and a regular expression every password must match. Policies are written
+//! in TOML so a team can keep one file per system and everyone generates compliant
+//! passwords from it (parsing them needs the default `serde` feature):
+//!
+//! ```toml
+//! min_length = 14
+//! max_length = 20
+//! min_uppercase = 1
+//! min_digits = 2
+//! max_specials = 3
+//! required = ["!#$%"]
+//! allowed = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!#$%"
+//! excluded_chars = "l1IO0"
+//! special_chars = "!#$%"
+//! max_consecutive = 2
+//! forbidden = ["acme", "password"]
+//! avoid_dates = true
+//! pattern = "^[A-Za-z]"
+//! ```
+//!
+//! [`PasswordPolicy::configure`] carries the rules over to a mixer, which then only
+//! returns passwords that pass [`PasswordPolicy::check`].
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::mixer::{ComplexityLevel, PenguinMixer};
+//! use penguin::policy::PasswordPolicy;
+//!
+//! let policy: PasswordPolicy = "min_length = 16\nmin_digits = 3\npattern = '^[a-z]'"
+//!     .parse()
+//!     .unwrap();
+//! let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 12);
+//! policy.configure(&mut mixer);
+//!
+//! let password = mixer.mix_password(&["glacier"]).unwrap();
+//! assert!(policy.check(&password));
+//! assert_eq!(password.len(), 16);
+//! ```
+
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::str::FromStr;
+
+use crate::mixer::{CharClass, PenguinMixer};
+
+/// Rules a password has to satisfy.
+///
+/// Policies compare equal when all their rules do, with patterns compared by their source.
+#[derive(Debug, Clone, Default)]
+pub struct PasswordPolicy {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub min_lowercase: usize,
+    pub min_uppercase: usize,
+    pub min_digits: usize,
+    pub min_specials: usize,
+    /// The most lowercase letters a password may contain; `Some(0)` leaves them out.
+    pub max_lowercase: Option<usize>,
+    pub max_uppercase: Option<usize>,
+    pub max_digits: Option<usize>,
+    pub max_specials: Option<usize>,
+    /// Characters no password may contain, such as the easily confused `l1IO0`.
+    pub excluded_chars: String,
+    /// The only special characters a password may use, or `None` for the mixer's own.
+    pub special_chars: Option<String>,
+    /// Character sets a password must contain at least one character of each.
+    pub required: Vec<String>,
+    /// The only characters a password may contain, or `None` to allow any.
+    pub allowed: Option<String>,
+    /// The longest run of one repeated character a password may contain.
+    pub max_consecutive: Option<usize>,
+    /// Substrings no password may contain, compared case-insensitively.
+    pub forbidden: Vec<String>,
+    /// Rejects passwords with digits that read as a year, a day and month, or a month and
+    /// year; see [`find_dates`](crate::dates::find_dates).
+    pub avoid_dates: bool,
+    /// A regular expression every password must match somewhere; anchor it with `^`
+    /// and `$` to match the whole password.
+    pub pattern: Option<Regex>,
+}
+
+impl PartialEq for PasswordPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        self.min_length == other.min_length
+            && self.max_length == other.max_length
+            && self.min_lowercase == other.min_lowercase
+            && self.min_uppercase == other.min_uppercase
+            && self.min_digits == other.min_digits
+            && self.min_specials == other.min_specials
+            && self.max_lowercase == other.max_lowercase
+            && self.max_uppercase == other.max_uppercase
+            && self.max_digits == other.max_digits
+            && self.max_specials == other.max_specials
+            && self.excluded_chars == other.excluded_chars
+            && self.special_chars == other.special_chars
+            && self.required == other.required
+            && self.allowed == other.allowed
+            && self.max_consecutive == other.max_consecutive
+            && self.forbidden == other.forbidden
+            && self.avoid_dates == other.avoid_dates
+            && self.pattern.as_ref().map(Regex::as_str) == other.pattern.as_ref().map(Regex::as_str)
+    }
+}
+
+impl Eq for PasswordPolicy {}
+
+/// Policies from fuzz input. Counts and lengths stay below 256, and the pattern is kept
+/// only when the generated text compiles as a regular expression.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PasswordPolicy {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let small = |u: &mut arbitrary::Unstructured<'a>| u.arbitrary::<u8>().map(usize::from);
+        let optional = |u: &mut arbitrary::Unstructured<'a>| {
+            u.arbitrary::<Option<u8>>()
+                .map(|value| value.map(usize::from))
+        };
+        Ok(PasswordPolicy {
+            min_length: optional(u)?,
+            max_length: optional(u)?,
+            min_lowercase: small(u)?,
+            min_uppercase: small(u)?,
+            min_digits: small(u)?,
+            min_specials: small(u)?,
+            max_lowercase: optional(u)?,
+            max_uppercase: optional(u)?,
+            max_digits: optional(u)?,
+            max_specials: optional(u)?,
+            excluded_chars: u.arbitrary()?,
+            special_chars: u.arbitrary()?,
+            required: u.arbitrary()?,
+            allowed: u.arbitrary()?,
+            max_consecutive: optional(u)?,
+            forbidden: u.arbitrary()?,
+            avoid_dates: u.arbitrary()?,
+            pattern: u
+                .arbitrary::<Option<&str>>()?
+                .and_then(|pattern| Regex::new(pattern).ok()),
+        })
+    }
+}
+
+/// The on-disk form of a policy, before the pattern is compiled.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    #[serde(default)]
+    min_lowercase: usize,
+    #[serde(default)]
+    min_uppercase: usize,
+    #[serde(default)]
+    min_digits: usize,
+    #[serde(default)]
+    min_specials: usize,
+    max_lowercase: Option<usize>,
+    max_uppercase: Option<usize>,
+    max_digits: Option<usize>,
+    max_specials: Option<usize>,
+    #[serde(default)]
+    excluded_chars: String,
+    special_chars: Option<String>,
+    #[serde(default)]
+    required: Vec<String>,
+    allowed: Option<String>,
+    max_consecutive: Option<usize>,
+    #[serde(default)]
+    forbidden: Vec<String>,
+    #[serde(default)]
+    avoid_dates: bool,
+    pattern: Option<String>,
+}
+
+/// The ways a policy definition can be invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// The text is not valid TOML or has unknown or mistyped keys.
+    Syntax(String),
+    /// The pattern is not a valid regular expression.
+    Pattern(String),
+    /// The length bounds leave no valid length, the class minimums don't fit in the
+    /// maximum length or exceed their maximums, or a required set or class has no
+    /// character left to use.
+    Unsatisfiable,
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Syntax(err) => write!(f, "invalid policy: {}", err),
+            PolicyError::Pattern(err) => write!(f, "invalid policy pattern: {}", err),
+            PolicyError::Unsatisfiable => {
+                write!(
+                    f,
+                    "the policy's length bounds, class counts, and character sets contradict each other"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+#[cfg(feature = "serde")]
+impl FromStr for PasswordPolicy {
+    type Err = PolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: PolicyFile =
+            toml::from_str(s).map_err(|err| PolicyError::Syntax(err.to_string()))?;
+        let pattern = file
+            .pattern
+            .map(|pattern| {
+                Regex::new(&pattern).map_err(|err| PolicyError::Pattern(err.to_string()))
+            })
+            .transpose()?;
+
+        let policy = PasswordPolicy {
+            min_length: file.min_length,
+            max_length: file.max_length,
+            min_lowercase: file.min_lowercase,
+            min_uppercase: file.min_uppercase,
+            min_digits: file.min_digits,
+            min_specials: file.min_specials,
+            max_lowercase: file.max_lowercase,
+            max_uppercase: file.max_uppercase,
+            max_digits: file.max_digits,
+            max_specials: file.max_specials,
+            excluded_chars: file.excluded_chars,
+            special_chars: file.special_chars,
+            required: file.required,
+            allowed: file.allowed,
+            max_consecutive: file.max_consecutive,
+            forbidden: file.forbidden,
+            avoid_dates: file.avoid_dates,
+            pattern,
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+}
+
+impl PasswordPolicy {
+    /// Checks that some password can satisfy the policy: the length bounds leave a valid
+    /// length, every minimum and required set fits in the maximum length, no class
+    /// minimum exceeds its maximum, every required set keeps a character that is allowed
+    /// and not excluded, and a minimum of specials keeps a special character to use.
+    ///
+    /// ```
+    /// use penguin::policy::{PasswordPolicy, PolicyError};
+    ///
+    /// let policy: Result<PasswordPolicy, _> = "min_digits = 3\nmax_digits = 2".parse();
+    /// assert_eq!(policy.unwrap_err(), PolicyError::Unsatisfiable);
+    ///
+    /// let policy: Result<PasswordPolicy, _> = "min_specials = 1\nspecial_chars = '!'\nexcluded_chars = '!'".parse();
+    /// assert_eq!(policy.unwrap_err(), PolicyError::Unsatisfiable);
+    /// ```
+    pub fn validate(&self) -> Result<(), PolicyError> {
+        let required = self.min_lowercase
+            + self.min_uppercase
+            + self.min_digits
+            + self.min_specials
+            + self.required.iter().filter(|set| !set.is_empty()).count();
+        if let Some(max) = self.max_length {
+            if self.min_length.is_some_and(|min| min > max) || required > max {
+                return Err(PolicyError::Unsatisfiable);
+            }
+        }
+        let usable = |c: char| {
+            !self.excluded_chars.contains(c)
+                && self
+                    .allowed
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(c))
+        };
+        let unreachable = |set: &str| !set.is_empty() && !set.chars().any(usable);
+        let over_maximum = [
+            (self.min_lowercase, self.max_lowercase),
+            (self.min_uppercase, self.max_uppercase),
+            (self.min_digits, self.max_digits),
+            (self.min_specials, self.max_specials),
+        ]
+        .iter()
+        .any(|&(min, max)| max.is_some_and(|max| min > max));
+        let no_specials = self.min_specials > 0
+            && self.special_chars.as_ref().is_some_and(|specials| {
+                !specials
+                    .chars()
+                    .any(|c| CharClass::of(c) == Some(CharClass::Specials) && usable(c))
+            });
+        if self.max_consecutive == Some(0)
+            || over_maximum
+            || no_specials
+            || self.required.iter().any(|set| unreachable(set))
+        {
+            return Err(PolicyError::Unsatisfiable);
+        }
+        Ok(())
+    }
+
+    /// Returns the most characters of the class a password may contain, if limited.
+    pub fn max_count(&self, class: CharClass) -> Option<usize> {
+        match class {
+            CharClass::Lowercase => self.max_lowercase,
+            CharClass::Uppercase => self.max_uppercase,
+            CharClass::Digits => self.max_digits,
+            CharClass::Specials => self.max_specials,
+        }
+    }
+
+    /// Returns the length closest to `length` that the policy allows.
+    pub fn clamp_length(&self, length: usize) -> usize {
+        let length = self.min_length.map_or(length, |min| length.max(min));
+        self.max_length.map_or(length, |max| length.min(max))
+    }
+
+    /// Returns whether a password satisfies every rule of the policy.
+    ///
+    /// ```
+    /// use penguin::policy::PasswordPolicy;
+    ///
+    /// let policy: PasswordPolicy = "max_length = 10\nmin_specials = 1\nforbidden = ['Acme']"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert!(policy.check("snow!fall"));
+    /// assert!(!policy.check("snowfall"));
+    /// assert!(!policy.check("acme!corp"));
+    /// assert!(!policy.check("snow!fall!ing"));
+    ///
+    /// let policy: PasswordPolicy = "required = ['!?']\nallowed = 'abc!?'\nmax_consecutive = 2"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert!(policy.check("abba?"));
+    /// assert!(!policy.check("abba"));
+    /// assert!(!policy.check("abbba?"));
+    /// assert!(!policy.check("abcd?"));
+    ///
+    /// let policy: PasswordPolicy = "avoid_dates = true".parse().unwrap();
+    /// assert!(policy.check("glacier-4471"));
+    /// assert!(!policy.check("glacier-1987"));
+    ///
+    /// let policy: PasswordPolicy = "max_digits = 2\nexcluded_chars = 'l1O0'\nspecial_chars = '-_'"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert!(policy.check("snow-42"));
+    /// assert!(!policy.check("snow-423"));
+    /// assert!(!policy.check("snow-10"));
+    /// assert!(!policy.check("snow!42"));
+    /// ```
+    pub fn check(&self, password: &str) -> bool {
+        let length = password.chars().count();
+        let count = |class| {
+            password
+                .chars()
+                .filter(|c| CharClass::of(*c) == Some(class))
+                .count()
+        };
+        let lowered = password.to_lowercase();
+
+        self.min_length.is_none_or(|min| length >= min)
+            && self.max_length.is_none_or(|max| length <= max)
+            && count(CharClass::Lowercase) >= self.min_lowercase
+            && count(CharClass::Uppercase) >= self.min_uppercase
+            && count(CharClass::Digits) >= self.min_digits
+            && count(CharClass::Specials) >= self.min_specials
+            && self
+                .max_lowercase
+                .is_none_or(|max| count(CharClass::Lowercase) <= max)
+            && self
+                .max_uppercase
+                .is_none_or(|max| count(CharClass::Uppercase) <= max)
+            && self
+                .max_digits
+                .is_none_or(|max| count(CharClass::Digits) <= max)
+            && self
+                .max_specials
+                .is_none_or(|max| count(CharClass::Specials) <= max)
+            && !password.contains(|c| self.excluded_chars.contains(c))
+            && self.special_chars.as_ref().is_none_or(|specials| {
+                password
+                    .chars()
+                    .filter(|c| CharClass::of(*c) == Some(CharClass::Specials))
+                    .all(|c| specials.contains(c))
+            })
+            && self
+                .required
+                .iter()
+                .all(|set| set.is_empty() || password.contains(|c| set.contains(c)))
+            && self
+                .allowed
+                .as_ref()
+                .is_none_or(|allowed| password.chars().all(|c| allowed.contains(c)))
+            && self
+                .max_consecutive
+                .is_none_or(|max| longest_run(password) <= max)
+            && !self.forbidden.iter().any(|substring| {
+                !substring.is_empty() && lowered.contains(&substring.to_lowercase())
+            })
+            && !(self.avoid_dates && crate::dates::contains_date(password))
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(password))
+    }
+
+    /// Carries the policy over to a mixer: its length is moved into the allowed bounds,
+    /// the policy's special characters replace the mixer's, classes without an allowed
+    /// character or with a maximum of zero are excluded and the specials narrowed to the
+    /// allowed ones, the excluded characters are added to the mixer's, every class minimum becomes that many required character
+    /// groups and every required set one more, the forbidden substrings are added, dates
+    /// are avoided if the policy says so, and the mixer keeps re-rolling until
+    /// [`check`](Self::check) passes.
+    pub fn configure(&self, mixer: &mut PenguinMixer) {
+        mixer.length = self.clamp_length(mixer.length);
+        if let Some(specials) = &self.special_chars {
+            mixer.special_chars = specials
+                .chars()
+                .filter(|c| CharClass::of(*c) == Some(CharClass::Specials))
+                .collect();
+        }
+        for (class, maximum) in [
+            (CharClass::Lowercase, self.max_lowercase),
+            (CharClass::Uppercase, self.max_uppercase),
+            (CharClass::Digits, self.max_digits),
+            (CharClass::Specials, self.max_specials),
+        ] {
+            if maximum == Some(0) && !mixer.excluded_classes.contains(&class) {
+                mixer.excluded_classes.push(class);
+            }
+        }
+        for c in self.excluded_chars.chars() {
+            if !mixer.excluded_chars.contains(c) {
+                mixer.excluded_chars.push(c);
+            }
+        }
+        if let Some(allowed) = &self.allowed {
+            for class in [
+                CharClass::Lowercase,
+                CharClass::Uppercase,
+                CharClass::Digits,
+                CharClass::Specials,
+            ] {
+                let reachable = allowed.chars().any(|c| CharClass::of(c) == Some(class));
+                if !reachable && !mixer.excluded_classes.contains(&class) {
+                    mixer.excluded_classes.push(class);
+                }
+            }
+            let specials: String = allowed
+                .chars()
+                .filter(|c| CharClass::of(*c) == Some(CharClass::Specials))
+                .collect();
+            let narrowed: String = mixer
+                .special_chars
+                .chars()
+                .filter(|c| specials.contains(*c))
+                .collect();
+            if !specials.is_empty() {
+                mixer.special_chars = if narrowed.is_empty() {
+                    specials
+                } else {
+                    narrowed
+                };
+            }
+        }
+        for (class, minimum) in [
+            (CharClass::Lowercase, self.min_lowercase),
+            (CharClass::Uppercase, self.min_uppercase),
+            (CharClass::Digits, self.min_digits),
+            (CharClass::Specials, self.min_specials),
+        ] {
+            let chars: String = match class {
+                CharClass::Specials => mixer.special_chars.as_str(),
+                class => class.chars(),
+            }
+            .chars()
+            .filter(|c| !mixer.excluded_chars.contains(*c))
+            .collect();
+            mixer
+                .required_chars
+                .extend(std::iter::repeat_n(chars, minimum));
+        }
+        mixer.required_chars.extend(self.required.iter().map(|set| {
+            set.chars()
+                .filter(|c| {
+                    !mixer.excluded_chars.contains(*c)
+                        && self
+                            .allowed
+                            .as_ref()
+                            .is_none_or(|allowed| allowed.contains(*c))
+                })
+                .collect::<String>()
+        }));
+        mixer
+            .forbidden_substrings
+            .extend(self.forbidden.iter().cloned());
+        mixer.avoid_dates |= self.avoid_dates;
+        mixer.policy = Some(self.clone());
+    }
+}
+
+/// Returns the length of the longest run of one repeated character.
+fn longest_run(password: &str) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for c in password.chars() {
+        run = if previous == Some(c) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        previous = Some(c);
+    }
+    longest
+}
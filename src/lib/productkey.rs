@@ -0,0 +1,65 @@
+//! Product keys and voucher codes for the Penguin password generator.
+//!
+//! A product key is a few groups of uppercase letters and digits joined by hyphens, like
+//! `7G3KD-PQ2XN-88RTY-ZC4MH`. Keys like these get read off packaging and typed in by
+//! hand, so the characters that are easily mistaken for one another (`0` and `O`, `1`,
+//! `I`, and `L`, `2` and `Z`, `5` and `S`, `8` and `B`) can be left out of the alphabet.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::productkey::generate;
+//!
+//! let key = generate(4, 5, true);
+//! assert_eq!(key.groups.len(), 4);
+//! assert!(key.groups.iter().all(|group| group.len() == 5));
+//! assert_eq!(key.to_string().len(), 23);
+//! assert!(!key.to_string().contains(['0', 'O', '1', 'I']));
+//! ```
+
+use rand::Rng;
+use std::fmt;
+
+/// Every character a key can use.
+pub const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Characters left out when ambiguous characters are excluded.
+pub const AMBIGUOUS: &str = "0O1IL2Z5S8B";
+
+/// A generated product key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductKey {
+    /// The groups of the key, in order.
+    pub groups: Vec<String>,
+    /// Total entropy of the key in bits.
+    pub entropy_bits: f64,
+}
+
+impl fmt::Display for ProductKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.groups.join("-"))
+    }
+}
+
+/// Generates a key of `groups` groups of `group_size` characters each, leaving out the
+/// [`AMBIGUOUS`] characters when `exclude_ambiguous` is set.
+pub fn generate(groups: usize, group_size: usize, exclude_ambiguous: bool) -> ProductKey {
+    let alphabet: Vec<char> = ALPHABET
+        .chars()
+        .filter(|c| !exclude_ambiguous || !AMBIGUOUS.contains(*c))
+        .collect();
+
+    let mut rng = crate::entropy::rng();
+    let groups: Vec<String> = (0..groups)
+        .map(|_| {
+            (0..group_size)
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect()
+        })
+        .collect();
+
+    ProductKey {
+        entropy_bits: (groups.len() * group_size) as f64 * (alphabet.len() as f64).log2(),
+        groups,
+    }
+}
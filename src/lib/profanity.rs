@@ -0,0 +1,63 @@
+//! Embedded profanity lists for filtering randomly generated passwords.
+//!
+//! Character mixing and random filler occasionally spell out offensive words, which is
+//! a problem when passwords end up on printed onboarding sheets. This module embeds
+//! short word lists for several languages and checks candidates against all of them.
+//!
+//! Matching is done on the lowercased text and on a copy with common digit and symbol
+//! substitutions undone (`5h1t` reads as `shit`), so disguised matches are caught as
+//! well. The lists are only compiled in with the `profanity` feature.
+//!
+//! # Examples
+//!
+//! The check plugs straight into the mixer's rejection predicates:
+//!
+//! ```
+//! use penguin::mixer::{ComplexityLevel, PenguinMixer};
+//! use penguin::profanity;
+//!
+//! let mut mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+//! mixer.reject_if.push(profanity::contains_profanity);
+//! let password = mixer.mix_password(&["penguin"]).unwrap();
+//! assert!(!profanity::contains_profanity(&password));
+//! ```
+
+use std::sync::OnceLock;
+
+/// Word lists by language, one lowercase ASCII entry per line.
+const LISTS: [&str; 6] = [
+    include_str!("data/profanity/en.txt"),
+    include_str!("data/profanity/es.txt"),
+    include_str!("data/profanity/fr.txt"),
+    include_str!("data/profanity/de.txt"),
+    include_str!("data/profanity/it.txt"),
+    include_str!("data/profanity/pt.txt"),
+];
+
+fn words() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        LISTS
+            .iter()
+            .flat_map(|list| list.lines())
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .collect()
+    })
+}
+
+/// Returns whether the text contains a word from any of the embedded lists.
+///
+/// ```
+/// use penguin::profanity::contains_profanity;
+///
+/// assert!(contains_profanity("x9Sh1t#q"));
+/// assert!(!contains_profanity("glacier7!"));
+/// ```
+pub fn contains_profanity(text: &str) -> bool {
+    let lowered = text.to_lowercase();
+    let decoded: String = lowered.chars().map(crate::undo_substitution).collect();
+    words()
+        .iter()
+        .any(|word| lowered.contains(word) || decoded.contains(word))
+}
@@ -0,0 +1,286 @@
+//! Pronounceable password generation for the Penguin password generator.
+//!
+//! Pronounceable passwords are built syllable by syllable from alternating consonants and
+//! vowels, in the spirit of the classic `apg` tool. Some syllables are capitalized and
+//! some are a single digit, so the result still mixes character classes while staying easy
+//! to say out loud. Each password keeps its syllable breakdown, which is what makes it
+//! memorable.
+//!
+//! The shape of the syllables comes from a [`Grammar`]. A grammar such as `CVC-CVVC-dd`
+//! lists syllables separated by `-`, where `C` stands for a consonant, `V` for a vowel,
+//! `d` for a digit, and any other character is copied literally. A syllable may offer
+//! alternatives separated by `|`, one of which is picked at random each time. The
+//! consonant and vowel sets can be replaced to suit other languages.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::pronounce::generate;
+//!
+//! let password = generate(14);
+//! assert_eq!(password.to_string().chars().count(), 14);
+//! assert_eq!(password.syllables.concat(), password.to_string());
+//! println!("{} ({})", password, password.breakdown());
+//! ```
+//!
+//! A custom grammar with German-leaning letter sets:
+//!
+//! ```
+//! use penguin::pronounce::Grammar;
+//!
+//! let mut grammar: Grammar = "CVC-CVVC-dd".parse().unwrap();
+//! grammar.vowels = "aeiouäöü".to_string();
+//! grammar.capital_chance = 0.0;
+//!
+//! let password = grammar.generate(None);
+//! assert_eq!(password.syllables.len(), 3);
+//! assert_eq!(password.to_string().chars().count(), 9);
+//! ```
+
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+pub const CONSONANTS: &str = "bcdfghjklmnprstvwz";
+pub const VOWELS: &str = "aeiou";
+const DIGITS: &str = "0123456789";
+
+/// Consonants and consonant pairs that start the syllables of the mixer's
+/// [`Pronounceable`](crate::mixer::GenerationStyle::Pronounceable) passwords.
+pub const ONSETS: [&str; 40] = [
+    "b", "bl", "br", "ch", "d", "dr", "f", "fl", "fr", "g", "gl", "gr", "h", "j", "k", "kl", "kr",
+    "l", "m", "n", "p", "pl", "pr", "r", "s", "sh", "sk", "sl", "sm", "sn", "sp", "st", "t", "th",
+    "tr", "v", "w", "y", "z", "zh",
+];
+
+/// Vowels and vowel pairs that follow the onsets.
+pub const NUCLEI: [&str; 13] = [
+    "a", "e", "i", "o", "u", "ai", "au", "ea", "ee", "ie", "oa", "oo", "ou",
+];
+
+/// Consonants and consonant pairs that can end a pseudo-word.
+pub const CODAS: [&str; 16] = [
+    "l", "m", "n", "r", "s", "t", "x", "ck", "ft", "nd", "ng", "nk", "nt", "rd", "rn", "st",
+];
+
+/// Letters of a run that may follow each other in a pronounceable password.
+const MAX_RUN: usize = 2;
+
+/// The grammar used when none is given: one syllable, repeated, of varying shape.
+pub const DEFAULT_GRAMMAR: &str = "CV|CVC|VC|CVV|CVCC|d";
+
+/// Probability that a syllable is capitalized by the default grammar.
+const CAPITAL_CHANCE: f64 = 0.2;
+
+/// Errors produced when parsing a syllable grammar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarError {
+    /// The grammar, or one of its syllables or alternatives, is empty.
+    EmptySyllable,
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrammarError::EmptySyllable => write!(f, "grammar contains an empty syllable"),
+        }
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+/// Describes the syllables pronounceable passwords are built from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grammar {
+    /// Syllables in order, each a list of alternative shapes.
+    pub syllables: Vec<Vec<String>>,
+    /// Letters substituted for `C`.
+    pub consonants: String,
+    /// Letters substituted for `V`.
+    pub vowels: String,
+    /// Probability that a syllable starting with a letter is capitalized.
+    pub capital_chance: f64,
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        DEFAULT_GRAMMAR
+            .parse()
+            .expect("the default grammar is valid")
+    }
+}
+
+impl FromStr for Grammar {
+    type Err = GrammarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let syllables = s
+            .split('-')
+            .map(|syllable| {
+                let shapes: Vec<String> = syllable.split('|').map(String::from).collect();
+                if shapes.iter().any(String::is_empty) {
+                    return Err(GrammarError::EmptySyllable);
+                }
+                Ok(shapes)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Grammar {
+            syllables,
+            consonants: CONSONANTS.to_string(),
+            vowels: VOWELS.to_string(),
+            capital_chance: CAPITAL_CHANCE,
+        })
+    }
+}
+
+impl Grammar {
+    /// Generates a pronounceable password following this grammar.
+    ///
+    /// Without a length, every syllable of the grammar is produced once. With a length,
+    /// the syllables are repeated until the password reaches exactly that many characters,
+    /// cutting the last syllable short if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grammar uses `C` or `V` while the matching letter set is empty.
+    pub fn generate(&self, length: Option<usize>) -> Pronounceable {
+        let mut rng = crate::entropy::rng();
+        let consonants: Vec<char> = self.consonants.chars().collect();
+        let vowels: Vec<char> = self.vowels.chars().collect();
+        let digits: Vec<char> = DIGITS.chars().collect();
+
+        let mut syllables: Vec<String> = Vec::new();
+        let mut remaining = length.unwrap_or(usize::MAX);
+        for shapes in self.syllables.iter().cycle() {
+            if remaining == 0 || (length.is_none() && syllables.len() == self.syllables.len()) {
+                break;
+            }
+
+            let shape = &shapes[rng.gen_range(0..shapes.len())];
+            let mut syllable: Vec<char> = shape
+                .chars()
+                .map(|slot| match slot {
+                    'C' => random_char(&consonants, &mut rng),
+                    'V' => random_char(&vowels, &mut rng),
+                    'd' => random_char(&digits, &mut rng),
+                    literal => literal,
+                })
+                .collect();
+            if syllable[0].is_alphabetic() && rng.gen_bool(self.capital_chance) {
+                let capital: Vec<char> = syllable[0].to_uppercase().collect();
+                syllable.splice(0..1, capital);
+            }
+            syllable.truncate(remaining);
+            if length.is_some() {
+                remaining -= syllable.len();
+            }
+            syllables.push(syllable.into_iter().collect());
+        }
+
+        Pronounceable { syllables }
+    }
+}
+
+/// A generated pronounceable password together with the syllables it was built from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pronounceable {
+    pub syllables: Vec<String>,
+}
+
+impl Pronounceable {
+    /// The syllables joined with hyphens, e.g. `drov-Uth-9-ka`.
+    pub fn breakdown(&self) -> String {
+        self.syllables.join("-")
+    }
+}
+
+impl fmt::Display for Pronounceable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.syllables.concat())
+    }
+}
+
+/// Generates a pronounceable password of exactly `length` characters using the default
+/// grammar.
+pub fn generate(length: usize) -> Pronounceable {
+    Grammar::default().generate(Some(length))
+}
+
+fn random_char(chars: &[char], rng: &mut impl Rng) -> char {
+    chars[rng.gen_range(0..chars.len())]
+}
+
+/// Splits a word into the syllables pronounceable mixer passwords can reuse: one or two
+/// consonants followed by one or two vowels, lowercased. Longer runs keep the consonants
+/// nearest the vowels and the vowels nearest the consonants, and letters outside such a
+/// syllable, like a leading vowel or the consonants at the end, are left out.
+///
+/// ```
+/// use penguin::pronounce::syllables_of;
+///
+/// assert_eq!(syllables_of("Glacier"), ["gla", "cie"]);
+/// assert_eq!(syllables_of("string"), ["tri"]);
+/// assert_eq!(syllables_of("ice-floe"), ["ce", "floe"]);
+/// assert!(syllables_of("2024").is_empty());
+/// ```
+pub fn syllables_of(word: &str) -> Vec<String> {
+    let letters: Vec<char> = word.to_lowercase().chars().collect();
+    let mut syllables = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let start = i;
+        while i < letters.len() && is_consonant(letters[i]) {
+            i += 1;
+        }
+        let onset = start.max(i.saturating_sub(MAX_RUN));
+        let vowels = i;
+        while i < letters.len() && is_vowel(letters[i]) {
+            i += 1;
+        }
+        if vowels > onset && i > vowels {
+            let end = i.min(vowels + MAX_RUN);
+            syllables.push(letters[onset..end].iter().collect());
+        }
+        if i == start {
+            i += 1;
+        }
+    }
+    syllables
+}
+
+/// Returns whether a password is easy to say: no run of letters in it has more than two
+/// consonants or more than two vowels in a row, ignoring case. Digits and other
+/// characters end a run, and any letter but `a`, `e`, `i`, `o`, and `u` is a consonant.
+///
+/// ```
+/// use penguin::pronounce::is_pronounceable;
+///
+/// assert!(is_pronounceable("Brakoo7!Daimsu"));
+/// assert!(!is_pronounceable("strength"));
+/// assert!(!is_pronounceable("queue"));
+/// ```
+pub fn is_pronounceable(password: &str) -> bool {
+    let (mut consonants, mut vowels) = (0, 0);
+    for c in password.chars().flat_map(char::to_lowercase) {
+        (consonants, vowels) = if is_vowel(c) {
+            (0, vowels + 1)
+        } else if c.is_alphabetic() {
+            (consonants + 1, 0)
+        } else {
+            (0, 0)
+        };
+        if consonants > MAX_RUN || vowels > MAX_RUN {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_vowel(c: char) -> bool {
+    VOWELS.contains(c)
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_ascii_alphabetic() && !is_vowel(c)
+}
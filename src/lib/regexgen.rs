@@ -0,0 +1,252 @@
+//! Random strings matching a regular expression, for the Penguin password generator.
+//!
+//! Some services publish their password rules only as a regular expression. Rather than
+//! generating candidates and hoping one matches, [`generate_matching`] walks the syntax
+//! tree of the pattern and makes a random choice at every character class and
+//! alternation, so what comes out matches by construction.
+//!
+//! Repetitions like `+`, `*`, and `{8,}` have no natural length, so their counts are
+//! chosen to bring the whole string close to a length hint, within the bounds the
+//! pattern sets. Character classes draw from printable ASCII without space whenever they
+//! include any of it, which keeps `.` and `\S` from producing characters nobody can type.
+//!
+//! The entropy reported is the sum of the bits of every random choice: a character from
+//! a class of 62 adds about 5.95 bits, a choice between three alternatives about 1.58.
+//! Repetition counts follow the hint and add nothing. Patterns that can produce the same
+//! string in more than one way, like `a|a`, make the figure an overestimate.
+//!
+//! Look-around and backreferences aren't part of the supported syntax and are rejected.
+//! Anchors are fine, and a string that breaks a word boundary is drawn again.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::regexgen::generate_matching;
+//!
+//! let matched = generate_matching("[A-Z][a-z]{6}[0-9]{2}[!?]", 0).unwrap();
+//! assert_eq!(matched.password.len(), 10);
+//! assert!(matched.password.starts_with(|c: char| c.is_ascii_uppercase()));
+//! assert!((matched.entropy_bits - 40.5).abs() < 0.1);
+//!
+//! let matched = generate_matching("[a-z0-9]+", 20).unwrap();
+//! assert_eq!(matched.password.len(), 20);
+//!
+//! assert!(generate_matching("(?=.*[A-Z])[a-z]+", 8).is_err());
+//! ```
+
+use std::fmt;
+
+use rand::Rng;
+use regex::Regex;
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind};
+
+/// How many strings are drawn before giving up on a pattern whose word boundaries keep
+/// failing.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// The code points that can't be characters.
+const SURROGATES: (u32, u32) = (0xD800, 0xDFFF);
+
+/// A random string matching a pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matched {
+    /// The generated string.
+    pub password: String,
+    /// Bits of randomness that went into the string.
+    pub entropy_bits: f64,
+}
+
+impl fmt::Display for Matched {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.password)
+    }
+}
+
+/// Generates a random string that the whole of `pattern` matches, using the length hint
+/// to pick the counts of open-ended repetitions.
+///
+/// Fails when the pattern is invalid, uses syntax the regex crate doesn't support, or
+/// can't match anything.
+///
+/// ```
+/// use penguin::regexgen::generate_matching;
+///
+/// let matched = generate_matching("(ice|snow)-[0-9]{4}", 0).unwrap();
+/// assert!(matched.password.starts_with("ice-") || matched.password.starts_with("snow-"));
+/// assert!(generate_matching("[^\\s\\S]", 8).is_err());
+/// ```
+pub fn generate_matching(pattern: &str, length_hint: usize) -> Result<Matched, String> {
+    let hir = regex_syntax::Parser::new()
+        .parse(pattern)
+        .map_err(|err| err.to_string())?;
+    if hir.properties().minimum_len().is_none() {
+        return Err(String::from("the pattern can't match anything"));
+    }
+    let whole = Regex::new(&format!("^(?:{})$", pattern)).map_err(|err| err.to_string())?;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let mut sampler = Sampler {
+            rng: crate::entropy::rng(),
+            password: String::new(),
+            bits: 0.0,
+        };
+        sampler.sample(&hir, length_hint)?;
+        if whole.is_match(&sampler.password) {
+            return Ok(Matched {
+                password: sampler.password,
+                entropy_bits: sampler.bits,
+            });
+        }
+    }
+    Err(format!(
+        "no string matching the pattern came up in {} attempts",
+        MAX_ATTEMPTS
+    ))
+}
+
+/// Returns whether the whole of a string matches a pattern. An invalid pattern matches
+/// nothing.
+///
+/// ```
+/// use penguin::regexgen::is_match;
+///
+/// assert!(is_match("[a-z]+[0-9]", "glacier7"));
+/// assert!(!is_match("[a-z]+[0-9]", "glacier7!"));
+/// ```
+pub fn is_match(pattern: &str, text: &str) -> bool {
+    Regex::new(&format!("^(?:{})$", pattern)).is_ok_and(|whole| whole.is_match(text))
+}
+
+/// Returns the fewest bytes a part of a pattern matches.
+fn minimum_len(hir: &Hir) -> usize {
+    hir.properties().minimum_len().unwrap_or(0)
+}
+
+/// Builds a string by making random choices along a syntax tree.
+struct Sampler<R> {
+    rng: R,
+    password: String,
+    bits: f64,
+}
+
+impl<R: Rng> Sampler<R> {
+    /// Appends a string matching `hir`, aiming for about `budget` bytes.
+    fn sample(&mut self, hir: &Hir, budget: usize) -> Result<(), String> {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => {}
+            HirKind::Literal(literal) => {
+                self.password.push_str(&String::from_utf8_lossy(&literal.0));
+            }
+            HirKind::Class(Class::Unicode(class)) => {
+                let mut printable = ClassUnicode::new([ClassUnicodeRange::new('!', '~')]);
+                printable.intersect(class);
+                let class = if printable.ranges().is_empty() {
+                    class
+                } else {
+                    &printable
+                };
+                let ranges: Vec<(u32, u32)> = class
+                    .ranges()
+                    .iter()
+                    .map(|range| (range.start() as u32, range.end() as u32))
+                    .collect();
+                self.pick(&ranges)?;
+            }
+            HirKind::Class(Class::Bytes(class)) => {
+                // Outside of ASCII a byte isn't a character, and the parser only accepts
+                // byte classes that stay within it.
+                let ranges: Vec<(u32, u32)> = class
+                    .ranges()
+                    .iter()
+                    .map(|range| (u32::from(range.start()), u32::from(range.end().min(0x7F))))
+                    .filter(|(start, end)| start <= end)
+                    .collect();
+                self.pick(&ranges)?;
+            }
+            HirKind::Repetition(repetition) => {
+                let unit = minimum_len(&repetition.sub).max(1);
+                let max = repetition.max.map_or(usize::MAX, |max| max as usize);
+                let min = repetition.min as usize;
+                let planned = (budget / unit).clamp(min, max);
+                let each = budget.checked_div(planned).unwrap_or(0);
+                // Repeat until the budget is used up, since alternatives inside the
+                // repetition may come out longer or shorter than planned.
+                let start = self.password.len();
+                let mut count = 0;
+                while count < min || (count < max && self.password.len() - start < budget) {
+                    let before = self.password.len();
+                    self.sample(&repetition.sub, each)?;
+                    count += 1;
+                    if count >= min && self.password.len() == before {
+                        break;
+                    }
+                }
+            }
+            HirKind::Capture(capture) => self.sample(&capture.sub, budget)?,
+            HirKind::Concat(parts) => {
+                // Every part gets its minimum, and the parts that can grow share the rest.
+                let minimum: usize = parts.iter().map(minimum_len).sum();
+                let mut extra = budget.saturating_sub(minimum);
+                let mut flexible = parts
+                    .iter()
+                    .filter(|part| {
+                        part.properties().maximum_len() != part.properties().minimum_len()
+                    })
+                    .count();
+                for part in parts {
+                    let least = minimum_len(part);
+                    let grows = part.properties().maximum_len() != part.properties().minimum_len();
+                    let share = if grows {
+                        let share = extra / flexible.max(1);
+                        flexible -= 1;
+                        share
+                    } else {
+                        0
+                    };
+                    let before = self.password.len();
+                    self.sample(part, least + share)?;
+                    let grown = (self.password.len() - before).saturating_sub(least);
+                    extra = extra.saturating_sub(grown);
+                }
+            }
+            HirKind::Alternation(branches) => {
+                let branch = &branches[self.rng.gen_range(0..branches.len())];
+                self.bits += (branches.len() as f64).log2();
+                self.sample(branch, budget)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends a character picked uniformly from inclusive ranges of code points.
+    fn pick(&mut self, ranges: &[(u32, u32)]) -> Result<(), String> {
+        let ranges: Vec<(u32, u32)> = ranges
+            .iter()
+            .flat_map(|&(start, end)| {
+                [
+                    (start, end.min(SURROGATES.0 - 1)),
+                    (start.max(SURROGATES.1 + 1), end),
+                ]
+            })
+            .filter(|(start, end)| start <= end)
+            .collect();
+        let total: u32 = ranges.iter().map(|(start, end)| end - start + 1).sum();
+        if total == 0 {
+            return Err(String::from("the pattern has a class that matches nothing"));
+        }
+
+        let mut index = self.rng.gen_range(0..total);
+        for (start, end) in ranges {
+            let size = end - start + 1;
+            if index < size {
+                let c = char::from_u32(start + index)
+                    .ok_or_else(|| String::from("the pattern has an invalid character"))?;
+                self.password.push(c);
+                self.bits += f64::from(total).log2();
+                return Ok(());
+            }
+            index -= size;
+        }
+        unreachable!("the index is below the total size of the ranges")
+    }
+}
@@ -0,0 +1,149 @@
+//! Strength meters for showing a password's [`Rating`] the way penguin does.
+//!
+//! Every frontend that draws a strength meter has to turn an entropy estimate into a
+//! label, a color, and how full the meter is. A [`Meter`] does all three from the same
+//! thresholds as [`StrengthReport::rating`], so a TUI or a web page embedding the
+//! library agrees with penguin's own output. [`Meter::bar`] draws the meter as text, the
+//! [`Color`] comes as an ANSI escape for terminals or a hex code for the web, and
+//! [`Meter::fill`] gives the fraction for drawing it any other way.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::render::{Color, Meter};
+//!
+//! let meter = Meter::of("glacier-Penguin-42-floe!");
+//! assert_eq!(meter.label(), "excellent");
+//! assert_eq!(meter.color(), Color::Blue);
+//! assert_eq!(Meter::from_bits(64.0).bar(8), "████░░░░");
+//! assert_eq!(Meter::from_bits(64.0).to_string(), "██████████░░░░░░░░░░ fair");
+//! ```
+
+use std::fmt;
+
+use crate::strength::{estimate_entropy, Rating, StrengthReport};
+
+/// Bits at which a meter is full, where passwords start to rate excellent.
+pub const FULL_BITS: f64 = 128.0;
+
+/// Cells in the bar of a meter's `Display` output.
+const DISPLAY_WIDTH: usize = 20;
+
+/// The color a rating is shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Yellow,
+    Green,
+    Blue,
+}
+
+impl Color {
+    /// The ANSI escape that switches a terminal to this color; end it with [`RESET`].
+    pub fn ansi(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Yellow => "\x1b[33m",
+            Color::Green => "\x1b[32m",
+            Color::Blue => "\x1b[34m",
+        }
+    }
+
+    /// The color as a CSS hex code.
+    pub fn hex(self) -> &'static str {
+        match self {
+            Color::Red => "#d32f2f",
+            Color::Yellow => "#f9a825",
+            Color::Green => "#388e3c",
+            Color::Blue => "#1976d2",
+        }
+    }
+}
+
+/// The ANSI escape that ends a [`Color::ansi`] run.
+pub const RESET: &str = "\x1b[0m";
+
+/// A strength meter for one entropy estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meter {
+    bits: f64,
+    rating: Rating,
+}
+
+impl Meter {
+    /// The meter for a password, from [`estimate_entropy`].
+    pub fn of(password: &str) -> Self {
+        Meter::from_report(&estimate_entropy(password))
+    }
+
+    /// The meter for a strength report.
+    pub fn from_report(report: &StrengthReport) -> Self {
+        Meter {
+            bits: report.entropy_bits,
+            rating: report.rating(),
+        }
+    }
+
+    /// The meter for an entropy estimate in bits.
+    pub fn from_bits(bits: f64) -> Self {
+        Meter {
+            bits,
+            rating: Rating::from_bits(bits),
+        }
+    }
+
+    /// The entropy estimate the meter shows.
+    pub fn bits(&self) -> f64 {
+        self.bits
+    }
+
+    /// The rating of the estimate.
+    pub fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    /// A one-word label: `weak`, `fair`, `strong`, or `excellent`.
+    pub fn label(&self) -> &'static str {
+        match self.rating {
+            Rating::Weak => "weak",
+            Rating::Fair => "fair",
+            Rating::Strong => "strong",
+            Rating::Excellent => "excellent",
+        }
+    }
+
+    /// The color the rating is shown in.
+    pub fn color(&self) -> Color {
+        match self.rating {
+            Rating::Weak => Color::Red,
+            Rating::Fair => Color::Yellow,
+            Rating::Strong => Color::Green,
+            Rating::Excellent => Color::Blue,
+        }
+    }
+
+    /// How full the meter is, from 0 to 1, reaching 1 at [`FULL_BITS`].
+    pub fn fill(&self) -> f64 {
+        (self.bits / FULL_BITS).clamp(0.0, 1.0)
+    }
+
+    /// Draws the meter as `width` cells, the filled ones first.
+    ///
+    /// ```
+    /// use penguin::render::Meter;
+    ///
+    /// assert_eq!(Meter::from_bits(0.0).bar(4), "░░░░");
+    /// assert_eq!(Meter::from_bits(400.0).bar(4), "████");
+    /// ```
+    pub fn bar(&self, width: usize) -> String {
+        let filled = (self.fill() * width as f64).round() as usize;
+        "█".repeat(filled) + &"░".repeat(width - filled)
+    }
+}
+
+/// The bar followed by the label, as in `█████████████░░░░░░░ strong`.
+impl fmt::Display for Meter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.bar(DISPLAY_WIDTH), self.label())
+    }
+}
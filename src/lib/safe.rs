@@ -0,0 +1,148 @@
+//! Passwords that need no quoting for the Penguin password generator.
+//!
+//! A generated secret pasted into a shell command, a YAML or JSON file, or an INI file
+//! breaks things when it contains a character the format treats specially: `$` and
+//! backticks expand in a shell, `#` starts a comment, quotes end a string, and a leading
+//! `-` turns a value into an option or a list item. [`SafeFor`] names a format and knows
+//! which characters pass through it untouched.
+//!
+//! Letters and digits are always safe. On top of them, each format allows:
+//!
+//! - [`SafeFor::Shell`]: `%+,-./:@_`, and no leading `-`, so the password is a single
+//!   unquoted word in a POSIX shell.
+//! - [`SafeFor::Yaml`]: `-._`, starting with a letter, so it is a plain scalar that YAML
+//!   reads as a string rather than a number, boolean, or null.
+//! - [`SafeFor::Json`]: any printable ASCII except `"` and `\`, which a JSON string would
+//!   have to escape.
+//! - [`SafeFor::Ini`]: `+-./@_`, leaving out the comment, delimiter, quote, and
+//!   interpolation characters INI parsers disagree on.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::safe::SafeFor;
+//!
+//! let shell: SafeFor = "shell".parse().unwrap();
+//! assert!(shell.is_safe("ice.floe@42"));
+//! assert!(!shell.is_safe("ice$floe"));
+//! assert!(!shell.is_safe("-icefloe"));
+//!
+//! assert!(SafeFor::Yaml.is_safe("glacier-42"));
+//! assert!(!SafeFor::Yaml.is_safe("42-glacier"));
+//! assert!(!SafeFor::Yaml.is_safe("yes"));
+//! assert!(SafeFor::Json.is_safe("ice$floe#1"));
+//! assert!(!SafeFor::Json.is_safe("ice\"floe"));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::charset::Charset;
+use crate::mixer::PenguinMixer;
+
+/// Words YAML may read as booleans or null instead of strings.
+const YAML_RESERVED: [&str; 12] = [
+    "true", "false", "yes", "no", "on", "off", "y", "n", "null", "nan", "inf", "infinity",
+];
+
+/// A format a password has to fit into without quoting or escaping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeFor {
+    Shell,
+    Yaml,
+    Json,
+    Ini,
+}
+
+impl SafeFor {
+    /// Returns the symbols that need no quoting in the format.
+    pub fn symbols(self) -> &'static str {
+        match self {
+            SafeFor::Shell => "%+,-./:@_",
+            SafeFor::Yaml => "-._",
+            SafeFor::Json => "!#$%&'()*+,-./:;<=>?@[]^_`{|}~",
+            SafeFor::Ini => "+-./@_",
+        }
+    }
+
+    /// Returns whether a character needs no quoting in the format, wherever it appears.
+    pub fn allows(self, c: char) -> bool {
+        c.is_alphanumeric() || self.symbols().contains(c)
+    }
+
+    /// Returns whether a password can be written into the format as is.
+    pub fn is_safe(self, password: &str) -> bool {
+        if !password.chars().all(|c| self.allows(c)) {
+            return false;
+        }
+        match self {
+            SafeFor::Shell => !password.starts_with('-'),
+            SafeFor::Yaml => {
+                let lowered = password.to_lowercase();
+                password.starts_with(|c: char| c.is_alphabetic())
+                    && !YAML_RESERVED.contains(&lowered.as_str())
+                    && password.parse::<f64>().is_err()
+            }
+            SafeFor::Json | SafeFor::Ini => true,
+        }
+    }
+
+    /// Restricts a mixer to passwords that are safe for the format: its specials and
+    /// charset lose the unsafe symbols, and candidates that still aren't safe, because of
+    /// a base word, separator, or a leading character, are rejected.
+    ///
+    /// ```
+    /// use penguin::mixer::{ComplexityLevel, PenguinMixer};
+    /// use penguin::safe::SafeFor;
+    ///
+    /// let mut mixer = PenguinMixer::new(ComplexityLevel::Penguin, false, 64);
+    /// SafeFor::Shell.configure(&mut mixer);
+    /// let password = mixer.mix_password(&[]).unwrap();
+    /// assert!(SafeFor::Shell.is_safe(&password));
+    /// ```
+    pub fn configure(self, mixer: &mut PenguinMixer) {
+        mixer.special_chars.retain(|c| self.allows(c));
+        if let Some(charset) = &mixer.charset {
+            let safe: String = charset
+                .as_str()
+                .chars()
+                .filter(|&c| self.allows(c))
+                .collect();
+            mixer.charset = Some(Charset::new(&safe));
+        }
+        mixer.reject_if.push(match self {
+            SafeFor::Shell => |p| !SafeFor::Shell.is_safe(p),
+            SafeFor::Yaml => |p| !SafeFor::Yaml.is_safe(p),
+            SafeFor::Json => |p| !SafeFor::Json.is_safe(p),
+            SafeFor::Ini => |p| !SafeFor::Ini.is_safe(p),
+        });
+    }
+}
+
+impl fmt::Display for SafeFor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SafeFor::Shell => "shell",
+            SafeFor::Yaml => "yaml",
+            SafeFor::Json => "json",
+            SafeFor::Ini => "ini",
+        })
+    }
+}
+
+impl FromStr for SafeFor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "shell" | "sh" => Ok(SafeFor::Shell),
+            "yaml" | "yml" => Ok(SafeFor::Yaml),
+            "json" => Ok(SafeFor::Json),
+            "ini" => Ok(SafeFor::Ini),
+            _ => Err(format!(
+                "unknown format '{}' (expected shell, yaml, json, or ini)",
+                s
+            )),
+        }
+    }
+}
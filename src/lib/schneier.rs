@@ -0,0 +1,150 @@
+//! Passwords condensed from a sentence, for the Penguin password generator.
+//!
+//! Bruce Schneier's scheme turns a sentence only you would think of into a password: take
+//! the first letter of every word, keep the punctuation and numbers, and swap some words
+//! for symbols that sound or look like them. "This little piggy went to market!" becomes
+//! `tlp` for the first three words, `2` for "to", `m` for "market", and the `!` at the
+//! end. With `went` spelled out in capitals the result is `tlpWENT2m!`: easy to recall
+//! from the sentence and meaningless without it.
+//!
+//! The strength comes from the sentence, which nothing here can judge, so no entropy is
+//! reported. Song lyrics and famous quotes are in attackers' dictionaries too.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::schneier::Scheme;
+//!
+//! let mut scheme = Scheme::default();
+//! scheme.substitute("went", "WENT");
+//! assert_eq!(scheme.apply("This little piggy went to market!"), "tlpWENT2m!");
+//! assert_eq!(scheme.apply("I ate 2 slices at 1984 Main St."), "i82s@1984ms.");
+//! ```
+
+/// Words replaced by the symbols or digits they sound like.
+pub const DEFAULT_SUBSTITUTIONS: [(&str, &str); 16] = [
+    ("to", "2"),
+    ("too", "2"),
+    ("two", "2"),
+    ("for", "4"),
+    ("four", "4"),
+    ("one", "1"),
+    ("won", "1"),
+    ("ate", "8"),
+    ("eight", "8"),
+    ("and", "&"),
+    ("at", "@"),
+    ("you", "u"),
+    ("are", "r"),
+    ("be", "b"),
+    ("see", "c"),
+    ("why", "y"),
+];
+
+/// How a sentence is condensed into a password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheme {
+    /// Words, matched ignoring case, that are replaced by the given text instead of being
+    /// shortened to their first letter. Later entries win.
+    pub substitutions: Vec<(String, String)>,
+    /// Keeps the case of the first letters as written; otherwise they are lowercased,
+    /// apart from the first letters of words that are all capitals.
+    pub keep_case: bool,
+}
+
+/// The [`DEFAULT_SUBSTITUTIONS`], with first letters lowercased.
+impl Default for Scheme {
+    fn default() -> Self {
+        Scheme {
+            substitutions: DEFAULT_SUBSTITUTIONS
+                .iter()
+                .map(|&(word, replacement)| (word.to_string(), replacement.to_string()))
+                .collect(),
+            keep_case: false,
+        }
+    }
+}
+
+impl Scheme {
+    /// A scheme without substitutions, keeping only first letters, punctuation, and
+    /// numbers.
+    ///
+    /// ```
+    /// use penguin::schneier::Scheme;
+    ///
+    /// assert_eq!(Scheme::initials().apply("Off to see the wizard."), "otstw.");
+    /// ```
+    pub fn initials() -> Self {
+        Scheme {
+            substitutions: Vec::new(),
+            keep_case: false,
+        }
+    }
+
+    /// Adds a substitution, replacing any earlier one for the same word.
+    pub fn substitute(&mut self, word: &str, replacement: &str) {
+        self.substitutions
+            .retain(|(known, _)| !known.eq_ignore_ascii_case(word));
+        self.substitutions
+            .push((word.to_string(), replacement.to_string()));
+    }
+
+    /// Condenses a sentence into a password.
+    ///
+    /// Every word becomes its substitution or its first letter, while punctuation around
+    /// and between words stays, and so does every digit of a number. Characters inside a
+    /// word, like the apostrophe in "don't", go with the rest of the word.
+    ///
+    /// ```
+    /// use penguin::schneier::Scheme;
+    ///
+    /// let mut scheme = Scheme::default();
+    /// assert_eq!(scheme.apply("Don't look, NASA: it's 3am!"), "dl,N:i3!");
+    /// scheme.keep_case = true;
+    /// assert_eq!(scheme.apply("Don't look, NASA: it's 3am!"), "Dl,N:i3!");
+    /// ```
+    pub fn apply(&self, sentence: &str) -> String {
+        let mut password = String::new();
+        for token in sentence.split_whitespace() {
+            let start = token
+                .find(|c: char| c.is_alphanumeric())
+                .unwrap_or(token.len());
+            let end = token
+                .rfind(|c: char| c.is_alphanumeric())
+                .map_or(start, |i| {
+                    i + token[i..].chars().next().map_or(1, char::len_utf8)
+                });
+            let (leading, word, trailing) = (&token[..start], &token[start..end], &token[end..]);
+            password.push_str(leading);
+            password.push_str(&self.condense_word(word));
+            password.push_str(trailing);
+        }
+        password
+    }
+
+    /// Returns what a single word, without surrounding punctuation, becomes.
+    fn condense_word(&self, word: &str) -> String {
+        if word.is_empty() {
+            return String::new();
+        }
+        if let Some((_, replacement)) = self
+            .substitutions
+            .iter()
+            .rev()
+            .find(|(known, _)| known.to_lowercase() == word.to_lowercase())
+        {
+            return replacement.clone();
+        }
+        let digits: String = word.chars().take_while(char::is_ascii_digit).collect();
+        if !digits.is_empty() {
+            return digits;
+        }
+        let first = word.chars().next().unwrap_or_default();
+        let shouting = word.chars().count() > 1 && !word.chars().any(char::is_lowercase);
+        if self.keep_case || shouting {
+            first.to_string()
+        } else {
+            first.to_lowercase().collect()
+        }
+    }
+}
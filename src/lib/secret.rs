@@ -0,0 +1,75 @@
+//! Passwords that wipe themselves from memory.
+//!
+//! A dropped `String` leaves its bytes behind in freed memory until the allocator hands
+//! the space out again, where a core dump, a swap file, or a memory-disclosure bug can
+//! still find them. A [`SecretString`] overwrites its bytes with zeros when it is
+//! dropped, keeps them out of its `Debug` output, and compares in constant time with
+//! [`compare`](crate::compare). Only [`expose`](SecretString::expose) reveals the
+//! password, which makes every place that reads it easy to find.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::mixer::{ComplexityLevel, PenguinMixer};
+//!
+//! let mixer = PenguinMixer::new(ComplexityLevel::Hard, false, 16);
+//! let secret = mixer.mix_secret(&["glacier"]).unwrap();
+//! assert_eq!(secret.expose().len(), 16);
+//! assert_eq!(format!("{:?}", secret), "SecretString(<redacted>)");
+//! ```
+
+use std::fmt;
+use zeroize::Zeroize;
+
+/// A password that is zeroed when dropped.
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Takes over the string, which is wiped along with the secret.
+    pub fn new(secret: String) -> Self {
+        SecretString(secret)
+    }
+
+    /// Returns the password.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the length of the password in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the password is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString::new(secret)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(<redacted>)")
+    }
+}
+
+/// Secrets compare in constant time.
+impl PartialEq for SecretString {
+    fn eq(&self, other: &Self) -> bool {
+        crate::compare(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretString {}
@@ -0,0 +1,219 @@
+//! Shamir secret sharing for escrowing generated passwords.
+//!
+//! A secret is split into `n` shares such that any `k` of them reconstruct it, while
+//! fewer than `k` reveal nothing about it. Each byte of the secret is the constant term
+//! of a random polynomial of degree `k - 1` over GF(256), and every share holds one
+//! point of each polynomial. Reconstruction uses Lagrange interpolation at zero.
+//!
+//! Shares are written as `<k>-<x>-<hex>`, for example `2-1-9f3a...`, so a share records
+//! how many shares are needed alongside its own index.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::shamir;
+//!
+//! let shares = shamir::split(b"correct horse", 2, 3).unwrap();
+//! let recovered = shamir::combine(&shares[1..]).unwrap();
+//! assert_eq!(recovered, b"correct horse");
+//!
+//! let share: shamir::Share = shares[0].to_string().parse().unwrap();
+//! assert_eq!(share, shares[0]);
+//! ```
+
+use rand::RngCore;
+use std::fmt;
+use std::str::FromStr;
+
+/// Errors that can occur while splitting or combining shares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShamirError {
+    /// The threshold must be at least 2 and no larger than the number of shares.
+    InvalidThreshold { threshold: u8, shares: u8 },
+    /// Fewer shares were given than their threshold requires.
+    NotEnoughShares { needed: u8, given: usize },
+    /// Two shares with the same index were given.
+    DuplicateShare(u8),
+    /// The shares disagree on their threshold or length, so they belong to different secrets.
+    MismatchedShares,
+    /// A share could not be parsed.
+    MalformedShare(String),
+}
+
+impl fmt::Display for ShamirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShamirError::InvalidThreshold { threshold, shares } => write!(
+                f,
+                "cannot split into {} shares with threshold {}; need 2 <= threshold <= shares",
+                shares, threshold
+            ),
+            ShamirError::NotEnoughShares { needed, given } => {
+                write!(
+                    f,
+                    "{} shares are needed but only {} were given",
+                    needed, given
+                )
+            }
+            ShamirError::DuplicateShare(x) => write!(f, "share {} was given more than once", x),
+            ShamirError::MismatchedShares => write!(f, "the shares belong to different secrets"),
+            ShamirError::MalformedShare(share) => write!(f, "malformed share '{}'", share),
+        }
+    }
+}
+
+impl std::error::Error for ShamirError {}
+
+/// A single share of a split secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    /// Number of shares required to reconstruct the secret.
+    pub threshold: u8,
+    /// The share's index, the x-coordinate of its points. Never zero.
+    pub x: u8,
+    /// One polynomial value per byte of the secret.
+    pub y: Vec<u8>,
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-", self.threshold, self.x)?;
+        for byte in &self.y {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Share {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || ShamirError::MalformedShare(s.to_string());
+        let mut parts = s.trim().splitn(3, '-');
+        let threshold = parts
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(malformed)?;
+        let x: u8 = parts
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(malformed)?;
+        let hex = parts.next().ok_or_else(malformed)?;
+        if x == 0 || hex.is_empty() || hex.len() % 2 != 0 {
+            return Err(malformed());
+        }
+
+        let y = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(malformed)?;
+        Ok(Share { threshold, x, y })
+    }
+}
+
+/// Splits a secret into `shares` shares, any `threshold` of which reconstruct it.
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<Share>, ShamirError> {
+    if threshold < 2 || threshold > shares {
+        return Err(ShamirError::InvalidThreshold { threshold, shares });
+    }
+
+    let mut result: Vec<Share> = (1..=shares)
+        .map(|x| Share {
+            threshold,
+            x,
+            y: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+
+    let mut rng = crate::entropy::rng();
+    let mut coefficients = vec![0u8; threshold as usize];
+    for &byte in secret {
+        coefficients[0] = byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+        for share in &mut result {
+            share.y.push(evaluate(&coefficients, share.x));
+        }
+    }
+    coefficients.fill(0);
+
+    Ok(result)
+}
+
+/// Reconstructs a secret from at least as many shares as its threshold.
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        needed: 2,
+        given: 0,
+    })?;
+    if shares
+        .iter()
+        .any(|share| share.threshold != first.threshold || share.y.len() != first.y.len())
+    {
+        return Err(ShamirError::MismatchedShares);
+    }
+    if shares.len() < first.threshold as usize {
+        return Err(ShamirError::NotEnoughShares {
+            needed: first.threshold,
+            given: shares.len(),
+        });
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|other| other.x == share.x) {
+            return Err(ShamirError::DuplicateShare(share.x));
+        }
+    }
+
+    let shares = &shares[..first.threshold as usize];
+    let secret = (0..first.y.len())
+        .map(|i| {
+            shares.iter().fold(0u8, |acc, share| {
+                let basis = shares
+                    .iter()
+                    .filter(|other| other.x != share.x)
+                    .fold(1u8, |basis, other| {
+                        gf_mul(basis, gf_mul(other.x, gf_inverse(other.x ^ share.x)))
+                    });
+                acc ^ gf_mul(share.y[i], basis)
+            })
+        })
+        .collect();
+    Ok(secret)
+}
+
+/// Evaluates a polynomial at `x` using Horner's method.
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0, |acc, &coefficient| gf_mul(acc, x) ^ coefficient)
+}
+
+/// Multiplies in GF(256) with the AES reduction polynomial, without lookup tables so
+/// the running time doesn't depend on the secret through cache behaviour.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Computes the multiplicative inverse as `a^254`, since `a^255 = 1` for non-zero `a`.
+fn gf_inverse(a: u8) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
@@ -0,0 +1,236 @@
+//! Per-site password rules for the Penguin password generator.
+//!
+//! Many sites cap the length of a password or accept only some symbols, and few say so
+//! until a generated password is rejected. [`SiteRules`] maps domains to the rules they
+//! enforce, written in the syntax of Apple's password-rules project:
+//!
+//! ```text
+//! minlength: 8; maxlength: 20; required: lower; required: upper; required: digit; allowed: [-_.!];
+//! ```
+//!
+//! Each rule becomes part of a [`PasswordPolicy`]: `minlength` and `maxlength` bound the
+//! length, every `required` set must appear in the password, `allowed` lists the only
+//! characters it may contain, and `max-consecutive` limits runs of one repeated
+//! character. Sets name the classes `lower`, `upper`, `digit`, `special`,
+//! `ascii-printable`, and `unicode`, or list characters between brackets. Without an
+//! `allowed` rule, a site with `required` rules accepts only the required characters,
+//! and a site with neither accepts any printable ASCII.
+//!
+//! A small database of sites ships with the crate, and [`SiteRules::extend`] adds to or
+//! overrides it.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::siterules::SiteRules;
+//!
+//! let mut sites = SiteRules::embedded();
+//! sites.extend("intranet.example.com  maxlength: 12; allowed: lower, digit;").unwrap();
+//!
+//! let policy = sites.get("https://login.intranet.example.com/sso").unwrap();
+//! assert_eq!(policy.max_length, Some(12));
+//! assert!(policy.check("glacier42"));
+//! assert!(!policy.check("Glacier42"));
+//! assert!(sites.get("unknown.example.org").is_none());
+//!
+//! let (domain, _) = sites.rules("www.github.com").unwrap();
+//! assert_eq!(domain, "github.com");
+//! assert_eq!(sites.get("github.com").unwrap().min_length, Some(15));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::policy::PasswordPolicy;
+
+/// The rules of the sites known out of the box, one `domain rules` pair per line.
+const SITES: &str = include_str!("data/siterules/sites.txt");
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGIT: &str = "0123456789";
+const SPECIAL: &str = "-~!@#$%^&*_+=`|(){}[:;\"'<>,.?]/\\ ";
+
+/// Parses a site's rules into the policy they describe.
+///
+/// ```
+/// use penguin::siterules::parse;
+///
+/// let policy = parse("minlength: 8; maxlength: 20; required: digit; allowed: lower, [!?];").unwrap();
+/// assert_eq!((policy.min_length, policy.max_length), (Some(8), Some(20)));
+/// assert!(policy.check("glacier7!"));
+/// assert!(!policy.check("glacier!!"));
+/// assert!(!policy.check("Glacier7!"));
+///
+/// let policy = parse("allowed: lower, [;:]; maxlength: 9;").unwrap();
+/// assert!(policy.check("ice;floe"));
+///
+/// assert!(parse("maxlength: twelve;").is_err());
+/// ```
+pub fn parse(rules: &str) -> Result<PasswordPolicy, String> {
+    let mut policy = PasswordPolicy::default();
+    let mut allowed: Option<String> = None;
+    let mut any_char = false;
+    for rule in split_rules(rules) {
+        let (name, value) = rule
+            .split_once(':')
+            .ok_or_else(|| format!("expected name: value in '{}'", rule))?;
+        let (name, value) = (name.trim(), value.trim());
+        let number = || {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("{} needs a number, not '{}'", name, value))
+        };
+        match name {
+            "minlength" => policy.min_length = Some(number()?),
+            "maxlength" => policy.max_length = Some(number()?),
+            "max-consecutive" => policy.max_consecutive = Some(number()?),
+            "required" => policy.required.push(parse_set(value)?),
+            "allowed" => {
+                any_char |= value.split(',').any(|class| class.trim() == "unicode");
+                let set = parse_set(value)?;
+                allowed.get_or_insert_with(String::new).push_str(&set);
+            }
+            _ => return Err(format!("unknown rule '{}'", name)),
+        }
+    }
+
+    policy.allowed = match allowed {
+        // Required characters are always allowed.
+        Some(mut allowed) => {
+            allowed.extend(policy.required.iter().flat_map(|set| set.chars()));
+            Some(allowed)
+        }
+        None if !policy.required.is_empty() => Some(policy.required.concat()),
+        None => Some([LOWER, UPPER, DIGIT, SPECIAL].concat()),
+    };
+    if any_char {
+        policy.allowed = None;
+    }
+    policy.validate().map_err(|err| err.to_string())?;
+    Ok(policy)
+}
+
+/// Splits rules at the semicolons between them, leaving those between brackets alone.
+fn split_rules(rules: &str) -> impl Iterator<Item = &str> {
+    let mut in_brackets = false;
+    let mut opened = false;
+    rules
+        .split(move |c| {
+            // Like in `parse_set`, a `]` right after the opening bracket doesn't close it.
+            let just_opened = std::mem::take(&mut opened);
+            match c {
+                '[' if !in_brackets => (in_brackets, opened) = (true, true),
+                ']' if in_brackets && !just_opened => in_brackets = false,
+                _ => {}
+            }
+            c == ';' && !in_brackets
+        })
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+}
+
+/// Parses a comma-separated list of class names and bracketed characters into the set of
+/// characters it covers.
+fn parse_set(value: &str) -> Result<String, String> {
+    let mut set = String::new();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        if let Some(inner) = rest.strip_prefix('[') {
+            // A `]` right after the opening bracket is one of the characters.
+            let end = inner
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == ']')
+                .map(|(i, _)| i)
+                .or_else(|| (inner == "]").then_some(0))
+                .ok_or_else(|| format!("unclosed '[' in '{}'", value))?;
+            set.push_str(&inner[..end]);
+            rest = &inner[end + 1..];
+        } else {
+            let (name, tail) = rest.split_once(',').unwrap_or((rest, ""));
+            match name.trim() {
+                "lower" => set.push_str(LOWER),
+                "upper" => set.push_str(UPPER),
+                "digit" => set.push_str(DIGIT),
+                "special" => set.push_str(SPECIAL),
+                "ascii-printable" => set.extend([LOWER, UPPER, DIGIT, SPECIAL]),
+                // Handled by `parse`, since no set of characters covers it.
+                "unicode" => {}
+                other => return Err(format!("unknown character class '{}'", other)),
+            }
+            rest = tail;
+        }
+        rest = rest.trim_start().trim_start_matches(',').trim_start();
+    }
+    Ok(set)
+}
+
+/// A database of sites and the rules their passwords have to follow.
+#[derive(Debug, Clone, Default)]
+pub struct SiteRules {
+    sites: HashMap<String, (String, PasswordPolicy)>,
+}
+
+impl SiteRules {
+    /// Returns the database of sites that ships with the crate.
+    pub fn embedded() -> Self {
+        let mut rules = SiteRules::default();
+        // An invalid line would leave the database empty, which the examples catch.
+        let _ = rules.extend(SITES);
+        rules
+    }
+
+    /// Adds the `domain rules` pairs of a file, one per line, replacing the rules of any
+    /// domain already known. Blank lines and lines starting with `#` are skipped. Nothing
+    /// is added when a line is invalid.
+    pub fn extend(&mut self, text: &str) -> Result<(), String> {
+        let mut added = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (domain, rules) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("line {}: expected a domain and its rules", number + 1))?;
+            let policy = parse(rules).map_err(|err| format!("line {}: {}", number + 1, err))?;
+            added.push((domain.to_lowercase(), (rules.trim().to_string(), policy)));
+        }
+        self.sites.extend(added);
+        Ok(())
+    }
+
+    /// Returns the policy for a site, given as a domain or URL. A subdomain falls back to
+    /// the rules of its closest parent domain.
+    pub fn get(&self, site: &str) -> Option<&PasswordPolicy> {
+        self.find(site).map(|(_, (_, policy))| policy)
+    }
+
+    /// Returns the domain whose rules apply to a site and the rules as written.
+    pub fn rules(&self, site: &str) -> Option<(&str, &str)> {
+        self.find(site)
+            .map(|(domain, (rules, _))| (domain.as_str(), rules.as_str()))
+    }
+
+    /// Returns every known domain, sorted.
+    pub fn domains(&self) -> Vec<&str> {
+        let mut domains: Vec<&str> = self.sites.keys().map(String::as_str).collect();
+        domains.sort_unstable();
+        domains
+    }
+
+    fn find(&self, site: &str) -> Option<(&String, &(String, PasswordPolicy))> {
+        let host = site.split_once("://").map_or(site, |(_, rest)| rest);
+        let host = host.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+        let host = host.split(':').next().unwrap_or_default().to_lowercase();
+
+        let mut domain = host.as_str();
+        loop {
+            if let Some(entry) = self.sites.get_key_value(domain) {
+                return Some(entry);
+            }
+            domain = domain.split_once('.')?.1;
+        }
+    }
+}
@@ -0,0 +1,468 @@
+//! Password strength estimation for the Penguin password generator.
+//!
+//! This module estimates how hard a password is to guess by looking at which character
+//! classes it draws from and how long it is. The estimate assumes every character was
+//! picked uniformly from the union of the classes present, which makes it an upper bound:
+//! passwords built from whole words carry less real entropy than their length suggests.
+//!
+//! Keyboard walks of four keys or more, like `qwerty` or `1qaz2wsx`, are the exception:
+//! after its first key, each key of a walk counts for a single bit, the choice of where
+//! the walk turns, rather than a free pick from the pool. With
+//! [`estimate_entropy_with`], names, birthdays, and other personal details in the
+//! password count for hardly anything at all, and with [`estimate_entropy_with_words`]
+//! so do the base words it was generated from.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::strength::estimate_entropy;
+//!
+//! let report = estimate_entropy("hello7World!");
+//! assert_eq!(report.length, 12);
+//! assert!(report.has_uppercase && report.has_digits && report.has_special);
+//! assert!(report.entropy_bits > 70.0);
+//! assert!(estimate_entropy("qwerty123456").entropy_bits < 25.0);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::dates::{self, DateMatch};
+use crate::layout::{self, KeyboardWalk, Layout};
+use crate::personal::PersonalInfo;
+
+/// Number of distinct characters in each class, matching the pools the mixer draws from.
+const LOWERCASE_POOL: usize = 26;
+const UPPERCASE_POOL: usize = 26;
+const DIGIT_POOL: usize = 10;
+const SPECIAL_POOL: usize = 33;
+
+/// The fewest keys a keyboard walk has to be long to be penalized.
+pub const MIN_WALK_KEYS: usize = 4;
+
+/// The fewest characters a base word needs for [`estimate_entropy_with_words`] to look
+/// for it; shorter words turn up by chance.
+const MIN_WORD_CHARS: usize = 3;
+
+/// Bits each key of a keyboard walk after the first is worth.
+const WALK_BITS_PER_KEY: f64 = 1.0;
+
+/// The result of analysing a single password.
+///
+/// The report records which character classes the password uses, the size of the
+/// character pool those classes imply, and the resulting entropy estimate in bits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthReport {
+    pub length: usize,
+    pub entropy_bits: f64,
+    pub charset_size: usize,
+    pub has_lowercase: bool,
+    pub has_uppercase: bool,
+    pub has_digits: bool,
+    pub has_special: bool,
+    /// The keyboard walks of at least [`MIN_WALK_KEYS`] keys, which lower the estimate.
+    pub keyboard_walks: Vec<KeyboardWalk>,
+    /// Digits that read as a date, which guessers try early. They are flagged without
+    /// changing the estimate.
+    pub dates: Vec<DateMatch>,
+    /// The personal details found by [`estimate_entropy_with`]; always empty otherwise.
+    pub personal_info: Vec<String>,
+    /// The base words found whole by [`estimate_entropy_with_words`]; always empty
+    /// otherwise.
+    pub base_words: Vec<String>,
+}
+
+/// Estimates the entropy of a password from its length and character-class coverage.
+///
+/// Characters outside printable ASCII letters, digits, and symbols (such as accented
+/// letters) are counted towards the special class. An empty password has zero entropy.
+///
+/// ```
+/// use penguin::strength::estimate_entropy;
+///
+/// let report = estimate_entropy("asdfgh-glacier");
+/// assert_eq!(report.keyboard_walks[0].keys, "asdfgh");
+/// assert!(report.dates.is_empty());
+/// assert_eq!(estimate_entropy("Summer2024!").dates[0].text, "2024");
+/// assert!(report.entropy_bits < estimate_entropy("hgfsda-glacier").entropy_bits);
+/// ```
+pub fn estimate_entropy(password: &str) -> StrengthReport {
+    let has_lowercase = password.chars().any(|c| c.is_ascii_lowercase());
+    let has_uppercase = password.chars().any(|c| c.is_ascii_uppercase());
+    let has_digits = password.chars().any(|c| c.is_ascii_digit());
+    let has_special = password.chars().any(|c| !c.is_ascii_alphanumeric());
+
+    let mut charset_size = 0;
+    if has_lowercase {
+        charset_size += LOWERCASE_POOL;
+    }
+    if has_uppercase {
+        charset_size += UPPERCASE_POOL;
+    }
+    if has_digits {
+        charset_size += DIGIT_POOL;
+    }
+    if has_special {
+        charset_size += SPECIAL_POOL;
+    }
+
+    let length = password.chars().count();
+    let keyboard_walks = layout::keyboard_walks(password, MIN_WALK_KEYS);
+    let walked: usize = keyboard_walks.iter().map(|walk| walk.len() - 1).sum();
+    let entropy_bits = if charset_size == 0 {
+        0.0
+    } else {
+        (length - walked) as f64 * (charset_size as f64).log2() + walked as f64 * WALK_BITS_PER_KEY
+    };
+
+    StrengthReport {
+        length,
+        entropy_bits,
+        charset_size,
+        has_lowercase,
+        has_uppercase,
+        has_digits,
+        has_special,
+        keyboard_walks,
+        dates: dates::find_dates(password),
+        personal_info: Vec::new(),
+        base_words: Vec::new(),
+    }
+}
+
+/// Estimates the entropy of a password like [`estimate_entropy`], for a password chosen
+/// by the person the details are about.
+///
+/// A guesser who knows the details tries them first, so every detail found is worth
+/// only the bits of picking it from the list of details, in place of the bits of its
+/// characters. Details that are part of a longer one found are counted once.
+///
+/// ```
+/// use penguin::personal::PersonalInfo;
+/// use penguin::strength::{estimate_entropy, estimate_entropy_with, Rating};
+///
+/// let mut info = PersonalInfo::new();
+/// info.add("Rosalind Franklin");
+/// info.add("1920-07-25");
+/// let report = estimate_entropy_with("R0sal1nd!2507", &info);
+/// assert_eq!(report.personal_info, ["rosalind", "2507"]);
+/// assert!(report.entropy_bits < 20.0);
+/// assert_eq!(report.rating(), Rating::Weak);
+/// assert!(estimate_entropy("R0sal1nd!2507").entropy_bits > 80.0);
+/// ```
+pub fn estimate_entropy_with(password: &str, personal: &PersonalInfo) -> StrengthReport {
+    let mut report = estimate_entropy(password);
+    let found = personal.find(password);
+    let found: Vec<&str> = found
+        .iter()
+        .filter(|token| {
+            !found
+                .iter()
+                .any(|other| other.len() > token.len() && other.contains(**token))
+        })
+        .copied()
+        .collect();
+    if report.charset_size > 0 {
+        let bits_per_char = (report.charset_size as f64).log2();
+        let bits_per_detail = (personal.tokens().len() as f64).log2();
+        for token in &found {
+            report.entropy_bits -= token.chars().count() as f64 * bits_per_char;
+            report.entropy_bits += bits_per_detail;
+        }
+        report.entropy_bits = report.entropy_bits.max(0.0);
+    }
+    report.personal_info = found.iter().map(|token| token.to_string()).collect();
+    report
+}
+
+/// Estimates the entropy of a password like [`estimate_entropy`], for a password
+/// generated from the given base words.
+///
+/// Whole words are the first thing a guesser who knows the base words tries, so every
+/// base word of three characters or more found in the password, ignoring case, is worth
+/// only the bits of picking it from the base words, in place of the bits of its
+/// characters. Words that are part of a longer one found are counted once.
+///
+/// ```
+/// use penguin::strength::{estimate_entropy, estimate_entropy_with_words};
+///
+/// let report = estimate_entropy_with_words("Glacier!4penguin", &["glacier", "penguin", "ice"]);
+/// assert_eq!(report.base_words, ["glacier", "penguin"]);
+/// assert!(report.entropy_bits < 20.0);
+/// assert!(estimate_entropy("Glacier!4penguin").entropy_bits > 90.0);
+/// ```
+pub fn estimate_entropy_with_words(password: &str, words: &[&str]) -> StrengthReport {
+    let mut report = estimate_entropy(password);
+    let lowered = password.to_lowercase();
+    let mut candidates: Vec<String> = words
+        .iter()
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| word.chars().count() >= MIN_WORD_CHARS)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    let found: Vec<&String> = candidates
+        .iter()
+        .filter(|word| lowered.contains(word.as_str()))
+        .collect();
+    let found: Vec<&String> = found
+        .iter()
+        .filter(|word| {
+            !found
+                .iter()
+                .any(|other| other.len() > word.len() && other.contains(word.as_str()))
+        })
+        .copied()
+        .collect();
+    if report.charset_size > 0 {
+        let bits_per_char = (report.charset_size as f64).log2();
+        let bits_per_word = (candidates.len() as f64).log2();
+        for word in &found {
+            report.entropy_bits -= word.chars().count() as f64 * bits_per_char;
+            report.entropy_bits += bits_per_word;
+        }
+        report.entropy_bits = report.entropy_bits.max(0.0);
+    }
+    report.base_words = found.into_iter().cloned().collect();
+    report
+}
+
+/// A coarse verdict on a password's estimated entropy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rating {
+    /// Under 60 bits: within reach of an offline attack on a fast hash.
+    Weak,
+    /// 60 to 80 bits: fine behind rate limiting or a slow hash.
+    Fair,
+    /// 80 to 128 bits.
+    Strong,
+    /// 128 bits or more: out of reach of any brute-force attack.
+    Excellent,
+}
+
+impl Rating {
+    /// Rates an entropy estimate in bits.
+    pub fn from_bits(bits: f64) -> Rating {
+        match bits {
+            bits if bits < 60.0 => Rating::Weak,
+            bits if bits < 80.0 => Rating::Fair,
+            bits if bits < 128.0 => Rating::Strong,
+            _ => Rating::Excellent,
+        }
+    }
+}
+
+impl StrengthReport {
+    /// Rates the entropy estimate.
+    ///
+    /// ```
+    /// use penguin::strength::{estimate_entropy, Rating};
+    ///
+    /// assert_eq!(estimate_entropy("penguin").rating(), Rating::Weak);
+    /// assert_eq!(estimate_entropy("hello7World!").rating(), Rating::Fair);
+    /// assert_eq!(estimate_entropy("hello7World!x9Q").rating(), Rating::Strong);
+    /// assert_eq!(estimate_entropy("glacier-Penguin-42-floe!").rating(), Rating::Excellent);
+    /// ```
+    pub fn rating(&self) -> Rating {
+        Rating::from_bits(self.entropy_bits)
+    }
+}
+
+/// Someone trying to guess a password, by how fast they can make guesses.
+///
+/// How long a password holds out depends far more on the attack than on the password: the
+/// same 40 bits last decades against a throttled login form and seconds against a leaked
+/// MD5 hash. [`seconds_to_crack`](Self::seconds_to_crack) puts a number on each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attacker {
+    /// Guessing through a login form that allows 100 attempts a second.
+    Online,
+    /// Guessing offline against bcrypt hashes, at 10,000 guesses a second.
+    Bcrypt,
+    /// Guessing offline against MD5 hashes on a GPU rig, at 100 billion guesses a second.
+    Md5,
+}
+
+impl Attacker {
+    /// Every attacker, slowest first.
+    pub const ALL: [Attacker; 3] = [Attacker::Online, Attacker::Bcrypt, Attacker::Md5];
+
+    /// How many guesses the attacker makes every second.
+    pub fn guesses_per_second(self) -> f64 {
+        match self {
+            Attacker::Online => 1e2,
+            Attacker::Bcrypt => 1e4,
+            Attacker::Md5 => 1e11,
+        }
+    }
+
+    /// Describes the attack and its speed.
+    pub fn description(self) -> &'static str {
+        match self {
+            Attacker::Online => "online, throttled (100/s)",
+            Attacker::Bcrypt => "offline, bcrypt (10k/s)",
+            Attacker::Md5 => "offline, MD5 on GPUs (100G/s)",
+        }
+    }
+
+    /// Returns how many seconds the attacker needs on average to guess a password with
+    /// `bits` of entropy, searching half of the possibilities.
+    ///
+    /// ```
+    /// use penguin::strength::Attacker;
+    ///
+    /// assert_eq!(Attacker::Online.seconds_to_crack(10.0), 5.12);
+    /// assert!(Attacker::Md5.seconds_to_crack(40.0) < 10.0);
+    /// ```
+    pub fn seconds_to_crack(self, bits: f64) -> f64 {
+        2f64.powf(bits - 1.0) / self.guesses_per_second()
+    }
+}
+
+impl fmt::Display for Attacker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Attacker::Online => "online",
+            Attacker::Bcrypt => "bcrypt",
+            Attacker::Md5 => "md5",
+        })
+    }
+}
+
+impl FromStr for Attacker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "online" | "throttled" => Ok(Attacker::Online),
+            "bcrypt" => Ok(Attacker::Bcrypt),
+            "md5" | "gpu" => Ok(Attacker::Md5),
+            _ => Err(format!(
+                "unknown attacker '{}' (expected online, bcrypt, or md5)",
+                s
+            )),
+        }
+    }
+}
+
+/// Writes a number of seconds as the largest whole unit that fits, such as `3 hours`,
+/// or `centuries` beyond a hundred years.
+///
+/// ```
+/// use penguin::strength::format_duration;
+///
+/// assert_eq!(format_duration(0.2), "less than a second");
+/// assert_eq!(format_duration(90.0), "1 minute");
+/// assert_eq!(format_duration(3.0 * 86_400.0), "3 days");
+/// assert_eq!(format_duration(1e12), "centuries");
+/// ```
+pub fn format_duration(seconds: f64) -> String {
+    const UNITS: [(&str, f64); 6] = [
+        ("year", 365.25 * 86_400.0),
+        ("month", 30.44 * 86_400.0),
+        ("day", 86_400.0),
+        ("hour", 3_600.0),
+        ("minute", 60.0),
+        ("second", 1.0),
+    ];
+    if seconds >= 100.0 * UNITS[0].1 {
+        return String::from("centuries");
+    }
+    for (unit, length) in UNITS {
+        let count = (seconds / length).floor();
+        if count >= 1.0 {
+            let plural = if count == 1.0 { "" } else { "s" };
+            return format!("{} {}{}", count, unit, plural);
+        }
+    }
+    String::from("less than a second")
+}
+
+/// Rates how close a new password is to an old one, from 0.0 (unrelated) to 1.0
+/// (the same keys pressed in the same order), so rotation tooling can reject a new
+/// password that is a trivial variant of the one it replaces.
+///
+/// The score is the highest of three measures. The edit distance between the two,
+/// normalized by the longer length, catches bumped counters and swapped symbols.
+/// Characters are compared by the US keyboard key that types them, so changing case or
+/// toggling Shift (`1` and `!`) doesn't count as a difference. One password containing
+/// the other, when the shorter is at least half as long, catches appended and prepended
+/// text. Every character moved the same number of keys along its row, like `qwerty123`
+/// and `wertyu234`, scores 0.9.
+///
+/// ```
+/// use penguin::strength::similarity;
+///
+/// assert!(similarity("Winter2023!", "Winter2024!") > 0.9);
+/// assert_eq!(similarity("Winter2023!", "wINTER2023!"), 1.0);
+/// assert_eq!(similarity("Winter2023!", "Winter2023?"), 1.0 - 1.0 / 11.0);
+/// assert_eq!(similarity("qwerty123", "wertyu234"), 0.9);
+/// assert!(similarity("Summer", "Summer-2024?") >= 0.75);
+/// assert!(similarity("glacier-Penguin-42", "Tr0ub4dor&3") < 0.3);
+/// ```
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let keys = |password: &str| -> Vec<Result<(usize, usize), char>> {
+        password
+            .chars()
+            .map(|c| Layout::Qwerty.key(c).ok_or(c))
+            .collect()
+    };
+    let (a, b) = (keys(a), keys(b));
+    let (short, long) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if long.is_empty() {
+        return 1.0;
+    }
+
+    let mut score = 1.0 - edit_distance(&a, &b) as f64 / long.len() as f64;
+    let contained = short.len() * 2 >= long.len()
+        && long
+            .windows(short.len())
+            .any(|window| window == short.as_slice());
+    if contained {
+        score = score.max(0.5 + 0.5 * short.len() as f64 / long.len() as f64);
+    }
+    if score < 0.9 && shifted_along_rows(&a, &b) {
+        score = 0.9;
+    }
+    score
+}
+
+/// Returns the Levenshtein distance between two sequences.
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != y);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Returns whether every key of `b` sits the same nonzero number of keys along the
+/// row from the matching key of `a`.
+fn shifted_along_rows(
+    a: &[Result<(usize, usize), char>],
+    b: &[Result<(usize, usize), char>],
+) -> bool {
+    if a.len() != b.len() || a.is_empty() {
+        return false;
+    }
+    let offsets: Option<Vec<isize>> = a
+        .iter()
+        .zip(b)
+        .map(|pair| match pair {
+            (Ok((row, x)), Ok((other_row, y))) if row == other_row => {
+                Some(*y as isize - *x as isize)
+            }
+            _ => None,
+        })
+        .collect();
+    offsets.is_some_and(|offsets| offsets[0] != 0 && offsets.iter().all(|o| *o == offsets[0]))
+}
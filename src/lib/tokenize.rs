@@ -0,0 +1,100 @@
+//! Splitting free-form phrases into base words for the Penguin password generator.
+//!
+//! People remember sentences more easily than lists, and identifiers come glued
+//! together. A [`Tokenizer`] turns either into the separate words the generator works
+//! with, so `the quick brown fox` needs no commas. Each tokenizer splits at everything the
+//! one before it does, and more: [`Tokenizer::Whitespace`] at spaces, tabs, and line
+//! breaks, [`Tokenizer::Punctuation`] also at any other character that is neither a
+//! letter nor a digit, and [`Tokenizer::CamelCase`] also where a new capitalized word
+//! starts. The words borrow from the phrase, so none of them is copied.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::tokenize::Tokenizer;
+//!
+//! assert_eq!(Tokenizer::Whitespace.split("the quick  brown"), ["the", "quick", "brown"]);
+//! assert_eq!(Tokenizer::Punctuation.split("ice,floe; krill!"), ["ice", "floe", "krill"]);
+//! assert_eq!(Tokenizer::CamelCase.split("emperorPenguin"), ["emperor", "Penguin"]);
+//! ```
+
+use std::str::FromStr;
+
+/// Where a phrase is split into words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tokenizer {
+    /// At runs of whitespace.
+    #[default]
+    Whitespace,
+    /// At runs of whitespace and of any other character that is neither a letter nor a
+    /// digit, which drops the punctuation.
+    Punctuation,
+    /// Like [`Punctuation`](Self::Punctuation), and also between a lowercase letter or a
+    /// digit and the uppercase letter after it, and before the last capital of a run of
+    /// capitals that starts a word, so `parseHTTPResponse` gives `parse`, `HTTP`, and
+    /// `Response`.
+    CamelCase,
+}
+
+impl Tokenizer {
+    /// Splits the phrase into words, in order, leaving out empty ones.
+    ///
+    /// ```
+    /// use penguin::tokenize::Tokenizer;
+    ///
+    /// assert_eq!(
+    ///     Tokenizer::CamelCase.split("parseHTTPResponse v2Beta"),
+    ///     ["parse", "HTTP", "Response", "v2", "Beta"]
+    /// );
+    /// assert!(Tokenizer::Punctuation.split(" -- ").is_empty());
+    /// ```
+    pub fn split(self, phrase: &str) -> Vec<&str> {
+        let separator = |c: char| match self {
+            Tokenizer::Whitespace => c.is_whitespace(),
+            Tokenizer::Punctuation | Tokenizer::CamelCase => !c.is_alphanumeric(),
+        };
+        let pieces = phrase.split(separator).filter(|piece| !piece.is_empty());
+        if self != Tokenizer::CamelCase {
+            return pieces.collect();
+        }
+        pieces.flat_map(camel_case).collect()
+    }
+}
+
+/// Splits a word without separators at its capitals.
+fn camel_case(word: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for i in 1..chars.len() {
+        let (at, current) = chars[i];
+        let previous = chars[i - 1].1;
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let boundary = current.is_uppercase()
+            && (previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next.is_some_and(char::is_lowercase)));
+        if boundary {
+            words.push(&word[start..at]);
+            start = at;
+        }
+    }
+    words.push(&word[start..]);
+    words
+}
+
+impl FromStr for Tokenizer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "whitespace" | "space" => Ok(Tokenizer::Whitespace),
+            "punctuation" | "punct" => Ok(Tokenizer::Punctuation),
+            "camel-case" | "camelcase" | "camel" => Ok(Tokenizer::CamelCase),
+            _ => Err(format!(
+                "unknown tokenizer '{}' (expected whitespace, punctuation, or camel-case)",
+                s
+            )),
+        }
+    }
+}
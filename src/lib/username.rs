@@ -0,0 +1,38 @@
+//! Username generation for the Penguin password generator.
+//!
+//! Usernames pair an adjective with a noun from the embedded
+//! [`wordlist`](crate::wordlist) and append a few digits, giving names like
+//! `brave_otter42`. They are meant for demo tenants and test accounts, where every
+//! account needs a distinct, readable name but nothing about it has to be secret.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::username;
+//!
+//! let name = username::generate(2);
+//! let (words, digits) = name.split_at(name.len() - 2);
+//! assert!(words.contains('_'));
+//! assert!(digits.chars().all(|c| c.is_ascii_digit()));
+//! ```
+
+use rand::Rng;
+
+use crate::wordlist::Category;
+
+/// Generates a username of the form `adjective_noun` followed by `digits` random digits.
+pub fn generate(digits: usize) -> String {
+    let mut rng = crate::entropy::rng();
+    let adjectives = Category::Adjective.words();
+    let nouns = Category::Noun.words();
+
+    let mut name = format!(
+        "{}_{}",
+        adjectives[rng.gen_range(0..adjectives.len())],
+        nouns[rng.gen_range(0..nouns.len())]
+    );
+    for _ in 0..digits {
+        name.push_str(&rng.gen_range(0..10).to_string());
+    }
+    name
+}
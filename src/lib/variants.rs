@@ -0,0 +1,134 @@
+//! Derived variants of base words for the Penguin password generator.
+//!
+//! Someone who only remembers two or three words gets few distinct passwords out of
+//! them. [`expand`] adds variants of every word before mixing: its plural, the word
+//! reversed, the word without its vowels, and the word capitalized. Each variant the
+//! rules produce is a new choice, so a list of `n` words grows to at most `5n`, and every
+//! word picked from it carries up to log2(5) ≈ 2.32 bits more than one picked from the
+//! originals.
+//!
+//! Those bits come from which variant the generator picks, not from the words: an
+//! attacker who knows the base words can apply the same rules. The expansion widens the
+//! choice between known words, but it can't make guessable words secret.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::variants::{expand, Variant};
+//!
+//! let words = expand(&["penguin", "glacier"], &Variant::ALL);
+//! assert_eq!(&words[..2], ["penguin", "glacier"]);
+//! assert!(words.contains(&String::from("penguins")));
+//! assert!(words.contains(&String::from("reicalg")));
+//! assert!(words.contains(&String::from("pngn")));
+//! assert!(words.contains(&String::from("Glacier")));
+//! assert_eq!(words.len(), 10);
+//! ```
+
+use std::str::FromStr;
+
+/// A way of deriving a new word from a base word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// The English plural: `glacier` becomes `glaciers`, `box` `boxes`, `berry` `berries`.
+    Plural,
+    /// The word spelled backwards. Palindromes have none.
+    Reversed,
+    /// The word without its vowels after the first letter, as long as two letters remain.
+    NoVowels,
+    /// The word with its first letter in uppercase.
+    Capitalized,
+}
+
+impl Variant {
+    /// Every variant, in the order [`expand`] applies them.
+    pub const ALL: [Variant; 4] = [
+        Variant::Plural,
+        Variant::Reversed,
+        Variant::NoVowels,
+        Variant::Capitalized,
+    ];
+
+    /// Derives this variant of a word, or `None` when it would be the word itself or too
+    /// short to be useful.
+    ///
+    /// ```
+    /// use penguin::variants::Variant;
+    ///
+    /// assert_eq!(Variant::Plural.apply("fish"), Some(String::from("fishes")));
+    /// assert_eq!(Variant::Reversed.apply("kayak"), None);
+    /// assert_eq!(Variant::NoVowels.apply("eau"), None);
+    /// assert_eq!(Variant::Capitalized.apply("otter"), Some(String::from("Otter")));
+    /// ```
+    pub fn apply(self, word: &str) -> Option<String> {
+        let variant = match self {
+            Variant::Plural => plural(word),
+            Variant::Reversed => word.chars().rev().collect(),
+            Variant::NoVowels => {
+                let mut chars = word.chars();
+                let first = chars.next()?;
+                let rest = chars.filter(|c| !"aeiouAEIOU".contains(*c));
+                let stripped: String = std::iter::once(first).chain(rest).collect();
+                if stripped.chars().count() < 2 {
+                    return None;
+                }
+                stripped
+            }
+            Variant::Capitalized => {
+                let mut chars = word.chars();
+                let first = chars.next()?;
+                first.to_uppercase().chain(chars).collect()
+            }
+        };
+        (variant != word).then_some(variant)
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plural" | "plurals" => Ok(Variant::Plural),
+            "reversed" | "reverse" => Ok(Variant::Reversed),
+            "no-vowels" | "novowels" => Ok(Variant::NoVowels),
+            "capitalized" | "capital" => Ok(Variant::Capitalized),
+            _ => Err(format!(
+                "unknown word variant '{}' (expected plural, reversed, no-vowels, or capitalized)",
+                s
+            )),
+        }
+    }
+}
+
+/// Returns the base words followed by the given variants of each, without repeats.
+pub fn expand(words: &[&str], variants: &[Variant]) -> Vec<String> {
+    let mut expanded: Vec<String> = Vec::new();
+    let derived = words
+        .iter()
+        .flat_map(|word| variants.iter().filter_map(|variant| variant.apply(word)));
+    for word in words.iter().map(|word| word.to_string()).chain(derived) {
+        if !word.trim().is_empty() && !expanded.contains(&word) {
+            expanded.push(word);
+        }
+    }
+    expanded
+}
+
+/// Returns the English plural of a word by the regular rules.
+fn plural(word: &str) -> String {
+    let lowered = word.to_lowercase();
+    let before_y = lowered
+        .strip_suffix('y')
+        .and_then(|stem| stem.chars().last());
+    if before_y.is_some_and(|c| !"aeiou".contains(c)) {
+        format!("{}ies", &word[..word.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|ending| lowered.ends_with(ending))
+    {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
@@ -0,0 +1,250 @@
+//! Word lists for the Penguin password generator.
+//!
+//! The embedded lists are grouped by part of speech so that generators can assemble
+//! grammatical phrases instead of random word strings. Every entry is a lowercase
+//! ASCII word, unique within its list.
+//!
+//! For plain passphrases, [`eff_large`] is the EFF's large diceware list: 7,776 words
+//! chosen to be easy to spell and type, so each word picked from it adds 12.9 bits.
+//! Four of its entries contain a hyphen, such as `t-shirt`. The list is published by the
+//! Electronic Frontier Foundation under CC BY 3.0 US.
+//!
+//! Lists from elsewhere are rarely that tidy. The same word shows up twice, once
+//! capitalized or with an accent, and every repeat makes a passphrase built from the list
+//! weaker than its length suggests. A [`Wordlist`] holds such a list, finds the repeats,
+//! and [`normalizes`](Wordlist::normalize) it into one that deserves its entropy.
+//!
+//! # Examples
+//!
+//! ```
+//! use penguin::wordlist::{eff_large, Category, Wordlist};
+//!
+//! let nouns = Category::Noun.words();
+//! assert!(nouns.contains(&"otter"));
+//! assert!(Category::Verb.entropy_bits() > 6.0);
+//!
+//! assert_eq!(eff_large().len(), 7776);
+//!
+//! let list = Wordlist::parse("Café\ncafe\nglacier\n# comment\nicefloe\nox\n");
+//! assert_eq!(list.len(), 5);
+//! assert_eq!(list.duplicates(), vec![vec!["Café", "cafe"]]);
+//!
+//! let clean = list.normalize(3..);
+//! assert_eq!(clean.words(), ["cafe", "glacier", "icefloe"]);
+//! assert_eq!(clean.entropy_bits(), clean.effective_entropy_bits());
+//! ```
+
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use unicode_normalization::UnicodeNormalization;
+
+const ADJECTIVES: &str = include_str!("data/wordlist/adjectives.txt");
+const NOUNS: &str = include_str!("data/wordlist/nouns.txt");
+const VERBS: &str = include_str!("data/wordlist/verbs.txt");
+const EFF_LARGE: &str = include_str!("data/wordlist/eff_large.txt");
+
+/// The EFF large wordlist, in its diceware order, from `abacus` to `zoom`.
+pub fn eff_large() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| EFF_LARGE.lines().filter(|word| !word.is_empty()).collect())
+}
+
+/// The part of speech a word list holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Adjective,
+    Noun,
+    /// Verbs in the third person singular, such as `eats`.
+    Verb,
+}
+
+impl Category {
+    /// The embedded words of this category.
+    pub fn words(self) -> &'static [&'static str] {
+        static ADJECTIVE_WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+        static NOUN_WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+        static VERB_WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+        let (cell, list) = match self {
+            Category::Adjective => (&ADJECTIVE_WORDS, ADJECTIVES),
+            Category::Noun => (&NOUN_WORDS, NOUNS),
+            Category::Verb => (&VERB_WORDS, VERBS),
+        };
+        cell.get_or_init(|| {
+            list.lines()
+                .map(str::trim)
+                .filter(|word| !word.is_empty())
+                .collect()
+        })
+    }
+
+    /// Bits of entropy contributed by one word picked uniformly from this category.
+    pub fn entropy_bits(self) -> f64 {
+        (self.words().len() as f64).log2()
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "adjective" | "adj" => Ok(Category::Adjective),
+            "noun" => Ok(Category::Noun),
+            "verb" => Ok(Category::Verb),
+            other => Err(format!(
+                "unknown word category '{}' (expected adjective, noun, or verb)",
+                other
+            )),
+        }
+    }
+}
+
+/// A list of words, such as one loaded from a file, in its original order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Wordlist {
+    words: Vec<String>,
+}
+
+impl Wordlist {
+    /// Creates a list of the given words, as they are.
+    pub fn new(words: Vec<String>) -> Self {
+        Wordlist { words }
+    }
+
+    /// Reads a list with one word per line, skipping blank lines and lines that start
+    /// with `#`. Surrounding whitespace is trimmed.
+    pub fn parse(text: &str) -> Self {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect();
+        Wordlist { words }
+    }
+
+    /// Returns the words of the list.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Returns the number of entries, counting repeats.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns whether the list has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns a copy of the list with every word lowercased and stripped of accents,
+    /// keeping only the first occurrence of each and only words whose length, in
+    /// characters, is within `lengths`.
+    ///
+    /// ```
+    /// use penguin::wordlist::Wordlist;
+    ///
+    /// let list = Wordlist::parse("Straße\nNaïve\nnaive\nÉCOLE\nat");
+    /// assert_eq!(list.normalize(..).words(), ["strasse", "naive", "ecole", "at"]);
+    /// assert_eq!(list.normalize(3..=6).words(), ["naive", "ecole"]);
+    /// ```
+    pub fn normalize(&self, lengths: impl RangeBounds<usize>) -> Wordlist {
+        let mut seen = std::collections::HashSet::new();
+        let words = self
+            .words
+            .iter()
+            .map(|word| normalize_word(word))
+            .filter(|word| !word.is_empty() && lengths.contains(&word.chars().count()))
+            .filter(|word| seen.insert(word.clone()))
+            .collect();
+        Wordlist { words }
+    }
+
+    /// Returns the groups of entries that normalize to the same word, each in list order,
+    /// in the order their first entries appear.
+    pub fn duplicates(&self) -> Vec<Vec<&str>> {
+        let mut groups: Vec<Vec<&str>> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for word in &self.words {
+            let key = normalize_word(word);
+            match index.get(&key) {
+                Some(&group) => groups[group].push(word),
+                None => {
+                    index.insert(key, groups.len());
+                    groups.push(vec![word]);
+                }
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+
+    /// Bits of entropy a word picked uniformly from the list appears to carry, as if
+    /// every entry were different.
+    pub fn entropy_bits(&self) -> f64 {
+        (self.words.len() as f64).log2().max(0.0)
+    }
+
+    /// Bits of entropy a word picked uniformly from the list really carries, counting
+    /// entries that normalize to the same word as one. Repeats make it lower than
+    /// [`entropy_bits`](Self::entropy_bits).
+    ///
+    /// ```
+    /// use penguin::wordlist::Wordlist;
+    ///
+    /// let list = Wordlist::parse("ice\nIce\nICE\nfloe");
+    /// assert_eq!(list.entropy_bits(), 2.0);
+    /// assert!((list.effective_entropy_bits() - 0.811).abs() < 0.001);
+    /// ```
+    pub fn effective_entropy_bits(&self) -> f64 {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for word in &self.words {
+            *counts.entry(normalize_word(word)).or_default() += 1;
+        }
+        let total = self.words.len() as f64;
+        counts
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum::<f64>()
+            .max(0.0)
+    }
+}
+
+/// Lowercases a word and strips its accents, spelling out the letters that have no
+/// unaccented form, like `ß` as `ss`.
+///
+/// ```
+/// use penguin::wordlist::normalize_word;
+///
+/// assert_eq!(normalize_word("Crème Brûlée"), "creme brulee");
+/// assert_eq!(normalize_word("Øresund"), "oresund");
+/// ```
+pub fn normalize_word(word: &str) -> String {
+    word.trim()
+        .to_lowercase()
+        .nfd()
+        // Combining diacritical marks, left over from decomposing accented letters.
+        .filter(|c| !('\u{300}'..='\u{36f}').contains(c))
+        .fold(String::new(), |mut word, c| {
+            match c {
+                'ß' => word.push_str("ss"),
+                'æ' => word.push_str("ae"),
+                'œ' => word.push_str("oe"),
+                'þ' => word.push_str("th"),
+                'ø' => word.push('o'),
+                'ł' => word.push('l'),
+                'đ' | 'ð' => word.push('d'),
+                'ı' => word.push('i'),
+                c => word.push(c),
+            }
+            word
+        })
+}